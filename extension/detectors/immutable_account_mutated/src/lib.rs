@@ -162,7 +162,8 @@ fn find_immutable_account_fields(cx: &LateContext<'_>, def_id: DefId) -> HashMap
         for field in &variant.fields {
             let field_ty = tcx.type_of(field.did).instantiate_identity();
 
-            // Check if this is an account type (Account, AccountInfo, AccountLoader)
+            // Check if this is an account type (Account, AccountInfo, AccountLoader),
+            // including boxed accounts like Box<Account<'info, T>>
             if !is_account_type(cx, field_ty) {
                 continue;
             }
@@ -180,9 +181,13 @@ fn find_immutable_account_fields(cx: &LateContext<'_>, def_id: DefId) -> HashMap
     immutable
 }
 
-/// Check if a type is one of the Anchor account types
+/// Check if a type is one of the Anchor account types.
+/// Boxed accounts (`Box<Account<'info, T>>`) are unwrapped to their inner wrapper type.
 fn is_account_type(cx: &LateContext<'_>, ty: rustc_middle::ty::Ty<'_>) -> bool {
-    if let rustc_middle::ty::TyKind::Adt(adt_def, _) = ty.kind() {
+    if let rustc_middle::ty::TyKind::Adt(adt_def, args) = ty.kind() {
+        if adt_def.is_box() {
+            return is_account_type(cx, args.type_at(0));
+        }
         let type_name = cx.tcx.item_name(adt_def.did());
         return ACCOUNT_TYPES.contains(&type_name.as_str());
     }
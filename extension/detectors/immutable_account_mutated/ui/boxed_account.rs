@@ -0,0 +1,40 @@
+// Minimal stand-ins for the Anchor types the lint matches by name
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+pub struct Context<'a, T> {
+    pub accounts: &'a mut T,
+}
+
+pub struct Account<'info, T> {
+    inner: T,
+    _marker: PhantomData<&'info ()>,
+}
+
+impl<'info, T> Deref for Account<'info, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<'info, T> DerefMut for Account<'info, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+pub struct Vault {
+    pub amount: u64,
+}
+
+pub struct UpdateVault<'info> {
+    pub vault: Box<Account<'info, Vault>>,
+}
+
+// Should trigger: boxed account is mutated without #[account(mut)]
+pub fn update_vault(ctx: Context<UpdateVault>, amount: u64) {
+    ctx.accounts.vault.amount = amount;
+}
+
+fn main() {}
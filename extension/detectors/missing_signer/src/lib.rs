@@ -1,6 +1,7 @@
 #![feature(rustc_private)]
 #![warn(unused_extern_crates)]
 
+extern crate rustc_errors;
 extern crate rustc_hir;
 extern crate rustc_middle;
 extern crate rustc_span;
@@ -67,15 +68,27 @@ dylint_linting::declare_late_lint! {
 struct AccountsAnalyzer {
     /// Maps struct DefId to whether it has a signer field
     has_signer: HashMap<DefId, bool>,
+    /// Nested Accounts structs (components of composite contexts) in the order they were found
+    components: Vec<DefId>,
 }
 
 impl AccountsAnalyzer {
     fn new() -> Self {
         Self {
             has_signer: HashMap::new(),
+            components: Vec::new(),
         }
     }
 
+    /// Components checked so far that have no signer, directly or in their own components
+    fn components_without_signer(&self) -> Vec<DefId> {
+        self.components
+            .iter()
+            .copied()
+            .filter(|def_id| self.has_signer.get(def_id) == Some(&false))
+            .collect()
+    }
+
     /// Check if a struct has a Signer field
     fn check_struct_has_signer(&mut self, cx: &LateContext<'_>, def_id: DefId) -> bool {
         // Check cache first
@@ -101,6 +114,10 @@ impl AccountsAnalyzer {
                 if let Some(nested_def_id) = self.get_struct_def_id(field_ty) {
                     // Only check if it's an Accounts struct to avoid checking random nested types
                     if self.has_accounts_derive(cx, nested_def_id) {
+                        if !self.components.contains(&nested_def_id) {
+                            self.components.push(nested_def_id);
+                        }
+
                         // Recursively check the nested Accounts struct
                         if self.check_struct_has_signer(cx, nested_def_id) {
                             self.has_signer.insert(def_id, true);
@@ -173,26 +190,46 @@ impl MissingSigner {
 
             // Check if the Accounts struct has a signer
             if !analyzer.check_struct_has_signer(cx, context_type_def_id) {
+                // Components of a composite context may be defined in other files, so point at
+                // each of them: the signer can be added to any one
+                let components: Vec<(rustc_span::Span, String)> = analyzer
+                    .components_without_signer()
+                    .into_iter()
+                    .filter_map(|def_id| {
+                        let span = self.get_struct_span(cx, def_id)?;
+                        Some((span, cx.tcx.item_name(def_id).to_string()))
+                    })
+                    .collect();
+                let note_components = |diag: &mut rustc_errors::Diag<'_, ()>| {
+                    for (span, name) in &components {
+                        diag.span_note(*span, format!("component '{}' has no signer field", name));
+                    }
+                };
+
                 // Report on the function (instruction level) - use ident span for just the function name
                 let fn_span = sig.span;
-                clippy_utils::diagnostics::span_lint_and_help(
+                clippy_utils::diagnostics::span_lint_and_then(
                     cx,
                     MISSING_SIGNER,
                     fn_span,
                     "Instruction has no signer account",
-                    None,
-                    "Consider adding a Signer<'info> field to the accounts struct to ensure proper authorization",
+                    |diag| {
+                        note_components(diag);
+                        diag.help("Consider adding a Signer<'info> field to the accounts struct to ensure proper authorization");
+                    },
                 );
 
                 // Also report on the struct definition itself
                 if let Some(struct_span) = self.get_struct_span(cx, context_type_def_id) {
-                    clippy_utils::diagnostics::span_lint_and_help(
+                    clippy_utils::diagnostics::span_lint_and_then(
                         cx,
                         MISSING_SIGNER,
                         struct_span,
                         "Accounts struct has no signer field",
-                        None,
-                        "Consider adding a Signer<'info> field to ensure proper authorization",
+                        |diag| {
+                            note_components(diag);
+                            diag.help("Consider adding a Signer<'info> field to ensure proper authorization");
+                        },
                     );
                 }
             }
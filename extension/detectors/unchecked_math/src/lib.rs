@@ -3,13 +3,26 @@
 
 extern crate rustc_ast;
 extern crate rustc_hir;
+extern crate rustc_lint;
 extern crate rustc_middle;
+extern crate rustc_session;
 
-use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_hir::{BinOpKind, Expr, ExprKind, MatchSource, QPath};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::TyKind;
+use serde::Deserialize;
 
-dylint_linting::declare_late_lint! {
+dylint_linting::dylint_library!();
+
+#[allow(clippy::no_mangle_with_rust_abi)]
+#[unsafe(no_mangle)]
+pub fn register_lints(sess: &rustc_session::Session, lint_store: &mut rustc_lint::LintStore) {
+    dylint_linting::init_config(sess);
+    lint_store.register_lints(&[UNCHECKED_MATH, UNCHECKED_MATH_BALANCE]);
+    lint_store.register_late_pass(|_| Box::new(UncheckedMath::new()));
+}
+
+rustc_session::declare_lint! {
     /// ### What it does
     /// Detects unchecked arithmetic operations (addition, subtraction, multiplication, division)
     /// that could lead to overflow or underflow vulnerabilities in Solana programs.
@@ -39,6 +52,61 @@ dylint_linting::declare_late_lint! {
     "detects unchecked arithmetic operations that could overflow/underflow"
 }
 
+rustc_session::declare_lint! {
+    /// ### What it does
+    /// Detects unchecked subtraction where the left operand is a balance-like field
+    /// (`balance`, `amount`, `supply`, `lamports` or `total_*`).
+    ///
+    /// ### Why is this bad?
+    /// Underflow on a balance wraps around to a huge value, which lets an attacker
+    /// withdraw more than they own or mint tokens out of thin air.
+    ///
+    /// ### Example
+    ///
+    /// Bad:
+    /// ```rust
+    /// vault.balance = vault.balance - amount;
+    /// ```
+    ///
+    /// Good:
+    /// ```rust
+    /// vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::Underflow)?;
+    /// ```
+    ///
+    /// ### Note
+    /// Declared as a warning: a deny lint fails `cargo check` for the crate, so crates depending
+    /// on it would not be linted. The language server reports it as an error instead.
+    pub UNCHECKED_MATH_BALANCE,
+    Warn,
+    "detects unchecked subtraction on balance fields that could underflow"
+}
+
+/// `[unchecked_math]` table of `dylint.toml`
+#[derive(Default, Deserialize)]
+struct Config {
+    /// Crates, types or paths (e.g. `spl_math` or `PreciseNumber`) whose method calls return
+    /// values from audited fixed-point math; arithmetic on them alone is not linted
+    #[serde(default)]
+    trusted_math_receivers: Vec<String>,
+}
+
+struct UncheckedMath {
+    config: Config,
+}
+
+impl UncheckedMath {
+    fn new() -> Self {
+        Self {
+            config: dylint_linting::config_or_default(env!("CARGO_PKG_NAME")),
+        }
+    }
+}
+
+rustc_session::impl_lint_pass!(UncheckedMath => [UNCHECKED_MATH, UNCHECKED_MATH_BALANCE]);
+
+/// Field names whose underflow is security-critical
+const BALANCE_FIELDS: &[&str] = &["balance", "amount", "supply", "lamports"];
+
 impl<'tcx> LateLintPass<'tcx> for UncheckedMath {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
         // Skip if inside a macro expansion to avoid false positives
@@ -47,8 +115,13 @@ impl<'tcx> LateLintPass<'tcx> for UncheckedMath {
         }
 
         match expr.kind {
+            ExprKind::Binary(_, left, right) | ExprKind::AssignOp(_, left, right)
+                if self.is_trusted_operand(cx, left) && self.is_trusted_operand(cx, right) => {}
             // Check binary operations (+, -, *, /)
             ExprKind::Binary(op, left, right) => {
+                if check_balance_subtraction(cx, expr, op.node, left, right, false) {
+                    return;
+                }
                 if let Some((msg, help)) = check_arithmetic_op(cx, op.node, left, right, false) {
                     clippy_utils::diagnostics::span_lint_and_help(
                         cx,
@@ -62,6 +135,9 @@ impl<'tcx> LateLintPass<'tcx> for UncheckedMath {
             }
             // Check compound assignment operators (+=, -=, *=, /=)
             ExprKind::AssignOp(op, left, right) => {
+                if check_balance_subtraction(cx, expr, op.node.into(), left, right, true) {
+                    return;
+                }
                 if let Some((msg, help)) =
                     check_arithmetic_op(cx, op.node.into(), left, right, true)
                 {
@@ -80,6 +156,109 @@ impl<'tcx> LateLintPass<'tcx> for UncheckedMath {
     }
 }
 
+impl UncheckedMath {
+    /// Check if an operand comes from a trusted math library or is a small literal
+    fn is_trusted_operand<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> bool {
+        !self.config.trusted_math_receivers.is_empty()
+            && (is_small_literal(expr) || self.is_trusted_call(cx, expr))
+    }
+
+    /// Check if a call chain (e.g. `a.checked_mul(&b)?.to_imprecise().unwrap()`) calls a
+    /// method or function of a trusted receiver
+    fn is_trusted_call<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> bool {
+        match expr.kind {
+            ExprKind::MethodCall(_, receiver, _, _) => {
+                cx.typeck_results()
+                    .type_dependent_def_id(expr.hir_id)
+                    .is_some_and(|def_id| self.is_trusted_path(&cx.tcx.def_path_str(def_id)))
+                    || self.is_trusted_call(cx, receiver)
+            }
+            ExprKind::Call(func, _) => match func.kind {
+                ExprKind::Path(
+                    ref qpath @ QPath::Resolved(..) | ref qpath @ QPath::TypeRelative(..),
+                ) => cx
+                    .qpath_res(qpath, func.hir_id)
+                    .opt_def_id()
+                    .is_some_and(|def_id| self.is_trusted_path(&cx.tcx.def_path_str(def_id))),
+                _ => false,
+            },
+            // `expr?` desugars to a match on `Try::branch(expr)`
+            ExprKind::Match(scrutinee, _, MatchSource::TryDesugar(_)) => match scrutinee.kind {
+                ExprKind::Call(_, [inner]) => self.is_trusted_call(cx, inner),
+                _ => false,
+            },
+            ExprKind::Unary(rustc_hir::UnOp::Deref, inner)
+            | ExprKind::AddrOf(_, _, inner)
+            | ExprKind::Cast(inner, _) => self.is_trusted_call(cx, inner),
+            _ => false,
+        }
+    }
+
+    /// Check if a definition path (e.g. `spl_math::precise_number::PreciseNumber::to_imprecise`)
+    /// lies in one of the trusted crates, types or paths
+    fn is_trusted_path(&self, path: &str) -> bool {
+        self.config.trusted_math_receivers.iter().any(|receiver| {
+            // Crate names are written with dashes in Cargo.toml but with underscores in paths
+            let receiver = receiver.replace('-', "_");
+            path == receiver
+                || path.starts_with(&format!("{receiver}::"))
+                || path.contains(&format!("::{receiver}::"))
+                || path.ends_with(&format!("::{receiver}"))
+        })
+    }
+}
+
+/// Emit the escalated lint for unchecked subtraction on a balance-like field
+/// Returns true if the lint was emitted
+fn check_balance_subtraction<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    op: BinOpKind,
+    left: &'tcx Expr<'tcx>,
+    right: &'tcx Expr<'tcx>,
+    is_assignment: bool,
+) -> bool {
+    if op != BinOpKind::Sub || !is_potentially_unsafe_operation(cx, left, right) {
+        return false;
+    }
+
+    let Some(field_name) = balance_field_name(left) else {
+        return false;
+    };
+
+    let msg = if is_assignment {
+        format!("unchecked subtraction assignment on balance field `{field_name}` may underflow")
+    } else {
+        format!("unchecked subtraction on balance field `{field_name}` may underflow")
+    };
+
+    clippy_utils::diagnostics::span_lint_and_help(
+        cx,
+        UNCHECKED_MATH_BALANCE,
+        expr.span,
+        msg,
+        None,
+        "use `checked_sub()` and return an error on underflow",
+    );
+    true
+}
+
+/// Get the name of a balance-like field accessed by an expression (e.g. `vault.balance`)
+fn balance_field_name(expr: &Expr<'_>) -> Option<String> {
+    match &expr.kind {
+        ExprKind::Field(_, ident) => {
+            let name = ident.as_str();
+            (BALANCE_FIELDS.contains(&name) || name.starts_with("total_"))
+                .then(|| name.to_string())
+        }
+        // Look through derefs and borrows (e.g. `*vault.balance`)
+        ExprKind::Unary(rustc_hir::UnOp::Deref, inner) | ExprKind::AddrOf(_, _, inner) => {
+            balance_field_name(inner)
+        }
+        _ => None,
+    }
+}
+
 /// Check if an arithmetic operation is potentially unsafe and return appropriate message
 fn check_arithmetic_op<'tcx>(
     cx: &LateContext<'tcx>,
@@ -215,3 +394,10 @@ fn is_small_literal(expr: &Expr<'_>) -> bool {
 fn ui() {
     dylint_testing::ui_test(env!("CARGO_PKG_NAME"), "ui");
 }
+
+#[test]
+fn ui_trusted_math_receivers() {
+    dylint_testing::ui::Test::src_base(env!("CARGO_PKG_NAME"), "ui_trusted")
+        .dylint_toml("unchecked_math.trusted_math_receivers = [\"PreciseNumber\"]")
+        .run();
+}
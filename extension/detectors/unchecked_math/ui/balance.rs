@@ -0,0 +1,19 @@
+struct Vault {
+    balance: u64,
+    total_deposits: u64,
+}
+
+fn withdraw(vault: &mut Vault, x: u64) {
+    vault.balance = vault.balance - x; // Should trigger balance warning (balance field)
+    vault.total_deposits -= x; // Should trigger balance warning (total_* field)
+}
+
+fn countdown(i: u64) -> u64 {
+    i - 1 // Should trigger normal warning
+}
+
+fn main() {
+    let mut vault = Vault { balance: 10, total_deposits: 10 };
+    withdraw(&mut vault, 1);
+    countdown(5);
+}
@@ -0,0 +1,20 @@
+mod precise_number {
+    pub struct PreciseNumber(pub u128);
+
+    impl PreciseNumber {
+        pub fn to_imprecise(&self) -> Option<u128> {
+            Some(self.0)
+        }
+    }
+}
+
+use precise_number::PreciseNumber;
+
+fn combine(a: &PreciseNumber, b: &PreciseNumber, x: u64, y: u64) -> (u128, u64) {
+    let total = a.to_imprecise().unwrap() + b.to_imprecise().unwrap(); // Should NOT trigger warning
+    (total, x + y) // Should trigger warning
+}
+
+fn main() {
+    combine(&PreciseNumber(1), &PreciseNumber(2), 1, 2);
+}
@@ -25,8 +25,10 @@ interface FileIssueInfo {
 }
 
 interface DetectorStatus {
-    status: string; // "initializing", "building", "running", "complete", "idle"
+    status: string; // "initializing", "building", "running", "complete", "idle", "missing_toolchain", "cargo_missing", "missing_driver", "timeout", "parse_failed", "compile_failed"
     message: string;
+    required_version?: string; // set for "missing_toolchain" and "missing_driver"
+    install_command?: string; // set for "missing_toolchain", "cargo_missing" and "missing_driver"
 }
 
 export class DetectorsManager {
@@ -174,6 +176,19 @@ export class DetectorsManager {
 		    diagnosticCollectionName: 'solana',
 		    revealOutputChannelOn: RevealOutputChannelOn.Debug,
 		    progressOnInitialization: true,
+            initializationOptions: {
+                // Resolve symlinked workspace roots so published diagnostics match open documents
+                canonicalizePaths: workspace.getConfiguration('server').get<boolean>('canonicalizePaths', true),
+                // Extra scan exclusions on top of the workspace .gitignore
+                ignoreGlobs: workspace.getConfiguration('server').get<string[]>('ignoreGlobs', []),
+                // Lint test files, and test-only code for the listed detectors
+                scanTests: workspace.getConfiguration('server').get<boolean>('scanTests', false),
+                testDetectors: workspace.getConfiguration('server').get<string[]>('testDetectors', []),
+                // Audited math libraries whose results the unchecked_math detector skips
+                trustedMathReceivers: workspace.getConfiguration('server').get<string[]>('trustedMathReceivers', []),
+                // Diagnostics are pushed, pulling them as well would show each one twice
+                pullDiagnostics: false
+            },
             synchronize: {
                // Notify the server about file changes to '.clientrc files contained in the workspace
             fileEvents: workspace.createFileSystemWatcher('**/*.rs')
@@ -262,6 +277,16 @@ export class DetectorsManager {
                 case 'idle':
                     this.statusBarUpdateCallback(StatusBarState.Chill, detectorStatus.message);
                     break;
+                case 'missing_toolchain':
+                case 'cargo_missing':
+                case 'missing_driver':
+                case 'timeout':
+                case 'compile_failed':
+                    this.statusBarUpdateCallback(StatusBarState.Warn, detectorStatus.message);
+                    break;
+                case 'parse_failed':
+                    this.statusBarUpdateCallback(StatusBarState.Error, detectorStatus.message);
+                    break;
                 default:
                     this.outputChannel.appendLine(`Unknown detector status: ${detectorStatus.status}`);
             }
@@ -269,6 +294,28 @@ export class DetectorsManager {
 
         // Log to output channel
         this.outputChannel.appendLine(`Detector Status: ${detectorStatus.message}`);
+
+        if (detectorStatus.status === 'missing_toolchain' && detectorStatus.install_command) {
+            this.handleMissingToolchain(detectorStatus.message, detectorStatus.install_command);
+        } else if (detectorStatus.status === 'cargo_missing' && detectorStatus.install_command) {
+            this.handleMissingToolchain(detectorStatus.message, detectorStatus.install_command, 'Install Rust');
+        } else if (detectorStatus.status === 'missing_driver' && detectorStatus.install_command) {
+            // Reported on every run, so only logged instead of prompting each time
+            this.outputChannel.appendLine(`Install with: ${detectorStatus.install_command}`);
+        }
+    }
+
+    private handleMissingToolchain(message: string, installCommand: string, terminalName = 'Install Nightly Rust') {
+        this.outputChannel.appendLine(`Install with: ${installCommand}`);
+        window.showWarningMessage(message, 'Install', 'Copy Command').then(selection => {
+            if (selection === 'Install') {
+                const terminal = window.createTerminal(terminalName);
+                terminal.sendText(installCommand);
+                terminal.show();
+            } else if (selection === 'Copy Command') {
+                vscode.env.clipboard.writeText(installCommand);
+            }
+        });
     }
 
     dispose() {
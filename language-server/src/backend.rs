@@ -1,27 +1,78 @@
+use crate::core::backend_stats::BackendManager;
+use crate::core::diagnostic_sink::{DiagnosticSink, sink_from_env};
 use crate::core::dylint::constants::REQUIRED_NIGHTLY_VERSION;
+use crate::core::utilities::{
+    DiagnosticBuilder, DocumentStore, PathNormalizer, PositionEncoding, PublishVersions,
+};
 use crate::core::{
+    AccountBoxingDetector, AccountTypeConfusionDetector, BorrowAcrossCpiDetector,
+    BumpSourceDetector, CloseDestinationNotMutDetector, CodeActionProvider, CompletionProvider,
+    ConstraintAssignmentDetector, DanglingInstructionAttributeDetector, DefinitionProvider,
     DetectorInfo, DetectorRegistry, DetectorRegistryBuilder, DetectorStatus,
-    DetectorStatusNotification, DylintDetectorManager, FileScanner,
-    InstructionAttributeInvalidDetector, InstructionAttributeUnusedDetector,
-    ManualLamportsZeroingDetector, MissingCheckCommentDetector, MissingInitspaceDetector,
-    ScanCompleteNotification, ScanResult, ScanSummary, SysvarAccountDetector,
+    DetectorStatusNotification, DylintDetectorManager, EmptyHandlerDetector, FileScanner,
+    GitContentProvider, HoverProvider, InitWithoutSpaceDetector, InlayHintProvider,
+    InsecureRandomnessDetector, InstructionAttributeInvalidDetector,
+    InstructionAttributeUnusedDetector, InvokeSignedSeedsMismatchDetector,
+    LamportsBelowRentDetector, LargeAccountNoZeroCopyDetector, LoadMutWithoutMutDetector,
+    LogBeforeReturnDetector, LossyRequireComparisonDetector, MalformedRequireDetector,
+    ManualLamportsZeroingDetector, MissingAccountAttributeDetector, MissingCheckCommentDetector,
+    MissingInitspaceDetector, MissingMaxLenDetector, MsgInLoopDetector, MutAccountArgumentDetector,
+    PayerNotMutDetector, PdaSeedsMismatchDetector, PdaTransferWithoutSignerDetector,
+    PdaTypedAsSignerDetector, ProgramIdMismatchDetector, ReallocWithoutZeroInitDetector,
+    RenameProvider, RuntimeAddressConstraintDetector, ScanCache, ScanCompleteNotification,
+    ScanResult, ScanSummary, SelectionRangeProvider, SemanticTokenizer, SensitiveLogDetector,
+    SeverityCounts, ShadowedAccountDetector, SysvarAccountDetector,
+    SysvarGetOutsideHandlerDetector, UnboundConstraintIdentDetector, UnboundedSeedDetector,
+    UncheckedAccountIndexDetector, UncheckedRemainingAccountsDetector,
+    UncheckedSetAuthorityDetector, UncheckedTokenTransferDetector,
+    UnconstrainedTokenAccountDetector, UnenforcedKeyCheckDetector, UnknownContextDetector,
+    UnnecessaryMutDetector, UnsignedAuthorityDetector, UntrustedPubkeyDetector,
+    UnusedSignerDetector, UnwrapInProgramDetector, WorkDoneProgressReporter, WorkspaceSymbolIndex,
+    ZeroAccountReinitDetector, ZeroConstraintDetector, detector_config::DetectorConfig,
 };
-use crate::dylint_runner::DylintRunner;
+use crate::dylint_runner::{DylintResult, DylintRunner, diagnostics_for_file};
 use log::{info, warn};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tower_lsp::{
     Client, LanguageServer,
     jsonrpc::Result as JsonRpcResult,
     lsp_types::{
-        DidChangeTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
-        InitializeParams, InitializeResult, PositionEncodingKind, SaveOptions, ServerCapabilities,
-        ServerInfo, TextDocumentItem, TextDocumentSyncCapability, TextDocumentSyncKind,
-        TextDocumentSyncOptions, TextDocumentSyncSaveOptions,
+        CodeActionOrCommand, CodeActionParams, CodeActionProviderCapability, CodeActionResponse,
+        CompletionOptions, CompletionParams, CompletionResponse, DiagnosticOptions,
+        DiagnosticServerCapabilities, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+        DidOpenTextDocumentParams, DidSaveTextDocumentParams, DocumentDiagnosticParams,
+        DocumentDiagnosticReport, DocumentDiagnosticReportResult, FullDocumentDiagnosticReport,
+        GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams, HoverProviderCapability,
+        InitializeParams, InitializeResult, InlayHint, InlayHintParams, Location, OneOf, Position,
+        PrepareRenameResponse, RelatedFullDocumentDiagnosticReport, RenameOptions, RenameParams,
+        SaveOptions, SelectionRange, SelectionRangeParams, SelectionRangeProviderCapability,
+        SemanticTokens, SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensParams,
+        SemanticTokensResult, SemanticTokensServerCapabilities, ServerCapabilities, ServerInfo,
+        SymbolInformation, TextDocumentItem, TextDocumentPositionParams,
+        TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+        TextDocumentSyncSaveOptions, TextEdit, Url, WorkspaceEdit, WorkspaceSymbolParams,
     },
 };
 
+/// Quiet period after the last edit before detectors run on the changed buffer
+pub const CHANGE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How diagnostics reach the client, chosen in `initialize` from its capabilities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum DiagnosticMode {
+    /// Sent with `textDocument/publishDiagnostics`
+    #[default]
+    Push,
+    /// Requested by the client with `textDocument/diagnostic`
+    /// `refresh` if it can be asked to pull again once dylint results arrive
+    Pull { refresh: bool },
+}
+
 #[derive(Debug, Clone)]
 pub struct Backend {
     client: Client,
@@ -30,6 +81,30 @@ pub struct Backend {
     dylint_runner: Option<Arc<DylintRunner>>,
     dylint_manager: Arc<Mutex<Option<DylintDetectorManager>>>,
     workspace_root: Arc<Mutex<Option<PathBuf>>>,
+    /// Maps scanned file paths to URIs matching the client's documents
+    path_normalizer: Arc<Mutex<PathNormalizer>>,
+    /// Whether diagnostics use full messages (kept across registry reloads)
+    verbose_messages: Arc<Mutex<bool>>,
+    /// Detectors that also analyze test code (kept across registry reloads)
+    test_detectors: Arc<Mutex<Vec<String>>>,
+    /// Latest known content of open documents (used by editor features and on-change analysis)
+    documents: Arc<Mutex<DocumentStore>>,
+    /// Handlers and Anchor structs of the workspace (built on the initial scan, updated on save)
+    symbol_index: Arc<Mutex<WorkspaceSymbolIndex>>,
+    /// Receives every published diagnostic (see `SOLANA_LSP_DIAGNOSTICS_LOG`)
+    diagnostic_sink: Arc<dyn DiagnosticSink>,
+    /// Version of the last diagnostics published per file, so late dylint results can't overwrite newer ones
+    publish_versions: Arc<Mutex<PublishVersions>>,
+    /// Set on shutdown to stop workspace scans, shared with `file_scanner`
+    scan_cancelled: Arc<AtomicBool>,
+    /// Set once the missing cargo has been reported, so the extension is told only once
+    cargo_missing_reported: Arc<AtomicBool>,
+    /// Column unit of positions sent to the client, negotiated in `initialize`
+    position_encoding: Arc<Mutex<PositionEncoding>>,
+    /// Dylint diagnostics of the last run per file, included in pulled diagnostics
+    dylint_diagnostics: Arc<Mutex<HashMap<Url, Vec<tower_lsp::lsp_types::Diagnostic>>>>,
+    /// Whether diagnostics are pushed or pulled, negotiated in `initialize`
+    diagnostic_mode: Arc<Mutex<DiagnosticMode>>,
 }
 
 #[tower_lsp::async_trait]
@@ -38,21 +113,143 @@ impl LanguageServer for Backend {
         &self,
         params: InitializeParams,
     ) -> Result<InitializeResult, tower_lsp::jsonrpc::Error> {
+        // Canonicalization of workspace paths can be disabled by the client
+        let canonicalize_paths = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("canonicalizePaths"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(true);
+
+        // Extra scan exclusions (gitignore syntax) from settings
+        let ignore_globs: Vec<String> = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("ignoreGlobs"))
+            .and_then(|value| value.as_array())
+            .map(|globs| {
+                globs
+                    .iter()
+                    .filter_map(|glob| glob.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Opt-in linting of test files and, per detector, of test-only code
+        let scan_tests = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("scanTests"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        let test_detectors: Vec<String> = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("testDetectors"))
+            .and_then(|value| value.as_array())
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| id.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        {
+            let mut registry = self.detector_registry.lock().await;
+            for detector_id in &test_detectors {
+                registry.set_include_tests(detector_id, true);
+            }
+        }
+        *self.test_detectors.lock().await = test_detectors.clone();
+
+        // Audited math libraries whose results the unchecked_math lint should not flag
+        let trusted_math_receivers: Vec<String> = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("trustedMathReceivers"))
+            .and_then(|value| value.as_array())
+            .map(|receivers| {
+                receivers
+                    .iter()
+                    .filter_map(|receiver| receiver.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(runner) = &self.dylint_runner {
+            runner.set_unchecked_math_config(&DetectorConfig {
+                trusted_math_receivers,
+                ..Default::default()
+            });
+        }
+
+        // Count columns in UTF-8 bytes if the client supports it, UTF-16 otherwise
+        let position_encoding = PositionEncoding::negotiate(
+            params
+                .capabilities
+                .general
+                .as_ref()
+                .and_then(|general| general.position_encodings.as_deref()),
+        );
+        *self.position_encoding.lock().await = position_encoding;
+
+        // Pull diagnostics if the client supports it, unless it opts out; push them otherwise
+        let pull_supported = params
+            .capabilities
+            .text_document
+            .as_ref()
+            .and_then(|text_document| text_document.diagnostic.as_ref())
+            .is_some();
+        let pull_diagnostics = pull_supported
+            && params
+                .initialization_options
+                .as_ref()
+                .and_then(|options| options.get("pullDiagnostics"))
+                .and_then(|value| value.as_bool())
+                .unwrap_or(true);
+        *self.diagnostic_mode.lock().await = if pull_diagnostics {
+            DiagnosticMode::Pull {
+                refresh: params
+                    .capabilities
+                    .workspace
+                    .as_ref()
+                    .and_then(|workspace| workspace.diagnostic.as_ref())
+                    .and_then(|diagnostic| diagnostic.refresh_support)
+                    .unwrap_or(false),
+            }
+        } else {
+            DiagnosticMode::Push
+        };
+
         // Set up workspace root if provided
         if let Some(workspace_folders) = params.workspace_folders
             && let Some(folder) = workspace_folders.first()
             && let Ok(path) = folder.uri.to_file_path()
         {
+            // Canonicalize the workspace root, publishing URIs relative to the client's root
+            let normalizer = PathNormalizer::new(path.clone(), canonicalize_paths);
+            let path = normalizer.workspace_root().unwrap_or(path);
+            *self.path_normalizer.lock().await = normalizer.clone();
+
             // Store workspace root for dylint
             *self.workspace_root.lock().await = Some(path.clone());
 
             let mut scanner = self.file_scanner.lock().await;
             scanner.set_workspace_root(path.clone());
+            scanner.set_ignore_globs(ignore_globs.clone());
+            scanner.set_scan_tests(scan_tests);
 
-            // Perform initial workspace scan
+            // Perform initial workspace scan, reusing cached results for unchanged files
+            // Cached results do not cover test code, so skip the cache if any detector opts in
             info!("Performing initial workspace scan...");
             let mut registry = self.detector_registry.lock().await;
-            let scan_result = scanner.scan_workspace(&mut registry).await;
+            let cache_path = ScanCache::default_path(&path).filter(|_| test_detectors.is_empty());
+            let scan_result = match cache_path {
+                Some(cache_path) => {
+                    scanner
+                        .scan_workspace_cached(&mut registry, &cache_path)
+                        .await
+                }
+                None => scanner.scan_workspace(&mut registry).await,
+            };
             drop(registry); // Release registry lock before initializing dylint
             drop(scanner); // Release scanner lock
 
@@ -73,14 +270,21 @@ impl LanguageServer for Backend {
             );
 
             // Publish diagnostics for ALL scanned files (including empty diagnostics for fixed files)
+            let mut published_versions = HashMap::new();
             for file_info in &scan_result.rust_files {
-                if let Ok(uri) = tower_lsp::lsp_types::Url::from_file_path(&file_info.path) {
-                    self.client
-                        .publish_diagnostics(uri, file_info.diagnostics.clone(), None)
+                if let Some(uri) = normalizer.to_uri(&file_info.path) {
+                    let version = self
+                        .publish_diagnostics(
+                            uri,
+                            DiagnosticBuilder::normalize(file_info.diagnostics.clone()),
+                        )
                         .await;
+                    published_versions.insert(file_info.path.clone(), version);
                 }
             }
 
+            self.index_workspace_symbols(&scan_result).await;
+
             // Send scan results to extension (initial scan, not manual)
             let scan_summary = ScanSummary::from_scan_result(&scan_result, false);
             self.client
@@ -95,6 +299,7 @@ impl LanguageServer for Backend {
                 .send_notification::<DetectorStatusNotification>(DetectorStatus {
                     status: "initializing".to_string(),
                     message: "Initializing security detectors...".to_string(),
+                    ..Default::default()
                 })
                 .await;
 
@@ -105,12 +310,24 @@ impl LanguageServer for Backend {
                 let runner = Arc::clone(dylint_runner);
                 let workspace = path.clone();
                 let client = self.client.clone();
-                let file_list: Vec<(std::path::PathBuf, Vec<tower_lsp::lsp_types::Diagnostic>)> =
-                    scan_result
-                        .rust_files
-                        .iter()
-                        .map(|f| (f.path.clone(), f.diagnostics.clone()))
-                        .collect();
+                let sink = Arc::clone(&self.diagnostic_sink);
+                let publish_versions = Arc::clone(&self.publish_versions);
+                let documents = Arc::clone(&self.documents);
+                let position_encoding = *self.position_encoding.lock().await;
+                let diagnostic_mode = *self.diagnostic_mode.lock().await;
+                let dylint_cache = Arc::clone(&self.dylint_diagnostics);
+                let file_list: Vec<(
+                    std::path::PathBuf,
+                    Vec<tower_lsp::lsp_types::Diagnostic>,
+                    i32,
+                )> = scan_result
+                    .rust_files
+                    .iter()
+                    .filter_map(|f| {
+                        let version = *published_versions.get(&f.path)?;
+                        Some((f.path.clone(), f.diagnostics.clone(), version))
+                    })
+                    .collect();
 
                 tokio::spawn(async move {
                     info!("Running dylint on project open...");
@@ -120,10 +337,20 @@ impl LanguageServer for Backend {
                         .send_notification::<DetectorStatusNotification>(DetectorStatus {
                             status: "running".to_string(),
                             message: "Running security detectors...".to_string(),
+                            ..Default::default()
                         })
                         .await;
 
-                    match runner.run_lints(&workspace).await {
+                    let progress = WorkDoneProgressReporter::begin(
+                        &client,
+                        "Running security detectors",
+                        Some(format!("Linting {} files", file_list.len())),
+                    )
+                    .await;
+                    let lint_result = runner.run_lints(&workspace).await;
+                    progress.end(Some(Self::lint_summary(&lint_result))).await;
+
+                    match lint_result {
                         Ok(dylint_diagnostics) => {
                             info!(
                                 "Dylint found {} total issues on project open",
@@ -131,25 +358,28 @@ impl LanguageServer for Backend {
                             );
 
                             // Merge dylint diagnostics with syn diagnostics for each file
-                            for (file_path, syn_diagnostics) in file_list {
-                                if let Ok(uri) =
-                                    tower_lsp::lsp_types::Url::from_file_path(&file_path)
-                                {
+                            for (file_path, syn_diagnostics, version) in file_list {
+                                if let Some(uri) = normalizer.to_uri(&file_path) {
                                     // Filter dylint diagnostics for this file
-                                    let dylint_file_diagnostics: Vec<_> = dylint_diagnostics
-                                        .iter()
-                                        .filter(|d| {
-                                            let path_str = file_path.to_string_lossy();
-                                            path_str.ends_with(&d.file_name)
-                                                || path_str.contains(&d.file_name)
-                                        })
-                                        .map(|d| d.to_lsp_diagnostic(Some(&workspace)))
-                                        .collect();
+                                    let dylint_file_diagnostics: Vec<_> =
+                                        diagnostics_for_file(&dylint_diagnostics, &file_path)
+                                            .into_iter()
+                                            .map(|d| {
+                                                d.to_lsp_diagnostic(Some(&workspace), &normalizer)
+                                            })
+                                            .collect();
+                                    dylint_cache
+                                        .lock()
+                                        .await
+                                        .insert(uri.clone(), dylint_file_diagnostics.clone());
 
                                     if !dylint_file_diagnostics.is_empty() {
                                         // Merge syn and dylint diagnostics
-                                        let mut merged_diagnostics = syn_diagnostics.clone();
-                                        merged_diagnostics.extend(dylint_file_diagnostics.clone());
+                                        let merged_diagnostics =
+                                            DiagnosticBuilder::merge_and_normalize(
+                                                syn_diagnostics.clone(),
+                                                dylint_file_diagnostics.clone(),
+                                            );
 
                                         info!(
                                             "Publishing {} total diagnostics ({} syn + {} dylint) for {}",
@@ -159,31 +389,40 @@ impl LanguageServer for Backend {
                                             file_path.display()
                                         );
 
-                                        // Publish merged diagnostics
-                                        client
-                                            .publish_diagnostics(uri, merged_diagnostics, None)
-                                            .await;
+                                        // Publish merged diagnostics unless newer ones were published meanwhile
+                                        Self::publish_if_current(
+                                            &client,
+                                            sink.as_ref(),
+                                            &publish_versions,
+                                            &documents,
+                                            position_encoding,
+                                            diagnostic_mode,
+                                            uri,
+                                            merged_diagnostics,
+                                            version,
+                                        )
+                                        .await;
                                     }
                                 }
                             }
 
+                            Self::refresh_pulled_diagnostics(&client, diagnostic_mode).await;
+
                             // Notify complete
                             client
                                 .send_notification::<DetectorStatusNotification>(DetectorStatus {
                                     status: "complete".to_string(),
                                     message: "Security scan complete".to_string(),
+                                    ..Default::default()
                                 })
                                 .await;
                         }
                         Err(e) => {
                             info!("Dylint failed on project open: {}", e);
 
-                            // Notify complete even on error
+                            // Tell the extension why detectors did not run
                             client
-                                .send_notification::<DetectorStatusNotification>(DetectorStatus {
-                                    status: "complete".to_string(),
-                                    message: "Security scan complete".to_string(),
-                                })
+                                .send_notification::<DetectorStatusNotification>(e.status())
                                 .await;
                         }
                     }
@@ -192,16 +431,32 @@ impl LanguageServer for Backend {
         } else if let Some(root_uri) = params.root_uri
             && let Ok(path) = root_uri.to_file_path()
         {
+            // Canonicalize the workspace root, publishing URIs relative to the client's root
+            let normalizer = PathNormalizer::new(path.clone(), canonicalize_paths);
+            let path = normalizer.workspace_root().unwrap_or(path);
+            *self.path_normalizer.lock().await = normalizer.clone();
+
             // Store workspace root for dylint
             *self.workspace_root.lock().await = Some(path.clone());
 
             let mut scanner = self.file_scanner.lock().await;
             scanner.set_workspace_root(path.clone());
+            scanner.set_ignore_globs(ignore_globs.clone());
+            scanner.set_scan_tests(scan_tests);
 
-            // Perform initial workspace scan
+            // Perform initial workspace scan, reusing cached results for unchanged files
+            // Cached results do not cover test code, so skip the cache if any detector opts in
             info!("Performing initial workspace scan...");
             let mut registry = self.detector_registry.lock().await;
-            let scan_result = scanner.scan_workspace(&mut registry).await;
+            let cache_path = ScanCache::default_path(&path).filter(|_| test_detectors.is_empty());
+            let scan_result = match cache_path {
+                Some(cache_path) => {
+                    scanner
+                        .scan_workspace_cached(&mut registry, &cache_path)
+                        .await
+                }
+                None => scanner.scan_workspace(&mut registry).await,
+            };
             drop(registry); // Release registry lock before initializing dylint
             drop(scanner); // Release scanner lock
 
@@ -222,14 +477,21 @@ impl LanguageServer for Backend {
             );
 
             // Publish diagnostics for ALL scanned files (including empty diagnostics for fixed files)
+            let mut published_versions = HashMap::new();
             for file_info in &scan_result.rust_files {
-                if let Ok(uri) = tower_lsp::lsp_types::Url::from_file_path(&file_info.path) {
-                    self.client
-                        .publish_diagnostics(uri, file_info.diagnostics.clone(), None)
+                if let Some(uri) = normalizer.to_uri(&file_info.path) {
+                    let version = self
+                        .publish_diagnostics(
+                            uri,
+                            DiagnosticBuilder::normalize(file_info.diagnostics.clone()),
+                        )
                         .await;
+                    published_versions.insert(file_info.path.clone(), version);
                 }
             }
 
+            self.index_workspace_symbols(&scan_result).await;
+
             // Send scan results to extension (initial scan, not manual)
             let scan_summary = ScanSummary::from_scan_result(&scan_result, false);
             self.client
@@ -244,6 +506,7 @@ impl LanguageServer for Backend {
                 .send_notification::<DetectorStatusNotification>(DetectorStatus {
                     status: "initializing".to_string(),
                     message: "Initializing security detectors...".to_string(),
+                    ..Default::default()
                 })
                 .await;
 
@@ -254,12 +517,24 @@ impl LanguageServer for Backend {
                 let runner = Arc::clone(dylint_runner);
                 let workspace = path.clone();
                 let client = self.client.clone();
-                let file_list: Vec<(std::path::PathBuf, Vec<tower_lsp::lsp_types::Diagnostic>)> =
-                    scan_result
-                        .rust_files
-                        .iter()
-                        .map(|f| (f.path.clone(), f.diagnostics.clone()))
-                        .collect();
+                let sink = Arc::clone(&self.diagnostic_sink);
+                let publish_versions = Arc::clone(&self.publish_versions);
+                let documents = Arc::clone(&self.documents);
+                let position_encoding = *self.position_encoding.lock().await;
+                let diagnostic_mode = *self.diagnostic_mode.lock().await;
+                let dylint_cache = Arc::clone(&self.dylint_diagnostics);
+                let file_list: Vec<(
+                    std::path::PathBuf,
+                    Vec<tower_lsp::lsp_types::Diagnostic>,
+                    i32,
+                )> = scan_result
+                    .rust_files
+                    .iter()
+                    .filter_map(|f| {
+                        let version = *published_versions.get(&f.path)?;
+                        Some((f.path.clone(), f.diagnostics.clone(), version))
+                    })
+                    .collect();
 
                 tokio::spawn(async move {
                     info!("Running dylint on project open...");
@@ -269,10 +544,20 @@ impl LanguageServer for Backend {
                         .send_notification::<DetectorStatusNotification>(DetectorStatus {
                             status: "running".to_string(),
                             message: "Running security detectors...".to_string(),
+                            ..Default::default()
                         })
                         .await;
 
-                    match runner.run_lints(&workspace).await {
+                    let progress = WorkDoneProgressReporter::begin(
+                        &client,
+                        "Running security detectors",
+                        Some(format!("Linting {} files", file_list.len())),
+                    )
+                    .await;
+                    let lint_result = runner.run_lints(&workspace).await;
+                    progress.end(Some(Self::lint_summary(&lint_result))).await;
+
+                    match lint_result {
                         Ok(dylint_diagnostics) => {
                             info!(
                                 "Dylint found {} total issues on project open",
@@ -280,25 +565,28 @@ impl LanguageServer for Backend {
                             );
 
                             // Merge dylint diagnostics with syn diagnostics for each file
-                            for (file_path, syn_diagnostics) in file_list {
-                                if let Ok(uri) =
-                                    tower_lsp::lsp_types::Url::from_file_path(&file_path)
-                                {
+                            for (file_path, syn_diagnostics, version) in file_list {
+                                if let Some(uri) = normalizer.to_uri(&file_path) {
                                     // Filter dylint diagnostics for this file
-                                    let dylint_file_diagnostics: Vec<_> = dylint_diagnostics
-                                        .iter()
-                                        .filter(|d| {
-                                            let path_str = file_path.to_string_lossy();
-                                            path_str.ends_with(&d.file_name)
-                                                || path_str.contains(&d.file_name)
-                                        })
-                                        .map(|d| d.to_lsp_diagnostic(Some(&workspace)))
-                                        .collect();
+                                    let dylint_file_diagnostics: Vec<_> =
+                                        diagnostics_for_file(&dylint_diagnostics, &file_path)
+                                            .into_iter()
+                                            .map(|d| {
+                                                d.to_lsp_diagnostic(Some(&workspace), &normalizer)
+                                            })
+                                            .collect();
+                                    dylint_cache
+                                        .lock()
+                                        .await
+                                        .insert(uri.clone(), dylint_file_diagnostics.clone());
 
                                     if !dylint_file_diagnostics.is_empty() {
                                         // Merge syn and dylint diagnostics
-                                        let mut merged_diagnostics = syn_diagnostics.clone();
-                                        merged_diagnostics.extend(dylint_file_diagnostics.clone());
+                                        let merged_diagnostics =
+                                            DiagnosticBuilder::merge_and_normalize(
+                                                syn_diagnostics.clone(),
+                                                dylint_file_diagnostics.clone(),
+                                            );
 
                                         info!(
                                             "Publishing {} total diagnostics ({} syn + {} dylint) for {}",
@@ -308,31 +596,40 @@ impl LanguageServer for Backend {
                                             file_path.display()
                                         );
 
-                                        // Publish merged diagnostics
-                                        client
-                                            .publish_diagnostics(uri, merged_diagnostics, None)
-                                            .await;
+                                        // Publish merged diagnostics unless newer ones were published meanwhile
+                                        Self::publish_if_current(
+                                            &client,
+                                            sink.as_ref(),
+                                            &publish_versions,
+                                            &documents,
+                                            position_encoding,
+                                            diagnostic_mode,
+                                            uri,
+                                            merged_diagnostics,
+                                            version,
+                                        )
+                                        .await;
                                     }
                                 }
                             }
 
+                            Self::refresh_pulled_diagnostics(&client, diagnostic_mode).await;
+
                             // Notify complete
                             client
                                 .send_notification::<DetectorStatusNotification>(DetectorStatus {
                                     status: "complete".to_string(),
                                     message: "Security scan complete".to_string(),
+                                    ..Default::default()
                                 })
                                 .await;
                         }
                         Err(e) => {
                             info!("Dylint failed on project open: {}", e);
 
-                            // Notify complete even on error
+                            // Tell the extension why detectors did not run
                             client
-                                .send_notification::<DetectorStatusNotification>(DetectorStatus {
-                                    status: "complete".to_string(),
-                                    message: "Security scan complete".to_string(),
-                                })
+                                .send_notification::<DetectorStatusNotification>(e.status())
                                 .await;
                         }
                     }
@@ -346,11 +643,11 @@ impl LanguageServer for Backend {
                 version: Some(env!("CARGO_PKG_VERSION").to_string()),
             }),
             capabilities: ServerCapabilities {
-                position_encoding: Some(PositionEncodingKind::UTF16),
+                position_encoding: Some(position_encoding.kind()),
                 text_document_sync: Some(TextDocumentSyncCapability::Options(
                     TextDocumentSyncOptions {
                         open_close: Some(true),
-                        change: Some(TextDocumentSyncKind::FULL),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
                         save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
                             include_text: Some(true),
                         })),
@@ -358,53 +655,143 @@ impl LanguageServer for Backend {
                     },
                 )),
                 execute_command_provider: Some(tower_lsp::lsp_types::ExecuteCommandOptions {
-                    commands: vec!["workspace.scan".to_string()],
+                    commands: vec![
+                        "workspace.scan".to_string(),
+                        "solana.setVerboseMessages".to_string(),
+                        "solana.scanRevision".to_string(),
+                        "solana.detectorStats".to_string(),
+                        "solana.diagnosticsSummary".to_string(),
+                        "solana.exportScan".to_string(),
+                        "solana.prebuildDetectors".to_string(),
+                    ],
+                    work_done_progress_options: Default::default(),
+                }),
+                definition_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                    ..Default::default()
+                }),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
                     work_done_progress_options: Default::default(),
+                })),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                diagnostic_provider: pull_diagnostics.then(|| {
+                    DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                        identifier: Some("solana".to_string()),
+                        // Some detectors resolve accounts structs and init sites in other files
+                        inter_file_dependencies: true,
+                        workspace_diagnostics: false,
+                        work_done_progress_options: Default::default(),
+                    })
                 }),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            legend: SemanticTokenizer::legend(),
+                            full: Some(SemanticTokensFullOptions::Bool(true)),
+                            range: None,
+                            ..Default::default()
+                        },
+                    ),
+                ),
                 ..Default::default()
             },
         };
         Ok(result)
     }
     async fn shutdown(&self) -> Result<(), tower_lsp::jsonrpc::Error> {
+        // Stop any running workspace scan so the server can exit promptly
+        self.scan_cancelled.store(true, Ordering::Relaxed);
         Ok(())
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.documents.lock().await.insert(
+            params.text_document.uri.clone(),
+            params.text_document.text.clone(),
+            params.text_document.version,
+        );
+
         // Run detectors on file open
         self.on_change(params.text_document).await;
     }
 
-    async fn did_change(&self, _params: DidChangeTextDocumentParams) {
-        // Don't run detectors on change - only on save (consistent with rust-analyzer)
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let uri = params.text_document.uri;
+        let version = params.text_document.version;
+        let position_encoding = *self.position_encoding.lock().await;
+        if self
+            .documents
+            .lock()
+            .await
+            .apply_changes(&uri, version, params.content_changes, position_encoding)
+            .is_none()
+        {
+            warn!("Received changes for unknown document {}", uri);
+            return;
+        }
+
+        // Run syn detectors on the buffer once typing pauses; dylint still waits for save
+        let backend = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(CHANGE_DEBOUNCE).await;
+            backend.analyze_document(uri, version).await;
+        });
     }
 
-    async fn did_save(&self, _params: DidSaveTextDocumentParams) {
-        info!("File saved, reloading detectors and performing full workspace scan...");
-        info!("[DEBUG] About to initialize dylint detectors...");
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.lock().await.remove(&uri);
+        self.dylint_diagnostics.lock().await.remove(&uri);
 
-        // Create a new detector registry with fresh detector instances
-        let new_registry = create_default_registry();
+        // Clear the file's diagnostics; the new publish version also drops pending dylint results
+        self.publish_diagnostics(uri, Vec::new()).await;
+    }
 
-        // Replace the existing registry
-        {
-            let mut registry = self.detector_registry.lock().await;
-            *registry = new_registry;
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        if let Some(text) = params.text {
+            self.documents
+                .lock()
+                .await
+                .set_text(params.text_document.uri.clone(), text);
+        }
+
+        let uri = &params.text_document.uri;
+        if let Some(content) = self.document_content(uri).await {
+            self.symbol_index.lock().await.index_file(uri, &content);
         }
 
+        info!("File saved, reloading detectors and performing full workspace scan...");
+        info!("[DEBUG] About to initialize dylint detectors...");
+
+        // Replace the registry with fresh detector instances
+        self.reload_registry().await;
+
         // Trigger a full workspace scan with the reloaded detectors
         let scan_result = {
             let scanner = self.file_scanner.lock().await;
             let mut registry = self.detector_registry.lock().await;
-            scanner.scan_workspace(&mut registry).await
+            scanner
+                .scan_workspace_with_client(&mut registry, Some(&self.client))
+                .await
         };
 
-        // Publish syn diagnostics for ALL scanned files
+        // Publish syn diagnostics for ALL scanned files, keeping the dylint diagnostics of the
+        // last run until the run below replaces them (it only covers the saved file's package)
+        let normalizer = self.path_normalizer.lock().await.clone();
+        let mut published_versions = HashMap::new();
         for file_info in &scan_result.rust_files {
-            if let Ok(uri) = tower_lsp::lsp_types::Url::from_file_path(&file_info.path) {
-                self.client
-                    .publish_diagnostics(uri, file_info.diagnostics.clone(), None)
+            if let Some(uri) = normalizer.to_uri(&file_info.path) {
+                let diagnostics = self
+                    .with_cached_dylint(&uri, file_info.diagnostics.clone())
                     .await;
+                let version = self.publish_diagnostics(uri, diagnostics).await;
+                published_versions.insert(file_info.path.clone(), version);
             }
         }
 
@@ -422,94 +809,417 @@ impl LanguageServer for Backend {
             .send_notification::<DetectorStatusNotification>(DetectorStatus {
                 status: "running".to_string(),
                 message: "Running security detectors...".to_string(),
+                ..Default::default()
             })
             .await;
 
         Self::ensure_dylint_detectors_initialized(self).await;
 
         // Run dylint in background and merge with syn diagnostics
-        if let Some(dylint_runner) = &self.dylint_runner {
-            if let Some(workspace_root) = self.workspace_root.lock().await.as_ref() {
-                let runner = Arc::clone(dylint_runner);
-                let workspace = workspace_root.clone();
-                let client = self.client.clone();
-                // Create a simplified file list for dylint merging
-                let file_list: Vec<(std::path::PathBuf, Vec<tower_lsp::lsp_types::Diagnostic>)> =
-                    scan_result
-                        .rust_files
-                        .iter()
-                        .map(|f| (f.path.clone(), f.diagnostics.clone()))
-                        .collect();
+        if let Some(dylint_runner) = &self.dylint_runner
+            && let Some(workspace_root) = self.workspace_root.lock().await.as_ref()
+        {
+            let runner = Arc::clone(dylint_runner);
+            let workspace = workspace_root.clone();
+            let client = self.client.clone();
+            let sink = Arc::clone(&self.diagnostic_sink);
+            // Create a simplified file list for dylint merging
+            let publish_versions = Arc::clone(&self.publish_versions);
+            let documents = Arc::clone(&self.documents);
+            let position_encoding = *self.position_encoding.lock().await;
+            let diagnostic_mode = *self.diagnostic_mode.lock().await;
+            let dylint_cache = Arc::clone(&self.dylint_diagnostics);
+            let file_list: Vec<(
+                std::path::PathBuf,
+                Vec<tower_lsp::lsp_types::Diagnostic>,
+                i32,
+            )> = scan_result
+                .rust_files
+                .iter()
+                .filter_map(|f| {
+                    let version = *published_versions.get(&f.path)?;
+                    Some((f.path.clone(), f.diagnostics.clone(), version))
+                })
+                .collect();
 
-                tokio::spawn(async move {
-                    info!("Running dylint after save...");
-                    match runner.run_lints(&workspace).await {
-                        Ok(dylint_diagnostics) => {
-                            info!(
-                                "Dylint found {} total issues after save",
-                                dylint_diagnostics.len()
-                            );
+            let saved_file = params.text_document.uri.to_file_path().ok();
+            // Package linted below; `None` lints the whole workspace
+            let linted_package = saved_file
+                .as_deref()
+                .and_then(DylintRunner::package_for_file);
 
-                            // Merge dylint diagnostics with syn diagnostics for each file
-                            for (file_path, syn_diagnostics) in file_list {
-                                if let Ok(uri) =
-                                    tower_lsp::lsp_types::Url::from_file_path(&file_path)
-                                {
-                                    // Filter dylint diagnostics for this file
-                                    let dylint_file_diagnostics: Vec<_> = dylint_diagnostics
-                                        .iter()
-                                        .filter(|d| {
-                                            let path_str = file_path.to_string_lossy();
-                                            path_str.ends_with(&d.file_name)
-                                                || path_str.contains(&d.file_name)
-                                        })
-                                        .map(|d| d.to_lsp_diagnostic(Some(&workspace)))
-                                        .collect();
+            tokio::spawn(async move {
+                info!("Running dylint after save...");
+                let progress = WorkDoneProgressReporter::begin(
+                    &client,
+                    "Running security detectors",
+                    Some(format!("Linting {} files", file_list.len())),
+                )
+                .await;
+                let lint_result = match &saved_file {
+                    Some(saved_file) => runner.run_lints_for_file(&workspace, saved_file).await,
+                    None => runner.run_lints(&workspace).await,
+                };
+                progress.end(Some(Self::lint_summary(&lint_result))).await;
 
-                                    if !dylint_file_diagnostics.is_empty() {
-                                        // Merge syn and dylint diagnostics
-                                        let mut merged_diagnostics = syn_diagnostics.clone();
-                                        merged_diagnostics.extend(dylint_file_diagnostics.clone());
+                match lint_result {
+                    Ok(dylint_diagnostics) => {
+                        info!(
+                            "Dylint found {} total issues after save",
+                            dylint_diagnostics.len()
+                        );
 
-                                        info!(
-                                            "Publishing {} total diagnostics ({} syn + {} dylint) for {}",
-                                            merged_diagnostics.len(),
-                                            syn_diagnostics.len(),
-                                            dylint_file_diagnostics.len(),
-                                            file_path.display()
-                                        );
+                        // Merge dylint diagnostics with syn diagnostics for each linted file
+                        // Files of other packages keep the cached diagnostics published above
+                        for (file_path, syn_diagnostics, version) in file_list {
+                            if linted_package.is_some()
+                                && DylintRunner::package_for_file(&file_path) != linted_package
+                            {
+                                continue;
+                            }
 
-                                        // Publish merged diagnostics
-                                        client
-                                            .publish_diagnostics(uri, merged_diagnostics, None)
-                                            .await;
-                                    }
+                            if let Some(uri) = normalizer.to_uri(&file_path) {
+                                // Filter dylint diagnostics for this file
+                                let dylint_file_diagnostics: Vec<_> =
+                                    diagnostics_for_file(&dylint_diagnostics, &file_path)
+                                        .into_iter()
+                                        .map(|d| d.to_lsp_diagnostic(Some(&workspace), &normalizer))
+                                        .collect();
+                                let cached = dylint_cache
+                                    .lock()
+                                    .await
+                                    .insert(uri.clone(), dylint_file_diagnostics.clone())
+                                    .unwrap_or_default();
+
+                                // Republish when there is something to add or stale results to drop
+                                if !dylint_file_diagnostics.is_empty() || !cached.is_empty() {
+                                    // Merge syn and dylint diagnostics
+                                    let merged_diagnostics = DiagnosticBuilder::merge_and_normalize(
+                                        syn_diagnostics.clone(),
+                                        dylint_file_diagnostics.clone(),
+                                    );
+
+                                    info!(
+                                        "Publishing {} total diagnostics ({} syn + {} dylint) for {}",
+                                        merged_diagnostics.len(),
+                                        syn_diagnostics.len(),
+                                        dylint_file_diagnostics.len(),
+                                        file_path.display()
+                                    );
+
+                                    // Publish merged diagnostics unless newer ones were published meanwhile
+                                    Self::publish_if_current(
+                                        &client,
+                                        sink.as_ref(),
+                                        &publish_versions,
+                                        &documents,
+                                        position_encoding,
+                                        diagnostic_mode,
+                                        uri,
+                                        merged_diagnostics,
+                                        version,
+                                    )
+                                    .await;
                                 }
                             }
-
-                            // Notify complete
-                            client
-                                .send_notification::<DetectorStatusNotification>(DetectorStatus {
-                                    status: "complete".to_string(),
-                                    message: "Security scan complete".to_string(),
-                                })
-                                .await;
                         }
-                        Err(e) => {
-                            info!("Dylint failed after save: {}", e);
 
-                            // Notify complete even on error
-                            client
-                                .send_notification::<DetectorStatusNotification>(DetectorStatus {
-                                    status: "complete".to_string(),
-                                    message: "Security scan complete".to_string(),
-                                })
-                                .await;
-                        }
+                        Self::refresh_pulled_diagnostics(&client, diagnostic_mode).await;
+
+                        // Notify complete
+                        client
+                            .send_notification::<DetectorStatusNotification>(DetectorStatus {
+                                status: "complete".to_string(),
+                                message: "Security scan complete".to_string(),
+                                ..Default::default()
+                            })
+                            .await;
                     }
-                });
-            }
+                    Err(e) => {
+                        info!("Dylint failed after save: {}", e);
+
+                        // Tell the extension why detectors did not run
+                        client
+                            .send_notification::<DetectorStatusNotification>(e.status())
+                            .await;
+                    }
+                }
+            });
+        }
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> JsonRpcResult<Option<SemanticTokensResult>> {
+        let Some(content) = self.document_content(&params.text_document.uri).await else {
+            return Ok(None);
+        };
+
+        let data = SemanticTokenizer::tokenize(&content);
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data,
+        })))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> JsonRpcResult<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let (Some(content), Ok(current_path)) =
+            (self.document_content(&uri).await, uri.to_file_path())
+        else {
+            return Ok(None);
+        };
+        let position_encoding = *self.position_encoding.lock().await;
+        let position = position_encoding
+            .decode_position(&content, params.text_document_position_params.position);
+
+        // Cheap check before walking the workspace
+        if DefinitionProvider::context_accounts_at(&content, position).is_none() {
+            return Ok(None);
+        }
+
+        let workspace_files = self.file_scanner.lock().await.rust_files();
+        let Some((path, range)) = DefinitionProvider::new().find_definition(
+            &current_path,
+            &content,
+            position,
+            &workspace_files,
+        ) else {
+            return Ok(None);
+        };
+
+        let (target_uri, range) = if path == current_path {
+            (Some(uri), position_encoding.encode_range(&content, range))
+        } else {
+            let target_content = std::fs::read_to_string(&path).unwrap_or_default();
+            (
+                self.path_normalizer.lock().await.to_uri(&path),
+                position_encoding.encode_range(&target_content, range),
+            )
+        };
+
+        Ok(target_uri.map(|uri| GotoDefinitionResponse::Scalar(Location { uri, range })))
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> JsonRpcResult<Option<Vec<SymbolInformation>>> {
+        Ok(Some(self.symbol_index.lock().await.query(&params.query)))
+    }
+
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> JsonRpcResult<Option<Vec<SelectionRange>>> {
+        let Some(content) = self.document_content(&params.text_document.uri).await else {
+            return Ok(None);
+        };
+
+        let position_encoding = *self.position_encoding.lock().await;
+        let positions: Vec<Position> = params
+            .positions
+            .iter()
+            .map(|&position| position_encoding.decode_position(&content, position))
+            .collect();
+
+        Ok(
+            SelectionRangeProvider::selection_ranges(&content, &positions).map(|mut ranges| {
+                for selection in &mut ranges {
+                    let mut selection = Some(selection);
+                    while let Some(current) = selection {
+                        current.range = position_encoding.encode_range(&content, current.range);
+                        selection = current.parent.as_deref_mut();
+                    }
+                }
+                ranges
+            }),
+        )
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> JsonRpcResult<Option<Vec<InlayHint>>> {
+        let Some(content) = self.document_content(&params.text_document.uri).await else {
+            return Ok(None);
+        };
+
+        let range = params.range;
+        let position_encoding = *self.position_encoding.lock().await;
+        let hints = InlayHintProvider::inlay_hints(&content)
+            .into_iter()
+            .map(|mut hint| {
+                hint.position = position_encoding.encode_position(&content, hint.position);
+                hint
+            })
+            .filter(|hint| range.start <= hint.position && hint.position <= range.end)
+            .collect();
+
+        Ok(Some(hints))
+    }
+
+    async fn hover(&self, params: HoverParams) -> JsonRpcResult<Option<Hover>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        // Match against the diagnostics the client shows, not a fresh analysis
+        // Both the position and the published ranges count columns in the negotiated encoding
+        let diagnostics = self.publish_versions.lock().await.published(uri).to_vec();
+        let registry = self.detector_registry.lock().await;
+
+        Ok(HoverProvider::hover(&diagnostics, position, &registry))
+    }
+
+    async fn completion(
+        &self,
+        params: CompletionParams,
+    ) -> JsonRpcResult<Option<CompletionResponse>> {
+        let Some(content) = self
+            .document_content(&params.text_document_position.text_document.uri)
+            .await
+        else {
+            return Ok(None);
+        };
+        let position = self
+            .position_encoding
+            .lock()
+            .await
+            .decode_position(&content, params.text_document_position.position);
+
+        Ok(CompletionProvider::completions(&content, position).map(CompletionResponse::Array))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> JsonRpcResult<Option<PrepareRenameResponse>> {
+        let Some(content) = self.document_content(&params.text_document.uri).await else {
+            return Ok(None);
+        };
+
+        let position_encoding = *self.position_encoding.lock().await;
+        let position = position_encoding.decode_position(&content, params.position);
+
+        Ok(
+            RenameProvider::prepare_rename(&content, position).map(|(range, _)| {
+                PrepareRenameResponse::Range(position_encoding.encode_range(&content, range))
+            }),
+        )
+    }
+
+    async fn rename(&self, params: RenameParams) -> JsonRpcResult<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let (Some(content), Ok(current_path)) =
+            (self.document_content(&uri).await, uri.to_file_path())
+        else {
+            return Ok(None);
+        };
+        let position_encoding = *self.position_encoding.lock().await;
+        let position =
+            position_encoding.decode_position(&content, params.text_document_position.position);
+
+        if RenameProvider::prepare_rename(&content, position).is_none() {
+            return Ok(None);
+        }
+        if syn::parse_str::<syn::Ident>(&params.new_name).is_err() {
+            return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "'{}' is not a valid identifier",
+                params.new_name
+            )));
+        }
+
+        let workspace_files = self.file_scanner.lock().await.rust_files();
+        let Some(file_edits) = RenameProvider::new().rename(
+            &current_path,
+            &content,
+            position,
+            &params.new_name,
+            &workspace_files,
+        ) else {
+            return Ok(None);
+        };
+
+        let normalizer = self.path_normalizer.lock().await;
+        let changes: HashMap<Url, Vec<TextEdit>> = file_edits
+            .into_iter()
+            .filter_map(|(path, mut edits)| {
+                let (target_uri, target_content) = if path == current_path {
+                    (Some(uri.clone()), content.clone())
+                } else {
+                    (
+                        normalizer.to_uri(&path),
+                        std::fs::read_to_string(&path).unwrap_or_default(),
+                    )
+                };
+                for edit in &mut edits {
+                    edit.range = position_encoding.encode_range(&target_content, edit.range);
+                }
+                target_uri.map(|uri| (uri, edits))
+            })
+            .collect();
+
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    async fn code_action(
+        &self,
+        params: CodeActionParams,
+    ) -> JsonRpcResult<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let Some(content) = self.document_content(&uri).await else {
+            return Ok(None);
+        };
+
+        let position_encoding = *self.position_encoding.lock().await;
+        let actions = CodeActionProvider::code_actions(
+            &content,
+            &uri,
+            &params.context.diagnostics,
+            position_encoding,
+        );
+        if actions.is_empty() {
+            return Ok(None);
         }
+
+        Ok(Some(
+            actions
+                .into_iter()
+                .map(CodeActionOrCommand::CodeAction)
+                .collect(),
+        ))
+    }
+
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> JsonRpcResult<DocumentDiagnosticReportResult> {
+        let uri = params.text_document.uri;
+        let items = match self.document_content(&uri).await {
+            Some(content) => {
+                let syn_diagnostics = {
+                    let mut registry = self.detector_registry.lock().await;
+                    let file_path = uri.to_file_path().ok();
+                    registry.analyze(&content, file_path.as_ref())
+                };
+                let mut items = self.with_cached_dylint(&uri, syn_diagnostics).await;
+                let position_encoding = *self.position_encoding.lock().await;
+                DiagnosticBuilder::encode_positions(&mut items, &content, position_encoding);
+                items
+            }
+            None => Vec::new(),
+        };
+
+        Ok(DocumentDiagnosticReportResult::Report(
+            DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+                related_documents: None,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    result_id: None,
+                    items,
+                },
+            }),
+        ))
     }
 
     async fn execute_command(
@@ -522,15 +1232,20 @@ impl LanguageServer for Backend {
                 let scan_result = {
                     let scanner = self.file_scanner.lock().await;
                     let mut registry = self.detector_registry.lock().await;
-                    scanner.scan_workspace(&mut registry).await
+                    scanner
+                        .scan_workspace_with_client(&mut registry, Some(&self.client))
+                        .await
                 };
 
                 // Publish diagnostics for ALL scanned files (including empty diagnostics for fixed files)
+                let normalizer = self.path_normalizer.lock().await.clone();
                 for file_info in &scan_result.rust_files {
-                    if let Ok(uri) = tower_lsp::lsp_types::Url::from_file_path(&file_info.path) {
-                        self.client
-                            .publish_diagnostics(uri, file_info.diagnostics.clone(), None)
-                            .await;
+                    if let Some(uri) = normalizer.to_uri(&file_info.path) {
+                        self.publish_diagnostics(
+                            uri,
+                            DiagnosticBuilder::normalize(file_info.diagnostics.clone()),
+                        )
+                        .await;
                     }
                 }
 
@@ -549,28 +1264,27 @@ impl LanguageServer for Backend {
             "solana.reloadDetectors" => {
                 info!("Reloading all detectors");
 
-                // Create a new detector registry with fresh detector instances
-                let new_registry = create_default_registry();
-
-                // Replace the existing registry
-                {
-                    let mut registry = self.detector_registry.lock().await;
-                    *registry = new_registry;
-                }
+                // Replace the registry with fresh detector instances
+                self.reload_registry().await;
 
                 // Trigger a full workspace scan with the new detectors
                 let scan_result = {
                     let scanner = self.file_scanner.lock().await;
                     let mut registry = self.detector_registry.lock().await;
-                    scanner.scan_workspace(&mut registry).await
+                    scanner
+                        .scan_workspace_with_client(&mut registry, Some(&self.client))
+                        .await
                 };
 
                 // Publish diagnostics for ALL scanned files (including empty diagnostics for fixed files)
+                let normalizer = self.path_normalizer.lock().await.clone();
                 for file_info in &scan_result.rust_files {
-                    if let Ok(uri) = tower_lsp::lsp_types::Url::from_file_path(&file_info.path) {
-                        self.client
-                            .publish_diagnostics(uri, file_info.diagnostics.clone(), None)
-                            .await;
+                    if let Some(uri) = normalizer.to_uri(&file_info.path) {
+                        self.publish_diagnostics(
+                            uri,
+                            DiagnosticBuilder::normalize(file_info.diagnostics.clone()),
+                        )
+                        .await;
                     }
                 }
 
@@ -585,6 +1299,234 @@ impl LanguageServer for Backend {
                     "message": "Detectors reloaded and workspace rescanned"
                 })))
             }
+            "solana.setVerboseMessages" => {
+                let Some(verbose) = params.arguments.first().and_then(|arg| arg.as_bool()) else {
+                    return Err(tower_lsp::jsonrpc::Error::invalid_params(
+                        "solana.setVerboseMessages expects a boolean argument",
+                    ));
+                };
+
+                info!("Setting verbose diagnostic messages: {}", verbose);
+                *self.verbose_messages.lock().await = verbose;
+
+                // Re-run detectors so published diagnostics use the new message form
+                let scan_result = {
+                    let scanner = self.file_scanner.lock().await;
+                    let mut registry = self.detector_registry.lock().await;
+                    registry.set_verbose_messages(verbose);
+                    scanner
+                        .scan_workspace_with_client(&mut registry, Some(&self.client))
+                        .await
+                };
+
+                let normalizer = self.path_normalizer.lock().await.clone();
+                for file_info in &scan_result.rust_files {
+                    if let Some(uri) = normalizer.to_uri(&file_info.path) {
+                        self.publish_diagnostics(
+                            uri,
+                            DiagnosticBuilder::normalize(file_info.diagnostics.clone()),
+                        )
+                        .await;
+                    }
+                }
+
+                Ok(Some(serde_json::json!({
+                    "success": true,
+                    "verbose": verbose
+                })))
+            }
+            "solana.scanRevision" => {
+                let Some(revision) = params.arguments.first().and_then(|arg| arg.as_str()) else {
+                    return Err(tower_lsp::jsonrpc::Error::invalid_params(
+                        "solana.scanRevision expects a git ref argument",
+                    ));
+                };
+                if revision.is_empty() || revision.starts_with('-') {
+                    return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                        "Invalid git ref: {}",
+                        revision
+                    )));
+                }
+
+                let Some(root) = self.workspace_root.lock().await.clone() else {
+                    return Err(tower_lsp::jsonrpc::Error::invalid_params(
+                        "solana.scanRevision requires an open workspace",
+                    ));
+                };
+
+                info!("Scanning revision {}", revision);
+                let provider = GitContentProvider::new(root);
+
+                // git runs on a blocking thread, without holding the scanner or registry locks
+                let reader = self.file_scanner.lock().await.clone();
+                let git_revision = revision.to_string();
+                let files = tokio::task::spawn_blocking(move || {
+                    reader.read_revision(&provider, &git_revision)
+                })
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("Revision read task failed: {}", e)));
+
+                let scan_result = match files {
+                    Ok(files) => {
+                        let scanner = self.file_scanner.lock().await;
+                        let mut registry = self.detector_registry.lock().await;
+                        Ok(scanner.scan_revision_files(files, revision, &mut registry))
+                    }
+                    Err(e) => Err(e),
+                };
+
+                // Historical results are returned to the caller, not published as live diagnostics
+                match scan_result {
+                    Ok(scan_result) => {
+                        let normalizer = self.path_normalizer.lock().await.clone();
+                        let files: Vec<serde_json::Value> = scan_result
+                            .files_with_issues()
+                            .into_iter()
+                            .map(|file_info| {
+                                serde_json::json!({
+                                    "path": normalizer.to_client_path(&file_info.path),
+                                    "diagnostics": file_info.diagnostics,
+                                })
+                            })
+                            .collect();
+
+                        Ok(Some(serde_json::json!({
+                            "success": true,
+                            "revision": revision,
+                            "total_files": scan_result.rust_files.len(),
+                            "total_issues": scan_result.total_issues(),
+                            "files": files
+                        })))
+                    }
+                    Err(e) => {
+                        warn!("Revision scan failed: {}", e);
+                        Ok(Some(serde_json::json!({
+                            "success": false,
+                            "revision": revision,
+                            "error": e.to_string()
+                        })))
+                    }
+                }
+            }
+            "solana.detectorStats" => {
+                info!("Timing detectors across the workspace");
+                let timings = {
+                    let scanner = self.file_scanner.lock().await;
+                    let mut registry = self.detector_registry.lock().await;
+                    BackendManager::get_detector_timings(&mut registry, &scanner).await
+                };
+
+                let detectors: Vec<serde_json::Value> = timings
+                    .iter()
+                    .map(|timing| {
+                        serde_json::json!({
+                            "id": timing.id,
+                            "duration_ms": timing.duration.map(|duration| duration.as_secs_f64() * 1000.0),
+                            "count": timing.count,
+                        })
+                    })
+                    .collect();
+
+                Ok(Some(serde_json::json!({
+                    "success": true,
+                    "detectors": detectors
+                })))
+            }
+            "solana.diagnosticsSummary" => {
+                info!("Summarizing workspace diagnostics");
+                let scan_result = {
+                    let scanner = self.file_scanner.lock().await;
+                    let mut registry = self.detector_registry.lock().await;
+                    scanner.scan_workspace(&mut registry).await
+                };
+
+                let scan_summary = ScanSummary::from_scan_result(&scan_result, true);
+                Ok(Some(serde_json::json!({
+                    "success": true,
+                    "by_detector": scan_summary.by_detector,
+                    "by_severity": SeverityCounts::from_scan_result(&scan_result),
+                    "total_files": scan_summary.total_rust_files,
+                    "files_with_issues": scan_summary.files_with_issues
+                })))
+            }
+            "solana.prebuildDetectors" => {
+                info!("Prebuilding dylint detectors");
+                if !self.ensure_cargo_or_report().await {
+                    return Ok(Some(serde_json::json!({
+                        "success": false,
+                        "error": "cargo is not installed"
+                    })));
+                }
+                if !DylintDetectorManager::ensure_nightly_or_report(&self.client).await {
+                    return Ok(Some(serde_json::json!({
+                        "success": false,
+                        "error": format!("Rust {} is not installed", REQUIRED_NIGHTLY_VERSION)
+                    })));
+                }
+
+                // Build before initializing, so the counts tell fresh builds from cache hits
+                let Some(manager) = self.take_dylint_manager().await else {
+                    return Ok(Some(serde_json::json!({
+                        "success": false,
+                        "error": "Dylint detectors are not available"
+                    })));
+                };
+                let prebuild = manager.prebuild_detectors(Some(&self.client)).await;
+                *self.dylint_manager.lock().await = Some(manager);
+
+                // Load the now cached detectors into the runner
+                Self::ensure_dylint_detectors_initialized(self).await;
+
+                match prebuild {
+                    Ok(summary) => {
+                        self.client
+                            .send_notification::<DetectorStatusNotification>(DetectorStatus {
+                                status: "complete".to_string(),
+                                message: format!(
+                                    "Detectors ready: {} compiled, {} cached",
+                                    summary.compiled, summary.cached
+                                ),
+                                ..Default::default()
+                            })
+                            .await;
+
+                        Ok(Some(serde_json::json!({
+                            "success": true,
+                            "compiled": summary.compiled,
+                            "cached": summary.cached,
+                            "failed": summary.failed
+                        })))
+                    }
+                    Err(e) => {
+                        warn!("Prebuilding detectors failed: {}", e);
+                        Ok(Some(serde_json::json!({
+                            "success": false,
+                            "error": e.to_string()
+                        })))
+                    }
+                }
+            }
+            "solana.exportScan" => {
+                info!("Exporting full workspace scan result");
+                let scan_result = {
+                    let scanner = self.file_scanner.lock().await;
+                    let mut registry = self.detector_registry.lock().await;
+                    scanner.scan_workspace(&mut registry).await
+                };
+
+                // Paths that are not valid UTF-8 cannot be represented in JSON
+                let scan = serde_json::to_value(&scan_result).map_err(|e| {
+                    warn!("Failed to serialize scan result: {}", e);
+                    tower_lsp::jsonrpc::Error::internal_error()
+                })?;
+
+                Ok(Some(serde_json::json!({
+                    "success": true,
+                    "total_files": scan_result.rust_files.len(),
+                    "total_issues": scan_result.total_issues(),
+                    "scan": scan
+                })))
+            }
             _ => Ok(None),
         }
     }
@@ -595,13 +1537,129 @@ impl Backend {
         // Try to initialize dylint runner (for pre-compiled detectors)
         let dylint_runner = Self::try_init_dylint_runner();
 
+        // The scanner stays locked for a whole scan, so shutdown signals it through a shared flag
+        let scan_cancelled = Arc::new(AtomicBool::new(false));
+        let mut file_scanner = FileScanner::default();
+        file_scanner.set_cancel_flag(Arc::clone(&scan_cancelled));
+
         Backend {
             client,
             detector_registry: Arc::new(Mutex::new(create_default_registry())),
-            file_scanner: Arc::new(Mutex::new(FileScanner::default())),
+            file_scanner: Arc::new(Mutex::new(file_scanner)),
             dylint_runner,
             dylint_manager: Arc::new(Mutex::new(None)),
             workspace_root: Arc::new(Mutex::new(None)),
+            path_normalizer: Arc::new(Mutex::new(PathNormalizer::default())),
+            verbose_messages: Arc::new(Mutex::new(true)),
+            test_detectors: Arc::new(Mutex::new(Vec::new())),
+            documents: Arc::new(Mutex::new(DocumentStore::default())),
+            symbol_index: Arc::new(Mutex::new(WorkspaceSymbolIndex::default())),
+            diagnostic_sink: sink_from_env(),
+            publish_versions: Arc::new(Mutex::new(PublishVersions::default())),
+            scan_cancelled,
+            cargo_missing_reported: Arc::new(AtomicBool::new(false)),
+            dylint_diagnostics: Arc::new(Mutex::new(HashMap::new())),
+            position_encoding: Arc::new(Mutex::new(PositionEncoding::default())),
+            diagnostic_mode: Arc::new(Mutex::new(DiagnosticMode::default())),
+        }
+    }
+
+    /// Replace the detector registry with fresh detector instances, keeping user settings
+    async fn reload_registry(&self) {
+        let mut new_registry = create_default_registry();
+        new_registry.set_verbose_messages(*self.verbose_messages.lock().await);
+        for detector_id in self.test_detectors.lock().await.iter() {
+            new_registry.set_include_tests(detector_id, true);
+        }
+
+        let mut registry = self.detector_registry.lock().await;
+        *registry = new_registry;
+    }
+
+    /// Run syn detectors on the buffer of an open document and publish the results
+    /// together with the file's dylint diagnostics from the last save
+    /// Skipped if the document changed again since `version`, a newer run is pending then
+    async fn analyze_document(&self, uri: Url, version: i32) {
+        let text = match self.documents.lock().await.get(&uri) {
+            Some(document) if document.version == version => document.text.clone(),
+            _ => return,
+        };
+
+        let syn_diagnostics = {
+            let mut registry = self.detector_registry.lock().await;
+            let file_path = uri.to_file_path().ok();
+            registry.analyze(&text, file_path.as_ref())
+        };
+
+        let diagnostics = self.with_cached_dylint(&uri, syn_diagnostics).await;
+        self.publish_diagnostics(uri, diagnostics).await;
+    }
+
+    /// Merge the syn diagnostics of a file with its dylint diagnostics from the last run
+    async fn with_cached_dylint(
+        &self,
+        uri: &Url,
+        syn_diagnostics: Vec<tower_lsp::lsp_types::Diagnostic>,
+    ) -> Vec<tower_lsp::lsp_types::Diagnostic> {
+        let dylint_diagnostics = self
+            .dylint_diagnostics
+            .lock()
+            .await
+            .get(uri)
+            .cloned()
+            .unwrap_or_default();
+        DiagnosticBuilder::merge_and_normalize(syn_diagnostics, dylint_diagnostics)
+    }
+
+    /// Rebuild the workspace symbol index from the files of a scan
+    async fn index_workspace_symbols(&self, scan_result: &ScanResult) {
+        let normalizer = self.path_normalizer.lock().await.clone();
+        let mut index = WorkspaceSymbolIndex::default();
+        for file_info in &scan_result.rust_files {
+            if let Some(uri) = normalizer.to_uri(&file_info.path)
+                && let Ok(content) = std::fs::read_to_string(&file_info.path)
+            {
+                index.index_file(&uri, &content);
+            }
+        }
+        *self.symbol_index.lock().await = index;
+    }
+
+    /// Get the latest content of a document, falling back to reading it from disk
+    async fn document_content(&self, uri: &Url) -> Option<String> {
+        Self::content_of(&self.documents, uri).await
+    }
+
+    async fn content_of(documents: &Mutex<DocumentStore>, uri: &Url) -> Option<String> {
+        if let Some(document) = documents.lock().await.get(uri) {
+            return Some(document.text.clone());
+        }
+
+        let path = uri.to_file_path().ok()?;
+        std::fs::read_to_string(path).ok()
+    }
+
+    /// Version of an open document as sent by the client, `None` if it is not open
+    async fn document_version(documents: &Mutex<DocumentStore>, uri: &Url) -> Option<i32> {
+        documents
+            .lock()
+            .await
+            .get(uri)
+            .map(|document| document.version)
+    }
+
+    /// Convert the char columns computed by detectors to the negotiated position encoding
+    async fn encode_positions(
+        documents: &Mutex<DocumentStore>,
+        position_encoding: PositionEncoding,
+        uri: &Url,
+        diagnostics: &mut [tower_lsp::lsp_types::Diagnostic],
+    ) {
+        if diagnostics.is_empty() {
+            return;
+        }
+        if let Some(content) = Self::content_of(documents, uri).await {
+            DiagnosticBuilder::encode_positions(diagnostics, &content, position_encoding);
         }
     }
 
@@ -634,16 +1692,13 @@ impl Backend {
 
         info!("[Extension Dylint] Initializing detectors on first save...");
 
+        // Without cargo neither the toolchain check nor dylint itself can run
+        if !self.ensure_cargo_or_report().await {
+            return;
+        }
+
         // Check if the required nightly version is available
-        if !DylintDetectorManager::check_nightly_available() {
-            warn!(
-                "[Extension Dylint] Required nightly Rust version not available: {}",
-                REQUIRED_NIGHTLY_VERSION
-            );
-            warn!(
-                "[Extension Dylint] Install with: rustup toolchain install {}",
-                REQUIRED_NIGHTLY_VERSION
-            );
+        if !DylintDetectorManager::ensure_nightly_or_report(&self.client).await {
             return;
         }
 
@@ -658,6 +1713,44 @@ impl Backend {
             return;
         }
 
+        let Some(mut manager) = self.take_dylint_manager().await else {
+            return;
+        };
+
+        // Initialize (will check cache and compile if needed)
+        match manager.initialize().await {
+            Ok(compiled_paths) => {
+                if !compiled_paths.is_empty() {
+                    info!(
+                        "[Extension Dylint] Successfully initialized {} detector(s)",
+                        compiled_paths.len()
+                    );
+
+                    // Add compiled detectors to dylint_runner
+                    if let Some(dylint_runner) = &self.dylint_runner {
+                        dylint_runner.add_workspace_detectors(compiled_paths);
+                        info!("[Extension Dylint] Detectors added to dylint runner");
+                    } else {
+                        warn!(
+                            "[Extension Dylint] Dylint runner not available, cannot add detectors"
+                        );
+                    }
+                } else {
+                    info!("[Extension Dylint] No detectors found in extension");
+                }
+            }
+            Err(e) => {
+                warn!("[Extension Dylint] Failed to initialize detectors: {}", e);
+            }
+        }
+
+        // Store manager back
+        *self.dylint_manager.lock().await = Some(manager);
+    }
+
+    /// Take the dylint manager out of its slot (creating it on first use) and point it at
+    /// the detectors bundled with the extension. Store it back when done.
+    async fn take_dylint_manager(&self) -> Option<DylintDetectorManager> {
         // Get extension path (where detectors are bundled)
         let extension_path = match std::env::current_exe() {
             Ok(exe_path) => exe_path
@@ -671,7 +1764,7 @@ impl Backend {
             warn!(
                 "[Extension Dylint] Could not determine extension path, skipping detector initialization"
             );
-            return;
+            return None;
         };
 
         info!(
@@ -686,42 +1779,30 @@ impl Backend {
                 Ok(m) => m,
                 Err(e) => {
                     warn!("[Extension Dylint] Failed to create manager: {}", e);
-                    return;
+                    return None;
                 }
             },
         };
 
         manager.set_extension_path(extension_path);
+        Some(manager)
+    }
 
-        // Initialize (will check cache and compile if needed)
-        match manager.initialize().await {
-            Ok(compiled_paths) => {
-                if !compiled_paths.is_empty() {
-                    info!(
-                        "[Extension Dylint] Successfully initialized {} detector(s)",
-                        compiled_paths.len()
-                    );
-
-                    // Add compiled detectors to dylint_runner
-                    if let Some(dylint_runner) = &self.dylint_runner {
-                        dylint_runner.add_workspace_detectors(compiled_paths);
-                        info!("[Extension Dylint] Detectors added to dylint runner");
-                    } else {
-                        warn!(
-                            "[Extension Dylint] Dylint runner not available, cannot add detectors"
-                        );
-                    }
-                } else {
-                    info!("[Extension Dylint] No detectors found in extension");
-                }
-            }
-            Err(e) => {
-                warn!("[Extension Dylint] Failed to initialize detectors: {}", e);
-            }
+    /// Check that cargo can be run, telling the extension the first time it cannot
+    /// The result is cached by the runner, so dylint stays disabled for the session
+    async fn ensure_cargo_or_report(&self) -> bool {
+        let Some(dylint_runner) = &self.dylint_runner else {
+            // Nothing would run cargo for dylint
+            return true;
+        };
+        if dylint_runner.cargo_available().await {
+            return true;
         }
 
-        // Store manager back
-        *self.dylint_manager.lock().await = Some(manager);
+        if !self.cargo_missing_reported.swap(true, Ordering::SeqCst) {
+            DylintDetectorManager::report_cargo_missing(&self.client).await;
+        }
+        false
     }
 
     fn try_init_dylint_runner() -> Option<Arc<DylintRunner>> {
@@ -750,6 +1831,81 @@ impl Backend {
         }
     }
 
+    /// Publish diagnostics for a file and record them in the diagnostic sink
+    /// Returns the publish version, to be passed to `publish_if_current` by background runs
+    /// The client is sent the version of the open document instead, or none for closed files
+    /// Clients pulling diagnostics are not sent anything, they are only recorded for hover
+    async fn publish_diagnostics(
+        &self,
+        uri: Url,
+        mut diagnostics: Vec<tower_lsp::lsp_types::Diagnostic>,
+    ) -> i32 {
+        let position_encoding = *self.position_encoding.lock().await;
+        let diagnostic_mode = *self.diagnostic_mode.lock().await;
+        Self::encode_positions(&self.documents, position_encoding, &uri, &mut diagnostics).await;
+        let document_version = Self::document_version(&self.documents, &uri).await;
+
+        // Hold the lock while publishing so diagnostics reach the client in order
+        let mut publish_versions = self.publish_versions.lock().await;
+        let version = publish_versions.next(&uri);
+        publish_versions.record(&uri, &diagnostics);
+        self.diagnostic_sink.record(&uri, &diagnostics);
+        if diagnostic_mode == DiagnosticMode::Push {
+            self.client
+                .publish_diagnostics(uri, diagnostics, document_version)
+                .await;
+        }
+        version
+    }
+
+    /// Publish diagnostics computed from those published as `version` (e.g. merged with dylint)
+    /// Dropped if diagnostics were published for the file since, as they are newer
+    #[allow(clippy::too_many_arguments)]
+    async fn publish_if_current(
+        client: &Client,
+        sink: &dyn DiagnosticSink,
+        publish_versions: &Mutex<PublishVersions>,
+        documents: &Mutex<DocumentStore>,
+        position_encoding: PositionEncoding,
+        diagnostic_mode: DiagnosticMode,
+        uri: Url,
+        mut diagnostics: Vec<tower_lsp::lsp_types::Diagnostic>,
+        version: i32,
+    ) {
+        Self::encode_positions(documents, position_encoding, &uri, &mut diagnostics).await;
+        let document_version = Self::document_version(documents, &uri).await;
+
+        let mut publish_versions = publish_versions.lock().await;
+        if publish_versions.next_if_current(&uri, version).is_none() {
+            info!("Dropping stale diagnostics for {}", uri);
+            return;
+        }
+        publish_versions.record(&uri, &diagnostics);
+        sink.record(&uri, &diagnostics);
+        if diagnostic_mode == DiagnosticMode::Push {
+            client
+                .publish_diagnostics(uri, diagnostics, document_version)
+                .await;
+        }
+    }
+
+    /// Ask a pulling client to pull diagnostics again, e.g. after dylint results were cached
+    async fn refresh_pulled_diagnostics(client: &Client, diagnostic_mode: DiagnosticMode) {
+        if diagnostic_mode == (DiagnosticMode::Pull { refresh: true })
+            && let Err(e) = client.workspace_diagnostic_refresh().await
+        {
+            warn!("Failed to refresh pulled diagnostics: {}", e);
+        }
+    }
+
+    /// Final progress message for a dylint run
+    fn lint_summary<T>(lint_result: &DylintResult<Vec<T>>) -> String {
+        match lint_result {
+            Ok(diagnostics) => format!("Found {} issues", diagnostics.len()),
+            Err(e) => format!("Detectors failed to run: {}", e),
+        }
+    }
+
     async fn on_change(&self, params: TextDocumentItem) {
         // 1. Run syn-based detectors (fast, real-time)
         let syn_diagnostics = {
@@ -758,13 +1914,13 @@ impl Backend {
             registry.analyze(&params.text, file_path.as_ref())
         };
 
-        // 2. Publish syn-based diagnostics immediately
-        self.client
-            .publish_diagnostics(
-                params.uri.clone(),
-                syn_diagnostics.clone(),
-                Some(params.version),
-            )
+        // 2. Publish syn-based diagnostics immediately, keeping the dylint diagnostics of the
+        // last run until the run below replaces them
+        let diagnostics = self
+            .with_cached_dylint(&params.uri, syn_diagnostics.clone())
+            .await;
+        let version = self
+            .publish_diagnostics(params.uri.clone(), diagnostics)
             .await;
 
         // 3. Run dylint in background and merge diagnostics
@@ -775,11 +1931,21 @@ impl Backend {
             let workspace = workspace_root.clone();
             let uri = params.uri.clone();
             let client = self.client.clone();
-            let version = params.version;
+            let sink = Arc::clone(&self.diagnostic_sink);
+            let publish_versions = Arc::clone(&self.publish_versions);
+            let documents = Arc::clone(&self.documents);
+            let position_encoding = *self.position_encoding.lock().await;
+            let diagnostic_mode = *self.diagnostic_mode.lock().await;
+            let dylint_cache = Arc::clone(&self.dylint_diagnostics);
+            let normalizer = self.path_normalizer.lock().await.clone();
 
             tokio::spawn(async move {
                 info!("Running dylint lints on workspace: {}", workspace.display());
-                match runner.run_lints(&workspace).await {
+                let lint_result = match uri.to_file_path() {
+                    Ok(changed_file) => runner.run_lints_for_file(&workspace, &changed_file).await,
+                    Err(_) => runner.run_lints(&workspace).await,
+                };
+                match lint_result {
                     Ok(dylint_diagnostics) => {
                         info!(
                             "Dylint returned {} total diagnostics",
@@ -787,57 +1953,63 @@ impl Backend {
                         );
 
                         // Filter diagnostics for this file
-                        let file_path = uri.to_file_path().ok();
-                        let dylint_file_diagnostics: Vec<_> = dylint_diagnostics
-                            .iter()
-                            .filter(|d| {
-                                let matches = file_path
-                                    .as_ref()
-                                    .map(|p| {
-                                        let path_str = p.to_string_lossy();
-                                        let diagnostic_file = &d.file_name;
-                                        let result = path_str.ends_with(diagnostic_file)
-                                            || path_str.contains(diagnostic_file);
-                                        info!(
-                                            "Comparing {} with {} = {}",
-                                            path_str, diagnostic_file, result
-                                        );
-                                        result
-                                    })
-                                    .unwrap_or(false);
-                                matches
-                            })
-                            .map(|d| d.to_lsp_diagnostic(Some(&workspace)))
-                            .collect();
+                        let dylint_file_diagnostics: Vec<_> = match uri.to_file_path() {
+                            Ok(file_path) => diagnostics_for_file(&dylint_diagnostics, &file_path)
+                                .into_iter()
+                                .map(|d| d.to_lsp_diagnostic(Some(&workspace), &normalizer))
+                                .collect(),
+                            Err(_) => Vec::new(),
+                        };
 
                         info!(
                             "Filtered to {} diagnostics for this file",
                             dylint_file_diagnostics.len()
                         );
-
-                        if !dylint_file_diagnostics.is_empty() {
+                        let cached = dylint_cache
+                            .lock()
+                            .await
+                            .insert(uri.clone(), dylint_file_diagnostics.clone())
+                            .unwrap_or_default();
+                        Self::refresh_pulled_diagnostics(&client, diagnostic_mode).await;
+
+                        // Republish when there is something to add or stale results to drop
+                        if !dylint_file_diagnostics.is_empty() || !cached.is_empty() {
                             info!(
                                 "Publishing {} dylint issues for file",
                                 dylint_file_diagnostics.len()
                             );
 
                             // Merge syn and dylint diagnostics
-                            let mut merged_diagnostics = syn_diagnostics;
-                            merged_diagnostics.extend(dylint_file_diagnostics);
+                            let merged_diagnostics = DiagnosticBuilder::merge_and_normalize(
+                                syn_diagnostics,
+                                dylint_file_diagnostics,
+                            );
 
                             info!(
                                 "Publishing {} total diagnostics (syn + dylint)",
                                 merged_diagnostics.len()
                             );
 
-                            // Publish merged diagnostics
-                            client
-                                .publish_diagnostics(uri, merged_diagnostics, Some(version))
-                                .await;
+                            // Publish merged diagnostics unless newer ones were published meanwhile
+                            Self::publish_if_current(
+                                &client,
+                                sink.as_ref(),
+                                &publish_versions,
+                                &documents,
+                                position_encoding,
+                                diagnostic_mode,
+                                uri,
+                                merged_diagnostics,
+                                version,
+                            )
+                            .await;
                         }
                     }
                     Err(e) => {
                         info!("Dylint failed: {}", e);
+                        client
+                            .send_notification::<DetectorStatusNotification>(e.status())
+                            .await;
                     }
                 }
             });
@@ -845,12 +2017,18 @@ impl Backend {
     }
 
     /// Get information about all registered detectors
-    #[allow(dead_code)]
     pub async fn list_detectors(&self) -> Vec<DetectorInfo> {
         let registry = self.detector_registry.lock().await;
         registry.list_detectors()
     }
 
+    /// Handle `solana/listDetectors`
+    pub async fn list_detectors_request(
+        &self,
+    ) -> Result<Vec<DetectorInfo>, tower_lsp::jsonrpc::Error> {
+        Ok(self.list_detectors().await)
+    }
+
     /// Enable or disable a specific detector
     #[allow(dead_code)]
     pub async fn set_detector_enabled(&self, detector_id: &str, enabled: bool) {
@@ -890,7 +2068,7 @@ pub struct DetectorStats {
 }
 
 /// Create a default detector registry with all available detectors
-fn create_default_registry() -> DetectorRegistry {
+pub fn create_default_registry() -> DetectorRegistry {
     info!("Creating new detector registry with all detectors");
     let registry = DetectorRegistryBuilder::new()
         .with_detector(ManualLamportsZeroingDetector::default())
@@ -899,6 +2077,56 @@ fn create_default_registry() -> DetectorRegistry {
         .with_detector(InstructionAttributeUnusedDetector::default())
         .with_detector(InstructionAttributeInvalidDetector::default())
         .with_detector(MissingCheckCommentDetector::default())
+        .with_detector(ZeroConstraintDetector::default())
+        .with_detector(InsecureRandomnessDetector::default())
+        .with_detector(LogBeforeReturnDetector::default())
+        .with_detector(UncheckedRemainingAccountsDetector::default())
+        .with_detector(SysvarGetOutsideHandlerDetector::default())
+        .with_detector(LargeAccountNoZeroCopyDetector::default())
+        .with_detector(PdaTypedAsSignerDetector::default())
+        .with_detector(ConstraintAssignmentDetector::default())
+        .with_detector(BumpSourceDetector::default())
+        .with_detector(UnboundedSeedDetector::default())
+        .with_detector(UnwrapInProgramDetector::default())
+        .with_detector(MsgInLoopDetector::default())
+        .with_detector(UncheckedTokenTransferDetector::default())
+        .with_detector(UnknownContextDetector::default())
+        .with_detector(MissingAccountAttributeDetector::default())
+        .with_detector(LossyRequireComparisonDetector::default())
+        .with_detector(BorrowAcrossCpiDetector::default())
+        .with_detector(UntrustedPubkeyDetector::default())
+        .with_detector(DanglingInstructionAttributeDetector::default())
+        .with_detector(LamportsBelowRentDetector::default())
+        .with_detector(ZeroAccountReinitDetector::default())
+        .with_detector(UnsignedAuthorityDetector::default())
+        .with_detector(AccountTypeConfusionDetector::default())
+        .with_detector(UncheckedSetAuthorityDetector::default())
+        .with_detector(ProgramIdMismatchDetector::default())
+        .with_detector(CloseDestinationNotMutDetector::default())
+        .with_detector(UnnecessaryMutDetector::default())
+        .with_detector(MissingMaxLenDetector::default())
+        .with_detector(PayerNotMutDetector::default())
+        .with_detector(LoadMutWithoutMutDetector::default())
+        .with_detector(PdaTransferWithoutSignerDetector::default())
+        .with_detector(ShadowedAccountDetector::default())
+        .with_detector(MalformedRequireDetector::default())
+        .with_detector(RuntimeAddressConstraintDetector::default())
+        .with_detector(MutAccountArgumentDetector::default())
+        .with_detector(UnconstrainedTokenAccountDetector::default())
+        .with_detector(SensitiveLogDetector::default())
+        .with_detector(UnenforcedKeyCheckDetector::default())
+        .with_detector(AccountBoxingDetector::default())
+        .with_detector(InitWithoutSpaceDetector::default())
+        .with_detector(PdaSeedsMismatchDetector::default())
+        .with_detector(ReallocWithoutZeroInitDetector::default())
+        .with_detector(EmptyHandlerDetector::default())
+        .with_detector(UncheckedAccountIndexDetector::default())
+        .with_detector(InvokeSignedSeedsMismatchDetector::default())
+        .with_detector(UnboundConstraintIdentDetector::default())
+        .with_detector(UnusedSignerDetector::default())
+        // Opt-in detectors
+        .with_config("REDUNDANT_ERROR_LOG", DetectorConfig::disabled())
+        .with_config("SYSVAR_GET_CONTEXT", DetectorConfig::disabled())
         .build();
 
     info!(
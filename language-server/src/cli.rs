@@ -0,0 +1,251 @@
+use crate::backend::create_default_registry;
+use crate::core::{DetectorRegistry, FileScanner, ScanResult};
+use log::warn;
+use notify::{Event, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+use tokio::time::timeout;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+/// Quiet period after the last file event before rescanning
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How the binary was asked to run
+#[derive(Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Language server over stdio (default; clients may pass `--stdio`)
+    Server,
+    /// `--scan <dir> [--severity-floor error|warning]`: scan once and print all diagnostics
+    Scan {
+        root: PathBuf,
+        /// Diagnostics less severe than this are raised to it before output
+        severity_floor: Option<DiagnosticSeverity>,
+    },
+    /// `--watch <dir>`: scan, then rescan on changes and print what changed
+    Watch(PathBuf),
+}
+
+impl Mode {
+    /// Parse command line arguments (without the program name)
+    /// Arguments other than `--scan`, `--watch` and `--severity-floor` are left to the server mode
+    pub fn from_args<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        let mut mode = Mode::Server;
+        let mut severity_floor = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--scan" | "--watch" if mode != Mode::Server => {
+                    return Err(format!("{} cannot be combined with another mode", arg));
+                }
+                "--scan" | "--watch" => {
+                    let dir = args
+                        .next()
+                        .map(PathBuf::from)
+                        .ok_or_else(|| format!("{} expects a directory", arg))?;
+                    mode = if arg == "--scan" {
+                        Mode::Scan {
+                            root: dir,
+                            severity_floor: None,
+                        }
+                    } else {
+                        Mode::Watch(dir)
+                    };
+                }
+                "--severity-floor" => {
+                    severity_floor = Some(match args.next().as_deref() {
+                        Some("error") => DiagnosticSeverity::ERROR,
+                        Some("warning") => DiagnosticSeverity::WARNING,
+                        _ => return Err("--severity-floor expects error or warning".to_string()),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        match (mode, severity_floor) {
+            (Mode::Scan { root, .. }, severity_floor) => Ok(Mode::Scan {
+                root,
+                severity_floor,
+            }),
+            (_, Some(_)) => Err("--severity-floor requires --scan".to_string()),
+            (mode, None) => Ok(mode),
+        }
+    }
+}
+
+/// Scan a directory with all default detectors
+/// Shared by `--scan` and `--watch`
+pub async fn scan_directory(root: &Path) -> ScanResult {
+    let scanner = FileScanner::new(root.to_path_buf());
+    let mut registry = create_default_registry();
+    scanner.scan_workspace(&mut registry).await
+}
+
+/// Run `--scan`: print every diagnostic and return the process exit code
+///
+/// With a `severity_floor`, less severe diagnostics are raised to the floor first.
+/// The exit code is non-zero if any ERROR diagnostic remains.
+pub async fn run_scan(root: &Path, severity_floor: Option<DiagnosticSeverity>) -> i32 {
+    let mut result = scan_directory(root).await;
+    if let Some(floor) = severity_floor {
+        for file in &mut result.rust_files {
+            DetectorRegistry::apply_severity_floor(&mut file.diagnostics, floor);
+        }
+    }
+    let lines = diagnostic_lines(&result, root);
+
+    let mut stdout = std::io::stdout().lock();
+    for line in &lines {
+        let _ = writeln!(stdout, "{}", line);
+    }
+    let _ = writeln!(
+        stdout,
+        "{} issues in {} files",
+        lines.len(),
+        result.rust_files.len()
+    );
+
+    scan_exit_code(&result)
+}
+
+/// Exit code for a finished scan: 1 if any diagnostic is an ERROR, 0 otherwise
+pub fn scan_exit_code(result: &ScanResult) -> i32 {
+    let has_errors = result
+        .rust_files
+        .iter()
+        .flat_map(|file| &file.diagnostics)
+        .any(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR));
+    i32::from(has_errors)
+}
+
+/// Run `--watch`: rescan on `.rs` changes until interrupted
+pub async fn run_watch(root: &Path) -> i32 {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let filter = FileScanner::new(root.clone());
+    let (sender, events) = unbounded_channel();
+
+    let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+        Ok(event) => {
+            let relevant = event.paths.iter().any(|path| {
+                path.extension().is_some_and(|ext| ext == "rs") && !filter.is_excluded(path)
+            });
+            if relevant {
+                let _ = sender.send(());
+            }
+        }
+        Err(e) => warn!("Watch error: {}", e),
+    });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!("Failed to start watcher: {}", e);
+            return 2;
+        }
+    };
+    if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+        eprintln!("Failed to watch {}: {}", root.display(), e);
+        return 2;
+    }
+
+    eprintln!("Watching {} for changes...", root.display());
+    watch_loop(
+        events,
+        WATCH_DEBOUNCE,
+        || scan_directory(&root),
+        &root,
+        &mut std::io::stderr(),
+    )
+    .await;
+
+    0
+}
+
+/// Rescan after each burst of events and write the diagnostics diff to `out`
+///
+/// An initial scan sets the baseline. Events arriving within `debounce` of each other
+/// trigger a single rescan. Returns when the event channel closes.
+pub async fn watch_loop<S, F, W>(
+    mut events: UnboundedReceiver<()>,
+    debounce: Duration,
+    mut scan: S,
+    root: &Path,
+    out: &mut W,
+) where
+    S: FnMut() -> F,
+    F: Future<Output = ScanResult>,
+    W: Write,
+{
+    let mut previous = diagnostic_lines(&scan().await, root);
+    let _ = writeln!(out, "{} issues", previous.len());
+
+    while events.recv().await.is_some() {
+        // Wait until events stop arriving
+        while let Ok(Some(())) = timeout(debounce, events.recv()).await {}
+
+        let current = diagnostic_lines(&scan().await, root);
+        for line in current.difference(&previous) {
+            let _ = writeln!(out, "+ {}", line);
+        }
+        for line in previous.difference(&current) {
+            let _ = writeln!(out, "- {}", line);
+        }
+        let _ = writeln!(
+            out,
+            "{} new, {} resolved, {} issues",
+            current.difference(&previous).count(),
+            previous.difference(&current).count(),
+            current.len()
+        );
+
+        previous = current;
+    }
+}
+
+/// One line per diagnostic: `path:line:col severity [CODE] message`
+/// Paths are relative to `root`; positions are 1-based
+pub fn diagnostic_lines(result: &ScanResult, root: &Path) -> BTreeSet<String> {
+    result
+        .rust_files
+        .iter()
+        .flat_map(|file| {
+            let path = file.path.strip_prefix(root).unwrap_or(&file.path);
+            file.diagnostics
+                .iter()
+                .map(move |diagnostic| format_diagnostic(path, diagnostic))
+        })
+        .collect()
+}
+
+fn format_diagnostic(path: &Path, diagnostic: &Diagnostic) -> String {
+    let severity = match diagnostic.severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) => "info",
+        _ => "hint",
+    };
+    let code = match &diagnostic.code {
+        Some(NumberOrString::String(code)) => code.clone(),
+        Some(NumberOrString::Number(code)) => code.to_string(),
+        None => String::new(),
+    };
+    // Keep each diagnostic on one line
+    let message = diagnostic.message.lines().next().unwrap_or_default();
+
+    format!(
+        "{}:{}:{} {} [{}] {}",
+        path.display(),
+        diagnostic.range.start.line + 1,
+        diagnostic.range.start.character + 1,
+        severity,
+        code,
+        message
+    )
+}
@@ -1,4 +1,5 @@
-use crate::core::{DetectorInfo, DetectorRegistry};
+use crate::core::{DetectorInfo, DetectorRegistry, DetectorTiming, FileScanner};
+use std::sync::Arc;
 
 /// Statistics about the detector system
 #[allow(dead_code)]
@@ -40,4 +41,40 @@ impl BackendManager {
             enabled_detectors: registry.enabled_count(),
         }
     }
+
+    /// Time every detector across all Rust files in the workspace
+    /// Durations and diagnostic counts are summed per detector, slowest first
+    pub async fn get_detector_timings(
+        registry: &mut DetectorRegistry,
+        scanner: &FileScanner,
+    ) -> Vec<DetectorTiming> {
+        let mut totals: Vec<DetectorTiming> = Vec::new();
+        let Some(workspace) = scanner.workspace_files().map(Arc::new) else {
+            return totals;
+        };
+
+        // Cross-file detectors share the contents instead of reloading them for every file
+        let previous = registry.workspace();
+        registry.set_workspace(Some(workspace.clone()));
+
+        for (path, content) in workspace.files() {
+            let timings = registry.analyze_file(content, Some(&path.to_path_buf()));
+            if totals.is_empty() {
+                totals = timings;
+                continue;
+            }
+            for (total, timing) in totals.iter_mut().zip(timings) {
+                total.duration = match (total.duration, timing.duration) {
+                    (Some(total), Some(duration)) => Some(total + duration),
+                    (total, duration) => total.or(duration),
+                };
+                total.count += timing.count;
+            }
+        }
+
+        registry.set_workspace(previous);
+
+        totals.sort_by_key(|timing| std::cmp::Reverse(timing.duration));
+        totals
+    }
 }
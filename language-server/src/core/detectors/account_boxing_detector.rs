@@ -0,0 +1,185 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, SizeEstimator, anchor_patterns::AnchorPatterns,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Wrappers that deserialize the account data onto the stack
+const DESERIALIZING_WRAPPERS: &[&str] = &["Account", "InterfaceAccount"];
+
+/// Number of deserialized accounts a struct may hold before boxing is suggested
+pub const MAX_UNBOXED_ACCOUNTS: usize = 8;
+
+/// Account data size (in bytes) at or below which boxing saves too little stack to matter
+pub const TINY_ACCOUNT_SIZE: usize = 64;
+
+#[derive(Default)]
+pub struct AccountBoxingDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Size of each `#[account]` struct in the file whose size is known
+    account_sizes: HashMap<String, usize>,
+}
+
+impl AccountBoxingDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Check if a field type is `Box<...>`
+    fn is_boxed(field: &syn::Field) -> bool {
+        matches!(&field.ty, syn::Type::Path(type_path)
+            if type_path.path.segments.last().is_some_and(|segment| segment.ident == "Box"))
+    }
+
+    fn report(&mut self, span: proc_macro2::Span, message: String) {
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(span, &self.lines),
+                format!("{} {}", message, self.message()),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+impl Detector for AccountBoxingDetector {
+    fn id(&self) -> &'static str {
+        "ACCOUNT_BOXING"
+    }
+
+    fn name(&self) -> &'static str {
+        "Account Boxing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects Accounts structs with many unboxed Account<'info, T> fields, or boxed tiny accounts"
+    }
+
+    fn message(&self) -> &'static str {
+        "Box<Account<'info, T>> keeps the deserialized data on the heap instead of the 4KB stack frame; it does not change the account's size on chain."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Anchor deserializes every Account<'info, T> of the struct into the handler's stack frame, which is limited to 4KB. A struct with many unboxed accounts overflows it and the program fails at runtime with an access violation, while boxing a tiny account only adds a heap allocation."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        let mut collector = AccountSizeCollector::default();
+        collector.visit_file(syntax_tree);
+        self.account_sizes = collector.sizes;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for AccountBoxingDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            let accounts: Vec<&syn::Field> = fields
+                .named
+                .iter()
+                .filter(|field| {
+                    AnchorPatterns::account_wrapper_name(field)
+                        .is_some_and(|wrapper| DESERIALIZING_WRAPPERS.contains(&wrapper.as_str()))
+                })
+                .collect();
+
+            if accounts.len() > MAX_UNBOXED_ACCOUNTS {
+                if !accounts.iter().any(|field| Self::is_boxed(field)) {
+                    self.report(
+                        node.ident.span(),
+                        format!(
+                            "'{}' deserializes {} accounts and none of them is boxed. Consider boxing the largest ones.",
+                            node.ident,
+                            accounts.len()
+                        ),
+                    );
+                }
+            } else {
+                for field in accounts.iter().filter(|field| Self::is_boxed(field)) {
+                    let Some(data_type) = AnchorPatterns::account_data_type_name(field) else {
+                        continue;
+                    };
+                    let Some(&size) = self.account_sizes.get(&data_type) else {
+                        continue;
+                    };
+                    if size > TINY_ACCOUNT_SIZE {
+                        continue;
+                    }
+
+                    let name = field
+                        .ident
+                        .as_ref()
+                        .map_or_else(|| data_type.clone(), ToString::to_string);
+                    self.report(
+                        field.ty.span(),
+                        format!(
+                            "Account '{}' ({} is {} bytes) is boxed, which saves little stack in a struct with few accounts.",
+                            name, data_type, size
+                        ),
+                    );
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+/// Collects the known sizes of `#[account]` structs
+#[derive(Default)]
+struct AccountSizeCollector {
+    sizes: HashMap<String, usize>,
+}
+
+impl<'ast> Visit<'ast> for AccountSizeCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_account_struct(node)
+            && let Some(size) = SizeEstimator::exact_fields_size(&node.fields)
+        {
+            self.sizes.insert(node.ident.to_string(), size);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
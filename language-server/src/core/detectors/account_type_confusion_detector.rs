@@ -0,0 +1,262 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Associated functions deserializing account data into `Self`
+const DESERIALIZE_FNS: &[&str] = &[
+    "try_from_slice",
+    "try_deserialize",
+    "try_deserialize_unchecked",
+    "deserialize",
+    "unpack",
+    "unpack_unchecked",
+];
+
+/// Traits whose deserializers are called with the target type as a turbofish
+const DESERIALIZE_TRAITS: &[&str] = &["BorshDeserialize", "AccountDeserialize", "Pack"];
+
+/// Accesses from an account to its raw data, dropped when naming the account
+const DATA_ACCESSORS: &[&str] = &[
+    "data",
+    "borrow",
+    "borrow_mut",
+    "try_borrow_data",
+    "try_borrow_mut_data",
+    "as_ref",
+    "as_slice",
+];
+
+#[derive(Default)]
+pub struct AccountTypeConfusionDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Per enclosing function: account to the type it was first deserialized as
+    functions: Vec<HashMap<String, String>>,
+}
+
+impl AccountTypeConfusionDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            functions: Vec::new(),
+        }
+    }
+
+    /// Get the target type of a deserializer path (`Vault::try_from_slice`,
+    /// `<Vault as BorshDeserialize>::try_from_slice`, `BorshDeserialize::try_from_slice::<Vault>`)
+    fn deserialized_type(func: &syn::ExprPath) -> Option<String> {
+        let segments = &func.path.segments;
+        let method = segments.last()?;
+        if !DESERIALIZE_FNS.iter().any(|name| method.ident == name) {
+            return None;
+        }
+
+        if let Some(ty) = Self::turbofish_type(&method.arguments) {
+            return Some(ty);
+        }
+        if let Some(qself) = &func.qself {
+            return Self::type_name(&qself.ty);
+        }
+
+        let owner = segments.iter().rev().nth(1)?;
+        let owner_name = owner.ident.to_string();
+        (!DESERIALIZE_TRAITS.contains(&owner_name.as_str())).then_some(owner_name)
+    }
+
+    /// Get the single type argument of `::<T>`
+    fn turbofish_type(arguments: &syn::PathArguments) -> Option<String> {
+        let syn::PathArguments::AngleBracketed(args) = arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Self::type_name(ty),
+            _ => None,
+        })
+    }
+
+    fn type_name(ty: &syn::Type) -> Option<String> {
+        match ty {
+            syn::Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Name the account whose data an expression reads, e.g. `ctx.accounts.vault` for
+    /// `&mut &ctx.accounts.vault.data.borrow()[8..]`
+    fn account_name(mut expr: &Expr) -> Option<String> {
+        loop {
+            expr = match expr {
+                Expr::Reference(reference) => &reference.expr,
+                Expr::Paren(paren) => &paren.expr,
+                Expr::Try(expr_try) => &expr_try.expr,
+                Expr::Index(index) if matches!(index.index.as_ref(), Expr::Range(_)) => &index.expr,
+                _ => break,
+            };
+        }
+
+        let (root, mut members) = AstAnalyzer::member_chain(expr);
+        while members
+            .last()
+            .is_some_and(|member| DATA_ACCESSORS.contains(&member.as_str()))
+        {
+            members.pop();
+        }
+
+        let mut name = root?;
+        for member in members {
+            name.push('.');
+            name.push_str(&member);
+        }
+        Some(name)
+    }
+
+    /// Remember the type an account is deserialized as and report a differing one
+    fn record_deserialize(&mut self, account: String, ty: String, span: proc_macro2::Span) {
+        let Some(function) = self.functions.last_mut() else {
+            return;
+        };
+
+        let first = function
+            .entry(account.clone())
+            .or_insert_with(|| ty.clone());
+        if *first == ty {
+            return;
+        }
+        let first = first.clone();
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(span, &self.lines),
+                format!(
+                    "Account '{}' is deserialized as {} after being deserialized as {}. {}",
+                    account,
+                    ty,
+                    first,
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, visit: F) {
+        self.functions.push(HashMap::new());
+        visit(self);
+        self.functions.pop();
+    }
+}
+
+impl Detector for AccountTypeConfusionDetector {
+    fn id(&self) -> &'static str {
+        "ACCOUNT_TYPE_CONFUSION"
+    }
+
+    fn name(&self) -> &'static str {
+        "Account Type Confusion"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects one account deserialized as different types in the same function"
+    }
+
+    fn message(&self) -> &'static str {
+        "Raw deserialization does not check which type the data holds, so an attacker can pass an account of one type where another is expected. Deserialize each account as a single type and check its discriminator or owner."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "try_from_slice and similar raw deserializers read whatever bytes the account holds. Two account types with compatible layouts both decode successfully, so code that treats one account as both types lets a caller substitute, say, a user record for a config account and pass checks meant for the other."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.functions.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for AccountTypeConfusionDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(|detector| syn::visit::visit_item_fn(detector, node));
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(|detector| syn::visit::visit_impl_item_fn(detector, node));
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let Expr::Path(func) = node.func.as_ref()
+            && let Some(ty) = Self::deserialized_type(func)
+            && let Some(account) = node.args.first().and_then(Self::account_name)
+        {
+            self.record_deserialize(account, ty, node.span());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        // `account.deserialize_data::<T>()`
+        if node.method == "deserialize_data"
+            && let Some(turbofish) = &node.turbofish
+            && let Some(ty) = turbofish.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Self::type_name(ty),
+                _ => None,
+            })
+            && let Some(account) = Self::account_name(&node.receiver)
+        {
+            self.record_deserialize(account, ty, node.span());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
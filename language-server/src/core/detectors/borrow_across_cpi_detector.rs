@@ -0,0 +1,263 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, Pat, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Methods returning a `RefMut` into account data or lamports
+const BORROW_METHODS: &[&str] = &[
+    "try_borrow_mut_data",
+    "try_borrow_mut_lamports",
+    "load_mut",
+    "load_init",
+];
+
+/// `RefCell` fields of an `AccountInfo` guarded by `borrow_mut()`
+const BORROWED_FIELDS: &[&str] = &["data", "lamports"];
+
+/// Functions that invoke another program
+const CPI_FUNCTIONS: &[&str] = &[
+    "invoke",
+    "invoke_signed",
+    "invoke_unchecked",
+    "invoke_signed_unchecked",
+];
+
+/// Modules whose functions are CPI helpers
+const CPI_MODULES: &[&str] = &["token", "token_interface", "token_2022", "system_program"];
+
+#[derive(Default)]
+pub struct BorrowAcrossCpiDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Locals holding a mutable borrow, one entry per enclosing block
+    scopes: Vec<Vec<String>>,
+}
+
+impl BorrowAcrossCpiDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Check if an initializer evaluates to the mutable borrow itself (not a value read through it)
+    fn holds_borrow(expr: &Expr) -> bool {
+        match expr {
+            Expr::Reference(reference) => Self::holds_borrow(&reference.expr),
+            Expr::Paren(paren) => Self::holds_borrow(&paren.expr),
+            Expr::Try(expr_try) => Self::holds_borrow(&expr_try.expr),
+            Expr::MethodCall(call) => {
+                let method = call.method.to_string();
+                if method == "unwrap" || method == "expect" {
+                    return Self::holds_borrow(&call.receiver);
+                }
+                if BORROW_METHODS.contains(&method.as_str()) {
+                    return true;
+                }
+
+                method == "borrow_mut"
+                    && matches!(
+                        call.receiver.as_ref(),
+                        Expr::Field(field)
+                            if matches!(&field.member, syn::Member::Named(name)
+                                if BORROWED_FIELDS.iter().any(|field| name == field))
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the name bound by a `let` pattern
+    fn binding_name(pat: &Pat) -> Option<String> {
+        match pat {
+            Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+            Pat::Type(pat_type) => Self::binding_name(&pat_type.pat),
+            _ => None,
+        }
+    }
+
+    /// Check if a call path invokes another program
+    fn is_cpi(path: &syn::Path) -> bool {
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+
+        match segments.as_slice() {
+            [.., module, _] if CPI_MODULES.contains(&module.as_str()) => true,
+            [.., name] => CPI_FUNCTIONS.contains(&name.as_str()),
+            [] => false,
+        }
+    }
+
+    /// Get the local passed to `drop(..)`, if the call is one
+    fn dropped_local(node: &syn::ExprCall) -> Option<String> {
+        let Expr::Path(func) = node.func.as_ref() else {
+            return None;
+        };
+        if func
+            .path
+            .segments
+            .last()
+            .is_none_or(|segment| segment.ident != "drop")
+        {
+            return None;
+        }
+
+        match node.args.first() {
+            Some(Expr::Path(arg)) => arg.path.get_ident().map(|ident| ident.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Get the innermost borrow still alive
+    fn live_borrow(&self) -> Option<&String> {
+        self.scopes.iter().rev().find_map(|scope| scope.last())
+    }
+}
+
+impl Detector for BorrowAcrossCpiDetector {
+    fn id(&self) -> &'static str {
+        "BORROW_ACROSS_CPI"
+    }
+
+    fn name(&self) -> &'static str {
+        "Borrow Across CPI"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects CPIs made while a mutable borrow of account data is still held"
+    }
+
+    fn message(&self) -> &'static str {
+        "The CPI borrows the account data again and fails with an \"already borrowed\" error at runtime. Drop the borrow (or end its scope) before invoking."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Account data lives in a RefCell shared with the runtime. The CPI needs to borrow that data again to serialize it for the callee, and a RefMut still alive in the caller makes that borrow fail, aborting the whole instruction."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::ERROR
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.scopes.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for BorrowAcrossCpiDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Borrows of an enclosing function do not reach nested items
+        let scopes = std::mem::take(&mut self.scopes);
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+
+        self.scopes = scopes;
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        let scopes = std::mem::take(&mut self.scopes);
+
+        // Continue visiting children
+        syn::visit::visit_impl_item_fn(self, node);
+
+        self.scopes = scopes;
+    }
+
+    fn visit_block(&mut self, node: &'ast syn::Block) {
+        // Borrows bound in this block are dropped at its end
+        self.scopes.push(Vec::new());
+
+        // Continue visiting children
+        syn::visit::visit_block(self, node);
+
+        self.scopes.pop();
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        // The initializer runs before the new binding exists
+        syn::visit::visit_local(self, node);
+
+        if let Some(init) = &node.init
+            && Self::holds_borrow(&init.expr)
+            && let Some(name) = Self::binding_name(&node.pat)
+            && let Some(scope) = self.scopes.last_mut()
+        {
+            scope.push(name);
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        // Arguments are evaluated before the call
+        syn::visit::visit_expr_call(self, node);
+
+        if let Some(name) = Self::dropped_local(node) {
+            for scope in self.scopes.iter_mut().rev() {
+                if let Some(index) = scope.iter().rposition(|local| *local == name) {
+                    scope.remove(index);
+                    break;
+                }
+            }
+            return;
+        }
+
+        if let Expr::Path(func) = node.func.as_ref()
+            && Self::is_cpi(&func.path)
+            && let Some(borrow) = self.live_borrow()
+        {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(func.span(), &self.lines),
+                    format!(
+                        "CPI while '{}' still mutably borrows account data. {}",
+                        borrow,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
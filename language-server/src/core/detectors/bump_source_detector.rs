@@ -0,0 +1,179 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Wrappers whose data Anchor validates (owner and discriminator) before constraints run
+const VALIDATED_WRAPPERS: &[&str] = &["Account", "AccountLoader", "InterfaceAccount"];
+
+#[derive(Default)]
+pub struct BumpSourceDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl BumpSourceDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if a `bump = <expr>` value comes from a trusted source:
+    /// `ctx.bumps`, `find_program_address` or a field of a validated account
+    fn is_trusted_source(&self, value: &TokenStream, validated_accounts: &HashSet<String>) -> bool {
+        if Self::mentions_ident(value, &["bumps", "find_program_address"]) {
+            return true;
+        }
+
+        let Ok(expr) = syn::parse2::<Expr>(value.clone()) else {
+            // Unparseable values are left alone (best effort)
+            return true;
+        };
+
+        Self::account_of_field_access(&expr)
+            .is_some_and(|account| validated_accounts.contains(&account))
+    }
+
+    /// Check if a token stream contains one of the given identifiers
+    fn mentions_ident(tokens: &TokenStream, idents: &[&str]) -> bool {
+        tokens.clone().into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => idents.iter().any(|name| ident == name),
+            TokenTree::Group(group) => Self::mentions_ident(&group.stream(), idents),
+            _ => false,
+        })
+    }
+
+    /// Get the account a field access reads from, e.g. `vault` for `vault.bump`
+    /// or `vault.load()?.bump`
+    fn account_of_field_access(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Field(field) => Self::base_ident(&field.base),
+            Expr::Unary(unary) => Self::account_of_field_access(&unary.expr),
+            Expr::Paren(paren) => Self::account_of_field_access(&paren.expr),
+            _ => None,
+        }
+    }
+
+    /// Get the identifier an expression chain starts from
+    fn base_ident(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+            Expr::Field(field) => Self::base_ident(&field.base),
+            Expr::MethodCall(call) => Self::base_ident(&call.receiver),
+            Expr::Try(expr_try) => Self::base_ident(&expr_try.expr),
+            Expr::Unary(unary) => Self::base_ident(&unary.expr),
+            Expr::Reference(reference) => Self::base_ident(&reference.expr),
+            Expr::Paren(paren) => Self::base_ident(&paren.expr),
+            _ => None,
+        }
+    }
+}
+
+impl Detector for BumpSourceDetector {
+    fn id(&self) -> &'static str {
+        "UNVERIFIED_BUMP_SOURCE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unverified Bump Source"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `bump = <expr>` constraints whose bump does not come from a validated source"
+    }
+
+    fn message(&self) -> &'static str {
+        "Verify that this bump is the canonical bump. Use a bump stored in a validated account (e.g. `bump = vault.bump`), ctx.bumps, or a bare `bump` to let Anchor derive it."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Several bumps can derive a valid PDA for the same seeds, and only the canonical one is unique. A bump taken from instruction data or another unvalidated place lets a caller pick a non-canonical bump and create or pass a second address for what should be one account."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for BumpSourceDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            let validated_accounts: HashSet<String> = fields
+                .named
+                .iter()
+                .filter(|field| {
+                    AnchorPatterns::account_wrapper_name(field)
+                        .is_some_and(|wrapper| VALIDATED_WRAPPERS.contains(&wrapper.as_str()))
+                })
+                .filter_map(|field| field.ident.as_ref().map(|ident| ident.to_string()))
+                .collect();
+
+            for field in &fields.named {
+                for constraint in AnchorPatterns::parse_account_constraints(field) {
+                    // Bare `bump` lets Anchor derive the canonical bump
+                    let Some(value) = &constraint.value else {
+                        continue;
+                    };
+                    if constraint.key != "bump"
+                        || self.is_trusted_source(value, &validated_accounts)
+                    {
+                        continue;
+                    }
+
+                    let severity = self
+                        .config
+                        .severity_override
+                        .unwrap_or(self.default_severity());
+
+                    self.diagnostics
+                        .push(DiagnosticBuilder::create_with_code_description(
+                            DiagnosticBuilder::create_range_from_span_with_lines(
+                                constraint.span,
+                                &self.lines,
+                            ),
+                            format!(
+                                "Bump source `{}` is not validated. {}",
+                                value,
+                                self.message()
+                            ),
+                            severity,
+                            self.id().to_string(),
+                            None,
+                        ));
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
@@ -0,0 +1,139 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use proc_macro2::TokenTree;
+use std::path::PathBuf;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct CloseDestinationNotMutDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl CloseDestinationNotMutDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if Anchor treats a field as writable (`mut`, `mut @ Error`, or created by `init`)
+    fn is_mutable(field: &syn::Field) -> bool {
+        AnchorPatterns::parse_account_constraints(field)
+            .iter()
+            .any(|constraint| {
+                constraint.key == "mut"
+                    || constraint.key.starts_with("mut@")
+                    || constraint.key == "init"
+                    || constraint.key == "init_if_needed"
+            })
+    }
+}
+
+impl Detector for CloseDestinationNotMutDetector {
+    fn id(&self) -> &'static str {
+        "CLOSE_DESTINATION_NOT_MUT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Close Destination Not Mutable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects close = <account> constraints whose destination is not marked mut"
+    }
+
+    fn message(&self) -> &'static str {
+        "Closing an account transfers its lamports to the destination, so the destination must be writable. Mark the destination field #[account(mut)]."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Closing moves all lamports of the closed account to the destination. The runtime rejects lamport changes on accounts the transaction did not mark writable, so the close fails every time the instruction runs."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::ERROR
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for CloseDestinationNotMutDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                for constraint in AnchorPatterns::parse_account_constraints(field) {
+                    if constraint.key != "close" {
+                        continue;
+                    }
+                    // `close = destination` or `close = destination @ ErrorCode::X`
+                    let Some(TokenTree::Ident(destination)) =
+                        constraint.value.and_then(|value| value.into_iter().next())
+                    else {
+                        continue;
+                    };
+
+                    // A destination outside the struct cannot be checked
+                    let Some(destination_field) = fields
+                        .named
+                        .iter()
+                        .find(|other| other.ident.as_ref() == Some(&destination))
+                    else {
+                        continue;
+                    };
+                    if Self::is_mutable(destination_field) {
+                        continue;
+                    }
+
+                    let severity = self
+                        .config
+                        .severity_override
+                        .unwrap_or(self.default_severity());
+
+                    self.diagnostics
+                        .push(DiagnosticBuilder::create_with_code_description(
+                            DiagnosticBuilder::create_range_from_span_with_lines(
+                                constraint.span,
+                                &self.lines,
+                            ),
+                            format!(
+                                "Close destination '{}' is not marked mut. {}",
+                                destination,
+                                self.message()
+                            ),
+                            severity,
+                            self.id().to_string(),
+                            None,
+                        ));
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
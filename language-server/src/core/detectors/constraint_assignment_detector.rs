@@ -0,0 +1,122 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use proc_macro2::{TokenStream, TokenTree};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct ConstraintAssignmentDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl ConstraintAssignmentDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if a `constraint = <expr>` value is an assignment rather than a comparison
+    fn is_assignment(&self, value: &TokenStream) -> bool {
+        // Drop the custom error (`@ ErrorCode::X`), it is not part of the expression
+        let expr_tokens: TokenStream = value
+            .clone()
+            .into_iter()
+            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == '@'))
+            .collect();
+
+        matches!(syn::parse2::<Expr>(expr_tokens), Ok(Expr::Assign(_)))
+    }
+}
+
+impl Detector for ConstraintAssignmentDetector {
+    fn id(&self) -> &'static str {
+        "CONSTRAINT_ASSIGNMENT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Constraint Assignment"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects #[account(constraint = ...)] expressions that assign instead of compare"
+    }
+
+    fn message(&self) -> &'static str {
+        "Constraint expression is an assignment (`=`), not a comparison. It does not check anything; use `==` instead."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Anchor evaluates the constraint expression as a boolean guard. An assignment evaluates to `()`, so the constraint either fails to compile or, inside a block, overwrites the field and never rejects any account."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::ERROR
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for ConstraintAssignmentDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                let has_assignment = AnchorPatterns::parse_account_constraints(field)
+                    .iter()
+                    .filter(|constraint| constraint.key == "constraint")
+                    .filter_map(|constraint| constraint.value.as_ref())
+                    .any(|value| self.is_assignment(value));
+
+                if !has_assignment {
+                    continue;
+                }
+
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            field.span(),
+                            &self.lines,
+                        ),
+                        self.message().to_string(),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
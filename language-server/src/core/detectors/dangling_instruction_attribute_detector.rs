@@ -0,0 +1,192 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, WorkspaceFacts, WorkspaceFiles, anchor_patterns::AnchorPatterns,
+    ast_analyzer::AstAnalyzer,
+};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct DanglingInstructionAttributeDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts structs referenced by a `Context<T>` in the analyzed file
+    local_contexts: HashSet<String>,
+    /// Accounts structs referenced elsewhere in the workspace, resolved on first unreferenced struct
+    workspace_contexts: Option<HashSet<String>>,
+    /// Path of the analyzed file, used to locate its workspace
+    file_path: Option<PathBuf>,
+    /// Workspace files shared by the registry for the current scan
+    workspace: Option<Arc<WorkspaceFiles>>,
+    /// `Context<T>` names used by each workspace file
+    workspace_usages: WorkspaceFacts<HashSet<String>>,
+}
+
+impl DanglingInstructionAttributeDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Check if any handler in the file or its workspace takes `Context<name>`
+    fn is_referenced(&mut self, name: &str) -> bool {
+        if self.local_contexts.contains(name) {
+            return true;
+        }
+
+        if self.workspace_contexts.is_none() {
+            self.workspace_contexts = Some(self.collect_workspace_contexts());
+        }
+
+        self.workspace_contexts
+            .as_ref()
+            .is_some_and(|contexts| contexts.contains(name))
+    }
+
+    fn collect_workspace_contexts(&mut self) -> HashSet<String> {
+        let Some(file_path) = self.file_path.as_deref() else {
+            return HashSet::new();
+        };
+
+        self.workspace_usages
+            .for_file(self.workspace.as_ref(), file_path, |syntax_tree| {
+                let mut collector = ContextUsageCollector::default();
+                collector.visit_file(syntax_tree);
+                collector.names
+            })
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+impl Detector for DanglingInstructionAttributeDetector {
+    fn id(&self) -> &'static str {
+        "DANGLING_INSTRUCTION_ATTRIBUTE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Dangling Instruction Attribute"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects #[instruction(...)] on accounts structs that no handler uses as Context<T>"
+    }
+
+    fn message(&self) -> &'static str {
+        "The instruction arguments are never deserialized for this struct, so constraints using them cannot match any handler. Use the struct in a handler or remove the attribute."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "#[instruction(...)] only has an effect when a handler deserializes its arguments into the accounts struct through Context<T>. Without such a handler the attribute and any constraint built on it are dead code that suggests a check which never runs."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn set_workspace(&mut self, workspace: Option<Arc<WorkspaceFiles>>) {
+        self.workspace = workspace;
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.workspace_contexts = None;
+        self.file_path = file_path.cloned();
+
+        // Handlers may follow the structs they use, so collect them first
+        let mut collector = ContextUsageCollector::default();
+        collector.visit_file(syntax_tree);
+        self.local_contexts = collector.names;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for DanglingInstructionAttributeDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        let Some(attr) = node
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("instruction"))
+        else {
+            return;
+        };
+
+        let name = node.ident.to_string();
+        if self.is_referenced(&name) {
+            return;
+        }
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(attr.span(), &self.lines),
+                format!(
+                    "No handler takes Context<{}>, so #[instruction(...)] is never used. {}",
+                    name,
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+/// Collects the accounts struct names of `Context<T>` handler arguments
+#[derive(Default)]
+struct ContextUsageCollector {
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for ContextUsageCollector {
+    fn visit_signature(&mut self, node: &'ast syn::Signature) {
+        for input in &node.inputs {
+            if let syn::FnArg::Typed(pat_type) = input
+                && let Some(ident) = AnchorPatterns::context_accounts_ident(&pat_type.ty)
+            {
+                self.names.insert(ident.to_string());
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_signature(self, node);
+    }
+}
@@ -1,4 +1,7 @@
+use crate::core::utilities::{LineIndex, WorkspaceFiles};
 use std::path::PathBuf;
+use std::sync::Arc;
+use syn::parse_str;
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
 /// Base trait for all security detectors in Anchor programs
@@ -15,9 +18,39 @@ pub trait Detector: Send + Sync {
     /// Message for detection
     fn message(&self) -> &'static str;
 
+    /// Why the detected pattern is a problem, shown when hovering a diagnostic
+    /// Every built-in detector explains this beyond its message; the default repeats the
+    /// message, which the hover leaves out
+    fn rationale(&self) -> &'static str {
+        self.message()
+    }
+
     /// Severity level for diagnostics produced by this detector
     fn default_severity(&self) -> DiagnosticSeverity;
 
+    /// Share the workspace files loaded for a scan, for detectors that resolve names across files
+    /// Without them (`None`) such detectors load the workspace of each analyzed file themselves
+    fn set_workspace(&mut self, _workspace: Option<Arc<WorkspaceFiles>>) {}
+
     /// Analyze the given content and return any security issues found
-    fn analyze(&mut self, content: &str, file_path: Option<&PathBuf>) -> Vec<Diagnostic>;
+    /// By default the content is parsed and handed to `analyze_ast`; unparsable content yields nothing
+    fn analyze(&mut self, content: &str, file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+        match parse_str::<syn::File>(content) {
+            Ok(syntax_tree) => {
+                self.analyze_ast(&syntax_tree, content, file_path, &LineIndex::new(content))
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Analyze an already parsed file, so the registry can share one AST across detectors
+    /// `lines` indexes `content` once per registry run, for detectors building ranges with
+    /// `DiagnosticBuilder::create_range_from_span_with_lines`
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        content: &str,
+        file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic>;
 }
@@ -6,6 +6,17 @@ use tower_lsp::lsp_types::DiagnosticSeverity;
 pub struct DetectorConfig {
     pub enabled: bool,
     pub severity_override: Option<DiagnosticSeverity>,
+    /// Also analyze test-only code (`#[cfg(test)]` modules, `#[test]` functions)
+    #[serde(default)]
+    pub include_tests: bool,
+    /// Regexes matched against code lines (not comments or imports), reported with the code
+    /// `<DETECTOR_ID>_CUSTOM_REGEX`; invalid ones are logged and skipped
+    #[serde(default)]
+    pub custom_regex_patterns: Vec<String>,
+    /// Crates, types or paths of audited math libraries (e.g. `spl_math`); the `unchecked_math`
+    /// dylint lint skips arithmetic on values returned by their methods
+    #[serde(default)]
+    pub trusted_math_receivers: Vec<String>,
 }
 
 impl Default for DetectorConfig {
@@ -13,6 +24,9 @@ impl Default for DetectorConfig {
         Self {
             enabled: true,
             severity_override: None,
+            include_tests: false,
+            custom_regex_patterns: Vec::new(),
+            trusted_math_receivers: Vec::new(),
         }
     }
 }
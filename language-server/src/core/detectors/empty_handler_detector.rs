@@ -0,0 +1,202 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use syn::{Expr, Fields, Stmt, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Constraints that mark an account as changed by the instruction, most telling first
+const STATE_CHANGING_CONSTRAINTS: &[&str] = &["init", "init_if_needed", "close", "mut"];
+
+#[derive(Default)]
+pub struct EmptyHandlerDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts structs in the file with their first state-changing field and constraint
+    state_changing_accounts: HashMap<String, (String, String)>,
+    /// Nesting depth of `#[program]` modules
+    program_depth: usize,
+}
+
+impl EmptyHandlerDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Check if an expression is `Ok(())`
+    fn is_ok_unit(expr: &Expr) -> bool {
+        let Expr::Call(call) = expr else {
+            return false;
+        };
+
+        matches!(call.func.as_ref(), Expr::Path(path) if path.path.is_ident("Ok"))
+            && call.args.len() == 1
+            && matches!(&call.args[0], Expr::Tuple(tuple) if tuple.elems.is_empty())
+    }
+
+    /// Check if a handler body does nothing but return `Ok(())`
+    fn is_effectively_empty(block: &syn::Block) -> bool {
+        let [stmt] = block.stmts.as_slice() else {
+            return false;
+        };
+
+        match stmt {
+            Stmt::Expr(Expr::Return(ret), _) => ret.expr.as_deref().is_some_and(Self::is_ok_unit),
+            Stmt::Expr(expr, _) => Self::is_ok_unit(expr),
+            _ => false,
+        }
+    }
+
+    fn check_handler(&mut self, node: &syn::ItemFn) {
+        if !matches!(node.vis, syn::Visibility::Public(_))
+            || !Self::is_effectively_empty(&node.block)
+        {
+            return;
+        }
+
+        let Some(accounts) = node.sig.inputs.iter().find_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => AnchorPatterns::context_accounts_ident(&pat_type.ty),
+            syn::FnArg::Receiver(_) => None,
+        }) else {
+            return;
+        };
+        // Accounts structs from other files are not known, so their handlers are skipped
+        let Some((field, constraint)) = self.state_changing_accounts.get(&accounts.to_string())
+        else {
+            return;
+        };
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(
+                    node.sig.ident.span(),
+                    &self.lines,
+                ),
+                format!(
+                    "Handler '{}' only returns Ok(()), but '{}' declares `{}` on '{}'. {}",
+                    node.sig.ident,
+                    accounts,
+                    constraint,
+                    field,
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+impl Detector for EmptyHandlerDetector {
+    fn id(&self) -> &'static str {
+        "EMPTY_HANDLER"
+    }
+
+    fn name(&self) -> &'static str {
+        "Empty Handler"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects #[program] handlers that only return Ok(()) while their accounts are mut, init or close"
+    }
+
+    fn message(&self) -> &'static str {
+        "Only the account constraints run for this instruction. If the handler should update state or check access, that logic may be missing."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Accounts marked mut, init or close signal that the instruction is meant to change state. When the handler body does nothing, the only effects are those of the constraints, which often means the actual logic or an access check was never written."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.program_depth = 0;
+
+        let mut collector = StateChangingAccountsCollector::default();
+        collector.visit_file(syntax_tree);
+        self.state_changing_accounts = collector.accounts;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for EmptyHandlerDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        let is_program = AnchorPatterns::is_program_module(node);
+        if is_program {
+            self.program_depth += 1;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+
+        if is_program {
+            self.program_depth -= 1;
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if self.program_depth > 0 {
+            self.check_handler(node);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+}
+
+/// Collects accounts structs with a `mut`, `init` or `close` field
+#[derive(Default)]
+struct StateChangingAccountsCollector {
+    accounts: HashMap<String, (String, String)>,
+}
+
+impl<'ast> Visit<'ast> for StateChangingAccountsCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node)
+            && let Fields::Named(fields) = &node.fields
+        {
+            // Report the most telling constraint, e.g. `close` over the `mut` it requires
+            let state_changing = STATE_CHANGING_CONSTRAINTS.iter().find_map(|key| {
+                fields
+                    .named
+                    .iter()
+                    .filter(|field| AnchorPatterns::has_constraint(field, key))
+                    .find_map(|field| field.ident.as_ref())
+                    .map(|ident| (ident.to_string(), key.to_string()))
+            });
+
+            if let Some(state_changing) = state_changing {
+                self.accounts.insert(node.ident.to_string(), state_changing);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
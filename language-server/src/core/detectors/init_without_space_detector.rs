@@ -0,0 +1,173 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Constraint namespaces for which Anchor computes the space itself
+const SIZED_NAMESPACES: &[&str] = &["token::", "mint::", "associated_token::"];
+
+#[derive(Default)]
+pub struct InitWithoutSpaceDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// `#[account]` structs in the file deriving `InitSpace`
+    init_space_structs: HashSet<String>,
+}
+
+impl InitWithoutSpaceDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Check if a struct has `#[derive(InitSpace)]`
+    fn derives_init_space(item_struct: &syn::ItemStruct) -> bool {
+        item_struct.attrs.iter().any(|attr| {
+            attr.path().is_ident("derive")
+                && attr
+                    .meta
+                    .require_list()
+                    .is_ok_and(|meta| meta.tokens.to_string().contains("InitSpace"))
+        })
+    }
+}
+
+impl Detector for InitWithoutSpaceDetector {
+    fn id(&self) -> &'static str {
+        "INIT_WITHOUT_SPACE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Init Without Space"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `init`/`init_if_needed` accounts with no `space =` whose type does not derive InitSpace"
+    }
+
+    fn message(&self) -> &'static str {
+        "Anchor needs to know how much space to allocate for the new account. Add `space = 8 + <size>`, or derive InitSpace on the account struct and use `space = 8 + T::INIT_SPACE`."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "`init` creates the account with exactly the requested number of bytes, and Anchor has no way to compute that size on its own. Without `space` or an InitSpace-derived size the account cannot be created."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::ERROR
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        let mut collector = InitSpaceCollector::default();
+        collector.visit_file(syntax_tree);
+        self.init_space_structs = collector.structs;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for InitWithoutSpaceDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                let constraints = AnchorPatterns::parse_account_constraints(field);
+                let Some(init) = constraints.iter().find(|constraint| {
+                    constraint.key == "init" || constraint.key == "init_if_needed"
+                }) else {
+                    continue;
+                };
+
+                let sized = constraints.iter().any(|constraint| {
+                    constraint.key == "space"
+                        || SIZED_NAMESPACES
+                            .iter()
+                            .any(|namespace| constraint.key.starts_with(namespace))
+                });
+                if sized {
+                    continue;
+                }
+
+                let data_type = AnchorPatterns::account_data_type_name(field);
+                if data_type
+                    .as_ref()
+                    .is_some_and(|data_type| self.init_space_structs.contains(data_type))
+                {
+                    continue;
+                }
+
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+                let name = field
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| "account".to_string(), ToString::to_string);
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(init.span, &self.lines),
+                        format!(
+                            "'{}' is created with `{}` but has no `space` and {} does not derive InitSpace. {}",
+                            name,
+                            init.key,
+                            data_type.map_or_else(
+                                || "its type".to_string(),
+                                |data_type| format!("'{}'", data_type)
+                            ),
+                            self.message()
+                        ),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+/// Collects `#[account]` structs deriving `InitSpace`
+#[derive(Default)]
+struct InitSpaceCollector {
+    structs: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for InitSpaceCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_account_struct(node)
+            && InitWithoutSpaceDetector::derives_init_space(node)
+        {
+            self.structs.insert(node.ident.to_string());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
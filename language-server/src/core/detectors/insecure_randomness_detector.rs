@@ -0,0 +1,263 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::DiagnosticBuilder;
+use crate::core::utilities::LineIndex;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, Pat, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Fields and identifiers whose values are predictable on-chain
+const PREDICTABLE_SOURCES: &[&str] = &["unix_timestamp", "slot", "recent_blockhashes"];
+
+/// Sysvar types whose values are predictable on-chain
+const PREDICTABLE_TYPES: &[&str] = &["Clock", "RecentBlockhashes", "SlotHashes"];
+
+/// Variable name fragments that suggest the value is used as randomness
+const RANDOMNESS_NAMES: &[&str] = &["random", "rand", "rng", "winner", "seed", "entropy"];
+
+#[derive(Default)]
+pub struct InsecureRandomnessDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Locals in the current function that hold values derived from predictable sources
+    tainted_locals: HashSet<String>,
+    /// Set while visiting an expression that was already reported as a whole
+    suppress_reports: bool,
+}
+
+impl InsecureRandomnessDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            tainted_locals: HashSet::new(),
+            suppress_reports: false,
+        }
+    }
+
+    /// Check if an expression reads from a predictable source or a tainted local
+    fn is_tainted(&self, expr: &Expr) -> bool {
+        let mut finder = TaintFinder {
+            tainted_locals: &self.tainted_locals,
+            found: false,
+        };
+        finder.visit_expr(expr);
+        finder.found
+    }
+
+    /// Check if a variable name suggests it holds randomness
+    fn is_randomness_name(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        RANDOMNESS_NAMES
+            .iter()
+            .any(|fragment| name.contains(fragment))
+    }
+
+    /// Get the name of an assignment target (`x = ..` or `foo.x = ..`)
+    fn assignment_target_name(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Path(expr_path) => expr_path.path.get_ident().map(|ident| ident.to_string()),
+            Expr::Field(expr_field) => match &expr_field.member {
+                syn::Member::Named(ident) => Some(ident.to_string()),
+                syn::Member::Unnamed(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn report<T: Spanned>(&mut self, node: &T, detail: String) {
+        if self.suppress_reports {
+            return;
+        }
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(node.span(), &self.lines),
+                format!("{} {}", detail, self.message()),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+
+    /// Visit an expression without reporting nested findings
+    fn visit_suppressed(&mut self, expr: &Expr) {
+        let previous = self.suppress_reports;
+        self.suppress_reports = true;
+        self.visit_expr(expr);
+        self.suppress_reports = previous;
+    }
+}
+
+impl Detector for InsecureRandomnessDetector {
+    fn id(&self) -> &'static str {
+        "INSECURE_RANDOMNESS"
+    }
+
+    fn name(&self) -> &'static str {
+        "Insecure Randomness"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects Clock, slot or recent blockhash values used as a source of randomness"
+    }
+
+    fn message(&self) -> &'static str {
+        "On-chain time, slots and blockhashes are predictable and can be influenced by validators. Use a verifiable randomness source (e.g. a VRF oracle) instead."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Every validator computes the same Clock, slot and blockhash values, and the leader chooses when to include a transaction. Anyone can predict the outcome before submitting, and a validator can delay or reorder transactions until the value favors them."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.tainted_locals.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for InsecureRandomnessDetector {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        // Taint tracking is local to a single function
+        let outer = std::mem::take(&mut self.tainted_locals);
+        syn::visit::visit_item_fn(self, node);
+        self.tainted_locals = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        let outer = std::mem::take(&mut self.tainted_locals);
+        syn::visit::visit_impl_item_fn(self, node);
+        self.tainted_locals = outer;
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        let Some(init) = &node.init else {
+            syn::visit::visit_local(self, node);
+            return;
+        };
+
+        let name = match &node.pat {
+            Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+            Pat::Type(pat_type) => match &*pat_type.pat {
+                Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if self.is_tainted(&init.expr) {
+            if let Some(name) = name {
+                if self.is_randomness_name(&name) {
+                    self.report(
+                        node,
+                        format!("'{}' is derived from a predictable on-chain value.", name),
+                    );
+                    // Already reported; later uses of this local are the same finding
+                    self.visit_suppressed(&init.expr);
+                    self.tainted_locals.remove(&name);
+                    return;
+                }
+                self.tainted_locals.insert(name);
+            }
+        } else if let Some(name) = name {
+            // Shadowing with an untainted value clears the taint
+            self.tainted_locals.remove(&name);
+        }
+
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_expr_assign(&mut self, node: &'ast syn::ExprAssign) {
+        if self.is_tainted(&node.right)
+            && let Some(name) = self.assignment_target_name(&node.left)
+        {
+            if self.is_randomness_name(&name) {
+                self.report(
+                    node,
+                    format!("'{}' is derived from a predictable on-chain value.", name),
+                );
+                self.visit_suppressed(&node.right);
+                return;
+            }
+
+            if matches!(&*node.left, Expr::Path(_)) {
+                self.tainted_locals.insert(name);
+            }
+        }
+
+        syn::visit::visit_expr_assign(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if matches!(node.op, BinOp::Rem(_) | BinOp::RemAssign(_)) && self.is_tainted(&node.left) {
+            self.report(
+                node,
+                "Modulo of a predictable on-chain value used for selection.".to_string(),
+            );
+        }
+
+        syn::visit::visit_expr_binary(self, node);
+    }
+}
+
+/// Finds reads of predictable on-chain values inside an expression
+struct TaintFinder<'a> {
+    tainted_locals: &'a HashSet<String>,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for TaintFinder<'_> {
+    fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+        if let syn::Member::Named(ident) = &node.member
+            && PREDICTABLE_SOURCES.contains(&ident.to_string().as_str())
+        {
+            self.found = true;
+        }
+        syn::visit::visit_expr_field(self, node);
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        let path = &node.path;
+        if let Some(ident) = path.get_ident() {
+            let name = ident.to_string();
+            if self.tainted_locals.contains(&name) || PREDICTABLE_SOURCES.contains(&name.as_str()) {
+                self.found = true;
+            }
+        }
+
+        if path
+            .segments
+            .iter()
+            .any(|segment| PREDICTABLE_TYPES.contains(&segment.ident.to_string().as_str()))
+        {
+            self.found = true;
+        }
+
+        syn::visit::visit_expr_path(self, node);
+    }
+}
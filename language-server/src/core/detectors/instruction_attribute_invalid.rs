@@ -1,15 +1,16 @@
 use super::detector::Detector;
 use super::detector_config::DetectorConfig;
-use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use syn::{FnArg, ItemFn, PatType, Type, TypePath, parse_str, visit::Visit};
+use syn::visit::Visit;
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
 #[derive(Default)]
 pub struct InstructionAttributeInvalidDetector {
     diagnostics: Vec<Diagnostic>,
     config: DetectorConfig,
+    lines: LineIndex,
     // Map from function name to its parameters (excluding Context)
     instruction_handlers: HashMap<String, Vec<(String, String)>>, // (param_name, param_type)
 }
@@ -20,132 +21,11 @@ impl InstructionAttributeInvalidDetector {
         Self {
             diagnostics: Vec::new(),
             config,
+            lines: LineIndex::default(),
             instruction_handlers: HashMap::new(),
         }
     }
 
-    /// Extract parameters from an instruction handler function and return (context_struct_name, parameters)
-    fn extract_handler_parameters(
-        &self,
-        item_fn: &ItemFn,
-    ) -> Option<(String, Vec<(String, String)>)> {
-        let mut parameters = Vec::new();
-        let mut context_struct_name: Option<String> = None;
-
-        for input in &item_fn.sig.inputs {
-            if let FnArg::Typed(PatType { pat, ty, .. }) = input {
-                if let syn::Pat::Ident(pat_ident) = &**pat {
-                    let param_name = pat_ident.ident.to_string();
-
-                    // Check if this is a Context parameter
-                    if let Type::Path(TypePath { path, .. }) = &**ty {
-                        if let Some(segment) = path.segments.first() {
-                            if segment.ident == "Context" {
-                                // Extract the context struct name from Context<StructName>
-                                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments
-                                {
-                                    if let Some(syn::GenericArgument::Type(Type::Path(
-                                        context_type,
-                                    ))) = args.args.first()
-                                    {
-                                        if let Some(context_segment) =
-                                            context_type.path.segments.first()
-                                        {
-                                            context_struct_name =
-                                                Some(context_segment.ident.to_string());
-                                        }
-                                    }
-                                }
-                                continue; // Skip the Context parameter itself
-                            }
-                        }
-                    }
-
-                    // Extract type as string with better formatting
-                    let type_str = self.extract_type_string(ty);
-                    parameters.push((param_name, type_str));
-                }
-            }
-        }
-
-        // Only return if we found a context struct name
-        context_struct_name.map(|struct_name| (struct_name, parameters))
-    }
-
-    /// Extract a clean type string from a syn::Type
-    fn extract_type_string(&self, ty: &Type) -> String {
-        match ty {
-            Type::Path(type_path) => {
-                let mut result = String::new();
-                for (i, segment) in type_path.path.segments.iter().enumerate() {
-                    if i > 0 {
-                        result.push_str("::");
-                    }
-                    result.push_str(&segment.ident.to_string());
-
-                    // Handle generic arguments
-                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                        result.push('<');
-                        for (j, arg) in args.args.iter().enumerate() {
-                            if j > 0 {
-                                result.push_str(", ");
-                            }
-                            match arg {
-                                syn::GenericArgument::Type(inner_ty) => {
-                                    result.push_str(&self.extract_type_string(inner_ty));
-                                }
-                                syn::GenericArgument::Lifetime(lifetime) => {
-                                    result.push_str(&format!("'{}", lifetime.ident));
-                                }
-                                _ => {
-                                    result.push_str(&format!("{:?}", arg));
-                                }
-                            }
-                        }
-                        result.push('>');
-                    }
-                }
-                result
-            }
-            Type::Reference(type_ref) => {
-                let mut result = String::from("&");
-                if let Some(lifetime) = &type_ref.lifetime {
-                    result.push_str(&format!("'{} ", lifetime.ident));
-                }
-                if type_ref.mutability.is_some() {
-                    result.push_str("mut ");
-                }
-                result.push_str(&self.extract_type_string(&type_ref.elem));
-                result
-            }
-            Type::Slice(type_slice) => {
-                format!("[{}]", self.extract_type_string(&type_slice.elem))
-            }
-            Type::Array(type_array) => {
-                format!(
-                    "[{}; {:?}]",
-                    self.extract_type_string(&type_array.elem),
-                    type_array.len
-                )
-            }
-            Type::Tuple(type_tuple) => {
-                let mut result = String::from("(");
-                for (i, elem) in type_tuple.elems.iter().enumerate() {
-                    if i > 0 {
-                        result.push_str(", ");
-                    }
-                    result.push_str(&self.extract_type_string(elem));
-                }
-                result.push(')');
-                result
-            }
-            _ => {
-                // Fallback to debug format
-                format!("{:?}", ty)
-            }
-        }
-    }
-
     /// Check if instruction attribute parameters match the handler parameters
     fn validate_instruction_parameters(
         &self,
@@ -169,8 +49,11 @@ impl InstructionAttributeInvalidDetector {
                         param_name
                     );
 
-                    diagnostics.push(DiagnosticBuilder::create(
-                        DiagnosticBuilder::create_range_from_span(*param_span),
+                    diagnostics.push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            *param_span,
+                            &self.lines,
+                        ),
                         message,
                         severity,
                         self.id().to_string(),
@@ -192,8 +75,11 @@ impl InstructionAttributeInvalidDetector {
                             i + 1
                         );
 
-                        diagnostics.push(DiagnosticBuilder::create(
-                            DiagnosticBuilder::create_range_from_span(*param_span),
+                        diagnostics.push(DiagnosticBuilder::create_with_code_description(
+                            DiagnosticBuilder::create_range_from_span_with_lines(
+                                *param_span,
+                                &self.lines,
+                            ),
                             message,
                             severity,
                             self.id().to_string(),
@@ -214,8 +100,11 @@ impl InstructionAttributeInvalidDetector {
                                 param_name, param_type, expected_type
                             );
 
-                            diagnostics.push(DiagnosticBuilder::create(
-                                DiagnosticBuilder::create_range_from_span(*param_span),
+                            diagnostics.push(DiagnosticBuilder::create_with_code_description(
+                                DiagnosticBuilder::create_range_from_span_with_lines(
+                                    *param_span,
+                                    &self.lines,
+                                ),
                                 message,
                                 severity,
                                 self.id().to_string(),
@@ -258,19 +147,28 @@ impl Detector for InstructionAttributeInvalidDetector {
         "Invalid use of instruction attribute"
     }
 
+    fn rationale(&self) -> &'static str {
+        "Anchor deserializes #[instruction(...)] arguments positionally from the instruction data. If their order or types differ from the handler's arguments, the struct reads the wrong bytes and its constraints check values the caller never meant to send."
+    }
+
     fn default_severity(&self) -> DiagnosticSeverity {
         DiagnosticSeverity::ERROR
     }
 
-    fn analyze(&mut self, content: &str, _file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
         self.diagnostics.clear();
         self.instruction_handlers.clear();
 
         // Run default detection logic
-        if let Ok(syntax_tree) = parse_str::<syn::File>(content) {
-            // Collect all instruction handler functions (any function with Context<T> parameter)
-            self.visit_file(&syntax_tree);
-        }
+        // Collect all instruction handler functions (any function with Context<T> parameter)
+        self.visit_file(syntax_tree);
 
         self.diagnostics.clone()
     }
@@ -279,7 +177,9 @@ impl Detector for InstructionAttributeInvalidDetector {
 impl<'ast> Visit<'ast> for InstructionAttributeInvalidDetector {
     fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
         // Collect any function that has a Context<T> parameter (potential instruction handler)
-        if let Some((context_struct_name, params)) = self.extract_handler_parameters(node) {
+        if let Some((context_struct_name, params)) =
+            AnchorPatterns::extract_handler_parameters(node)
+        {
             self.instruction_handlers
                 .insert(context_struct_name, params);
         }
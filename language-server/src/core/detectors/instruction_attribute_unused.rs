@@ -1,14 +1,15 @@
 use super::detector::Detector;
 use super::detector_config::DetectorConfig;
-use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
 use std::path::PathBuf;
-use syn::{Fields, Meta, parse_str, visit::Visit};
+use syn::{Fields, Meta, visit::Visit};
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
 #[derive(Default)]
 pub struct InstructionAttributeUnusedDetector {
     diagnostics: Vec<Diagnostic>,
     config: DetectorConfig,
+    lines: LineIndex,
 }
 
 impl InstructionAttributeUnusedDetector {
@@ -17,6 +18,7 @@ impl InstructionAttributeUnusedDetector {
         Self {
             diagnostics: Vec::new(),
             config,
+            lines: LineIndex::default(),
         }
     }
 
@@ -29,14 +31,14 @@ impl InstructionAttributeUnusedDetector {
         for field in &fields.named {
             // Check account constraints in field attributes
             for attr in &field.attrs {
-                if attr.path().is_ident("account") {
-                    if let Meta::List(meta_list) = &attr.meta {
-                        let constraint_tokens = meta_list.tokens.to_string();
-
-                        // Check if parameter is referenced in constraints
-                        if constraint_tokens.contains(param_name) {
-                            return true;
-                        }
+                if attr.path().is_ident("account")
+                    && let Meta::List(meta_list) = &attr.meta
+                {
+                    let constraint_tokens = meta_list.tokens.to_string();
+
+                    // Check if parameter is referenced in constraints
+                    if constraint_tokens.contains(param_name) {
+                        return true;
                     }
                 }
             }
@@ -70,17 +72,26 @@ impl Detector for InstructionAttributeUnusedDetector {
         "Unused instruction parameter"
     }
 
+    fn rationale(&self) -> &'static str {
+        "Every #[instruction(...)] argument is deserialized for the accounts struct. One that no constraint uses costs compute for nothing and often points to a seed or check that was meant to use it but was left out."
+    }
+
     fn default_severity(&self) -> DiagnosticSeverity {
         DiagnosticSeverity::WARNING
     }
 
-    fn analyze(&mut self, content: &str, _file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
         self.diagnostics.clear();
 
         // Run default detection logic
-        if let Ok(syntax_tree) = parse_str::<syn::File>(content) {
-            self.visit_file(&syntax_tree);
-        }
+        self.visit_file(syntax_tree);
 
         self.diagnostics.clone()
     }
@@ -106,13 +117,17 @@ impl<'ast> Visit<'ast> for InstructionAttributeUnusedDetector {
 
                 let message = format!("{}: '{}'", self.message(), param_name);
 
-                self.diagnostics.push(DiagnosticBuilder::create(
-                    DiagnosticBuilder::create_range_from_span(param_span),
-                    message,
-                    severity,
-                    self.id().to_string(),
-                    None,
-                ));
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            param_span,
+                            &self.lines,
+                        ),
+                        message,
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
             }
         }
 
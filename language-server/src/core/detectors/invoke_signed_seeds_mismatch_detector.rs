@@ -0,0 +1,410 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// Bindings followed when resolving signer seeds; shadowed names (`let seeds = &seeds[..]`)
+/// are collected as their last binding and would otherwise resolve to themselves
+const MAX_LOCAL_HOPS: usize = 4;
+
+/// Seeds declared by a PDA field, e.g. `seeds = [b"vault", user.key().as_ref()]`
+struct DeclaredSeeds {
+    /// Seeds with every non-literal seed replaced by `_`, used for comparison
+    shape: String,
+    /// Seeds as written, used in messages
+    source: String,
+}
+
+/// Signer seeds of an `invoke_signed` call matching none of the PDAs it passes
+struct SeedsMismatch {
+    range: Range,
+    /// Shape of the signer seeds, without the bump
+    shape: String,
+    field: String,
+    declared: String,
+}
+
+#[derive(Default)]
+pub struct InvokeSignedSeedsMismatchDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+}
+
+impl InvokeSignedSeedsMismatchDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+}
+
+impl Detector for InvokeSignedSeedsMismatchDetector {
+    fn id(&self) -> &'static str {
+        "INVOKE_SIGNED_SEEDS_MISMATCH"
+    }
+
+    fn name(&self) -> &'static str {
+        "Invoke Signed Seeds Mismatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects invoke_signed signer seeds that differ from the seeds declared on the PDA account"
+    }
+
+    fn message(&self) -> &'static str {
+        "Signer seeds that do not derive the PDA's address do not make it a signer, so the CPI fails. Sign with the declared seeds followed by the bump."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "The runtime grants signer privileges to a PDA only when the signer seeds hash to its address. Seeds that differ from the ones the account was validated with derive some other address, so the callee sees no signature."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.diagnostics.clear();
+
+        let mut collector = DeclaredSeedsCollector::default();
+        collector.visit_file(syntax_tree);
+        if collector.declared_seeds.is_empty() {
+            return Vec::new();
+        }
+
+        let mut checker = SignerSeedsChecker {
+            declared_seeds: &collector.declared_seeds,
+            lines,
+            accounts_struct: None,
+            locals: HashMap::new(),
+            mismatches: Vec::new(),
+        };
+        checker.visit_file(syntax_tree);
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        for mismatch in checker.mismatches {
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    mismatch.range,
+                    format!(
+                        "invoke_signed signs with seeds `[{}]`, but '{}' declares seeds `{}`. {}",
+                        mismatch.shape,
+                        mismatch.field,
+                        mismatch.declared,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        self.diagnostics.clone()
+    }
+}
+
+/// Compares the signer seeds of `invoke_signed` calls with the seeds of the PDAs they pass
+struct SignerSeedsChecker<'a, 'ast> {
+    /// PDA fields of the accounts structs in the file, per struct and field
+    declared_seeds: &'a HashMap<String, HashMap<String, DeclaredSeeds>>,
+    lines: &'a LineIndex,
+    /// Accounts struct of the function being visited (its `Context<..>` or `impl` type)
+    accounts_struct: Option<String>,
+    /// Initializers of the `let` bindings in the function being visited
+    locals: HashMap<String, &'ast Expr>,
+    mismatches: Vec<SeedsMismatch>,
+}
+
+impl<'ast> SignerSeedsChecker<'_, 'ast> {
+    /// Follow a local binding to its initializer, looking through `&` and `seeds[..]`
+    fn resolve(&self, expr: &'ast Expr) -> &'ast Expr {
+        self.resolve_within(expr, MAX_LOCAL_HOPS)
+    }
+
+    fn resolve_within(&self, expr: &'ast Expr, hops: usize) -> &'ast Expr {
+        match AstAnalyzer::strip_wrappers(expr) {
+            Expr::Index(index) if matches!(index.index.as_ref(), Expr::Range(_)) => {
+                self.resolve_within(&index.expr, hops)
+            }
+            Expr::Path(path) if hops > 0 => match path
+                .path
+                .get_ident()
+                .and_then(|ident| self.locals.get(&ident.to_string()))
+            {
+                Some(init) => self.resolve_within(init, hops - 1),
+                None => expr,
+            },
+            expr => expr,
+        }
+    }
+
+    /// Check if a signer seed is the trailing bump, e.g. `&[bump]` or `&[ctx.bumps.vault]`
+    fn is_bump_seed(&self, seed: &'ast Expr) -> bool {
+        match AnchorPatterns::strip_seed_conversions(seed) {
+            Expr::Array(array) => array.elems.len() == 1,
+            Expr::Path(path) => {
+                path.path
+                    .get_ident()
+                    .is_some_and(|ident| ident.to_string().contains("bump"))
+                    || matches!(self.resolve(seed), Expr::Array(array) if array.elems.len() == 1)
+            }
+            _ => false,
+        }
+    }
+
+    /// Seed arrays of the `signers_seeds` argument; `None` if any of them cannot be resolved
+    fn signer_seed_sets(&self, signers_seeds: &'ast Expr) -> Option<Vec<&'ast syn::ExprArray>> {
+        let Expr::Array(signers) = self.resolve(signers_seeds) else {
+            return None;
+        };
+
+        signers
+            .elems
+            .iter()
+            .map(|signer| match self.resolve(signer) {
+                Expr::Array(seeds) => Some(seeds),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn check_invoke_signed(&mut self, call: &'ast syn::ExprCall) {
+        let Some(fields) = self
+            .accounts_struct
+            .as_ref()
+            .and_then(|accounts_struct| self.declared_seeds.get(accounts_struct))
+        else {
+            return;
+        };
+
+        // PDA fields whose account infos are passed to the call
+        let mut finder = AccountFieldFinder {
+            locals: Some(&self.locals),
+            fields: BTreeSet::new(),
+        };
+        finder.visit_expr(&call.args[0]);
+        finder.visit_expr(&call.args[1]);
+        let pdas: Vec<(&String, &DeclaredSeeds)> = finder
+            .fields
+            .iter()
+            .filter_map(|field| fields.get_key_value(field))
+            .collect();
+        if pdas.is_empty() {
+            return;
+        }
+
+        let Some(seed_sets) = self.signer_seed_sets(&call.args[2]) else {
+            return;
+        };
+
+        for seeds in seed_sets {
+            let mut elems: Vec<&Expr> = seeds.elems.iter().collect();
+            if elems.last().is_some_and(|last| self.is_bump_seed(last)) {
+                elems.pop();
+            }
+
+            let shape = AnchorPatterns::seed_shape(elems);
+            if pdas.iter().any(|(_, declared)| declared.shape == shape) {
+                continue;
+            }
+
+            let (field, declared) = pdas[0];
+            self.mismatches.push(SeedsMismatch {
+                range: DiagnosticBuilder::create_range_from_span_with_lines(
+                    seeds.span(),
+                    self.lines,
+                ),
+                shape,
+                field: field.clone(),
+                declared: declared.source.clone(),
+            });
+        }
+    }
+
+    /// Visit a function body with its `let` bindings known
+    fn visit_body(&mut self, accounts_struct: Option<String>, block: &'ast syn::Block) {
+        let mut collector = LocalCollector::default();
+        collector.visit_block(block);
+
+        let outer_struct = std::mem::replace(&mut self.accounts_struct, accounts_struct);
+        let outer_locals = std::mem::replace(&mut self.locals, collector.locals);
+        self.visit_block(block);
+        self.accounts_struct = outer_struct;
+        self.locals = outer_locals;
+    }
+}
+
+impl<'ast> Visit<'ast> for SignerSeedsChecker<'_, 'ast> {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        let accounts_struct = node.sig.inputs.iter().find_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => {
+                AnchorPatterns::context_accounts_ident(&pat_type.ty).map(ToString::to_string)
+            }
+            syn::FnArg::Receiver(_) => None,
+        });
+
+        self.visit_body(accounts_struct, &node.block);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        // Helpers on the accounts struct itself reach its fields through `self`
+        let self_struct = match node.self_ty.as_ref() {
+            syn::Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string()),
+            _ => None,
+        };
+
+        for item in &node.items {
+            if let syn::ImplItem::Fn(method) = item {
+                self.visit_body(self_struct.clone(), &method.block);
+            }
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = node.func.as_ref()
+            && path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "invoke_signed")
+            && node.args.len() == 3
+        {
+            self.check_invoke_signed(node);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+}
+
+/// Collects the declared seeds of PDA fields in accounts structs
+#[derive(Default)]
+struct DeclaredSeedsCollector {
+    declared_seeds: HashMap<String, HashMap<String, DeclaredSeeds>>,
+}
+
+impl<'ast> Visit<'ast> for DeclaredSeedsCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node)
+            && let Fields::Named(fields) = &node.fields
+        {
+            for field in &fields.named {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let Some(value) = AnchorPatterns::parse_account_constraints(field)
+                    .into_iter()
+                    .find(|constraint| constraint.key == "seeds")
+                    .and_then(|constraint| constraint.value)
+                else {
+                    continue;
+                };
+                // Seeds given as a constant or expression are not compared
+                let Ok(Expr::Array(array)) = syn::parse2::<Expr>(value.clone()) else {
+                    continue;
+                };
+
+                self.declared_seeds
+                    .entry(node.ident.to_string())
+                    .or_default()
+                    .insert(
+                        ident.to_string(),
+                        DeclaredSeeds {
+                            shape: AnchorPatterns::seed_shape(&array.elems),
+                            source: value.to_string(),
+                        },
+                    );
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+/// Collects the initializers of `let name = <expr>;` bindings
+#[derive(Default)]
+struct LocalCollector<'ast> {
+    locals: HashMap<String, &'ast Expr>,
+}
+
+impl<'ast> Visit<'ast> for LocalCollector<'ast> {
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        let pat = match &node.pat {
+            syn::Pat::Type(pat_type) => pat_type.pat.as_ref(),
+            pat => pat,
+        };
+        if let syn::Pat::Ident(ident) = pat
+            && let Some(init) = &node.init
+        {
+            self.locals.insert(ident.ident.to_string(), &init.expr);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_local(self, node);
+    }
+}
+
+/// Finds accounts struct fields used in an expression: `ctx.accounts.<field>` or `self.<field>`
+struct AccountFieldFinder<'a, 'ast> {
+    /// Bindings to follow, taken while following one so they are followed one level only
+    locals: Option<&'a HashMap<String, &'ast Expr>>,
+    fields: BTreeSet<String>,
+}
+
+impl<'ast> Visit<'ast> for AccountFieldFinder<'_, 'ast> {
+    fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+        let is_accounts_base = match node.base.as_ref() {
+            Expr::Field(base) => {
+                matches!(&base.member, syn::Member::Named(ident) if ident == "accounts")
+            }
+            Expr::Path(path) => path.path.is_ident("self"),
+            _ => false,
+        };
+        if is_accounts_base && let syn::Member::Named(ident) = &node.member {
+            self.fields.insert(ident.to_string());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_field(self, node);
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        // `let vault = &ctx.accounts.vault;` then `vault.to_account_info()`
+        if let Some(locals) = self.locals
+            && let Some(init) = node
+                .path
+                .get_ident()
+                .and_then(|ident| locals.get(&ident.to_string()))
+        {
+            self.locals = None;
+            self.visit_expr(init);
+            self.locals = Some(locals);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_path(self, node);
+    }
+}
@@ -0,0 +1,205 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// Identifiers showing that a function takes the rent-exempt minimum into account
+const RENT_IDENTS: &[&str] = &["minimum_balance", "is_exempt", "Rent", "rent"];
+
+/// Methods that subtract from their receiver
+const SUB_METHODS: &[&str] = &["checked_sub", "saturating_sub", "wrapping_sub"];
+
+/// Lamport subtractions of the function being visited
+#[derive(Default)]
+struct FunctionLamports {
+    /// Ranges of subtractions whose minuend is an account's lamports
+    subtractions: Vec<Range>,
+    /// Whether the function mentions rent anywhere
+    rent_aware: bool,
+}
+
+#[derive(Default)]
+pub struct LamportsBelowRentDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// One entry per enclosing function
+    functions: Vec<FunctionLamports>,
+}
+
+impl LamportsBelowRentDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            functions: Vec::new(),
+        }
+    }
+
+    /// Check if an identifier refers to rent (e.g. `Rent::get()`, `rent_exempt_minimum`)
+    fn is_rent_ident(ident: &str) -> bool {
+        RENT_IDENTS.contains(&ident) || ident.starts_with("rent_") || ident.ends_with("_rent")
+    }
+
+    fn record_subtraction(&mut self, range: Range) {
+        if let Some(function) = self.functions.last_mut() {
+            function.subtractions.push(range);
+        }
+    }
+
+    /// Visit a function body and report lamport subtractions if rent is never considered
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, visit: F) {
+        self.functions.push(FunctionLamports::default());
+        visit(self);
+        let Some(function) = self.functions.pop() else {
+            return;
+        };
+
+        if function.rent_aware {
+            return;
+        }
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        for range in function.subtractions {
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    range,
+                    format!(
+                        "Lamports are withdrawn without checking the rent-exempt minimum. {}",
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
+
+impl Detector for LamportsBelowRentDetector {
+    fn id(&self) -> &'static str {
+        "LAMPORTS_BELOW_RENT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Lamports Below Rent"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects lamport subtractions that may leave an account below the rent-exempt minimum"
+    }
+
+    fn message(&self) -> &'static str {
+        "An account left with fewer lamports than Rent::minimum_balance(data_len) is no longer rent-exempt and the transaction fails. Compare the remaining balance against the rent-exempt minimum, or close the account entirely."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "The runtime checks after every instruction that accounts with data hold at least the rent-exempt minimum for their size. A withdrawal that does not account for that minimum fails for balances close to it, which is easy to miss in tests with generously funded accounts."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.functions.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for LamportsBelowRentDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(|detector| syn::visit::visit_item_fn(detector, node));
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(|detector| syn::visit::visit_impl_item_fn(detector, node));
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if matches!(node.op, BinOp::Sub(_) | BinOp::SubAssign(_))
+            && AnchorPatterns::is_lamports_access(&node.left)
+        {
+            self.record_subtraction(DiagnosticBuilder::create_range_from_span_with_lines(
+                node.span(),
+                &self.lines,
+            ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        if SUB_METHODS.iter().any(|method| node.method == method)
+            && AnchorPatterns::is_lamports_access(&node.receiver)
+        {
+            self.record_subtraction(DiagnosticBuilder::create_range_from_span_with_lines(
+                node.span(),
+                &self.lines,
+            ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        // Rent checks are usually written inside require!(..)
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+
+    fn visit_ident(&mut self, node: &'ast proc_macro2::Ident) {
+        if Self::is_rent_ident(&node.to_string())
+            && let Some(function) = self.functions.last_mut()
+        {
+            function.rent_aware = true;
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, SizeEstimator, anchor_patterns::AnchorPatterns,
+};
+use std::path::PathBuf;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Account size (in bytes) above which zero-copy is recommended
+pub const DEFAULT_SIZE_THRESHOLD: usize = 10240;
+
+pub struct LargeAccountNoZeroCopyDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    threshold: usize,
+}
+
+impl Default for LargeAccountNoZeroCopyDetector {
+    fn default() -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config: DetectorConfig::default(),
+            lines: LineIndex::default(),
+            threshold: DEFAULT_SIZE_THRESHOLD,
+        }
+    }
+}
+
+impl LargeAccountNoZeroCopyDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Default::default()
+        }
+    }
+
+    /// Use a custom size threshold (in bytes)
+    #[allow(dead_code)]
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+impl Detector for LargeAccountNoZeroCopyDetector {
+    fn id(&self) -> &'static str {
+        "LARGE_ACCOUNT_NO_ZERO_COPY"
+    }
+
+    fn name(&self) -> &'static str {
+        "Large Account Without Zero-Copy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects large #[account] structs that are not zero-copy"
+    }
+
+    fn message(&self) -> &'static str {
+        "Deserializing large accounts can overflow the stack/heap. Consider #[account(zero_copy)] with AccountLoader<'info, T>."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Account<'info, T> copies and deserializes the full account into program memory on every access. Large accounts exceed the 4KB stack or the 32KB heap, and even when they fit, the copy costs compute that zero-copy access avoids by reading the data in place."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for LargeAccountNoZeroCopyDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if !AnchorPatterns::is_account_struct(node) || AnchorPatterns::is_zero_copy_struct(node) {
+            return;
+        }
+
+        let estimated_size = SizeEstimator::estimate_fields(&node.fields);
+        if estimated_size > self.threshold {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(
+                        node.ident.span(),
+                        &self.lines,
+                    ),
+                    format!(
+                        "Account '{}' is at least {} bytes (threshold {}). {}",
+                        node.ident,
+                        estimated_size,
+                        self.threshold,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
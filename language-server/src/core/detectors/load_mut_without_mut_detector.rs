@@ -0,0 +1,224 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// Constraints that make Anchor treat the annotated field as writable
+const MUT_CONSTRAINTS: &[&str] = &["mut", "init", "init_if_needed", "zero"];
+
+/// Context parameter of the handler being visited
+struct Handler {
+    /// Name of the `Context<T>` parameter
+    ctx: String,
+    /// Accounts struct of the handler's `Context<T>`
+    accounts: String,
+}
+
+#[derive(Default)]
+pub struct LoadMutWithoutMutDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to its fields not marked writable, with the range of their name
+    immutable_fields: HashMap<String, HashMap<String, Range>>,
+    /// One entry per enclosing function, `None` if it takes no `Context<T>`
+    handlers: Vec<Option<Handler>>,
+    /// Fields already reported, keyed by accounts struct and field name
+    reported: HashSet<(String, String)>,
+}
+
+impl LoadMutWithoutMutDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    fn collect_struct(&mut self, node: &syn::ItemStruct) {
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+
+        let immutable = fields
+            .named
+            .iter()
+            .filter(|field| {
+                // `mut`, `mut @ ErrorCode::X` or a constraint creating the account
+                !AnchorPatterns::parse_account_constraints(field)
+                    .iter()
+                    .any(|constraint| {
+                        MUT_CONSTRAINTS.contains(&constraint.key.as_str())
+                            || constraint.key.starts_with("mut@")
+                    })
+            })
+            .filter_map(|field| {
+                let ident = field.ident.as_ref()?;
+                Some((
+                    ident.to_string(),
+                    DiagnosticBuilder::create_range_from_span_with_lines(ident.span(), &self.lines),
+                ))
+            })
+            .collect();
+
+        self.immutable_fields
+            .insert(node.ident.to_string(), immutable);
+    }
+
+    /// Track the `Context<T>` parameter of a function while visiting its body
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, sig: &syn::Signature, visit: F) {
+        let handler = sig.inputs.iter().find_map(|input| {
+            let syn::FnArg::Typed(pat_type) = input else {
+                return None;
+            };
+            let accounts = AnchorPatterns::context_accounts_ident(&pat_type.ty)?;
+            let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+                return None;
+            };
+            Some(Handler {
+                ctx: pat_ident.ident.to_string(),
+                accounts: accounts.to_string(),
+            })
+        });
+
+        self.handlers.push(handler);
+        visit(self);
+        self.handlers.pop();
+    }
+}
+
+impl Detector for LoadMutWithoutMutDetector {
+    fn id(&self) -> &'static str {
+        "LOAD_MUT_WITHOUT_MUT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Load Mut Without Mut"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects AccountLoader::load_mut() calls on account fields not marked mut"
+    }
+
+    fn message(&self) -> &'static str {
+        "load_mut() fails at runtime unless the account is writable, so the instruction always errors. Mark the field #[account(mut)]."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "AccountLoader::load_mut checks that the account is writable before handing out mutable data. Without #[account(mut)] the transaction does not mark it writable, so that check fails on every call."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::ERROR
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.immutable_fields.clear();
+        self.handlers.clear();
+        self.reported.clear();
+
+        // Accounts structs may follow the handlers using them, so collect them first
+        let mut collector = AccountsCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for LoadMutWithoutMutDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_item_fn(detector, node)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        if node.method == "load_mut"
+            && let Some(Some(handler)) = self.handlers.last()
+            && let (Some(root), members) = AstAnalyzer::member_chain(&node.receiver)
+            && root == handler.ctx
+            && let [accounts, field] = members.as_slice()
+            && accounts == "accounts"
+            && let Some(range) = self
+                .immutable_fields
+                .get(&handler.accounts)
+                .and_then(|fields| fields.get(field))
+                .copied()
+            && self
+                .reported
+                .insert((handler.accounts.clone(), field.clone()))
+        {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    range,
+                    format!(
+                        "Account '{}' is loaded with load_mut() but is not marked mut. {}",
+                        field,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
+
+/// Records the read-only fields of every accounts struct before handlers are checked
+struct AccountsCollector<'a> {
+    detector: &'a mut LoadMutWithoutMutDetector,
+}
+
+impl<'ast> Visit<'ast> for AccountsCollector<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.detector.collect_struct(node);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::DiagnosticBuilder;
+use crate::core::utilities::LineIndex;
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, Macro, Stmt, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct LogBeforeReturnDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl LogBeforeReturnDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Get the `msg!` macro of a statement, if it is one
+    fn as_msg_macro<'a>(&self, stmt: &'a Stmt) -> Option<&'a Macro> {
+        let mac = match stmt {
+            Stmt::Macro(stmt_macro) => &stmt_macro.mac,
+            Stmt::Expr(Expr::Macro(expr_macro), _) => &expr_macro.mac,
+            _ => return None,
+        };
+
+        mac.path
+            .segments
+            .last()
+            .filter(|segment| segment.ident == "msg")
+            .map(|_| mac)
+    }
+
+    /// Check if a statement returns an error: `Err(..)`, `return Err(..)` or `return err!(..)`
+    fn is_error_return(&self, stmt: &Stmt) -> bool {
+        let expr = match stmt {
+            Stmt::Expr(expr, _) => expr,
+            _ => return false,
+        };
+
+        match expr {
+            Expr::Return(expr_return) => expr_return
+                .expr
+                .as_ref()
+                .is_some_and(|inner| self.is_error_value(inner)),
+            // A trailing `Err(..)` without semicolon is the block's return value
+            Expr::Call(_) => matches!(stmt, Stmt::Expr(_, None)) && self.is_error_value(expr),
+            _ => false,
+        }
+    }
+
+    /// Check if an expression constructs an error value
+    fn is_error_value(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Call(call) => match &*call.func {
+                Expr::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Err"),
+                _ => false,
+            },
+            Expr::Macro(expr_macro) => expr_macro
+                .mac
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "err"),
+            _ => false,
+        }
+    }
+}
+
+impl Detector for LogBeforeReturnDetector {
+    fn id(&self) -> &'static str {
+        "REDUNDANT_ERROR_LOG"
+    }
+
+    fn name(&self) -> &'static str {
+        "Redundant Error Log"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects msg! logs placed directly before returning an error"
+    }
+
+    fn message(&self) -> &'static str {
+        "msg! directly before returning an error is redundant: the error already describes the failure. Consider removing it to save compute on the failure path."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "The error returned to the client already identifies the failure, and Anchor logs it together with the file and line it was raised at. A msg! right before it repeats that information and costs compute on the failure path."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for LogBeforeReturnDetector {
+    fn visit_block(&mut self, node: &'ast syn::Block) {
+        for pair in node.stmts.windows(2) {
+            if let Some(mac) = self.as_msg_macro(&pair[0])
+                && self.is_error_return(&pair[1])
+            {
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            mac.span(),
+                            &self.lines,
+                        ),
+                        self.message().to_string(),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_block(self, node);
+    }
+}
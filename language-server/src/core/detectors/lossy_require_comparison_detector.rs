@@ -0,0 +1,210 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Macros taking a boolean condition as their first argument
+const CONDITION_MACROS: &[&str] = &["require", "assert"];
+
+/// Macros comparing their first two arguments
+const COMPARISON_MACROS: &[&str] = &[
+    "require_eq",
+    "require_neq",
+    "require_gt",
+    "require_gte",
+    "assert_eq",
+    "assert_ne",
+];
+
+#[derive(Default)]
+pub struct LossyRequireComparisonDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl LossyRequireComparisonDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Get the lossy operand of a comparison, if any side is cast or a float literal
+    fn lossy_operand<'a>(left: &'a Expr, right: &'a Expr) -> Option<&'a Expr> {
+        [left, right].into_iter().find(|operand| {
+            let mut finder = LossyOperandFinder::default();
+            finder.visit_expr(operand);
+            finder.found
+        })
+    }
+
+    /// Find comparisons with a lossy operand in a macro's arguments
+    fn check_macro(&mut self, node: &syn::Macro) {
+        let Some(name) = node
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+        else {
+            return;
+        };
+        let is_condition = CONDITION_MACROS.contains(&name.as_str());
+        if !is_condition && !COMPARISON_MACROS.contains(&name.as_str()) {
+            return;
+        }
+
+        let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        else {
+            return;
+        };
+        let args: Vec<&Expr> = args.iter().collect();
+
+        let lossy = if is_condition {
+            args.first().and_then(|condition| {
+                let mut comparisons = ComparisonCollector::default();
+                comparisons.visit_expr(condition);
+                comparisons
+                    .comparisons
+                    .into_iter()
+                    .find_map(|(left, right)| Self::lossy_operand(left, right))
+            })
+        } else {
+            match args.as_slice() {
+                [left, right, ..] => Self::lossy_operand(left, right),
+                _ => None,
+            }
+        };
+
+        if let Some(operand) = lossy {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(
+                        operand.span(),
+                        &self.lines,
+                    ),
+                    format!("Lossy operand in {}! comparison. {}", name, self.message()),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
+
+impl Detector for LossyRequireComparisonDetector {
+    fn id(&self) -> &'static str {
+        "LOSSY_REQUIRE_COMPARISON"
+    }
+
+    fn name(&self) -> &'static str {
+        "Lossy Require Comparison"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects require!/assert! comparisons on `as` casts or float literals"
+    }
+
+    fn message(&self) -> &'static str {
+        "`as` casts silently truncate and floats lose precision, so the check may pass for unintended values. Compare the original values or use try_from() and integer math."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "An `as` cast wraps or truncates values that do not fit the target type, and floats cannot represent every integer exactly. A check on the converted value can therefore pass for an input whose real value is out of range."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.visit_file(syntax_tree);
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for LossyRequireComparisonDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        self.check_macro(node);
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+}
+
+/// Collects the operands of comparison operators in a condition
+#[derive(Default)]
+struct ComparisonCollector<'ast> {
+    comparisons: Vec<(&'ast Expr, &'ast Expr)>,
+}
+
+impl<'ast> Visit<'ast> for ComparisonCollector<'ast> {
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if matches!(
+            node.op,
+            BinOp::Eq(_) | BinOp::Ne(_) | BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_)
+        ) {
+            self.comparisons.push((&node.left, &node.right));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_binary(self, node);
+    }
+}
+
+/// Looks for an `as` cast or a float literal in an operand
+#[derive(Default)]
+struct LossyOperandFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for LossyOperandFinder {
+    fn visit_expr_cast(&mut self, _node: &'ast syn::ExprCast) {
+        self.found = true;
+    }
+
+    fn visit_lit_float(&mut self, _node: &'ast syn::LitFloat) {
+        self.found = true;
+    }
+
+    // A closure body is not the compared value
+    fn visit_expr_closure(&mut self, _node: &'ast syn::ExprClosure) {}
+}
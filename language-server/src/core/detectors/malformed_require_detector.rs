@@ -0,0 +1,154 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct MalformedRequireDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl MalformedRequireDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if an expression looks like an error code rather than a condition,
+    /// e.g. `ErrorCode::Bad`, `error!(ErrorCode::Bad)` or `ErrorCode::Bad.into()`
+    fn is_error_code(expr: &Expr) -> bool {
+        match AstAnalyzer::strip_wrappers(expr) {
+            Expr::Path(path) => {
+                path.path.segments.len() > 1
+                    && path.path.segments.last().is_some_and(|segment| {
+                        segment
+                            .ident
+                            .to_string()
+                            .starts_with(|ch: char| ch.is_ascii_uppercase())
+                    })
+            }
+            Expr::MethodCall(call) => call.method == "into" && Self::is_error_code(&call.receiver),
+            Expr::Macro(mac) => mac
+                .mac
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "error"),
+            _ => false,
+        }
+    }
+
+    fn check_macro(&mut self, node: &syn::Macro) {
+        if node
+            .path
+            .segments
+            .last()
+            .is_none_or(|segment| segment.ident != "require")
+        {
+            return;
+        }
+
+        let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        else {
+            return;
+        };
+
+        let problem = match (args.get(0), args.get(1)) {
+            (Some(first), _) if Self::is_error_code(first) => {
+                "Error code passed as the condition of require!"
+            }
+            (Some(_), None) => "require! without an error code",
+            _ => return,
+        };
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(node.span(), &self.lines),
+                format!("{}. {}", problem, self.message()),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+impl Detector for MalformedRequireDetector {
+    fn id(&self) -> &'static str {
+        "MALFORMED_REQUIRE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Malformed Require"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects require! calls with reversed arguments or a missing error code"
+    }
+
+    fn message(&self) -> &'static str {
+        "require! takes the condition first and the error second, so the check does not do what it reads like. Write require!(condition, ErrorCode::X)."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "require! expands to `if !(condition) { return Err(error) }`. With the arguments swapped or the error missing, the condition checked is not the one written first, or the macro fails to expand, so the guard does not protect what it seems to."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.visit_file(syntax_tree);
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for MalformedRequireDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        self.check_macro(node);
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+}
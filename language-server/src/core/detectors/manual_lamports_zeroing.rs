@@ -1,17 +1,21 @@
 use super::detector::Detector;
 use super::detector_config::DetectorConfig;
 use crate::core::utilities::DiagnosticBuilder;
+use crate::core::utilities::LineIndex;
+use crate::core::utilities::{anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use syn::spanned::Spanned;
-use syn::{
-    Expr, ExprAssign, ExprField, ExprLit, ExprMethodCall, Lit, UnOp, parse_str, visit::Visit,
-};
+use syn::{Expr, ExprAssign, ExprLit, ExprMethodCall, Lit, visit::Visit};
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
 #[derive(Default)]
 pub struct ManualLamportsZeroingDetector {
     diagnostics: Vec<Diagnostic>,
     config: DetectorConfig,
+    lines: LineIndex,
+    /// Locals of the current function currently holding zero (e.g. `let zero = 0;`)
+    zero_locals: HashSet<String>,
 }
 
 impl ManualLamportsZeroingDetector {
@@ -20,82 +24,55 @@ impl ManualLamportsZeroingDetector {
         Self {
             diagnostics: Vec::new(),
             config,
+            lines: LineIndex::default(),
+            zero_locals: HashSet::new(),
         }
     }
 
-    /// Peel common wrappers so we can reason about the "real" expression:
-    /// - Parentheses: ( .. )
-    /// - References: &expr
-    /// - Deref: *expr
-    /// - Try: expr?  (Expr::Try)
-    fn strip_wrappers<'a>(&self, mut expr: &'a Expr) -> &'a Expr {
-        loop {
-            expr = match expr {
-                Expr::Paren(p) => &p.expr,
-                Expr::Reference(r) => &r.expr,
-                Expr::Unary(u) if matches!(u.op, UnOp::Deref(_)) => &u.expr, // *
-                Expr::Try(t) => &t.expr,                                     // ?
-                _ => break expr,
-            };
-        }
-    }
-
-    /// Return true if the expression represents an access to lamports:
-    /// - foo.lamports
-    /// - foo.lamports()   (some code uses a method accessor)
-    /// - foo.lamports.borrow_mut()
-    /// - foo.try_borrow_mut_lamports()
-    fn is_lamports_access(&self, expr: &Expr) -> bool {
-        let e = self.strip_wrappers(expr);
-
-        match e {
-            // 1) foo.lamports
-            Expr::Field(ExprField {
-                member: syn::Member::Named(ident),
-                ..
-            }) if ident == "lamports" => true,
-
-            // 2) foo.lamports() — allow method named `lamports`
-            Expr::MethodCall(ExprMethodCall { method, .. }) if method == "lamports" => true,
-
-            // 3) foo.lamports.borrow_mut() — the receiver of borrow_mut() must be a lamports access
-            Expr::MethodCall(ExprMethodCall {
-                method, receiver, ..
-            }) if method == "borrow_mut" => self.is_lamports_access(receiver),
-
-            // 4) foo.try_borrow_mut_lamports()
-            Expr::MethodCall(ExprMethodCall { method, .. })
-                if method == "try_borrow_mut_lamports" =>
-            {
-                true
-            }
-
-            _ => false,
-        }
-    }
-
-    /// Return true if expression is the integer literal 0 (possibly wrapped).
+    /// Return true if expression provably evaluates to 0: `0`, `0u64`, `0 as u64`,
+    /// or a local last assigned one of those (possibly wrapped).
     fn is_zero_literal(&self, expr: &Expr) -> bool {
-        match self.strip_wrappers(expr) {
+        match AstAnalyzer::strip_wrappers(expr) {
             Expr::Lit(ExprLit {
                 lit: Lit::Int(lit_int),
                 ..
             }) => lit_int.base10_digits() == "0",
+            Expr::Cast(cast) => self.is_zero_literal(&cast.expr),
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .is_some_and(|ident| self.zero_locals.contains(&ident.to_string())),
             _ => false,
         }
     }
 
+    /// Track whether a local holds zero after `name = value`
+    fn track_local(&mut self, name: String, value: Option<&Expr>) {
+        if value.is_some_and(|value| self.is_zero_literal(value)) {
+            self.zero_locals.insert(name);
+        } else {
+            self.zero_locals.remove(&name);
+        }
+    }
+
+    /// Visit a function with its own set of zero-valued locals
+    fn with_function_scope<F: FnOnce(&mut Self)>(&mut self, visit: F) {
+        let outer = std::mem::take(&mut self.zero_locals);
+        visit(self);
+        self.zero_locals = outer;
+    }
+
     /// Detect `lamports = 0`
     fn is_lamports_zero_assignment(&self, assign: &ExprAssign) -> bool {
-        self.is_lamports_access(&assign.left) && self.is_zero_literal(&assign.right)
+        AnchorPatterns::is_lamports_access(&assign.left) && self.is_zero_literal(&assign.right)
     }
 
     /// Detect method forms that set lamports to zero, e.g. `account.set_lamports(0)`
     fn is_lamports_zero_method_call(&self, method_call: &ExprMethodCall) -> bool {
-        if method_call.method == "set_lamports" {
-            if let Some(arg) = method_call.args.first() {
-                return self.is_zero_literal(arg);
-            }
+        if method_call.method == "set_lamports"
+            && let Some(arg) = method_call.args.first()
+        {
+            return self.is_zero_literal(arg);
         }
         false
     }
@@ -127,22 +104,54 @@ impl Detector for ManualLamportsZeroingDetector {
         "Manual lamports zeroing detected. Use proper account closure mechanisms like `close` or transfer lamports to another account instead of setting to zero."
     }
 
+    fn rationale(&self) -> &'static str {
+        "Setting lamports to zero only drains the balance. The account data and owner stay intact until the runtime garbage-collects the account at the end of the transaction, so a later instruction in the same transaction can refund it and keep using the old data."
+    }
+
     fn default_severity(&self) -> DiagnosticSeverity {
         DiagnosticSeverity::ERROR
     }
 
-    fn analyze(&mut self, content: &str, _file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
         self.diagnostics.clear();
+        self.zero_locals.clear();
 
-        if let Ok(syntax_tree) = parse_str::<syn::File>(content) {
-            self.visit_file(&syntax_tree);
-        }
+        self.visit_file(syntax_tree);
 
         self.diagnostics.clone()
     }
 }
 
 impl<'ast> Visit<'ast> for ManualLamportsZeroingDetector {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.with_function_scope(|detector| syn::visit::visit_item_fn(detector, node));
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.with_function_scope(|detector| syn::visit::visit_impl_item_fn(detector, node));
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        // Visit the initializer first: `let zero = zero;` reads the outer binding
+        syn::visit::visit_local(self, node);
+
+        let pat = match &node.pat {
+            syn::Pat::Type(pat_type) => pat_type.pat.as_ref(),
+            pat => pat,
+        };
+        if let syn::Pat::Ident(pat_ident) = pat {
+            let value = node.init.as_ref().map(|init| init.expr.as_ref());
+            self.track_local(pat_ident.ident.to_string(), value);
+        }
+    }
+
     fn visit_expr(&mut self, node: &'ast Expr) {
         // Check manual lamports zeroing patterns like:
         // **ctx.accounts.victim.try_borrow_mut_lamports()? = 0;
@@ -154,16 +163,25 @@ impl<'ast> Visit<'ast> for ManualLamportsZeroingDetector {
                 .severity_override
                 .unwrap_or(self.default_severity());
 
-            self.diagnostics.push(DiagnosticBuilder::create(
-                DiagnosticBuilder::create_range_from_span(node.span()),
-                self.message().to_string(),
-                severity,
-                self.id().to_string(),
-                None,
-            ));
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(node.span(), &self.lines),
+                    self.message().to_string(),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
         }
 
         // Continue traversal
         syn::visit::visit_expr(self, node);
+
+        // `zero = 1;` reassigns a tracked local
+        if let Expr::Assign(assign) = node
+            && let Expr::Path(path) = assign.left.as_ref()
+            && let Some(ident) = path.path.get_ident()
+        {
+            self.track_local(ident.to_string(), Some(&assign.right));
+        }
     }
 }
@@ -0,0 +1,167 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Account types provided by anchor_spl, which implement the account traits themselves
+const SPL_ACCOUNT_TYPES: &[&str] = &["TokenAccount", "Mint"];
+
+/// Traits whose manual implementation makes a plain struct usable in `Account<'info, T>`
+const ACCOUNT_TRAITS: &[&str] = &["AccountDeserialize", "Owner"];
+
+#[derive(Default)]
+pub struct MissingAccountAttributeDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Structs defined in the file, mapped to whether they carry `#[account]`
+    local_structs: HashMap<String, bool>,
+    /// Types with a manual `AccountDeserialize` or `Owner` implementation
+    manual_impls: HashSet<String>,
+}
+
+impl MissingAccountAttributeDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            local_structs: HashMap::new(),
+            manual_impls: HashSet::new(),
+        }
+    }
+
+    /// Check if a type name is a local struct without `#[account]`
+    fn is_plain_local_struct(&self, name: &str) -> bool {
+        self.local_structs.get(name) == Some(&false)
+            && !self.manual_impls.contains(name)
+            && !SPL_ACCOUNT_TYPES.contains(&name)
+    }
+}
+
+impl Detector for MissingAccountAttributeDetector {
+    fn id(&self) -> &'static str {
+        "MISSING_ACCOUNT_ATTRIBUTE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Account Attribute"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects Account<'info, T> fields where T is a local struct without #[account]"
+    }
+
+    fn message(&self) -> &'static str {
+        "Account<'info, T> requires T to implement the Anchor account traits, which #[account] derives along with the discriminator and owner checks. Add #[account] to the struct or use a different account type."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Account<'info, T> relies on the traits #[account] generates to check the discriminator and owner. A plain struct either fails to compile or, with hand-written impls, loads data from any account without verifying that the program owns it."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        // Structs may be declared after the accounts structs using them
+        let mut collector = StructCollector::default();
+        collector.visit_file(syntax_tree);
+        self.local_structs = collector.structs;
+        self.manual_impls = collector.manual_impls;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for MissingAccountAttributeDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node)
+            && let Fields::Named(fields) = &node.fields
+        {
+            for field in &fields.named {
+                if AnchorPatterns::account_wrapper_name(field).as_deref() != Some("Account") {
+                    continue;
+                }
+                let Some(data_type) = AnchorPatterns::account_data_type(field) else {
+                    continue;
+                };
+
+                let name = data_type.to_string();
+                if !self.is_plain_local_struct(&name) {
+                    continue;
+                }
+
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            data_type.span(),
+                            &self.lines,
+                        ),
+                        format!("'{}' is not an #[account] struct. {}", name, self.message()),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+/// Collects struct definitions and manual account trait implementations
+#[derive(Default)]
+struct StructCollector {
+    structs: HashMap<String, bool>,
+    manual_impls: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for StructCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        let is_account = AnchorPatterns::is_account_struct(node);
+        // A name defined twice counts as an account if either definition is one
+        *self.structs.entry(node.ident.to_string()).or_default() |= is_account;
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if let Some((_, trait_path, _)) = &node.trait_
+            && trait_path
+                .segments
+                .last()
+                .is_some_and(|segment| ACCOUNT_TRAITS.iter().any(|name| segment.ident == name))
+            && let syn::Type::Path(self_ty) = node.self_ty.as_ref()
+            && let Some(segment) = self_ty.path.segments.last()
+        {
+            self.manual_impls.insert(segment.ident.to_string());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_impl(self, node);
+    }
+}
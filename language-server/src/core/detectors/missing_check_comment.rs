@@ -1,15 +1,16 @@
 use super::detector::Detector;
 use super::detector_config::DetectorConfig;
-use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
 use std::path::PathBuf;
 use syn::spanned::Spanned;
-use syn::{Fields, parse_str, visit::Visit};
+use syn::{Fields, visit::Visit};
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
 #[derive(Default)]
 pub struct MissingCheckCommentDetector {
     diagnostics: Vec<Diagnostic>,
     config: DetectorConfig,
+    lines: LineIndex,
 }
 
 impl MissingCheckCommentDetector {
@@ -18,23 +19,22 @@ impl MissingCheckCommentDetector {
         Self {
             diagnostics: Vec::new(),
             config,
+            lines: LineIndex::default(),
         }
     }
 
     /// Check if field has a /// CHECK: doc comment
     fn has_check_doc_comment(&self, field: &syn::Field) -> bool {
         for attr in &field.attrs {
-            if attr.path().is_ident("doc") {
-                if let syn::Meta::NameValue(meta_name_value) = &attr.meta {
-                    if let syn::Expr::Lit(expr_lit) = &meta_name_value.value {
-                        if let syn::Lit::Str(lit_str) = &expr_lit.lit {
-                            let doc_content = lit_str.value();
-                            let trimmed_content = doc_content.trim();
-                            if trimmed_content.starts_with("CHECK:") {
-                                return true;
-                            }
-                        }
-                    }
+            if attr.path().is_ident("doc")
+                && let syn::Meta::NameValue(meta_name_value) = &attr.meta
+                && let syn::Expr::Lit(expr_lit) = &meta_name_value.value
+                && let syn::Lit::Str(lit_str) = &expr_lit.lit
+            {
+                let doc_content = lit_str.value();
+                let trimmed_content = doc_content.trim();
+                if trimmed_content.starts_with("CHECK:") {
+                    return true;
                 }
             }
         }
@@ -67,17 +67,26 @@ impl Detector for MissingCheckCommentDetector {
         "Missing /// CHECK: doc comment for unchecked account"
     }
 
+    fn rationale(&self) -> &'static str {
+        "AccountInfo and UncheckedAccount skip all of Anchor's type and owner validation. Anchor requires a /// CHECK: comment on them so that every such account has a written justification a reviewer can verify."
+    }
+
     fn default_severity(&self) -> DiagnosticSeverity {
         DiagnosticSeverity::ERROR
     }
 
-    fn analyze(&mut self, content: &str, _file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
         self.diagnostics.clear();
 
         // Run default detection logic
-        if let Ok(syntax_tree) = parse_str::<syn::File>(content) {
-            self.visit_file(&syntax_tree);
-        }
+        self.visit_file(syntax_tree);
 
         self.diagnostics.clone()
     }
@@ -93,29 +102,33 @@ impl<'ast> Visit<'ast> for MissingCheckCommentDetector {
         // Check each field for unchecked account types
         if let Fields::Named(fields) = &node.fields {
             for field in &fields.named {
-                if let Some(account_type) = AnchorPatterns::is_unchecked_account_type(field) {
-                    if !self.has_check_doc_comment(field) {
-                        let field_name = field
-                            .ident
-                            .as_ref()
-                            .map(|ident| ident.to_string())
-                            .unwrap_or_else(|| "unknown".to_string());
-
-                        let severity = self
-                            .config
-                            .severity_override
-                            .unwrap_or(self.default_severity());
-
-                        let message = self.get_suggestion_message(&field_name, &account_type);
-
-                        self.diagnostics.push(DiagnosticBuilder::create(
-                            DiagnosticBuilder::create_range_from_span(field.span()),
+                if let Some(account_type) = AnchorPatterns::is_unchecked_account_type(field)
+                    && !self.has_check_doc_comment(field)
+                {
+                    let field_name = field
+                        .ident
+                        .as_ref()
+                        .map(|ident| ident.to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+
+                    let severity = self
+                        .config
+                        .severity_override
+                        .unwrap_or(self.default_severity());
+
+                    let message = self.get_suggestion_message(&field_name, &account_type);
+
+                    self.diagnostics
+                        .push(DiagnosticBuilder::create_with_code_description(
+                            DiagnosticBuilder::create_range_from_span_with_lines(
+                                field.span(),
+                                &self.lines,
+                            ),
                             message,
                             severity,
                             self.id().to_string(),
                             None,
                         ));
-                    }
                 }
             }
         }
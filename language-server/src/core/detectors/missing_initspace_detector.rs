@@ -1,9 +1,9 @@
 use super::detector::Detector;
 use super::detector_config::DetectorConfig;
-use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
 use std::path::PathBuf;
 use syn::spanned::Spanned;
-use syn::{parse_str, visit::Visit};
+use syn::visit::Visit;
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
 #[derive(Default)]
@@ -39,16 +39,24 @@ impl Detector for MissingInitspaceDetector {
         "Accounts struct has no #[derive(InitSpace)] macro. Consider adding it for proper space allocation."
     }
 
+    fn rationale(&self) -> &'static str {
+        "Hand-counted account sizes go stale when fields are added or resized, and an account created too small cannot hold its data. Deriving InitSpace computes the size from the struct definition, so it stays correct."
+    }
+
     fn default_severity(&self) -> DiagnosticSeverity {
         DiagnosticSeverity::WARNING
     }
 
-    fn analyze(&mut self, content: &str, _file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        _lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
         self.diagnostics.clear();
 
-        if let Ok(syntax_tree) = parse_str::<syn::File>(content) {
-            self.visit_file(&syntax_tree);
-        }
+        self.visit_file(syntax_tree);
 
         self.diagnostics.clone()
     }
@@ -62,11 +70,11 @@ impl<'ast> Visit<'ast> for MissingInitspaceDetector {
 
         // Check if the struct has the #[derive(InitSpace)] macro
         let has_initspace_macro = node.attrs.iter().any(|attr| {
-            if attr.path().is_ident("derive") {
-                if let Ok(meta) = attr.meta.require_list() {
-                    let tokens = meta.tokens.to_string();
-                    return tokens.contains("InitSpace");
-                }
+            if attr.path().is_ident("derive")
+                && let Ok(meta) = attr.meta.require_list()
+            {
+                let tokens = meta.tokens.to_string();
+                return tokens.contains("InitSpace");
             }
             false
         });
@@ -81,13 +89,14 @@ impl<'ast> Visit<'ast> for MissingInitspaceDetector {
             let line = node.span().start().line as u32;
             let range = DiagnosticBuilder::create_range_from_line(line);
 
-            self.diagnostics.push(DiagnosticBuilder::create(
-                range,
-                self.message().to_string(),
-                severity,
-                self.id().to_string(),
-                None,
-            ));
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    range,
+                    self.message().to_string(),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
         }
 
         // Continue visiting children
@@ -0,0 +1,159 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Types whose size depends on their length
+const UNBOUNDED_TYPES: &[&str] = &["Vec", "String"];
+
+#[derive(Default)]
+pub struct MissingMaxLenDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl MissingMaxLenDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if a struct has the #[derive(InitSpace)] macro
+    fn derives_init_space(node: &syn::ItemStruct) -> bool {
+        node.attrs.iter().any(|attr| {
+            if attr.path().is_ident("derive")
+                && let Ok(meta) = attr.meta.require_list()
+            {
+                return meta.tokens.to_string().contains("InitSpace");
+            }
+            false
+        })
+    }
+
+    /// Get the name of a `Vec`/`String` type, looking through `Option<...>`
+    fn unbounded_type_name(ty: &syn::Type) -> Option<String> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+
+        if segment.ident == "Option"
+            && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+            && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+        {
+            return Self::unbounded_type_name(inner);
+        }
+
+        let name = segment.ident.to_string();
+        UNBOUNDED_TYPES.contains(&name.as_str()).then_some(name)
+    }
+
+    fn has_max_len(field: &syn::Field) -> bool {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("max_len"))
+    }
+}
+
+impl Detector for MissingMaxLenDetector {
+    fn id(&self) -> &'static str {
+        "MISSING_MAX_LEN"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Max Len"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects Vec and String fields of account or InitSpace structs without #[max_len(...)]"
+    }
+
+    fn message(&self) -> &'static str {
+        "InitSpace cannot size a Vec or String without a bound, so the account space is miscalculated or the derive fails. Add #[max_len(N)] with the largest length the field may hold."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "The size of an account is fixed when it is created, but a Vec or String can grow without limit. InitSpace needs #[max_len] to reserve room for the largest value the field may hold."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for MissingMaxLenDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        let is_account =
+            AnchorPatterns::is_account_struct(node) && !AnchorPatterns::is_zero_copy_struct(node);
+        if !is_account && !Self::derives_init_space(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                let Some(type_name) = Self::unbounded_type_name(&field.ty) else {
+                    continue;
+                };
+                if Self::has_max_len(field) {
+                    continue;
+                }
+
+                let field_name = field
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            field.span(),
+                            &self.lines,
+                        ),
+                        format!(
+                            "{} field '{}' in '{}' has no #[max_len(...)]. {}",
+                            type_name,
+                            field_name,
+                            node.ident,
+                            self.message()
+                        ),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
@@ -1,15 +1,109 @@
+pub mod account_boxing_detector;
+pub mod account_type_confusion_detector;
+pub mod borrow_across_cpi_detector;
+pub mod bump_source_detector;
+pub mod close_destination_not_mut_detector;
+pub mod constraint_assignment_detector;
+pub mod dangling_instruction_attribute_detector;
 pub mod detector;
 pub mod detector_config;
+pub mod empty_handler_detector;
+pub mod init_without_space_detector;
+pub mod insecure_randomness_detector;
 pub mod instruction_attribute_invalid;
 pub mod instruction_attribute_unused;
+pub mod invoke_signed_seeds_mismatch_detector;
+pub mod lamports_below_rent_detector;
+pub mod large_account_no_zero_copy_detector;
+pub mod load_mut_without_mut_detector;
+pub mod log_before_return_detector;
+pub mod lossy_require_comparison_detector;
+pub mod malformed_require_detector;
 pub mod manual_lamports_zeroing;
+pub mod missing_account_attribute_detector;
 pub mod missing_check_comment;
 pub mod missing_initspace_detector;
+pub mod missing_max_len_detector;
+pub mod msg_in_loop_detector;
+pub mod mut_account_argument_detector;
+pub mod payer_not_mut_detector;
+pub mod pda_seeds_mismatch_detector;
+pub mod pda_transfer_without_signer_detector;
+pub mod pda_typed_as_signer_detector;
+pub mod program_id_mismatch_detector;
+pub mod realloc_without_zero_init_detector;
+pub mod runtime_address_constraint_detector;
+pub mod sensitive_log_detector;
+pub mod shadowed_account_detector;
 pub mod sysvar_account_detector;
+pub mod sysvar_get_outside_handler_detector;
+pub mod unbound_constraint_ident_detector;
+pub mod unbounded_seed_detector;
+pub mod unchecked_account_index_detector;
+pub mod unchecked_remaining_accounts_detector;
+pub mod unchecked_set_authority_detector;
+pub mod unchecked_token_transfer_detector;
+pub mod unconstrained_token_account_detector;
+pub mod unenforced_key_check_detector;
+pub mod unknown_context_detector;
+pub mod unnecessary_mut_detector;
+pub mod unsigned_authority_detector;
+pub mod untrusted_pubkey_detector;
+pub mod unused_signer_detector;
+pub mod unwrap_in_program_detector;
+pub mod zero_account_reinit_detector;
+pub mod zero_constraint_detector;
 
+pub use account_boxing_detector::*;
+pub use account_type_confusion_detector::*;
+pub use borrow_across_cpi_detector::*;
+pub use bump_source_detector::*;
+pub use close_destination_not_mut_detector::*;
+pub use constraint_assignment_detector::*;
+pub use dangling_instruction_attribute_detector::*;
+pub use empty_handler_detector::*;
+pub use init_without_space_detector::*;
+pub use insecure_randomness_detector::*;
 pub use instruction_attribute_invalid::*;
 pub use instruction_attribute_unused::*;
+pub use invoke_signed_seeds_mismatch_detector::*;
+pub use lamports_below_rent_detector::*;
+pub use large_account_no_zero_copy_detector::*;
+pub use load_mut_without_mut_detector::*;
+pub use log_before_return_detector::*;
+pub use lossy_require_comparison_detector::*;
+pub use malformed_require_detector::*;
 pub use manual_lamports_zeroing::*;
+pub use missing_account_attribute_detector::*;
 pub use missing_check_comment::*;
 pub use missing_initspace_detector::*;
+pub use missing_max_len_detector::*;
+pub use msg_in_loop_detector::*;
+pub use mut_account_argument_detector::*;
+pub use payer_not_mut_detector::*;
+pub use pda_seeds_mismatch_detector::*;
+pub use pda_transfer_without_signer_detector::*;
+pub use pda_typed_as_signer_detector::*;
+pub use program_id_mismatch_detector::*;
+pub use realloc_without_zero_init_detector::*;
+pub use runtime_address_constraint_detector::*;
+pub use sensitive_log_detector::*;
+pub use shadowed_account_detector::*;
 pub use sysvar_account_detector::*;
+pub use sysvar_get_outside_handler_detector::*;
+pub use unbound_constraint_ident_detector::*;
+pub use unbounded_seed_detector::*;
+pub use unchecked_account_index_detector::*;
+pub use unchecked_remaining_accounts_detector::*;
+pub use unchecked_set_authority_detector::*;
+pub use unchecked_token_transfer_detector::*;
+pub use unconstrained_token_account_detector::*;
+pub use unenforced_key_check_detector::*;
+pub use unknown_context_detector::*;
+pub use unnecessary_mut_detector::*;
+pub use unsigned_authority_detector::*;
+pub use untrusted_pubkey_detector::*;
+pub use unused_signer_detector::*;
+pub use unwrap_in_program_detector::*;
+pub use zero_account_reinit_detector::*;
+pub use zero_constraint_detector::*;
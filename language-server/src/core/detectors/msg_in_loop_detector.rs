@@ -0,0 +1,150 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct MsgInLoopDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Nesting depth of `#[program]` modules
+    program_depth: usize,
+    /// Nesting depth of `for`/`while`/`loop` bodies
+    loop_depth: usize,
+}
+
+impl MsgInLoopDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            program_depth: 0,
+            loop_depth: 0,
+        }
+    }
+}
+
+impl Detector for MsgInLoopDetector {
+    fn id(&self) -> &'static str {
+        "MSG_IN_LOOP"
+    }
+
+    fn name(&self) -> &'static str {
+        "Msg In Loop"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects msg! logging inside loops in #[program] modules"
+    }
+
+    fn message(&self) -> &'static str {
+        "Logging costs compute units on every iteration and may expose data in transaction logs. Log once outside the loop or remove the log."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Each msg! call formats its arguments and pays per logged byte. Inside a loop the cost grows with the input, which lets a caller push the instruction over its compute budget, and the log limit truncates output past a certain size anyway."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.program_depth = 0;
+        self.loop_depth = 0;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for MsgInLoopDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        let is_program = AnchorPatterns::is_program_module(node);
+        if is_program {
+            self.program_depth += 1;
+        }
+
+        syn::visit::visit_item_mod(self, node);
+
+        if is_program {
+            self.program_depth -= 1;
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_for_loop(self, node);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_while(self, node);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_loop(&mut self, node: &'ast syn::ExprLoop) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_loop(self, node);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if self.program_depth > 0
+            && self.loop_depth > 0
+            && node
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "msg")
+        {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(node.span(), &self.lines),
+                    format!("msg! inside a loop. {}", self.message()),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+}
@@ -0,0 +1,185 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct MutAccountArgumentDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Name of the `Context<T>` parameter per enclosing function, `None` if it takes none
+    contexts: Vec<Option<String>>,
+}
+
+impl MutAccountArgumentDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Track the `Context<T>` parameter of a function while visiting its body
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, sig: &syn::Signature, visit: F) {
+        let ctx = sig.inputs.iter().find_map(|input| {
+            let syn::FnArg::Typed(pat_type) = input else {
+                return None;
+            };
+            AnchorPatterns::context_accounts_ident(&pat_type.ty)?;
+            let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+                return None;
+            };
+            Some(pat_ident.ident.to_string())
+        });
+
+        self.contexts.push(ctx);
+        visit(self);
+        self.contexts.pop();
+    }
+
+    /// The account an argument such as `&mut ctx.accounts.vault` borrows mutably
+    fn borrowed_account(&self, arg: &Expr) -> Option<String> {
+        let ctx = self.contexts.last()?.as_ref()?;
+
+        let Expr::Reference(reference) = arg else {
+            return None;
+        };
+        reference.mutability?;
+
+        let (root, members) = AstAnalyzer::member_chain(&reference.expr);
+        match members.as_slice() {
+            [accounts, account] if root.as_ref() == Some(ctx) && accounts == "accounts" => {
+                Some(account.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn check_arguments<'a>(&mut self, args: impl IntoIterator<Item = &'a Expr>, callee: String) {
+        for arg in args {
+            let Some(account) = self.borrowed_account(arg) else {
+                continue;
+            };
+
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(arg.span(), &self.lines),
+                    format!(
+                        "Account '{}' is passed mutably to '{}'. {}",
+                        account,
+                        callee,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
+
+impl Detector for MutAccountArgumentDetector {
+    fn id(&self) -> &'static str {
+        "MUT_ACCOUNT_ARGUMENT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Mutable Account Argument"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `&mut ctx.accounts.<account>` passed as an argument to a function or method"
+    }
+
+    fn message(&self) -> &'static str {
+        "The callee can change the account or keep the reference beyond this use, which is easy to miss when reading the handler. Review what the callee writes and whether it retains the reference."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Handing out a mutable reference moves the account's writes out of the handler. A reviewer reading the handler no longer sees which fields change, and checks done before the call may no longer hold afterwards."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.contexts.clear();
+        self.visit_file(syntax_tree);
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for MutAccountArgumentDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_item_fn(detector, node)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        let callee = match node.func.as_ref() {
+            Expr::Path(path) => path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::"),
+            _ => "function".to_string(),
+        };
+        self.check_arguments(&node.args, callee);
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        self.check_arguments(&node.args, node.method.to_string());
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
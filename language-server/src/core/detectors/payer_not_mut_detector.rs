@@ -0,0 +1,129 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use proc_macro2::TokenTree;
+use std::path::PathBuf;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct PayerNotMutDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl PayerNotMutDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if a field is marked `mut` or `mut @ Error`
+    fn is_mutable(field: &syn::Field) -> bool {
+        AnchorPatterns::parse_account_constraints(field)
+            .iter()
+            .any(|constraint| constraint.key == "mut" || constraint.key.starts_with("mut@"))
+    }
+}
+
+impl Detector for PayerNotMutDetector {
+    fn id(&self) -> &'static str {
+        "PAYER_NOT_MUT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Payer Not Mutable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects payer = <account> constraints whose payer is not marked mut"
+    }
+
+    fn message(&self) -> &'static str {
+        "The payer funds the rent of the created account, so its lamports change and it must be writable. Mark the payer field #[account(mut)]."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Creating an account transfers rent from the payer through the System Program. The payer's balance changes, which the runtime only allows for accounts the transaction marks writable, so account creation fails."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::ERROR
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for PayerNotMutDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                for constraint in AnchorPatterns::parse_account_constraints(field) {
+                    if constraint.key != "payer" {
+                        continue;
+                    }
+                    let Some(TokenTree::Ident(payer)) =
+                        constraint.value.and_then(|value| value.into_iter().next())
+                    else {
+                        continue;
+                    };
+
+                    // A payer outside the struct cannot be checked
+                    let Some(payer_field) = fields
+                        .named
+                        .iter()
+                        .find(|other| other.ident.as_ref() == Some(&payer))
+                    else {
+                        continue;
+                    };
+                    if Self::is_mutable(payer_field) {
+                        continue;
+                    }
+
+                    let severity = self
+                        .config
+                        .severity_override
+                        .unwrap_or(self.default_severity());
+
+                    self.diagnostics
+                        .push(DiagnosticBuilder::create_with_code_description(
+                            DiagnosticBuilder::create_range_from_span_with_lines(
+                                constraint.span,
+                                &self.lines,
+                            ),
+                            format!("Payer '{}' is not marked mut. {}", payer, self.message()),
+                            severity,
+                            self.id().to_string(),
+                            None,
+                        ));
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
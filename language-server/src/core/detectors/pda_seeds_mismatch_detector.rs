@@ -0,0 +1,240 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, WorkspaceFacts, WorkspaceFiles, anchor_patterns::AnchorPatterns,
+};
+use proc_macro2::{Span, TokenStream};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Seeds of PDAs initialized with a canonical `bump`, keyed by account data type
+type InitSeeds = HashMap<String, Vec<PdaSeeds>>;
+
+/// Seeds of a PDA field
+#[derive(Clone)]
+struct PdaSeeds {
+    /// Seeds with every non-literal seed replaced by `_`, used for comparison
+    shape: String,
+    /// Seeds as written, used in messages
+    source: String,
+}
+
+/// A PDA field validated with a stored bump (`bump = <expr>`)
+struct ReuseSite {
+    field: String,
+    data_type: String,
+    seeds: PdaSeeds,
+    span: Span,
+}
+
+#[derive(Default)]
+pub struct PdaSeedsMismatchDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Workspace files shared by the registry for the current scan
+    workspace: Option<Arc<WorkspaceFiles>>,
+    /// Init-site seeds defined by each workspace file
+    workspace_init_seeds: WorkspaceFacts<InitSeeds>,
+}
+
+impl PdaSeedsMismatchDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    fn collect_workspace_init_seeds(&mut self, file_path: &Path) -> InitSeeds {
+        let mut init_seeds = InitSeeds::new();
+        let workspace_seeds =
+            self.workspace_init_seeds
+                .for_file(self.workspace.as_ref(), file_path, |syntax_tree| {
+                    let mut collector = PdaSiteCollector::default();
+                    collector.visit_file(syntax_tree);
+                    collector.init_seeds
+                });
+        for seeds in workspace_seeds {
+            Self::merge(&mut init_seeds, seeds.clone());
+        }
+
+        init_seeds
+    }
+
+    fn merge(into: &mut InitSeeds, from: InitSeeds) {
+        for (data_type, seeds) in from {
+            into.entry(data_type).or_default().extend(seeds);
+        }
+    }
+
+    /// Parse a `seeds = [...]` value; seeds given as a constant or expression are not compared
+    fn parse_seeds(value: &TokenStream) -> Option<PdaSeeds> {
+        let Ok(Expr::Array(array)) = syn::parse2::<Expr>(value.clone()) else {
+            return None;
+        };
+
+        Some(PdaSeeds {
+            shape: AnchorPatterns::seed_shape(&array.elems),
+            source: value.to_string(),
+        })
+    }
+}
+
+impl Detector for PdaSeedsMismatchDetector {
+    fn id(&self) -> &'static str {
+        "PDA_SEEDS_MISMATCH"
+    }
+
+    fn name(&self) -> &'static str {
+        "PDA Seeds Mismatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects PDAs validated with a stored bump whose seeds differ from the seeds used at init"
+    }
+
+    fn message(&self) -> &'static str {
+        "The stored bump is the canonical bump for the init seeds. With different seeds it derives a different address, or accepts a non-canonical one. Use the same seeds as the init site."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A bump is only canonical for the seeds it was found with. Reusing a stored bump with other seeds either rejects the real account or accepts a PDA derived from different seeds than the one created at init."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn set_workspace(&mut self, workspace: Option<Arc<WorkspaceFiles>>) {
+        self.workspace = workspace;
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        // The analyzed content may be newer than the file on disk, so collect its sites from the AST
+        let mut collector = PdaSiteCollector::default();
+        collector.visit_file(syntax_tree);
+        if collector.reuse_sites.is_empty() {
+            return Vec::new();
+        }
+
+        let mut init_seeds = collector.init_seeds;
+        if let Some(file_path) = file_path {
+            let workspace_seeds = self.collect_workspace_init_seeds(file_path);
+            Self::merge(&mut init_seeds, workspace_seeds);
+        }
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        for site in &collector.reuse_sites {
+            // Without an init site there is nothing to compare against
+            let Some(inits) = init_seeds.get(&site.data_type) else {
+                continue;
+            };
+            if inits.iter().any(|init| init.shape == site.seeds.shape) {
+                continue;
+            }
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(site.span, &self.lines),
+                    format!(
+                        "'{}' uses a stored bump with seeds `{}`, but '{}' is initialized with seeds `{}`. {}",
+                        site.field,
+                        site.seeds.source,
+                        site.data_type,
+                        inits[0].source,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        self.diagnostics.clone()
+    }
+}
+
+/// Collects PDA init sites (`init` with a bare `bump`) and reuse sites (`bump = <expr>`)
+#[derive(Default)]
+struct PdaSiteCollector {
+    init_seeds: InitSeeds,
+    reuse_sites: Vec<ReuseSite>,
+}
+
+impl<'ast> Visit<'ast> for PdaSiteCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                let Some(data_type) = AnchorPatterns::account_data_type_name(field) else {
+                    continue;
+                };
+
+                let constraints = AnchorPatterns::parse_account_constraints(field);
+                let Some(seeds_constraint) = constraints
+                    .iter()
+                    .find(|constraint| constraint.key == "seeds")
+                else {
+                    continue;
+                };
+                let Some(seeds) = seeds_constraint
+                    .value
+                    .as_ref()
+                    .and_then(PdaSeedsMismatchDetector::parse_seeds)
+                else {
+                    continue;
+                };
+                let Some(bump) = constraints
+                    .iter()
+                    .find(|constraint| constraint.key == "bump")
+                else {
+                    continue;
+                };
+
+                let is_init = constraints.iter().any(|constraint| {
+                    constraint.key == "init" || constraint.key == "init_if_needed"
+                });
+
+                match (is_init, &bump.value) {
+                    (true, None) => self.init_seeds.entry(data_type).or_default().push(seeds),
+                    (false, Some(_)) => self.reuse_sites.push(ReuseSite {
+                        field: field
+                            .ident
+                            .as_ref()
+                            .map_or_else(|| "account".to_string(), ToString::to_string),
+                        data_type,
+                        seeds,
+                        span: seeds_constraint.span,
+                    }),
+                    _ => {}
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
@@ -0,0 +1,353 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Context parameter and PDA-backed locals of the handler being visited
+#[derive(Default)]
+struct Handler {
+    /// Name of the `Context<T>` parameter
+    ctx: String,
+    /// Accounts struct of the handler's `Context<T>`
+    accounts: String,
+    /// Locals bound to `system_instruction::transfer` from a PDA, to the PDA field
+    transfer_instructions: HashMap<String, String>,
+    /// Locals bound to `CpiContext::new` moving lamports from a PDA, to the PDA field
+    cpi_contexts: HashMap<String, String>,
+}
+
+#[derive(Default)]
+pub struct PdaTransferWithoutSignerDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to its fields with a `seeds` constraint
+    pda_fields: HashMap<String, HashSet<String>>,
+    /// One entry per enclosing function, `None` if it takes no `Context<T>`
+    handlers: Vec<Option<Handler>>,
+}
+
+impl PdaTransferWithoutSignerDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    fn collect_struct(&mut self, node: &syn::ItemStruct) {
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+
+        let pdas = fields
+            .named
+            .iter()
+            .filter(|field| AnchorPatterns::has_constraint(field, "seeds"))
+            .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+            .collect();
+
+        self.pda_fields.insert(node.ident.to_string(), pdas);
+    }
+
+    /// Track the `Context<T>` parameter of a function while visiting its body
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, sig: &syn::Signature, visit: F) {
+        let handler = sig.inputs.iter().find_map(|input| {
+            let syn::FnArg::Typed(pat_type) = input else {
+                return None;
+            };
+            let accounts = AnchorPatterns::context_accounts_ident(&pat_type.ty)?;
+            let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+                return None;
+            };
+            Some(Handler {
+                ctx: pat_ident.ident.to_string(),
+                accounts: accounts.to_string(),
+                ..Handler::default()
+            })
+        });
+
+        self.handlers.push(handler);
+        visit(self);
+        self.handlers.pop();
+    }
+
+    /// The PDA field an expression such as `ctx.accounts.vault.to_account_info()` refers to
+    fn pda_field(&self, expr: &Expr) -> Option<String> {
+        let handler = self.handlers.last()?.as_ref()?;
+        let (root, members) = AstAnalyzer::member_chain(expr);
+        if root.as_deref() != Some(handler.ctx.as_str()) {
+            return None;
+        }
+
+        match members.as_slice() {
+            [accounts, field, ..]
+                if accounts == "accounts"
+                    && self
+                        .pda_fields
+                        .get(&handler.accounts)
+                        .is_some_and(|pdas| pdas.contains(field)) =>
+            {
+                Some(field.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Name of a plain local variable expression
+    fn local_name(expr: &Expr) -> Option<String> {
+        match AstAnalyzer::strip_wrappers(expr) {
+            Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+            _ => None,
+        }
+    }
+
+    /// Segments of a call's function path, e.g. `["system_instruction", "transfer"]`
+    fn call_path(call: &syn::ExprCall) -> Vec<String> {
+        match call.func.as_ref() {
+            Expr::Path(path) => path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// PDA field a `system_instruction::transfer(from, to, lamports)` instruction moves lamports from
+    fn transfer_instruction_source(&self, expr: &Expr) -> Option<String> {
+        let expr = AstAnalyzer::strip_wrappers(expr);
+        if let Some(local) = Self::local_name(expr) {
+            return self
+                .handlers
+                .last()?
+                .as_ref()?
+                .transfer_instructions
+                .get(&local)
+                .cloned();
+        }
+
+        let Expr::Call(call) = expr else {
+            return None;
+        };
+        if !matches!(
+            Self::call_path(call).as_slice(),
+            [.., module, transfer] if module == "system_instruction" && transfer == "transfer"
+        ) {
+            return None;
+        }
+        self.pda_field(call.args.first()?)
+    }
+
+    /// PDA field a `CpiContext::new(program, Transfer { from, .. })` moves lamports from
+    /// `CpiContext::new_with_signer` and `.with_signer(..)` carry seeds and are not matched
+    fn cpi_context_source(&self, expr: &Expr) -> Option<String> {
+        let expr = AstAnalyzer::strip_wrappers(expr);
+        if let Some(local) = Self::local_name(expr) {
+            return self
+                .handlers
+                .last()?
+                .as_ref()?
+                .cpi_contexts
+                .get(&local)
+                .cloned();
+        }
+
+        let Expr::Call(call) = expr else {
+            return None;
+        };
+        if !matches!(
+            Self::call_path(call).as_slice(),
+            [.., context, new] if context == "CpiContext" && new == "new"
+        ) {
+            return None;
+        }
+
+        call.args.iter().find_map(|arg| {
+            let Expr::Struct(accounts) = AstAnalyzer::strip_wrappers(arg) else {
+                return None;
+            };
+            accounts
+                .fields
+                .iter()
+                .find_map(|field| match &field.member {
+                    syn::Member::Named(name) if name == "from" => self.pda_field(&field.expr),
+                    _ => None,
+                })
+        })
+    }
+
+    fn report(&mut self, span: proc_macro2::Span, pda: &str, call: &str) {
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(span, &self.lines),
+                format!(
+                    "Lamports are transferred from PDA '{}' with {} and no signer seeds. {}",
+                    pda,
+                    call,
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+impl Detector for PdaTransferWithoutSignerDetector {
+    fn id(&self) -> &'static str {
+        "PDA_TRANSFER_WITHOUT_SIGNER"
+    }
+
+    fn name(&self) -> &'static str {
+        "PDA Transfer Without Signer Seeds"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects System Program transfers from a PDA that are invoked without signer seeds"
+    }
+
+    fn message(&self) -> &'static str {
+        "A PDA has no private key and can only sign through its seeds, so the transfer fails. Use invoke_signed or CpiContext::new_with_signer with the PDA's seeds and bump."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A System Program transfer requires the source account to sign. A PDA can only sign inside a CPI made with invoke_signed and its seeds, so a plain invoke fails with a missing signature."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.pda_fields.clear();
+        self.handlers.clear();
+
+        // Accounts structs may follow the handlers using them, so collect them first
+        let mut collector = AccountsCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for PdaTransferWithoutSignerDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_item_fn(detector, node)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        // `let ix = system_instruction::transfer(..)` or `let cpi_ctx = CpiContext::new(..)`
+        if let syn::Pat::Ident(pat_ident) = &node.pat
+            && let Some(init) = &node.init
+        {
+            let name = pat_ident.ident.to_string();
+            let instruction = self.transfer_instruction_source(&init.expr);
+            let cpi_context = self.cpi_context_source(&init.expr);
+
+            if let Some(Some(handler)) = self.handlers.last_mut() {
+                match instruction {
+                    Some(pda) => handler.transfer_instructions.insert(name.clone(), pda),
+                    None => handler.transfer_instructions.remove(&name),
+                };
+                match cpi_context {
+                    Some(pda) => handler.cpi_contexts.insert(name, pda),
+                    None => handler.cpi_contexts.remove(&name),
+                };
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        let path = Self::call_path(node);
+        let source = match path
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            // `invoke(&system_instruction::transfer(pda, to, lamports), &[..])`
+            [.., "invoke"] => node
+                .args
+                .first()
+                .and_then(|instruction| self.transfer_instruction_source(instruction))
+                .map(|pda| (pda, "invoke")),
+            // `system_program::transfer(CpiContext::new(..), lamports)`
+            [.., "system_program", "transfer"] => node
+                .args
+                .first()
+                .and_then(|cpi_context| self.cpi_context_source(cpi_context))
+                .map(|pda| (pda, "CpiContext::new")),
+            _ => None,
+        };
+        if let Some((pda, call)) = source {
+            self.report(node.func.span(), &pda, call);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+}
+
+/// Records the PDA fields of every accounts struct before handlers are checked
+struct AccountsCollector<'a> {
+    detector: &'a mut PdaTransferWithoutSignerDetector,
+}
+
+impl<'ast> Visit<'ast> for AccountsCollector<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.detector.collect_struct(node);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct PdaTypedAsSignerDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl PdaTypedAsSignerDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// A field with `seeds` or `bump` constraints is derived as a PDA
+    fn is_pda_field(&self, field: &syn::Field) -> bool {
+        AnchorPatterns::has_constraint(field, "seeds")
+            || AnchorPatterns::has_constraint(field, "bump")
+    }
+}
+
+impl Detector for PdaTypedAsSignerDetector {
+    fn id(&self) -> &'static str {
+        "PDA_TYPED_AS_SIGNER"
+    }
+
+    fn name(&self) -> &'static str {
+        "PDA Typed As Signer"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects accounts with seeds/bump constraints that are typed as Signer"
+    }
+
+    fn message(&self) -> &'static str {
+        "A PDA has no private key and cannot sign the transaction. Use Account, UncheckedAccount or SystemAccount and sign CPIs with the PDA seeds instead."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Signer<'info> requires the account to have signed the transaction. No private key exists for a PDA, so no transaction can satisfy the constraint and the instruction can never be called."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for PdaTypedAsSignerDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                if !self.is_pda_field(field)
+                    || AnchorPatterns::account_wrapper_name(field).as_deref() != Some("Signer")
+                {
+                    continue;
+                }
+
+                let field_name = field
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            field.span(),
+                            &self.lines,
+                        ),
+                        format!(
+                            "Account '{}' has seeds/bump constraints but is typed as Signer. {}",
+                            field_name,
+                            self.message()
+                        ),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
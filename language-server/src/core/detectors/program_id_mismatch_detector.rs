@@ -0,0 +1,302 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Macros comparing two keys
+const KEY_CHECK_MACROS: &[&str] = &[
+    "require_keys_eq",
+    "require_keys_neq",
+    "assert_keys_eq",
+    "assert_keys_neq",
+    "require_eq",
+    "require_neq",
+    "assert_eq",
+    "assert_ne",
+];
+
+/// `Pubkey` constructors taking a base58 string
+const PUBKEY_FROM_STR: &[&str] = &["from_str", "from_str_const"];
+
+#[derive(Default)]
+pub struct ProgramIdMismatchDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Program id from `declare_id!` in the analyzed file
+    declared_id: Option<String>,
+    /// Constants holding a `Pubkey` literal, with their value
+    pubkey_consts: HashMap<String, String>,
+}
+
+impl ProgramIdMismatchDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Get the base58 string of a `Pubkey` literal: `pubkey!("..")`, `Pubkey::from_str("..")`
+    /// (optionally unwrapped), or a constant initialized with one
+    fn pubkey_literal(&self, expr: &Expr) -> Option<String> {
+        match AstAnalyzer::strip_wrappers(expr) {
+            Expr::MethodCall(call) if call.method == "unwrap" || call.method == "expect" => {
+                self.pubkey_literal(&call.receiver)
+            }
+            Expr::Macro(expr_macro) => {
+                let name = &expr_macro.mac.path.segments.last()?.ident;
+                if name != "pubkey" {
+                    return None;
+                }
+                let literal: syn::LitStr = expr_macro.mac.parse_body().ok()?;
+                Some(literal.value())
+            }
+            Expr::Call(call) => {
+                let Expr::Path(func) = call.func.as_ref() else {
+                    return None;
+                };
+                let segments: Vec<String> = func
+                    .path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect();
+                if !matches!(
+                    segments.as_slice(),
+                    [.., ty, constructor]
+                        if ty == "Pubkey" && PUBKEY_FROM_STR.contains(&constructor.as_str())
+                ) {
+                    return None;
+                }
+                match call.args.first()? {
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(literal),
+                        ..
+                    }) => Some(literal.value()),
+                    _ => None,
+                }
+            }
+            Expr::Path(path) => {
+                let name = path.path.segments.last()?.ident.to_string();
+                self.pubkey_consts.get(&name).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Check if an expression refers to the program id (`program_id`, `ID`, `id()`)
+    fn mentions_program_id(expr: &Expr) -> bool {
+        let mut finder = ProgramIdFinder::default();
+        finder.visit_expr(expr);
+        finder.found
+    }
+
+    /// Report `literal` if it is compared against the program id and differs from `declare_id!`
+    fn check_comparison(&mut self, literal: &Expr, other: &Expr) {
+        let Some(declared) = &self.declared_id else {
+            return;
+        };
+        let Some(value) = self.pubkey_literal(literal) else {
+            return;
+        };
+        if value == *declared || !Self::mentions_program_id(other) {
+            return;
+        }
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        let message = format!(
+            "Program id is compared against {} but declare_id! is {}. {}",
+            value,
+            declared,
+            self.message()
+        );
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(literal.span(), &self.lines),
+                message,
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+impl Detector for ProgramIdMismatchDetector {
+    fn id(&self) -> &'static str {
+        "PROGRAM_ID_MISMATCH"
+    }
+
+    fn name(&self) -> &'static str {
+        "Program ID Mismatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects hardcoded program ids that differ from declare_id!"
+    }
+
+    fn message(&self) -> &'static str {
+        "A hardcoded copy of the program id goes stale when the program is redeployed under a new address. Use the ID constant or id() generated by declare_id! instead."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "The program id is the address of the deployed program. A copy that differs from declare_id! makes ownership checks and PDA derivations use the wrong program, so they reject valid accounts or accept accounts of another program."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        // `declare_id!` and constants are usually declared before they are compared, but need not be
+        let mut collector = DeclarationCollector::default();
+        collector.visit_file(syntax_tree);
+        self.declared_id = collector.declared_id;
+        self.pubkey_consts.clear();
+        for (name, value) in collector.consts {
+            if let Some(literal) = self.pubkey_literal(&value) {
+                self.pubkey_consts.insert(name, literal);
+            }
+        }
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for ProgramIdMismatchDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if matches!(node.op, BinOp::Eq(_) | BinOp::Ne(_)) {
+            self.check_comparison(&node.left, &node.right);
+            self.check_comparison(&node.right, &node.left);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        let is_key_check = node
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| KEY_CHECK_MACROS.iter().any(|name| segment.ident == name));
+
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        {
+            if is_key_check && let (Some(left), Some(right)) = (args.get(0), args.get(1)) {
+                self.check_comparison(left, right);
+                self.check_comparison(right, left);
+            }
+
+            // Comparisons are usually written inside require!(..)
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+}
+
+/// Collects the `declare_id!` value and the initializers of constants
+#[derive(Default)]
+struct DeclarationCollector {
+    declared_id: Option<String>,
+    consts: Vec<(String, Expr)>,
+}
+
+impl<'ast> Visit<'ast> for DeclarationCollector {
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if node
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "declare_id")
+            && let Ok(literal) = node.parse_body::<syn::LitStr>()
+        {
+            self.declared_id = Some(literal.value());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+
+    fn visit_item_const(&mut self, node: &'ast syn::ItemConst) {
+        self.consts
+            .push((node.ident.to_string(), node.expr.as_ref().clone()));
+
+        // Continue visiting children
+        syn::visit::visit_item_const(self, node);
+    }
+}
+
+/// Looks for a reference to the program id in an expression
+#[derive(Default)]
+struct ProgramIdFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for ProgramIdFinder {
+    fn visit_ident(&mut self, node: &'ast proc_macro2::Ident) {
+        if node == "program_id" || node == "ID" {
+            self.found = true;
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        // `id()` / `crate::id()` generated by declare_id!
+        if let Expr::Path(func) = node.func.as_ref()
+            && func
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "id")
+        {
+            self.found = true;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+}
@@ -0,0 +1,201 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct ReallocWithoutZeroInitDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Whether the function being visited zeroes memory itself (`sol_memset` or `fill(0)`)
+    zeroes_data: bool,
+}
+
+impl ReallocWithoutZeroInitDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Check if an expression is the literal `false`
+    fn is_false(expr: &Expr) -> bool {
+        matches!(
+            AstAnalyzer::strip_wrappers(expr),
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Bool(lit),
+                ..
+            }) if !lit.value
+        )
+    }
+
+    /// Check `zero_init`, the last `realloc` argument
+    fn check_realloc(&mut self, zero_init: &Expr) {
+        if self.zeroes_data || !Self::is_false(zero_init) {
+            return;
+        }
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(zero_init.span(), &self.lines),
+                format!(
+                    "`realloc` is called with `zero_init = false`. {}",
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+
+    /// Visit a function body, noting first whether it zeroes memory anywhere
+    fn visit_body(&mut self, block: &syn::Block) {
+        let mut finder = ZeroingFinder::default();
+        finder.visit_block(block);
+
+        let outer = std::mem::replace(&mut self.zeroes_data, finder.found);
+        self.visit_block(block);
+        self.zeroes_data = outer;
+    }
+}
+
+impl Detector for ReallocWithoutZeroInitDetector {
+    fn id(&self) -> &'static str {
+        "REALLOC_WITHOUT_ZERO_INIT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Realloc Without Zero Init"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `AccountInfo::realloc(new_len, false)` calls in functions that do not zero memory"
+    }
+
+    fn message(&self) -> &'static str {
+        "When the account grows, bytes left over from an earlier shrink in the same instruction are kept. Pass `true` or zero the new bytes with sol_memset."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "realloc with zero_init set to false keeps whatever bytes occupied the new range. After an earlier shrink in the same instruction these are stale data, which the program then reads back as if it were freshly initialized."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.zeroes_data = false;
+        self.visit_file(syntax_tree);
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for ReallocWithoutZeroInitDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        self.visit_body(&node.block);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.visit_body(&node.block);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        // `account.realloc(new_len, zero_init)`
+        if node.method == "realloc" && node.args.len() == 2 {
+            self.check_realloc(&node.args[1]);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        // `AccountInfo::realloc(&account, new_len, zero_init)`
+        if let Expr::Path(path) = node.func.as_ref()
+            && path.path.segments.len() > 1
+            && path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "realloc")
+            && node.args.len() == 3
+        {
+            self.check_realloc(&node.args[2]);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+}
+
+/// Finds `sol_memset(..)` calls and `.fill(0)` in a function body
+#[derive(Default)]
+struct ZeroingFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for ZeroingFinder {
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = node.func.as_ref()
+            && path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "sol_memset")
+        {
+            self.found = true;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        if node.method == "fill"
+            && node.args.len() == 1
+            && matches!(&node.args[0], Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }) if lit.base10_digits() == "0")
+        {
+            self.found = true;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
@@ -0,0 +1,151 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Wrappers whose data Anchor validates (owner and discriminator) before constraints run
+const VALIDATED_WRAPPERS: &[&str] = &["Account", "AccountLoader", "InterfaceAccount"];
+
+#[derive(Default)]
+pub struct RuntimeAddressConstraintDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl RuntimeAddressConstraintDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Get the unvalidated account an `address = <expr>` value reads from,
+    /// e.g. `some_account` for `some_account.owner`
+    /// Constants, `ID` and `pubkey!(..)` have no account root and are not matched
+    fn runtime_source(
+        value: &TokenStream,
+        unvalidated_accounts: &HashSet<String>,
+    ) -> Option<String> {
+        // `address = <expr> @ ErrorCode::X` carries a custom error after `@`
+        let expr_tokens: TokenStream = value
+            .clone()
+            .into_iter()
+            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == '@'))
+            .collect();
+        let expr = syn::parse2::<Expr>(expr_tokens).ok()?;
+
+        let (root, members) = AstAnalyzer::member_chain(&expr);
+        let root = root?;
+        (!members.is_empty() && unvalidated_accounts.contains(&root)).then_some(root)
+    }
+}
+
+impl Detector for RuntimeAddressConstraintDetector {
+    fn id(&self) -> &'static str {
+        "RUNTIME_ADDRESS_CONSTRAINT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Runtime Address Constraint"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `address = <expr>` constraints comparing against a field of an unvalidated account"
+    }
+
+    fn message(&self) -> &'static str {
+        "The expected address comes from an account the caller supplies, so the caller also chooses what the check accepts. Compare against a constant, a program ID or pubkey!(..), or a field of a validated Account<'info, T>."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "An address constraint is only as trustworthy as the value it compares against. When that value comes from an account the caller chose and Anchor did not validate, the caller controls both sides of the comparison."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for RuntimeAddressConstraintDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            let unvalidated_accounts: HashSet<String> = fields
+                .named
+                .iter()
+                .filter(|field| {
+                    AnchorPatterns::account_wrapper_name(field)
+                        .is_none_or(|wrapper| !VALIDATED_WRAPPERS.contains(&wrapper.as_str()))
+                })
+                .filter_map(|field| field.ident.as_ref().map(|ident| ident.to_string()))
+                .collect();
+
+            for field in &fields.named {
+                for constraint in AnchorPatterns::parse_account_constraints(field) {
+                    let Some(value) = &constraint.value else {
+                        continue;
+                    };
+                    if constraint.key != "address" {
+                        continue;
+                    }
+                    let Some(source) = Self::runtime_source(value, &unvalidated_accounts) else {
+                        continue;
+                    };
+
+                    let severity = self
+                        .config
+                        .severity_override
+                        .unwrap_or(self.default_severity());
+
+                    self.diagnostics
+                        .push(DiagnosticBuilder::create_with_code_description(
+                            DiagnosticBuilder::create_range_from_span_with_lines(
+                                constraint.span,
+                                &self.lines,
+                            ),
+                            format!(
+                                "Address constraint compares against a field of account '{}'. {}",
+                                source,
+                                self.message()
+                            ),
+                            severity,
+                            self.id().to_string(),
+                            None,
+                        ));
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
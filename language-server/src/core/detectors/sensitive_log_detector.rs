@@ -0,0 +1,200 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Words of an identifier (split on `_`) that mark seed or key material
+const SENSITIVE_WORDS: &[&str] = &[
+    "bump", "seed", "seeds", "secret", "private", "keypair", "mnemonic",
+];
+
+/// Logging functions taking values directly, e.g. `sol_log_64(a, b, c, d, e)`
+const LOG_FUNCTIONS: &[&str] = &["sol_log", "sol_log_64", "sol_log_data"];
+
+#[derive(Default)]
+pub struct SensitiveLogDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl SensitiveLogDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if an identifier names seed or key material, e.g. `bump_seed` or `secret_key`
+    fn is_sensitive(name: &str) -> bool {
+        name.to_lowercase()
+            .split('_')
+            .any(|word| SENSITIVE_WORDS.contains(&word))
+    }
+
+    /// First sensitive identifier among the arguments, including `{name}` captures in
+    /// format strings
+    fn sensitive_argument<'a>(args: impl IntoIterator<Item = &'a Expr>) -> Option<String> {
+        args.into_iter().find_map(|arg| {
+            if let Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(format),
+                ..
+            }) = arg
+            {
+                return Self::captured_names(&format.value())
+                    .into_iter()
+                    .find(|name| Self::is_sensitive(name));
+            }
+
+            let mut collector = IdentCollector::default();
+            collector.visit_expr(arg);
+            collector
+                .idents
+                .into_iter()
+                .find(|name| Self::is_sensitive(name))
+        })
+    }
+
+    /// Names captured inline by a format string, e.g. `bump` in `"bump: {bump:?}"`
+    fn captured_names(format: &str) -> Vec<String> {
+        format
+            .replace("{{", "")
+            .split('{')
+            .skip(1)
+            .filter_map(|placeholder| {
+                let name = placeholder.split(['}', ':']).next()?;
+                (!name.is_empty() && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_'))
+                    .then(|| name.to_string())
+            })
+            .collect()
+    }
+
+    fn report(&mut self, span: proc_macro2::Span, name: &str, call: &str) {
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(span, &self.lines),
+                format!(
+                    "'{}' is written to the log by {}. {}",
+                    name,
+                    call,
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+impl Detector for SensitiveLogDetector {
+    fn id(&self) -> &'static str {
+        "SENSITIVE_LOG"
+    }
+
+    fn name(&self) -> &'static str {
+        "Sensitive Log"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects msg!/sol_log calls logging values named like seeds, bumps or secrets"
+    }
+
+    fn message(&self) -> &'static str {
+        "Program logs are public and kept with the transaction, so anything logged can be read by anyone. Make sure this value is not secret or needed to derive one."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Transaction logs are stored by every validator and RPC node and anyone can fetch them. Logging a seed, bump or secret exposes values that let others derive the same addresses or reproduce the secret."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.visit_file(syntax_tree);
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for SensitiveLogDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if node
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "msg")
+            && let Ok(args) =
+                node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+            && let Some(name) = Self::sensitive_argument(&args)
+        {
+            self.report(node.span(), &name, "msg!");
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = node.func.as_ref()
+            && let Some(function) = path.path.segments.last()
+            && LOG_FUNCTIONS.iter().any(|name| function.ident == name)
+            && let Some(name) = Self::sensitive_argument(&node.args)
+        {
+            self.report(node.span(), &name, &function.ident.to_string());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+}
+
+/// Collects every identifier in an expression
+#[derive(Default)]
+struct IdentCollector {
+    idents: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for IdentCollector {
+    fn visit_ident(&mut self, node: &'ast proc_macro2::Ident) {
+        self.idents.push(node.to_string());
+    }
+}
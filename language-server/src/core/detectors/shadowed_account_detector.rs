@@ -0,0 +1,238 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Context parameter of the handler being visited
+struct Handler {
+    /// Name of the `Context<T>` parameter
+    ctx: String,
+    /// Accounts struct of the handler's `Context<T>`
+    accounts: String,
+}
+
+#[derive(Default)]
+pub struct ShadowedAccountDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to its field names
+    account_fields: HashMap<String, HashSet<String>>,
+    /// One entry per enclosing function, `None` if it takes no `Context<T>`
+    handlers: Vec<Option<Handler>>,
+}
+
+impl ShadowedAccountDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    fn collect_struct(&mut self, node: &syn::ItemStruct) {
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+
+        let names = fields
+            .named
+            .iter()
+            .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+            .collect();
+
+        self.account_fields.insert(node.ident.to_string(), names);
+    }
+
+    /// Track the `Context<T>` parameter of a function while visiting its body
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, sig: &syn::Signature, visit: F) {
+        let handler = sig.inputs.iter().find_map(|input| {
+            let syn::FnArg::Typed(pat_type) = input else {
+                return None;
+            };
+            let accounts = AnchorPatterns::context_accounts_ident(&pat_type.ty)?;
+            let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+                return None;
+            };
+            Some(Handler {
+                ctx: pat_ident.ident.to_string(),
+                accounts: accounts.to_string(),
+            })
+        });
+
+        self.handlers.push(handler);
+        visit(self);
+        self.handlers.pop();
+    }
+}
+
+impl Detector for ShadowedAccountDetector {
+    fn id(&self) -> &'static str {
+        "SHADOWED_ACCOUNT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Shadowed Account"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects locals named like an account of the handler's context but bound to something else"
+    }
+
+    fn message(&self) -> &'static str {
+        "A local with the name of an account but a different value is easily mistaken for that account, so checks or writes may hit the wrong one. Rename the local or bind it to the account it is named after."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Code in a handler usually refers to accounts by their field names. A local reusing such a name for a different value means later checks or writes may target the local instead of the account the reader expects."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.account_fields.clear();
+        self.handlers.clear();
+
+        // Accounts structs may follow the handlers using them, so collect them first
+        let mut collector = AccountsCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for ShadowedAccountDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_item_fn(detector, node)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        let pat = match &node.pat {
+            syn::Pat::Type(pat_type) => pat_type.pat.as_ref(),
+            pat => pat,
+        };
+
+        if let syn::Pat::Ident(pat_ident) = pat
+            && let Some(Some(handler)) = self.handlers.last()
+            && let Some(init) = &node.init
+        {
+            let name = pat_ident.ident.to_string();
+            let is_account = self
+                .account_fields
+                .get(&handler.accounts)
+                .is_some_and(|fields| fields.contains(&name));
+
+            // `let vault = &mut ctx.accounts.vault;` or `let vault = ctx.accounts.vault.key();`
+            let mut finder = AccountReferenceFinder {
+                ctx: &handler.ctx,
+                field: &name,
+                found: false,
+            };
+            finder.visit_expr(&init.expr);
+
+            if is_account && !finder.found {
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(pat_ident.ident.span(), &self.lines),
+                        format!(
+                            "Local '{}' shadows the account '{}' of '{}' but is bound to a different value. {}",
+                            name,
+                            name,
+                            handler.accounts,
+                            self.message()
+                        ),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_local(self, node);
+    }
+}
+
+/// Records the fields of every accounts struct before handlers are checked
+struct AccountsCollector<'a> {
+    detector: &'a mut ShadowedAccountDetector,
+}
+
+impl<'ast> Visit<'ast> for AccountsCollector<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.detector.collect_struct(node);
+        }
+    }
+}
+
+/// Finds `<ctx>.accounts.<field>` anywhere in an expression
+struct AccountReferenceFinder<'a> {
+    ctx: &'a str,
+    field: &'a str,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for AccountReferenceFinder<'_> {
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        if let Expr::Field(field) = node
+            && matches!(&field.member, syn::Member::Named(name) if name == self.field)
+            && let (Some(root), members) = AstAnalyzer::member_chain(&field.base)
+            && root == self.ctx
+            && members == ["accounts"]
+        {
+            self.found = true;
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr(self, node);
+    }
+}
@@ -1,15 +1,16 @@
 use super::detector::Detector;
 use super::detector_config::DetectorConfig;
-use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
 use std::path::PathBuf;
 use syn::spanned::Spanned;
-use syn::{Fields, Type, TypePath, parse_str, visit::Visit};
+use syn::{Fields, Type, TypePath, visit::Visit};
 use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
 
 #[derive(Default)]
 pub struct SysvarAccountDetector {
     diagnostics: Vec<Diagnostic>,
     config: DetectorConfig,
+    lines: LineIndex,
 }
 
 impl SysvarAccountDetector {
@@ -18,30 +19,28 @@ impl SysvarAccountDetector {
         Self {
             diagnostics: Vec::new(),
             config,
+            lines: LineIndex::default(),
         }
     }
 
     /// Check if a field is a Sysvar account type
     fn is_sysvar_field(&self, field: &syn::Field) -> Option<String> {
-        if let Type::Path(TypePath { path, .. }) = &field.ty {
-            if let Some(segment) = path.segments.last() {
-                if segment.ident == "Sysvar" {
-                    // Extract the sysvar type from generic arguments
-                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                        // Look for the second generic argument (the sysvar type)
-                        // to provide a more accurate suggestion
-                        if args.args.len() >= 2 {
-                            if let Some(syn::GenericArgument::Type(Type::Path(type_path))) =
-                                args.args.iter().nth(1)
-                            {
-                                if let Some(type_segment) = type_path.path.segments.last() {
-                                    let sysvar_type = type_segment.ident.to_string();
-                                    // All sysvars support get() through the Sysvar trait
-                                    return Some(sysvar_type);
-                                }
-                            }
-                        }
-                    }
+        if let Type::Path(TypePath { path, .. }) = &field.ty
+            && let Some(segment) = path.segments.last()
+            && segment.ident == "Sysvar"
+        {
+            // Extract the sysvar type from generic arguments
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                // Look for the second generic argument (the sysvar type)
+                // to provide a more accurate suggestion
+                if args.args.len() >= 2
+                    && let Some(syn::GenericArgument::Type(Type::Path(type_path))) =
+                        args.args.iter().nth(1)
+                    && let Some(type_segment) = type_path.path.segments.last()
+                {
+                    let sysvar_type = type_segment.ident.to_string();
+                    // All sysvars support get() through the Sysvar trait
+                    return Some(sysvar_type);
                 }
             }
         }
@@ -74,17 +73,26 @@ impl Detector for SysvarAccountDetector {
         "Sysvar account usage detected. Consider using the get() method for better efficiency."
     }
 
+    fn rationale(&self) -> &'static str {
+        "A sysvar passed as an account has to be listed in every transaction and deserialized from account data. Sysvar::get() reads the same value through a syscall, which keeps the transaction smaller and costs less compute."
+    }
+
     fn default_severity(&self) -> DiagnosticSeverity {
         DiagnosticSeverity::WARNING
     }
 
-    fn analyze(&mut self, content: &str, _file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
         self.diagnostics.clear();
 
         // Run default detection logic
-        if let Ok(syntax_tree) = parse_str::<syn::File>(content) {
-            self.visit_file(&syntax_tree);
-        }
+        self.visit_file(syntax_tree);
 
         self.diagnostics.clone()
     }
@@ -108,13 +116,24 @@ impl<'ast> Visit<'ast> for SysvarAccountDetector {
 
                     let message = self.get_suggestion_message(&sysvar_type);
 
-                    self.diagnostics.push(DiagnosticBuilder::create(
-                        DiagnosticBuilder::create_range_from_span(field.span()),
+                    let mut diagnostic = DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            field.span(),
+                            &self.lines,
+                        ),
                         message,
                         severity,
                         self.id().to_string(),
                         None,
-                    ));
+                    );
+
+                    // Recorded for the code action that removes the field
+                    diagnostic.data = Some(serde_json::json!({
+                        "sysvar_type": sysvar_type,
+                        "field_name": field.ident.as_ref().map(|ident| ident.to_string()),
+                    }));
+
+                    self.diagnostics.push(diagnostic);
                 }
             }
         }
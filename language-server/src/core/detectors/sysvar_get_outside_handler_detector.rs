@@ -0,0 +1,159 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Sysvars whose `get()` relies on the Solana runtime syscall
+const SYSVAR_TYPES: &[&str] = &[
+    "Clock",
+    "Rent",
+    "EpochSchedule",
+    "EpochRewards",
+    "Fees",
+    "LastRestartSlot",
+];
+
+#[derive(Default)]
+pub struct SysvarGetOutsideHandlerDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Nesting depth of code that does not run inside the Solana runtime
+    off_chain_depth: usize,
+}
+
+impl SysvarGetOutsideHandlerDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            off_chain_depth: 0,
+        }
+    }
+
+    /// Get the sysvar name of a `Sysvar::get()` call
+    fn sysvar_get_call(&self, call: &syn::ExprCall) -> Option<String> {
+        if !call.args.is_empty() {
+            return None;
+        }
+
+        let Expr::Path(func) = &*call.func else {
+            return None;
+        };
+
+        let segments: Vec<_> = func.path.segments.iter().collect();
+        let [.., sysvar, method] = segments.as_slice() else {
+            return None;
+        };
+
+        let sysvar = sysvar.ident.to_string();
+        (method.ident == "get" && SYSVAR_TYPES.contains(&sysvar.as_str())).then_some(sysvar)
+    }
+}
+
+impl Detector for SysvarGetOutsideHandlerDetector {
+    fn id(&self) -> &'static str {
+        "SYSVAR_GET_CONTEXT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Sysvar Get Outside Handler"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects sysvar get() calls in code that runs outside the Solana runtime (tests, build scripts)"
+    }
+
+    fn message(&self) -> &'static str {
+        "Sysvar get() uses a runtime syscall and fails outside the Solana runtime. Pass the value in or mock the sysvar instead."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Sysvar::get() is a syscall that only exists inside the Solana runtime. In unit tests, build scripts or off-chain clients it returns an error or panics, so code calling it there cannot run."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        // Build scripts never run on-chain
+        let is_build_script = file_path
+            .and_then(|path| path.file_name())
+            .is_some_and(|name| name == "build.rs");
+        self.off_chain_depth = usize::from(is_build_script);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for SysvarGetOutsideHandlerDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        let is_test = AstAnalyzer::is_test_only(&node.attrs);
+        if is_test {
+            self.off_chain_depth += 1;
+        }
+
+        syn::visit::visit_item_mod(self, node);
+
+        if is_test {
+            self.off_chain_depth -= 1;
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        let is_test = AstAnalyzer::is_test_only(&node.attrs);
+        if is_test {
+            self.off_chain_depth += 1;
+        }
+
+        syn::visit::visit_item_fn(self, node);
+
+        if is_test {
+            self.off_chain_depth -= 1;
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if self.off_chain_depth > 0
+            && let Some(sysvar) = self.sysvar_get_call(node)
+        {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(node.span(), &self.lines),
+                    format!(
+                        "{}::get() is called outside an instruction handler. {}",
+                        sysvar,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+}
@@ -0,0 +1,235 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Names handlers can use but `constraint` expressions cannot, with the replacement to suggest
+const HANDLER_ONLY_NAMES: &[(&str, &str)] = &[
+    ("program_id", "use `crate::ID` (or `ID`) instead"),
+    (
+        "ctx",
+        "refer to the accounts struct's fields by name instead",
+    ),
+];
+
+#[derive(Default)]
+pub struct UnboundConstraintIdentDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl UnboundConstraintIdentDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// The expression of a `constraint` value, without a trailing `@ CustomError`
+    fn constraint_expr(value: TokenStream) -> Option<Expr> {
+        let tokens: TokenStream = value
+            .into_iter()
+            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == '@'))
+            .collect();
+
+        syn::parse2::<Expr>(tokens).ok()
+    }
+
+    fn check_constraint(&mut self, expr: &Expr, bound: &HashSet<String>) {
+        let mut finder = UnboundIdentFinder {
+            bound,
+            locals: HashSet::new(),
+            unbound: Vec::new(),
+        };
+        finder.visit_expr(expr);
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        let mut reported = HashSet::new();
+        for ident in finder.unbound {
+            let name = ident.to_string();
+            if !reported.insert(name.clone()) {
+                continue;
+            }
+
+            let hint = HANDLER_ONLY_NAMES
+                .iter()
+                .find(|(handler_name, _)| *handler_name == name)
+                .map_or(
+                    "Declare it as an account field or in #[instruction(..)]",
+                    |(_, hint)| *hint,
+                );
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(ident.span(), &self.lines),
+                    format!(
+                        "'{}' is not bound in this constraint; {}. {}",
+                        name,
+                        hint,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
+
+impl Detector for UnboundConstraintIdentDetector {
+    fn id(&self) -> &'static str {
+        "UNBOUND_CONSTRAINT_IDENT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unbound Constraint Identifier"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `constraint = <expr>` referencing names that are neither account fields nor #[instruction(..)] arguments"
+    }
+
+    fn message(&self) -> &'static str {
+        "Constraints are expanded inside the generated account validation, where only the struct's fields and instruction arguments are in scope, so this fails to compile."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Anchor copies constraint expressions into the generated try_accounts function. Names from the handler or the surrounding module are not in scope there, so the program fails to compile with an error pointing at generated code."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnboundConstraintIdentDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            let bound: HashSet<String> = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+                .chain(
+                    AnchorPatterns::extract_instruction_parameters(node)
+                        .into_iter()
+                        .map(|(name, _, _)| name),
+                )
+                .collect();
+
+            for field in &fields.named {
+                for constraint in AnchorPatterns::parse_account_constraints(field) {
+                    if constraint.key != "constraint" {
+                        continue;
+                    }
+                    // Unparsable expressions are reported by the compiler itself
+                    if let Some(expr) = constraint.value.and_then(Self::constraint_expr) {
+                        self.check_constraint(&expr, &bound);
+                    }
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+/// Finds single-segment value paths that are not bound
+///
+/// Capitalized names (constants, unit structs, `ID`) and multi-segment paths such as
+/// `crate::ID` are assumed to be in scope, as are called functions and closure parameters
+struct UnboundIdentFinder<'a> {
+    bound: &'a HashSet<String>,
+    /// Parameters of the closures in the expression, e.g. `|item| item.active`
+    locals: HashSet<String>,
+    unbound: Vec<syn::Ident>,
+}
+
+impl<'ast> Visit<'ast> for UnboundIdentFinder<'_> {
+    fn visit_expr_closure(&mut self, node: &'ast syn::ExprClosure) {
+        let mut collector = PatIdentCollector::default();
+        for input in &node.inputs {
+            collector.visit_pat(input);
+        }
+        self.locals.extend(collector.idents);
+
+        // Continue visiting children
+        syn::visit::visit_expr_closure(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        // The callee is a function, not a binding
+        if !matches!(node.func.as_ref(), Expr::Path(_)) {
+            self.visit_expr(&node.func);
+        }
+        for arg in &node.args {
+            self.visit_expr(arg);
+        }
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        if node.qself.is_none()
+            && let Some(ident) = node.path.get_ident()
+        {
+            let name = ident.to_string();
+            let is_capitalized = name.starts_with(|c: char| c.is_ascii_uppercase());
+            if !is_capitalized
+                && name != "self"
+                && !self.bound.contains(&name)
+                && !self.locals.contains(&name)
+            {
+                self.unbound.push(ident.clone());
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_path(self, node);
+    }
+}
+
+/// Collects the identifiers bound by a pattern
+#[derive(Default)]
+struct PatIdentCollector {
+    idents: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for PatIdentCollector {
+    fn visit_pat_ident(&mut self, node: &'ast syn::PatIdent) {
+        self.idents.push(node.ident.to_string());
+
+        // Continue visiting children
+        syn::visit::visit_pat_ident(self, node);
+    }
+}
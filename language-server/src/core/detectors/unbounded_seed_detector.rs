@@ -0,0 +1,232 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct UnboundedSeedDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    // Map from accounts struct name to its handler's parameters (excluding Context)
+    instruction_handlers: HashMap<String, Vec<(String, String)>>, // (param_name, param_type)
+}
+
+impl UnboundedSeedDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            instruction_handlers: HashMap::new(),
+        }
+    }
+
+    /// Check if a parameter type has an arbitrary length
+    fn is_unbounded_type(&self, type_str: &str) -> bool {
+        let normalized = type_str.replace(' ', "");
+        matches!(normalized.as_str(), "String" | "&str" | "Vec<u8>" | "&[u8]")
+            || (normalized.starts_with("&'") && normalized.ends_with("str"))
+    }
+
+    /// Parameters of an accounts struct that have an arbitrary length
+    /// Types come from the linked handler, falling back to the `#[instruction(...)]` attribute
+    fn unbounded_parameters(&self, item_struct: &syn::ItemStruct) -> Vec<String> {
+        let handler_params = self
+            .instruction_handlers
+            .get(&item_struct.ident.to_string());
+
+        AnchorPatterns::extract_instruction_parameters(item_struct)
+            .into_iter()
+            .filter(|(name, attr_type, _)| {
+                let param_type = handler_params
+                    .and_then(|params| params.iter().find(|(param, _)| param == name))
+                    .map(|(_, handler_type)| handler_type.as_str())
+                    .unwrap_or(attr_type);
+                self.is_unbounded_type(param_type)
+            })
+            .map(|(name, _, _)| name)
+            .collect()
+    }
+
+    /// Check if any `constraint = ...` in the struct bounds the parameter's length
+    fn has_length_constraint(&self, fields: &syn::FieldsNamed, param: &str) -> bool {
+        fields.named.iter().any(|field| {
+            AnchorPatterns::parse_account_constraints(field)
+                .iter()
+                .filter(|constraint| constraint.key == "constraint")
+                .filter_map(|constraint| constraint.value.as_ref())
+                .any(|value| {
+                    Self::mentions_ident(value, param) && Self::mentions_ident(value, "len")
+                })
+        })
+    }
+
+    /// Split a `seeds = [...]` value into its elements
+    fn seed_elements(value: &TokenStream) -> Vec<Vec<TokenTree>> {
+        let Some(TokenTree::Group(group)) = value.clone().into_iter().next() else {
+            return Vec::new();
+        };
+        if group.delimiter() != Delimiter::Bracket {
+            return Vec::new();
+        }
+
+        let mut elements = Vec::new();
+        let mut current = Vec::new();
+        for token in group.stream() {
+            if let TokenTree::Punct(punct) = &token
+                && punct.as_char() == ','
+            {
+                elements.push(std::mem::take(&mut current));
+            } else {
+                current.push(token);
+            }
+        }
+        if !current.is_empty() {
+            elements.push(current);
+        }
+        elements
+    }
+
+    /// Check if a token stream contains the given identifier
+    fn mentions_ident(tokens: &TokenStream, name: &str) -> bool {
+        tokens.clone().into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == name,
+            TokenTree::Group(group) => Self::mentions_ident(&group.stream(), name),
+            _ => false,
+        })
+    }
+}
+
+impl Detector for UnboundedSeedDetector {
+    fn id(&self) -> &'static str {
+        "UNBOUNDED_SEED"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unbounded Seed"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects PDA seeds built from variable-length instruction parameters without a length check"
+    }
+
+    fn message(&self) -> &'static str {
+        "Seeds longer than 32 bytes make PDA derivation fail, and variable-length seeds can collide. Use a fixed-size seed or check the length in a constraint."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Each PDA seed may be at most 32 bytes, so a longer argument makes the address derivation fail. Concatenated variable-length seeds are also ambiguous: `[\"ab\", \"c\"]` and `[\"a\", \"bc\"]` derive the same address."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.instruction_handlers.clear();
+
+        // Link handlers to their accounts structs first, wherever they appear in the file
+        let mut collector = HandlerCollector {
+            instruction_handlers: &mut self.instruction_handlers,
+        };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnboundedSeedDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        let unbounded = self.unbounded_parameters(node);
+
+        if !unbounded.is_empty()
+            && let Fields::Named(fields) = &node.fields
+        {
+            for field in &fields.named {
+                for constraint in AnchorPatterns::parse_account_constraints(field) {
+                    let Some(value) = constraint
+                        .value
+                        .as_ref()
+                        .filter(|_| constraint.key == "seeds")
+                    else {
+                        continue;
+                    };
+
+                    for element in Self::seed_elements(value) {
+                        let element_tokens: TokenStream = element.iter().cloned().collect();
+                        let Some(param) = unbounded.iter().find(|param| {
+                            Self::mentions_ident(&element_tokens, param)
+                                && !self.has_length_constraint(fields, param)
+                        }) else {
+                            continue;
+                        };
+
+                        let severity = self
+                            .config
+                            .severity_override
+                            .unwrap_or(self.default_severity());
+
+                        self.diagnostics
+                            .push(DiagnosticBuilder::create_with_code_description(
+                                DiagnosticBuilder::create_range_from_span_with_lines(
+                                    element[0].span(),
+                                    &self.lines,
+                                ),
+                                format!(
+                                    "Seed uses variable-length instruction parameter '{}'. {}",
+                                    param,
+                                    self.message()
+                                ),
+                                severity,
+                                self.id().to_string(),
+                                None,
+                            ));
+                    }
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+/// Collects instruction handlers keyed by their `Context<T>` accounts struct
+struct HandlerCollector<'a> {
+    instruction_handlers: &'a mut HashMap<String, Vec<(String, String)>>,
+}
+
+impl<'ast> Visit<'ast> for HandlerCollector<'_> {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if let Some((context_struct_name, params)) =
+            AnchorPatterns::extract_handler_parameters(node)
+        {
+            self.instruction_handlers
+                .insert(context_struct_name, params);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+}
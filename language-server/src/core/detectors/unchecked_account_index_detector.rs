@@ -0,0 +1,255 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// How far a length guard makes indexing a slice safe
+#[derive(Clone, Copy)]
+enum Guard {
+    /// Indices below the bound, e.g. `accounts.len() >= 4` guards `accounts[3]`
+    Below(u64),
+    /// Compared against a non-literal length, any index is assumed checked
+    Any,
+}
+
+#[derive(Default)]
+pub struct UncheckedAccountIndexDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// `&[AccountInfo]` parameters of the function being visited
+    account_slices: HashSet<String>,
+    /// Length guards seen so far in the function, per slice
+    guards: HashMap<String, Guard>,
+}
+
+impl UncheckedAccountIndexDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Check if a type is a slice of account infos, e.g. `&[AccountInfo<'a>]`
+    fn is_account_info_slice(ty: &syn::Type) -> bool {
+        let syn::Type::Reference(reference) = ty else {
+            return false;
+        };
+        let syn::Type::Slice(slice) = reference.elem.as_ref() else {
+            return false;
+        };
+
+        matches!(slice.elem.as_ref(), syn::Type::Path(path)
+            if path.path.segments.last().is_some_and(|segment| segment.ident == "AccountInfo"))
+    }
+
+    /// Get the tracked slice of a `<slice>.len()` call
+    fn len_receiver(&self, expr: &Expr) -> Option<String> {
+        let Expr::MethodCall(call) = AstAnalyzer::strip_wrappers(expr) else {
+            return None;
+        };
+        let Expr::Path(path) = call.receiver.as_ref() else {
+            return None;
+        };
+        let name = path.path.get_ident()?.to_string();
+
+        (call.method == "len" && self.account_slices.contains(&name)).then_some(name)
+    }
+
+    fn int_literal(expr: &Expr) -> Option<u64> {
+        match AstAnalyzer::strip_wrappers(expr) {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }) => lit.base10_parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Record a length comparison such as `accounts.len() >= 4` or `4 > accounts.len()`
+    fn record_guard(&mut self, node: &syn::ExprBinary) {
+        let (name, bound, len_on_left) = match (
+            self.len_receiver(&node.left),
+            self.len_receiver(&node.right),
+        ) {
+            (Some(name), _) => (name, Self::int_literal(&node.right), true),
+            (_, Some(name)) => (name, Self::int_literal(&node.left), false),
+            _ => return,
+        };
+
+        // Normalize to `len <op> bound`; the guarded indices do not depend on which branch errors
+        let strict = match (&node.op, len_on_left) {
+            (BinOp::Ge(_) | BinOp::Lt(_), true) | (BinOp::Le(_) | BinOp::Gt(_), false) => false,
+            (BinOp::Gt(_) | BinOp::Le(_), true) | (BinOp::Lt(_) | BinOp::Ge(_), false) => true,
+            (BinOp::Eq(_) | BinOp::Ne(_), _) => false,
+            _ => return,
+        };
+
+        let guard = match bound {
+            Some(bound) => Guard::Below(if strict { bound + 1 } else { bound }),
+            None => Guard::Any,
+        };
+        let widest = match (self.guards.get(&name), guard) {
+            (Some(Guard::Any), _) | (_, Guard::Any) => Guard::Any,
+            (Some(Guard::Below(seen)), Guard::Below(bound)) => Guard::Below(bound.max(*seen)),
+            (None, guard) => guard,
+        };
+        self.guards.insert(name, widest);
+    }
+
+    fn is_guarded(&self, name: &str, index: u64) -> bool {
+        match self.guards.get(name) {
+            Some(Guard::Any) => true,
+            Some(Guard::Below(bound)) => index < *bound,
+            None => false,
+        }
+    }
+
+    /// Visit a function with its `&[AccountInfo]` parameters tracked
+    fn visit_function(&mut self, sig: &syn::Signature, block: &syn::Block) {
+        let slices: HashSet<String> = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::FnArg::Typed(pat_type) if Self::is_account_info_slice(&pat_type.ty) => {
+                    match pat_type.pat.as_ref() {
+                        syn::Pat::Ident(ident) => Some(ident.ident.to_string()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+
+        let outer_slices = std::mem::replace(&mut self.account_slices, slices);
+        let outer_guards = std::mem::take(&mut self.guards);
+        self.visit_block(block);
+        self.account_slices = outer_slices;
+        self.guards = outer_guards;
+    }
+}
+
+impl Detector for UncheckedAccountIndexDetector {
+    fn id(&self) -> &'static str {
+        "UNCHECKED_ACCOUNT_INDEX"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked Account Index"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `accounts[i]` on a `&[AccountInfo]` slice without a preceding length check"
+    }
+
+    fn message(&self) -> &'static str {
+        "Indexing panics when the transaction passes fewer accounts. Use `accounts.get(i).ok_or(ProgramError::NotEnoughAccountKeys)?` or check `accounts.len()` first."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Native programs receive their accounts as a plain slice whose length the caller chooses. Indexing past its end panics, aborting the transaction with an opaque error instead of `NotEnoughAccountKeys`, and hides which account was missing."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.account_slices.clear();
+        self.guards.clear();
+        self.visit_file(syntax_tree);
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UncheckedAccountIndexDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        self.visit_function(&node.sig, &node.block);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.visit_function(&node.sig, &node.block);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        self.record_guard(node);
+
+        // Continue visiting children
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        // Guards inside `require!(..)`/`assert!(..)` are not part of the syntax tree
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+
+    fn visit_expr_index(&mut self, node: &'ast syn::ExprIndex) {
+        if let Expr::Path(path) = node.expr.as_ref()
+            && let Some(name) = path.path.get_ident().map(ToString::to_string)
+            && self.account_slices.contains(&name)
+            && let Some(index) = Self::int_literal(&node.index)
+            && !self.is_guarded(&name, index)
+        {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(node.span(), &self.lines),
+                    format!(
+                        "'{}[{}]' is read without checking that '{}' has {} accounts. {}",
+                        name,
+                        index,
+                        name,
+                        index + 1,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_index(self, node);
+    }
+}
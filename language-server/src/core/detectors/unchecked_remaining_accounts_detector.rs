@@ -0,0 +1,170 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct UncheckedRemainingAccountsDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl UncheckedRemainingAccountsDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if the iterated expression reads `remaining_accounts`
+    fn iterates_remaining_accounts(&self, expr: &Expr) -> bool {
+        let mut finder = RemainingAccountsFinder { found: false };
+        finder.visit_expr(expr);
+        finder.found
+    }
+
+    /// Check if a loop body validates the accounts it iterates over
+    fn has_account_check(&self, body: &syn::Block) -> bool {
+        let mut finder = AccountCheckFinder { found: false };
+        finder.visit_block(body);
+        finder.found
+    }
+}
+
+impl Detector for UncheckedRemainingAccountsDetector {
+    fn id(&self) -> &'static str {
+        "UNCHECKED_REMAINING_ACCOUNTS"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked Remaining Accounts"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects iteration over ctx.remaining_accounts without validating each account's owner or key"
+    }
+
+    fn message(&self) -> &'static str {
+        "Accounts in ctx.remaining_accounts are not validated by Anchor. Check each account's owner or key (e.g. require_keys_eq!) before using it."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Anchor validates only the accounts declared in the accounts struct. Remaining accounts arrive exactly as the caller sent them, with any owner, type and data, so using them unchecked lets a caller substitute accounts they control."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UncheckedRemainingAccountsDetector {
+    fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+        if self.iterates_remaining_accounts(&node.expr) && !self.has_account_check(&node.body) {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(node.span(), &self.lines),
+                    self.message().to_string(),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_for_loop(self, node);
+    }
+}
+
+/// Finds `remaining_accounts` field accesses
+struct RemainingAccountsFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for RemainingAccountsFinder {
+    fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+        if let syn::Member::Named(ident) = &node.member
+            && ident == "remaining_accounts"
+        {
+            self.found = true;
+        }
+        syn::visit::visit_expr_field(self, node);
+    }
+}
+
+/// Finds owner/key validation inside a loop body
+struct AccountCheckFinder {
+    found: bool,
+}
+
+impl AccountCheckFinder {
+    /// Check if an expression reads an account's owner or key
+    fn is_owner_or_key(expr: &Expr) -> bool {
+        match expr {
+            Expr::Field(field) => {
+                matches!(&field.member, syn::Member::Named(ident) if ident == "owner" || ident == "key")
+            }
+            Expr::MethodCall(call) => call.method == "key" || call.method == "owner",
+            Expr::Reference(reference) => Self::is_owner_or_key(&reference.expr),
+            Expr::Unary(unary) => Self::is_owner_or_key(&unary.expr),
+            Expr::Paren(paren) => Self::is_owner_or_key(&paren.expr),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for AccountCheckFinder {
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        if AnchorPatterns::has_security_check(node) {
+            self.found = true;
+        }
+
+        if let Expr::Binary(binary) = node
+            && matches!(binary.op, BinOp::Eq(_) | BinOp::Ne(_))
+            && (Self::is_owner_or_key(&binary.left) || Self::is_owner_or_key(&binary.right))
+        {
+            self.found = true;
+        }
+
+        syn::visit::visit_expr(self, node);
+    }
+
+    fn visit_stmt_macro(&mut self, node: &'ast syn::StmtMacro) {
+        // `require_keys_eq!(..);` in statement position is not an expression
+        let expr = Expr::Macro(syn::ExprMacro {
+            attrs: Vec::new(),
+            mac: node.mac.clone(),
+        });
+        if AnchorPatterns::has_security_check(&expr) {
+            self.found = true;
+        }
+
+        syn::visit::visit_stmt_macro(self, node);
+    }
+}
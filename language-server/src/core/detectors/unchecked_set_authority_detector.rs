@@ -0,0 +1,261 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// Signer fields of an accounts struct and the fields its `has_one` constraints tie to state
+#[derive(Default)]
+struct AccountsSigners {
+    signers: HashSet<String>,
+    has_one: HashSet<String>,
+}
+
+/// `set_authority` calls of the handler being visited
+#[derive(Default)]
+struct HandlerCalls {
+    /// Accounts struct of the handler's `Context<T>`
+    accounts: Option<String>,
+    /// Ranges of `set_authority` calls
+    calls: Vec<Range>,
+    /// Fields passed as `current_authority` of a `SetAuthority` CPI
+    current_authorities: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct UncheckedSetAuthorityDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to its signers
+    structs: HashMap<String, AccountsSigners>,
+    /// One entry per enclosing function
+    handlers: Vec<HandlerCalls>,
+}
+
+impl UncheckedSetAuthorityDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    fn collect_struct(&mut self, node: &syn::ItemStruct) {
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+
+        let mut signers = AccountsSigners::default();
+        for field in &fields.named {
+            if let Some(ident) = &field.ident
+                && AnchorPatterns::is_signer_field(field)
+            {
+                signers.signers.insert(ident.to_string());
+            }
+
+            // `has_one = authority @ ErrorCode::Unauthorized` ties `authority`
+            for constraint in AnchorPatterns::parse_account_constraints(field) {
+                if constraint.key == "has_one"
+                    && let Some(proc_macro2::TokenTree::Ident(target)) =
+                        constraint.value.and_then(|value| value.into_iter().next())
+                {
+                    signers.has_one.insert(target.to_string());
+                }
+            }
+        }
+
+        self.structs.insert(node.ident.to_string(), signers);
+    }
+
+    /// Visit a handler body and report `set_authority` calls no signer guards
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, sig: &syn::Signature, visit: F) {
+        let accounts = sig.inputs.iter().find_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => AnchorPatterns::context_accounts_ident(&pat_type.ty),
+            syn::FnArg::Receiver(_) => None,
+        });
+        self.handlers.push(HandlerCalls {
+            accounts: accounts.map(|ident| ident.to_string()),
+            ..HandlerCalls::default()
+        });
+        visit(self);
+        let Some(handler) = self.handlers.pop() else {
+            return;
+        };
+
+        // Without the accounts struct there is nothing to cross-reference
+        let Some(signers) = handler
+            .accounts
+            .as_ref()
+            .and_then(|accounts| self.structs.get(accounts))
+        else {
+            return;
+        };
+
+        // The current authority signs itself, or a signer is tied to state with `has_one`
+        let guarded = handler
+            .current_authorities
+            .iter()
+            .any(|field| signers.signers.contains(field))
+            || signers
+                .signers
+                .iter()
+                .any(|signer| signers.has_one.contains(signer));
+        if guarded {
+            return;
+        }
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        for range in handler.calls {
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    range,
+                    format!(
+                        "Authority is changed without a signer matching the current authority. {}",
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
+
+impl Detector for UncheckedSetAuthorityDetector {
+    fn id(&self) -> &'static str {
+        "UNCHECKED_SET_AUTHORITY"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked Set Authority"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects set_authority CPIs whose accounts struct has no signer tied to the current authority"
+    }
+
+    fn message(&self) -> &'static str {
+        "When the program signs for the current authority, anyone can call this instruction and take over the mint or account. Require a Signer that is the current authority or is linked to it with has_one."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "set_authority hands control of a mint or token account to a new key. If nothing ties the instruction to a signature of the current authority, any caller can transfer that control to themselves."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::ERROR
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.structs.clear();
+        self.handlers.clear();
+
+        // Accounts structs may follow the handlers using them, so collect them first
+        let mut collector = AccountsCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UncheckedSetAuthorityDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_item_fn(detector, node)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let Expr::Path(func) = node.func.as_ref()
+            && func
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "set_authority")
+            && let Some(handler) = self.handlers.last_mut()
+        {
+            handler
+                .calls
+                .push(DiagnosticBuilder::create_range_from_span_with_lines(
+                    node.span(),
+                    &self.lines,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_field_value(&mut self, node: &'ast syn::FieldValue) {
+        // `SetAuthority { current_authority: ctx.accounts.owner.to_account_info(), .. }`
+        if matches!(&node.member, syn::Member::Named(name) if name == "current_authority")
+            && let Some(handler) = self.handlers.last_mut()
+        {
+            let (_, members) = AstAnalyzer::member_chain(&node.expr);
+            if let Some(accounts) = members.iter().position(|member| member == "accounts")
+                && let Some(field) = members.get(accounts + 1)
+            {
+                handler.current_authorities.push(field.clone());
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_field_value(self, node);
+    }
+}
+
+/// Records the signers of every accounts struct before handlers are checked
+struct AccountsCollector<'a> {
+    detector: &'a mut UncheckedSetAuthorityDetector,
+}
+
+impl<'ast> Visit<'ast> for AccountsCollector<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.detector.collect_struct(node);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Token program modules whose `transfer` skips the mint decimals check
+const TOKEN_MODULES: &[&str] = &["token", "token_interface", "token_2022"];
+
+#[derive(Default)]
+pub struct UncheckedTokenTransferDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Local names bound to a token `transfer` by `use` items
+    imported_transfers: HashSet<String>,
+}
+
+impl UncheckedTokenTransferDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            imported_transfers: HashSet::new(),
+        }
+    }
+
+    /// Check if a call path is `token::transfer` (or a `use`d alias of it)
+    fn is_unchecked_transfer(&self, path: &syn::Path) -> bool {
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+
+        match segments.as_slice() {
+            [name] => self.imported_transfers.contains(name),
+            [.., module, name] => name == "transfer" && TOKEN_MODULES.contains(&module.as_str()),
+            [] => false,
+        }
+    }
+
+    /// Record names bound to `<token module>::transfer` in a use tree
+    fn collect_imports(&mut self, tree: &syn::UseTree, parent: Option<&syn::Ident>) {
+        let from_token_module =
+            parent.is_some_and(|module| TOKEN_MODULES.iter().any(|name| module == name));
+
+        match tree {
+            syn::UseTree::Path(use_path) => {
+                self.collect_imports(&use_path.tree, Some(&use_path.ident))
+            }
+            syn::UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect_imports(item, parent);
+                }
+            }
+            syn::UseTree::Name(name) if from_token_module && name.ident == "transfer" => {
+                self.imported_transfers.insert(name.ident.to_string());
+            }
+            syn::UseTree::Rename(rename) if from_token_module && rename.ident == "transfer" => {
+                self.imported_transfers.insert(rename.rename.to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Detector for UncheckedTokenTransferDetector {
+    fn id(&self) -> &'static str {
+        "UNCHECKED_TOKEN_TRANSFER"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked Token Transfer"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects token transfers that do not verify the mint decimals"
+    }
+
+    fn message(&self) -> &'static str {
+        "token::transfer does not check the mint or its decimals. Use transfer_checked with the mint account and the expected decimals."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "The legacy transfer instruction accepts any token accounts with matching mints and ignores decimals. transfer_checked also verifies the mint and the decimals, which guards against amounts scaled for a different mint."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.imported_transfers.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UncheckedTokenTransferDetector {
+    fn visit_file(&mut self, node: &'ast syn::File) {
+        // Imports may follow their uses, so collect them all first
+        for item in &node.items {
+            if let syn::Item::Use(item_use) = item {
+                self.collect_imports(&item_use.tree, None);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_file(self, node);
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        if let Some((_, items)) = &node.content {
+            for item in items {
+                if let syn::Item::Use(item_use) = item {
+                    self.collect_imports(&item_use.tree, None);
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(expr_path) = node.func.as_ref()
+            && self.is_unchecked_transfer(&expr_path.path)
+        {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(
+                        expr_path.span(),
+                        &self.lines,
+                    ),
+                    format!("Token transfer without decimals check. {}", self.message()),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+}
@@ -0,0 +1,131 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::path::PathBuf;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Wrappers that deserialize the token account data (and so accept any token account)
+const TOKEN_ACCOUNT_WRAPPERS: &[&str] = &["Account", "InterfaceAccount"];
+
+#[derive(Default)]
+pub struct UnconstrainedTokenAccountDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl UnconstrainedTokenAccountDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Check if a field is an `Account<'info, TokenAccount>` (or the token interface variant)
+    fn is_token_account(field: &syn::Field) -> bool {
+        AnchorPatterns::account_wrapper_name(field)
+            .is_some_and(|wrapper| TOKEN_ACCOUNT_WRAPPERS.contains(&wrapper.as_str()))
+            && AnchorPatterns::account_data_type_name(field).as_deref() == Some("TokenAccount")
+    }
+
+    /// Check if a constraint ties the token account to an expected mint, authority or address
+    /// `seeds` and `address` pin the account itself, so its mint and owner are fixed as well
+    fn is_validating(key: &str) -> bool {
+        key.starts_with("token::")
+            || key.starts_with("associated_token::")
+            || matches!(key, "constraint" | "address" | "seeds")
+    }
+}
+
+impl Detector for UnconstrainedTokenAccountDetector {
+    fn id(&self) -> &'static str {
+        "UNCONSTRAINED_TOKEN_ACCOUNT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unconstrained Token Account"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects TokenAccount fields without token::mint/token::authority or constraint validation"
+    }
+
+    fn message(&self) -> &'static str {
+        "Anchor only checks that this is some token account, so a caller can pass one with any mint or owner, including their own. Add token::mint = ... and token::authority = ..., or a constraint on its mint and owner."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Account<'info, TokenAccount> only proves that the data is a token account owned by the token program. Without a mint or authority check, a caller can pass their own token account of a worthless mint where the program expects its vault."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnconstrainedTokenAccountDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                if !Self::is_token_account(field)
+                    || AnchorPatterns::parse_account_constraints(field)
+                        .iter()
+                        .any(|constraint| Self::is_validating(&constraint.key))
+                {
+                    continue;
+                }
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+
+                let severity = self
+                    .config
+                    .severity_override
+                    .unwrap_or(self.default_severity());
+
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        DiagnosticBuilder::create_range_from_span_with_lines(
+                            ident.span(),
+                            &self.lines,
+                        ),
+                        format!(
+                            "Token account '{}' has no mint or authority constraint. {}",
+                            ident,
+                            self.message()
+                        ),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
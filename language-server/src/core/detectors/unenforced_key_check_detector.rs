@@ -0,0 +1,204 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Members naming a public key, e.g. `account.key()` or `vault.authority`
+const KEY_MEMBERS: &[&str] = &["key", "pubkey", "owner", "authority"];
+
+/// Macros that stop the handler, e.g. `panic!` or `require!`
+const FAILING_MACROS: &[&str] = &["panic", "unreachable", "err", "error"];
+
+#[derive(Default)]
+pub struct UnenforcedKeyCheckDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Whether each enclosing function is an instruction handler (takes a `Context<T>`)
+    handlers: Vec<bool>,
+}
+
+impl UnenforcedKeyCheckDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Track whether a function is a handler while visiting its body
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, sig: &syn::Signature, visit: F) {
+        let is_handler = sig.inputs.iter().any(|input| {
+            matches!(input, syn::FnArg::Typed(pat_type)
+                if AnchorPatterns::context_accounts_ident(&pat_type.ty).is_some())
+        });
+
+        self.handlers.push(is_handler);
+        visit(self);
+        self.handlers.pop();
+    }
+
+    /// Check if an expression reads a public key, e.g. `ctx.accounts.signer.key()`
+    fn is_key(expr: &Expr) -> bool {
+        let (_, members) = AstAnalyzer::member_chain(expr);
+        members
+            .last()
+            .is_some_and(|member| KEY_MEMBERS.contains(&member.as_str()))
+    }
+
+    /// Check if a condition holds when two keys differ, e.g. `a.key() != b` or
+    /// `x != y || a.key() != b`
+    /// `==` checks are not matched, as their body only runs when the keys agree
+    fn is_key_mismatch(cond: &Expr) -> bool {
+        match AstAnalyzer::strip_wrappers(cond) {
+            Expr::Binary(binary) => match binary.op {
+                BinOp::Ne(_) => Self::is_key(&binary.left) || Self::is_key(&binary.right),
+                BinOp::Or(_) => {
+                    Self::is_key_mismatch(&binary.left) || Self::is_key_mismatch(&binary.right)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Check if a block stops the handler: returns, propagates with `?`, or panics
+    fn fails(block: &syn::Block) -> bool {
+        let mut finder = FailureFinder::default();
+        finder.visit_block(block);
+        finder.found
+    }
+}
+
+impl Detector for UnenforcedKeyCheckDetector {
+    fn id(&self) -> &'static str {
+        "UNENFORCED_KEY_CHECK"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unenforced Key Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `if <key> != <expected> { .. }` checks in handlers whose body does not return an error"
+    }
+
+    fn message(&self) -> &'static str {
+        "The handler continues after the keys are found to differ, so the check does not stop an unauthorized caller. Use require_keys_eq!(..) or return an error from the branch."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A comparison whose branch only logs or sets a flag does not stop the instruction. Execution continues with the unexpected key, so the check documents an assumption without enforcing it."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.handlers.clear();
+        self.visit_file(syntax_tree);
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnenforcedKeyCheckDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_item_fn(detector, node)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_expr_if(&mut self, node: &'ast syn::ExprIf) {
+        if self.handlers.last() == Some(&true)
+            && node.else_branch.is_none()
+            && Self::is_key_mismatch(&node.cond)
+            && !Self::fails(&node.then_branch)
+        {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(
+                        node.cond.span(),
+                        &self.lines,
+                    ),
+                    format!("Key check does not return an error. {}", self.message()),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_if(self, node);
+    }
+}
+
+/// Looks for a `return`, `?` or failing macro in a block
+#[derive(Default)]
+struct FailureFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for FailureFinder {
+    fn visit_expr_return(&mut self, _node: &'ast syn::ExprReturn) {
+        self.found = true;
+    }
+
+    fn visit_expr_try(&mut self, _node: &'ast syn::ExprTry) {
+        self.found = true;
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if let Some(segment) = node.path.segments.last() {
+            let name = segment.ident.to_string();
+            if name.starts_with("require") || FAILING_MACROS.contains(&name.as_str()) {
+                self.found = true;
+            }
+        }
+    }
+
+    fn visit_expr_closure(&mut self, _node: &'ast syn::ExprClosure) {
+        // A `return` or `?` inside a closure does not leave the handler
+    }
+}
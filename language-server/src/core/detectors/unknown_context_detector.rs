@@ -0,0 +1,210 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, WorkspaceFacts, WorkspaceFiles, anchor_patterns::AnchorPatterns,
+    ast_analyzer::AstAnalyzer,
+};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct UnknownContextDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts structs defined in the analyzed file
+    local_accounts: HashSet<String>,
+    /// Accounts structs defined elsewhere in the workspace, resolved on first unknown name
+    workspace_accounts: Option<HashSet<String>>,
+    /// Path of the analyzed file, used to locate its workspace
+    file_path: Option<PathBuf>,
+    /// Workspace files shared by the registry for the current scan
+    workspace: Option<Arc<WorkspaceFiles>>,
+    /// Accounts structs defined by each workspace file
+    workspace_definitions: WorkspaceFacts<HashSet<String>>,
+}
+
+impl UnknownContextDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Check if an accounts struct with this name exists in the file or its workspace
+    fn is_defined(&mut self, name: &str) -> bool {
+        if self.local_accounts.contains(name) {
+            return true;
+        }
+
+        if self.workspace_accounts.is_none() {
+            self.workspace_accounts = Some(self.collect_workspace_accounts());
+        }
+
+        self.workspace_accounts
+            .as_ref()
+            .is_some_and(|accounts| accounts.contains(name))
+    }
+
+    fn collect_workspace_accounts(&mut self) -> HashSet<String> {
+        let Some(file_path) = self.file_path.as_deref() else {
+            return HashSet::new();
+        };
+
+        self.workspace_definitions
+            .for_file(self.workspace.as_ref(), file_path, |syntax_tree| {
+                let mut collector = AccountsStructCollector::default();
+                collector.visit_file(syntax_tree);
+                collector.names
+            })
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Check a handler signature's `Context<T>` argument
+    fn check_signature(&mut self, sig: &syn::Signature) {
+        // `fn helper<T: Accounts>(ctx: Context<T>)` refers to a type parameter, not a struct
+        let type_params: HashSet<String> = sig
+            .generics
+            .type_params()
+            .map(|param| param.ident.to_string())
+            .collect();
+
+        for input in &sig.inputs {
+            let syn::FnArg::Typed(pat_type) = input else {
+                continue;
+            };
+            let Some(ident) = AnchorPatterns::context_accounts_ident(&pat_type.ty) else {
+                continue;
+            };
+
+            let name = ident.to_string();
+            if type_params.contains(&name) || self.is_defined(&name) {
+                continue;
+            }
+
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(ident.span(), &self.lines),
+                    format!(
+                        "No #[derive(Accounts)] struct named '{}' in the workspace. {}",
+                        name,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
+
+impl Detector for UnknownContextDetector {
+    fn id(&self) -> &'static str {
+        "UNKNOWN_CONTEXT_ACCOUNTS"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unknown Context Accounts"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects Context<T> where no #[derive(Accounts)] struct T exists in the workspace"
+    }
+
+    fn message(&self) -> &'static str {
+        "This is likely a typo or a missing import. Context<T> must name a #[derive(Accounts)] struct."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Context<T> is resolved at compile time against an Accounts struct. If no such struct exists the program does not build, and a near-miss name often means the handler was meant to use a different struct."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn set_workspace(&mut self, workspace: Option<Arc<WorkspaceFiles>>) {
+        self.workspace = workspace;
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.workspace_accounts = None;
+        self.file_path = file_path.cloned();
+
+        // The analyzed content may be newer than the file on disk, so collect its structs from the AST
+        let mut collector = AccountsStructCollector::default();
+        collector.visit_file(syntax_tree);
+        self.local_accounts = collector.names;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnknownContextDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        self.check_signature(&node.sig);
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.check_signature(&node.sig);
+
+        // Continue visiting children
+        syn::visit::visit_impl_item_fn(self, node);
+    }
+}
+
+/// Collects the names of `#[derive(Accounts)]` structs
+#[derive(Default)]
+struct AccountsStructCollector {
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for AccountsStructCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.names.insert(node.ident.to_string());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
@@ -0,0 +1,494 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::{BinOp, Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// Methods that mutate their receiver (mirrors the `immutable_account_mutated` lint)
+const MUTATING_METHODS: &[&str] = &[
+    "set_lamports",
+    "set_data",
+    "set_owner",
+    "set_executable",
+    "close",
+    "realloc",
+    "resize",
+    "assign",
+    "push",
+    "insert",
+    "remove",
+    "clear",
+    "set",
+    "replace",
+    "extend",
+    "append",
+    "truncate",
+    "retain",
+    "swap",
+    "sort",
+    "rotate",
+    "fill",
+    "borrow_mut",
+    "try_borrow_mut",
+    "load_mut",
+    "load_init",
+    "add_lamports",
+    "sub_lamports",
+    "exit",
+    "serialize",
+    "try_serialize",
+];
+
+/// Methods returning a plain value, so passing their result on cannot mutate the account
+const READ_ONLY_METHODS: &[&str] = &["key", "get_lamports"];
+
+/// Constraints that make Anchor write to the annotated field itself
+const IMPLICIT_MUT_CONSTRAINTS: &[&str] = &["init", "init_if_needed", "close", "realloc"];
+
+/// Constraints whose value names another field that must be writable
+const MUT_TARGET_CONSTRAINTS: &[&str] = &["payer", "close", "realloc::payer"];
+
+/// `#[account(mut)]` fields of an accounts struct with the range of their `mut` constraint
+type MutFields = Vec<(String, Range)>;
+
+/// How the handlers of one accounts struct use its fields
+#[derive(Default)]
+struct StructUsage {
+    mutated: HashSet<String>,
+    /// A handler hands the whole context or accounts struct to code we cannot see
+    opaque: bool,
+}
+
+/// Accounts usage of the handler being visited
+#[derive(Default)]
+struct HandlerUsage {
+    /// Accounts struct of the handler's `Context<T>`
+    accounts: Option<String>,
+    /// Name of the `Context<T>` parameter
+    ctx: Option<String>,
+    mutated: HashSet<String>,
+    opaque: bool,
+}
+
+/// Reference from an expression into the handler's accounts
+enum AccountsRef {
+    /// The whole context or accounts struct (`ctx`, `ctx.accounts`)
+    Whole,
+    /// `ctx.accounts.<field>` with the members accessed on it, `true` for method calls
+    Field(String, Vec<(String, bool)>),
+}
+
+#[derive(Default)]
+pub struct UnnecessaryMutDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to its `mut` fields, in declaration order
+    structs: Vec<(String, MutFields)>,
+    /// Accounts struct name to how its handlers use it
+    usages: HashMap<String, StructUsage>,
+    /// One entry per enclosing function
+    handlers: Vec<HandlerUsage>,
+}
+
+impl UnnecessaryMutDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    fn collect_struct(&mut self, node: &syn::ItemStruct) {
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+
+        // Fields other constraints require to be writable, e.g. `payer = authority`
+        let mut required = HashSet::new();
+        for field in &fields.named {
+            for constraint in AnchorPatterns::parse_account_constraints(field) {
+                if MUT_TARGET_CONSTRAINTS.contains(&constraint.key.as_str())
+                    && let Some(proc_macro2::TokenTree::Ident(target)) =
+                        constraint.value.and_then(|value| value.into_iter().next())
+                {
+                    required.insert(target.to_string());
+                }
+            }
+        }
+
+        let mut mut_fields = MutFields::new();
+        for field in &fields.named {
+            let Some(ident) = &field.ident else {
+                continue;
+            };
+            let name = ident.to_string();
+
+            let constraints = AnchorPatterns::parse_account_constraints(field);
+            if required.contains(&name)
+                || constraints
+                    .iter()
+                    .any(|constraint| IMPLICIT_MUT_CONSTRAINTS.contains(&constraint.key.as_str()))
+            {
+                continue;
+            }
+            // `mut` or `mut @ ErrorCode::X`
+            if let Some(constraint) = constraints
+                .iter()
+                .find(|constraint| constraint.key == "mut" || constraint.key.starts_with("mut@"))
+            {
+                mut_fields.push((
+                    name,
+                    DiagnosticBuilder::create_range_from_span_with_lines(
+                        constraint.span,
+                        &self.lines,
+                    ),
+                ));
+            }
+        }
+
+        self.structs.push((node.ident.to_string(), mut_fields));
+    }
+
+    /// Resolve an expression to the part of the handler's accounts it refers to
+    fn accounts_ref(&self, mut expr: &Expr) -> Option<AccountsRef> {
+        let ctx = self.handlers.last()?.ctx.as_deref()?;
+
+        // Walk from the outermost access down to the root, e.g. `ctx.accounts.vault.items[0]`
+        let mut members = Vec::new();
+        let root = loop {
+            expr = match expr {
+                Expr::Field(field) => {
+                    if let syn::Member::Named(name) = &field.member {
+                        members.push((name.to_string(), false));
+                    }
+                    &field.base
+                }
+                Expr::MethodCall(call) => {
+                    members.push((call.method.to_string(), true));
+                    &call.receiver
+                }
+                Expr::Index(index) => &index.expr,
+                Expr::Paren(paren) => &paren.expr,
+                Expr::Reference(reference) => &reference.expr,
+                Expr::Unary(unary) => &unary.expr,
+                Expr::Try(expr_try) => &expr_try.expr,
+                Expr::Cast(cast) => &cast.expr,
+                Expr::Path(path) => break path.path.get_ident()?.to_string(),
+                _ => return None,
+            };
+        };
+        if root != ctx {
+            return None;
+        }
+        members.reverse();
+
+        match members.as_slice() {
+            [] => Some(AccountsRef::Whole),
+            [(accounts, false), rest @ ..] if accounts == "accounts" => match rest {
+                [] | [(_, true), ..] => Some(AccountsRef::Whole),
+                [(field, false), rest @ ..] => {
+                    Some(AccountsRef::Field(field.clone(), rest.to_vec()))
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Record that an expression mutates the account it refers to
+    fn mark_mutated(&mut self, expr: &Expr) {
+        match self.accounts_ref(expr) {
+            Some(AccountsRef::Whole) => self.mark_opaque(),
+            Some(AccountsRef::Field(field, _)) => {
+                if let Some(handler) = self.handlers.last_mut() {
+                    handler.mutated.insert(field);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Record an expression handed to other code (a call, CPI accounts, a local binding),
+    /// which may mutate the account unless only a plain value is passed on
+    fn mark_passed(&mut self, expr: &Expr) {
+        match self.accounts_ref(expr) {
+            Some(AccountsRef::Whole) => self.mark_opaque(),
+            Some(AccountsRef::Field(field, rest)) => {
+                let passes_value = rest.iter().any(|(member, is_method)| {
+                    !is_method || READ_ONLY_METHODS.contains(&member.as_str())
+                });
+                if !passes_value && let Some(handler) = self.handlers.last_mut() {
+                    handler.mutated.insert(field);
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn mark_opaque(&mut self) {
+        if let Some(handler) = self.handlers.last_mut() {
+            handler.opaque = true;
+        }
+    }
+
+    /// Visit a handler body and merge how it uses its accounts struct
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, sig: &syn::Signature, visit: F) {
+        let context = sig.inputs.iter().find_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => AnchorPatterns::context_accounts_ident(&pat_type.ty)
+                .map(|accounts| (accounts.to_string(), pat_type.pat.as_ref())),
+            syn::FnArg::Receiver(_) => None,
+        });
+        let ctx = context.as_ref().and_then(|(_, pat)| match pat {
+            syn::Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+            _ => None,
+        });
+        self.handlers.push(HandlerUsage {
+            accounts: context.map(|(accounts, _)| accounts),
+            ctx,
+            ..HandlerUsage::default()
+        });
+        visit(self);
+        let Some(handler) = self.handlers.pop() else {
+            return;
+        };
+        let Some(accounts) = handler.accounts else {
+            return;
+        };
+
+        let usage = self.usages.entry(accounts).or_default();
+        // A context bound to a pattern cannot be followed either
+        usage.opaque |= handler.opaque || handler.ctx.is_none();
+        usage.mutated.extend(handler.mutated);
+    }
+
+    /// Report `mut` fields no handler of their struct mutates
+    fn report_unused_mut(&mut self) {
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        for (name, mut_fields) in &self.structs {
+            // Without a handler in the file the struct may be used anywhere
+            let Some(usage) = self.usages.get(name) else {
+                continue;
+            };
+            if usage.opaque {
+                continue;
+            }
+
+            for (field, range) in mut_fields {
+                if usage.mutated.contains(field) {
+                    continue;
+                }
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        *range,
+                        format!(
+                            "Account '{}' is marked mut but no handler mutates it. {}",
+                            field,
+                            self.message()
+                        ),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+    }
+}
+
+impl Detector for UnnecessaryMutDetector {
+    fn id(&self) -> &'static str {
+        "UNNECESSARY_MUT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unnecessary Mut"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects #[account(mut)] fields that the handlers using them never mutate"
+    }
+
+    fn message(&self) -> &'static str {
+        "Marking an account writable that is only read grants more access than needed and locks it for parallel transactions. Remove `mut` if the account does not need to change."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Writable accounts cannot be used by other transactions in the same block in parallel, and they widen what the instruction is allowed to change. Marking read-only accounts writable slows the program down and hides which accounts it really modifies."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.structs.clear();
+        self.usages.clear();
+        self.handlers.clear();
+
+        // Accounts structs may follow the handlers using them, so collect them first
+        let mut collector = AccountsCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+        self.report_unused_mut();
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnnecessaryMutDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_item_fn(detector, node)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(&node.sig, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_expr_assign(&mut self, node: &'ast syn::ExprAssign) {
+        self.mark_mutated(&node.left);
+
+        // Continue visiting children
+        syn::visit::visit_expr_assign(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if matches!(
+            node.op,
+            BinOp::AddAssign(_)
+                | BinOp::SubAssign(_)
+                | BinOp::MulAssign(_)
+                | BinOp::DivAssign(_)
+                | BinOp::RemAssign(_)
+                | BinOp::BitXorAssign(_)
+                | BinOp::BitAndAssign(_)
+                | BinOp::BitOrAssign(_)
+                | BinOp::ShlAssign(_)
+                | BinOp::ShrAssign(_)
+        ) {
+            self.mark_mutated(&node.left);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_reference(&mut self, node: &'ast syn::ExprReference) {
+        if node.mutability.is_some() {
+            self.mark_mutated(&node.expr);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_reference(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        let method = node.method.to_string();
+        if MUTATING_METHODS
+            .iter()
+            .any(|mutating| method == *mutating || method.starts_with(mutating))
+        {
+            self.mark_mutated(&node.receiver);
+        } else if matches!(self.accounts_ref(&node.receiver), Some(AccountsRef::Whole)) {
+            // `ctx.accounts.transfer_ctx()` may mutate any field
+            self.mark_opaque();
+        }
+        for arg in &node.args {
+            self.mark_passed(arg);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        // CPIs and helpers receiving an account may write to it
+        for arg in &node.args {
+            self.mark_passed(arg);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_field_value(&mut self, node: &'ast syn::FieldValue) {
+        // `Transfer { from: ctx.accounts.vault.to_account_info(), .. }`
+        self.mark_passed(&node.expr);
+
+        // Continue visiting children
+        syn::visit::visit_field_value(self, node);
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        // Aliases such as `let vault = ctx.accounts.vault.to_account_info();`
+        if let Some(init) = &node.init {
+            self.mark_passed(&init.expr);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        // Mutations may be written inside macros, e.g. `emit!` or custom helpers
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+}
+
+/// Records the `mut` fields of every accounts struct before handlers are checked
+struct AccountsCollector<'a> {
+    detector: &'a mut UnnecessaryMutDetector,
+}
+
+impl<'ast> Visit<'ast> for AccountsCollector<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.detector.collect_struct(node);
+        }
+    }
+}
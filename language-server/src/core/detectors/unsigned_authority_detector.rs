@@ -0,0 +1,318 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Account types that do not check `is_signer` on their own
+const RAW_ACCOUNT_TYPES: &[&str] = &["AccountInfo", "UncheckedAccount"];
+
+/// Name fragments of stored keys that identify who may act on an account
+const AUTHORITY_NAMES: &[&str] = &["authority", "admin", "owner"];
+
+/// Macros comparing two keys
+const KEY_CHECK_MACROS: &[&str] = &[
+    "require_keys_eq",
+    "require_keys_neq",
+    "assert_keys_eq",
+    "assert_keys_neq",
+    "require_eq",
+    "assert_eq",
+];
+
+/// How the handlers of one accounts struct use its fields
+#[derive(Default)]
+struct AuthorityUsage {
+    /// Fields whose key is checked against a stored authority or passed as a CPI authority
+    authorities: HashSet<String>,
+    /// Fields whose `is_signer` flag is read
+    signer_checked: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct UnsignedAuthorityDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to how its handlers use its fields
+    usages: HashMap<String, AuthorityUsage>,
+}
+
+impl UnsignedAuthorityDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Record how a handler taking `Context<T>` uses the accounts of `T`
+    fn collect_handler(&mut self, sig: &syn::Signature, block: &syn::Block) {
+        let Some(accounts) = sig.inputs.iter().find_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => AnchorPatterns::context_accounts_ident(&pat_type.ty),
+            syn::FnArg::Receiver(_) => None,
+        }) else {
+            return;
+        };
+
+        let mut finder = AuthorityFinder::default();
+        finder.visit_block(block);
+
+        let usage = self.usages.entry(accounts.to_string()).or_default();
+        usage.authorities.extend(finder.usage.authorities);
+        usage.signer_checked.extend(finder.usage.signer_checked);
+    }
+
+    /// Check if the field's constraints assert that it signed
+    fn has_signer_constraint(field: &syn::Field) -> bool {
+        AnchorPatterns::parse_account_constraints(field)
+            .iter()
+            .any(|constraint| {
+                constraint.key == "signer"
+                    || constraint
+                        .value
+                        .as_ref()
+                        .is_some_and(|value| value.to_string().contains("is_signer"))
+            })
+    }
+}
+
+impl Detector for UnsignedAuthorityDetector {
+    fn id(&self) -> &'static str {
+        "UNSIGNED_AUTHORITY"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unsigned Authority"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects AccountInfo/UncheckedAccount fields used as an authority without a signer check"
+    }
+
+    fn message(&self) -> &'static str {
+        "Matching the authority's key does not prove the authority approved the transaction; anyone can pass that account. Type it as Signer<'info> or add #[account(signer)]."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "Comparing an account's key with the stored authority only proves the caller knows that public key, which is public. Only a signature proves the authority approved the transaction."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.usages.clear();
+
+        // Handlers may follow the structs they use, so collect them first
+        let mut collector = HandlerCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnsignedAuthorityDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+        let Some(usage) = self.usages.get(&node.ident.to_string()) else {
+            return;
+        };
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        let mut diagnostics = Vec::new();
+        for field in &fields.named {
+            let Some(field_name) = field.ident.as_ref().map(|ident| ident.to_string()) else {
+                continue;
+            };
+            let is_raw = AnchorPatterns::account_wrapper_name(field)
+                .is_some_and(|wrapper| RAW_ACCOUNT_TYPES.contains(&wrapper.as_str()));
+            if !is_raw
+                || !usage.authorities.contains(&field_name)
+                || usage.signer_checked.contains(&field_name)
+                || Self::has_signer_constraint(field)
+            {
+                continue;
+            }
+
+            diagnostics.push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(field.span(), &self.lines),
+                format!(
+                    "Account '{}' is used as an authority but never required to sign. {}",
+                    field_name,
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+        }
+        self.diagnostics.extend(diagnostics);
+    }
+}
+
+/// Feeds every handler of a file to the detector before structs are checked
+struct HandlerCollector<'a> {
+    detector: &'a mut UnsignedAuthorityDetector,
+}
+
+impl<'ast> Visit<'ast> for HandlerCollector<'_> {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.detector.collect_handler(&node.sig, &node.block);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.detector.collect_handler(&node.sig, &node.block);
+    }
+}
+
+/// Finds `ctx.accounts.<field>` accounts a handler treats as an authority
+#[derive(Default)]
+struct AuthorityFinder {
+    usage: AuthorityUsage,
+}
+
+impl AuthorityFinder {
+    /// Get the field of an `accounts.<field>` expression and the members accessed after it
+    fn accounts_field(expr: &Expr) -> Option<(String, Vec<String>)> {
+        let (_, members) = AstAnalyzer::member_chain(expr);
+        let accounts = members.iter().position(|member| member == "accounts")?;
+        let field = members.get(accounts + 1)?.clone();
+        Some((field, members[accounts + 2..].to_vec()))
+    }
+
+    /// Check if an expression names a stored authority, e.g. `vault.authority` or `ADMIN`
+    fn is_authority(expr: &Expr) -> bool {
+        let (root, members) = AstAnalyzer::member_chain(expr);
+        let name = match (members.last(), expr) {
+            (Some(member), _) => member.to_lowercase(),
+            (None, Expr::Path(path)) => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string().to_lowercase())
+                .unwrap_or_default(),
+            (None, _) => root.unwrap_or_default(),
+        };
+        AUTHORITY_NAMES
+            .iter()
+            .any(|fragment| name.contains(fragment))
+    }
+
+    /// Record the field if `key` is `ctx.accounts.<field>.key()` (or `.key`) compared to an authority
+    fn record_key_check(&mut self, key: &Expr, other: &Expr) {
+        if let Some((field, rest)) = Self::accounts_field(key)
+            && rest.iter().any(|member| member == "key")
+            && Self::is_authority(other)
+        {
+            self.usage.authorities.insert(field);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for AuthorityFinder {
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if matches!(node.op, BinOp::Eq(_) | BinOp::Ne(_)) {
+            self.record_key_check(&node.left, &node.right);
+            self.record_key_check(&node.right, &node.left);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+        if matches!(&node.member, syn::Member::Named(name) if name == "is_signer")
+            && let Some((field, _)) = Self::accounts_field(&node.base)
+        {
+            self.usage.signer_checked.insert(field);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_field(self, node);
+    }
+
+    fn visit_field_value(&mut self, node: &'ast syn::FieldValue) {
+        // CPI accounts, e.g. `Transfer { authority: ctx.accounts.owner.to_account_info(), .. }`
+        if matches!(&node.member, syn::Member::Named(name) if name == "authority")
+            && let Some((field, _)) = Self::accounts_field(&node.expr)
+        {
+            self.usage.authorities.insert(field);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_field_value(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        let is_key_check = node
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| KEY_CHECK_MACROS.iter().any(|name| segment.ident == name));
+
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        {
+            if is_key_check && let (Some(left), Some(right)) = (args.get(0), args.get(1)) {
+                self.record_key_check(left, right);
+                self.record_key_check(right, left);
+            }
+
+            // Checks are usually written inside require!(..)
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+}
@@ -0,0 +1,317 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, ast_analyzer::AstAnalyzer};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, Pat, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// `Pubkey` constructors taking raw bytes
+const PUBKEY_CONSTRUCTORS: &[&str] = &["new", "new_from_array", "try_from", "from"];
+
+/// Macros that validate a key against an expected one
+const KEY_CHECK_MACROS: &[&str] = &[
+    "require_keys_eq",
+    "require_keys_neq",
+    "assert_keys_eq",
+    "assert_keys_neq",
+];
+
+/// Pubkey locals of the function being visited
+#[derive(Default)]
+struct FunctionKeys {
+    /// Locals bound to a `Pubkey` built from bytes, with the constructor range
+    constructed: Vec<(String, Range)>,
+    /// Locals that are checked somewhere in the function
+    validated: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct UntrustedPubkeyDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// One entry per enclosing function
+    functions: Vec<FunctionKeys>,
+}
+
+impl UntrustedPubkeyDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            functions: Vec::new(),
+        }
+    }
+
+    /// Get the `Pubkey` constructor call an initializer evaluates to, if its input is not constant
+    fn pubkey_from_bytes(expr: &Expr) -> Option<&syn::ExprCall> {
+        match expr {
+            Expr::Paren(paren) => Self::pubkey_from_bytes(&paren.expr),
+            Expr::Try(expr_try) => Self::pubkey_from_bytes(&expr_try.expr),
+            Expr::MethodCall(call)
+                if matches!(
+                    call.method.to_string().as_str(),
+                    "unwrap" | "expect" | "map_err"
+                ) =>
+            {
+                Self::pubkey_from_bytes(&call.receiver)
+            }
+            Expr::Call(call) => {
+                let Expr::Path(func) = call.func.as_ref() else {
+                    return None;
+                };
+                let segments: Vec<String> = func
+                    .path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect();
+                let is_constructor = matches!(
+                    segments.as_slice(),
+                    [.., ty, constructor]
+                        if ty == "Pubkey" && PUBKEY_CONSTRUCTORS.contains(&constructor.as_str())
+                );
+
+                (is_constructor && call.args.first().is_some_and(|arg| !Self::is_constant(arg)))
+                    .then_some(call)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check if constructor input is a literal (e.g. `[0u8; 32]`)
+    fn is_constant(expr: &Expr) -> bool {
+        match expr {
+            Expr::Lit(_) | Expr::Repeat(_) => true,
+            Expr::Reference(reference) => Self::is_constant(&reference.expr),
+            Expr::Array(array) => array.elems.iter().all(Self::is_constant),
+            _ => false,
+        }
+    }
+
+    /// Get the name bound by a `let` pattern
+    fn binding_name(pat: &Pat) -> Option<String> {
+        match pat {
+            Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+            Pat::Type(pat_type) => Self::binding_name(&pat_type.pat),
+            _ => None,
+        }
+    }
+
+    /// Mark every local mentioned in an expression as validated
+    fn mark_validated(&mut self, expr: &Expr) {
+        let Some(function) = self.functions.last_mut() else {
+            return;
+        };
+
+        let mut locals = LocalCollector::default();
+        locals.visit_expr(expr);
+        function.validated.extend(locals.names);
+    }
+
+    /// Check if an expression reads an account's `owner`
+    fn mentions_owner(expr: &Expr) -> bool {
+        let mut finder = OwnerFinder::default();
+        finder.visit_expr(expr);
+        finder.found
+    }
+
+    /// Visit a function body and report keys that were never validated
+    fn check_function<F: FnOnce(&mut Self)>(&mut self, visit: F) {
+        self.functions.push(FunctionKeys::default());
+        visit(self);
+        let Some(function) = self.functions.pop() else {
+            return;
+        };
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        for (name, range) in function.constructed {
+            if function.validated.contains(&name) {
+                continue;
+            }
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    range,
+                    format!(
+                        "Pubkey '{}' is built from untrusted bytes and never validated. {}",
+                        name,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+    }
+}
+
+impl Detector for UntrustedPubkeyDetector {
+    fn id(&self) -> &'static str {
+        "UNTRUSTED_PUBKEY"
+    }
+
+    fn name(&self) -> &'static str {
+        "Untrusted Pubkey"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects Pubkeys built from caller-supplied bytes without validation"
+    }
+
+    fn message(&self) -> &'static str {
+        "A key decoded from instruction data can be any value the caller chooses. Compare it against an expected key (require_keys_eq!), an account owner, or check is_on_curve() before trusting it."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A Pubkey built from instruction data is an arbitrary 32-byte value. Using it as a destination, authority or program id without comparing it to a known key lets the caller redirect funds or authority."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.functions.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UntrustedPubkeyDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        self.check_function(|detector| syn::visit::visit_item_fn(detector, node));
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        self.check_function(|detector| syn::visit::visit_impl_item_fn(detector, node));
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        if let Some(init) = &node.init
+            && let Some(call) = Self::pubkey_from_bytes(&init.expr)
+            && let Some(name) = Self::binding_name(&node.pat)
+            && let Some(function) = self.functions.last_mut()
+        {
+            function.constructed.push((
+                name,
+                DiagnosticBuilder::create_range_from_span_with_lines(call.span(), &self.lines),
+            ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        let is_key_check = node
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| KEY_CHECK_MACROS.iter().any(|name| segment.ident == name));
+
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        {
+            if is_key_check {
+                for arg in args.iter().take(2) {
+                    self.mark_validated(arg);
+                }
+            }
+
+            // Checks are usually written inside require!(..)
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        if node.method == "is_on_curve" {
+            self.mark_validated(&node.receiver);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        // Comparing against an account owner ties the key to on-chain state
+        if matches!(node.op, BinOp::Eq(_) | BinOp::Ne(_))
+            && (Self::mentions_owner(&node.left) || Self::mentions_owner(&node.right))
+        {
+            self.mark_validated(&node.left);
+            self.mark_validated(&node.right);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_binary(self, node);
+    }
+}
+
+/// Collects single-segment paths (locals) in an expression
+#[derive(Default)]
+struct LocalCollector {
+    names: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for LocalCollector {
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        if let Some(ident) = node.path.get_ident() {
+            self.names.push(ident.to_string());
+        }
+    }
+}
+
+/// Looks for an `.owner` field access in an expression
+#[derive(Default)]
+struct OwnerFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for OwnerFinder {
+    fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+        if matches!(&node.member, syn::Member::Named(name) if name == "owner") {
+            self.found = true;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_field(self, node);
+    }
+}
@@ -0,0 +1,468 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::punctuated::Punctuated;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// Constraints on a signer field that do not check anything about its key
+const NON_CHECKING_CONSTRAINTS: &[&str] = &["mut", "signer"];
+
+/// Signer fields of an accounts struct with the range of their name, in declaration order
+type SignerFields = Vec<(String, Range)>;
+
+/// How the code of one accounts struct uses its signers
+#[derive(Default)]
+struct StructUsage {
+    used: HashSet<String>,
+    /// The whole accounts struct is handed to code we cannot see
+    opaque: bool,
+}
+
+/// Accounts of the function being visited and how they are reached
+struct Scope {
+    /// Accounts struct the function works on
+    accounts: String,
+    /// `ctx` for `ctx.accounts.<field>` in handlers, `self` for `self.<field>` in methods
+    root: String,
+    /// Whether fields are reached through `<root>.accounts`
+    through_accounts: bool,
+    used: HashSet<String>,
+    opaque: bool,
+}
+
+/// How a field expression refers to the accounts of the current scope
+enum AccountsAccess {
+    /// A single field, `ctx.accounts.<field>` or `self.<field>`
+    Field(String),
+    /// The whole accounts struct (`ctx.accounts`)
+    Whole,
+    /// Anything else on the context, e.g. `ctx.bumps` or `ctx.program_id`
+    Other,
+}
+
+#[derive(Default)]
+pub struct UnusedSignerDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to its signer fields
+    structs: Vec<(String, SignerFields)>,
+    /// Accounts struct name to the methods its impl blocks define
+    methods: HashMap<String, HashSet<String>>,
+    /// Accounts struct name to how handlers and methods use its signers
+    usages: HashMap<String, StructUsage>,
+    /// One entry per enclosing function working on an accounts struct
+    scopes: Vec<Option<Scope>>,
+    /// Accounts struct of the impl block being visited
+    current_impl: Option<String>,
+}
+
+impl UnusedSignerDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    fn collect_struct(&mut self, node: &syn::ItemStruct) {
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+
+        // Names mentioned by any constraint, e.g. `has_one = authority` or
+        // `seeds = [authority.key().as_ref()]`
+        let mut mentioned = HashSet::new();
+        for field in &fields.named {
+            for constraint in AnchorPatterns::parse_account_constraints(field) {
+                if let Some(value) = constraint.value {
+                    Self::collect_idents(value, &mut mentioned);
+                }
+            }
+        }
+
+        let mut signers = SignerFields::new();
+        for field in &fields.named {
+            let Some(ident) = &field.ident else {
+                continue;
+            };
+            if AnchorPatterns::account_wrapper_name(field).as_deref() != Some("Signer") {
+                continue;
+            }
+
+            let name = ident.to_string();
+            // `address = ADMIN` or `constraint = ...` on the signer itself checks its key
+            let checked_by_own_constraints = AnchorPatterns::parse_account_constraints(field)
+                .iter()
+                .any(|constraint| !NON_CHECKING_CONSTRAINTS.contains(&constraint.key.as_str()));
+            if mentioned.contains(&name) || checked_by_own_constraints {
+                continue;
+            }
+
+            signers.push((
+                name,
+                DiagnosticBuilder::create_range_from_span_with_lines(ident.span(), &self.lines),
+            ));
+        }
+
+        self.structs.push((node.ident.to_string(), signers));
+    }
+
+    fn collect_idents(tokens: TokenStream, idents: &mut HashSet<String>) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => {
+                    idents.insert(ident.to_string());
+                }
+                TokenTree::Group(group) => Self::collect_idents(group.stream(), idents),
+                _ => {}
+            }
+        }
+    }
+
+    fn is_accounts_struct(&self, name: &str) -> bool {
+        self.structs.iter().any(|(accounts, _)| accounts == name)
+    }
+
+    /// Classify `node` as an access to the accounts of the current scope
+    fn accounts_access(&self, node: &syn::ExprField) -> Option<AccountsAccess> {
+        let scope = self.scopes.last()?.as_ref()?;
+        let syn::Member::Named(member) = &node.member else {
+            return None;
+        };
+        let root = scope.root.as_str();
+        let is_root = |expr: &Expr| matches!(expr, Expr::Path(path) if path.path.is_ident(root));
+
+        if !scope.through_accounts {
+            return is_root(&node.base).then(|| AccountsAccess::Field(member.to_string()));
+        }
+
+        if is_root(&node.base) {
+            return Some(if member == "accounts" {
+                AccountsAccess::Whole
+            } else {
+                AccountsAccess::Other
+            });
+        }
+        match node.base.as_ref() {
+            Expr::Field(base)
+                if is_root(&base.base)
+                    && matches!(&base.member, syn::Member::Named(name) if name == "accounts") =>
+            {
+                Some(AccountsAccess::Field(member.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `expr` is the whole accounts struct of the current scope
+    fn is_whole_accounts(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Field(field) => {
+                matches!(self.accounts_access(field), Some(AccountsAccess::Whole))
+            }
+            Expr::Path(path) => self
+                .scopes
+                .last()
+                .and_then(Option::as_ref)
+                .is_some_and(|scope| !scope.through_accounts && path.path.is_ident("self")),
+            _ => false,
+        }
+    }
+
+    fn with_scope<F: FnOnce(&mut Self)>(&mut self, scope: Option<Scope>, visit: F) {
+        self.scopes.push(scope);
+        visit(self);
+        let Some(Some(scope)) = self.scopes.pop() else {
+            return;
+        };
+
+        let usage = self.usages.entry(scope.accounts).or_default();
+        usage.opaque |= scope.opaque;
+        usage.used.extend(scope.used);
+    }
+
+    /// Scope of a handler taking `Context<T>`, or of a method on an accounts struct
+    fn function_scope(&self, sig: &syn::Signature) -> Option<Scope> {
+        for input in &sig.inputs {
+            match input {
+                syn::FnArg::Typed(pat_type) => {
+                    let Some(accounts) = AnchorPatterns::context_accounts_ident(&pat_type.ty)
+                    else {
+                        continue;
+                    };
+                    let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+                        // A context bound to a pattern cannot be followed
+                        return Some(Scope {
+                            accounts: accounts.to_string(),
+                            root: String::new(),
+                            through_accounts: true,
+                            used: HashSet::new(),
+                            opaque: true,
+                        });
+                    };
+                    return Some(Scope {
+                        accounts: accounts.to_string(),
+                        root: pat_ident.ident.to_string(),
+                        through_accounts: true,
+                        used: HashSet::new(),
+                        opaque: false,
+                    });
+                }
+                syn::FnArg::Receiver(_) => {
+                    if let Some(accounts) = &self.current_impl {
+                        return Some(Scope {
+                            accounts: accounts.clone(),
+                            root: "self".to_string(),
+                            through_accounts: false,
+                            used: HashSet::new(),
+                            opaque: false,
+                        });
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn current_scope(&mut self) -> Option<&mut Scope> {
+        self.scopes.last_mut()?.as_mut()
+    }
+
+    /// Report signers nothing in the file uses
+    fn report_unused_signers(&mut self) {
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        for (name, signers) in &self.structs {
+            // Without a handler in the file the struct may be used anywhere
+            let Some(usage) = self.usages.get(name) else {
+                continue;
+            };
+            if usage.opaque {
+                continue;
+            }
+
+            for (signer, range) in signers {
+                if usage.used.contains(signer) {
+                    continue;
+                }
+                self.diagnostics
+                    .push(DiagnosticBuilder::create_with_code_description(
+                        *range,
+                        format!(
+                            "Signer '{}' is not used for authorization. {}",
+                            signer,
+                            self.message()
+                        ),
+                        severity,
+                        self.id().to_string(),
+                        None,
+                    ));
+            }
+        }
+    }
+}
+
+impl Detector for UnusedSignerDetector {
+    fn id(&self) -> &'static str {
+        "UNUSED_SIGNER"
+    }
+
+    fn name(&self) -> &'static str {
+        "Signer Not Used for Authorization"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects Signer<'info> fields whose key is never checked by a constraint or used by a handler"
+    }
+
+    fn message(&self) -> &'static str {
+        "Requiring a signature only restricts who can call the instruction if the signer's key is checked, e.g. with `has_one`, a constraint, or a comparison in the handler. Check it or remove the signer."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A signer restricts the instruction only when the program checks who signed. If the key is never compared or used, any keypair can sign and the instruction is effectively permissionless."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.structs.clear();
+        self.methods.clear();
+        self.usages.clear();
+        self.scopes.clear();
+        self.current_impl = None;
+
+        // Accounts structs may follow the handlers using them, so collect them first
+        let mut collector = AccountsCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+        self.report_unused_signers();
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnusedSignerDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        let accounts = match node.self_ty.as_ref() {
+            syn::Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .filter(|name| self.is_accounts_struct(name)),
+            _ => None,
+        };
+        let outer = std::mem::replace(&mut self.current_impl, accounts);
+
+        // Continue visiting children
+        syn::visit::visit_item_impl(self, node);
+        self.current_impl = outer;
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        let scope = self.function_scope(&node.sig);
+        self.with_scope(scope, |detector| syn::visit::visit_item_fn(detector, node));
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        // Continue visiting children
+        let scope = self.function_scope(&node.sig);
+        self.with_scope(scope, |detector| {
+            syn::visit::visit_impl_item_fn(detector, node)
+        });
+    }
+
+    fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+        match self.accounts_access(node) {
+            Some(AccountsAccess::Field(field)) => {
+                if let Some(scope) = self.current_scope() {
+                    scope.used.insert(field);
+                }
+            }
+            Some(AccountsAccess::Whole) => {
+                if let Some(scope) = self.current_scope() {
+                    scope.opaque = true;
+                }
+            }
+            Some(AccountsAccess::Other) => {}
+            // Continue visiting children
+            None => syn::visit::visit_expr_field(self, node),
+        }
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        // The bare context or `self` handed to other code may use any field
+        if let Some(scope) = self.current_scope()
+            && node.path.is_ident(scope.root.as_str())
+        {
+            scope.opaque = true;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_path(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        // `ctx.accounts.process()` is followed into the impl block defining `process`
+        let known_method = self.is_whole_accounts(&node.receiver)
+            && self
+                .scopes
+                .last()
+                .and_then(Option::as_ref)
+                .and_then(|scope| self.methods.get(&scope.accounts))
+                .is_some_and(|methods| methods.contains(&node.method.to_string()));
+        if known_method {
+            for arg in &node.args {
+                self.visit_expr(arg);
+            }
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        // Keys are often compared inside macros, e.g. `require_keys_eq!`
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_macro(self, node);
+    }
+}
+
+/// Records the signers and impl methods of every accounts struct before handlers are checked
+struct AccountsCollector<'a> {
+    detector: &'a mut UnusedSignerDetector,
+}
+
+impl<'ast> Visit<'ast> for AccountsCollector<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.detector.collect_struct(node);
+        }
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if let syn::Type::Path(type_path) = node.self_ty.as_ref()
+            && let Some(segment) = type_path.path.segments.last()
+        {
+            let methods = node.items.iter().filter_map(|item| match item {
+                syn::ImplItem::Fn(method) => Some(method.sig.ident.to_string()),
+                _ => None,
+            });
+            self.detector
+                .methods
+                .entry(segment.ident.to_string())
+                .or_default()
+                .extend(methods);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_impl(self, node);
+    }
+}
@@ -0,0 +1,128 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::path::PathBuf;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct UnwrapInProgramDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Nesting depth of `#[program]` modules
+    program_depth: usize,
+}
+
+impl UnwrapInProgramDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+            program_depth: 0,
+        }
+    }
+}
+
+impl Detector for UnwrapInProgramDetector {
+    fn id(&self) -> &'static str {
+        "UNWRAP_IN_PROGRAM"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unwrap In Program"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects unwrap()/expect() calls inside #[program] module functions"
+    }
+
+    fn message(&self) -> &'static str {
+        "A panic aborts the transaction with an opaque error. Return an error instead, e.g. with `ok_or(ErrorCode::X)?` or `?`."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "A panic inside a program aborts the transaction with a generic error that clients cannot match on. Returning a custom error tells the caller what went wrong and keeps failure paths explicit in the code."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.program_depth = 0;
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for UnwrapInProgramDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        // Test code may panic freely
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        let is_program = AnchorPatterns::is_program_module(node);
+        if is_program {
+            self.program_depth += 1;
+        }
+
+        syn::visit::visit_item_mod(self, node);
+
+        if is_program {
+            self.program_depth -= 1;
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        if self.program_depth > 0 && (node.method == "unwrap" || node.method == "expect") {
+            let severity = self
+                .config
+                .severity_override
+                .unwrap_or(self.default_severity());
+
+            self.diagnostics
+                .push(DiagnosticBuilder::create_with_code_description(
+                    DiagnosticBuilder::create_range_from_span_with_lines(
+                        node.method.span(),
+                        &self.lines,
+                    ),
+                    format!(
+                        "`{}()` can panic in program code. {}",
+                        node.method,
+                        self.message()
+                    ),
+                    severity,
+                    self.id().to_string(),
+                    None,
+                ));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
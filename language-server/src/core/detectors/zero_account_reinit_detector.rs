@@ -0,0 +1,237 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns, ast_analyzer::AstAnalyzer,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Expr, Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Account data fields that mark an account as initialized
+const INIT_FLAG_FIELDS: &[&str] = &["is_initialized", "initialized", "discriminator"];
+
+#[derive(Default)]
+pub struct ZeroAccountReinitDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+    /// Accounts struct name to the fields its handlers mark as initialized
+    initialized_fields: HashMap<String, HashSet<String>>,
+}
+
+impl ZeroAccountReinitDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            config,
+            lines: LineIndex::default(),
+            ..Self::default()
+        }
+    }
+
+    /// Record the `ctx.accounts.<field>` flags set by a handler taking `Context<T>`
+    fn collect_handler(&mut self, sig: &syn::Signature, block: &syn::Block) {
+        let Some(accounts) = sig.inputs.iter().find_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => AnchorPatterns::context_accounts_ident(&pat_type.ty),
+            syn::FnArg::Receiver(_) => None,
+        }) else {
+            return;
+        };
+
+        let mut finder = InitFlagFinder::default();
+        finder.visit_block(block);
+
+        self.initialized_fields
+            .entry(accounts.to_string())
+            .or_default()
+            .extend(finder.fields);
+    }
+}
+
+impl Detector for ZeroAccountReinitDetector {
+    fn id(&self) -> &'static str {
+        "ZERO_ACCOUNT_REINIT"
+    }
+
+    fn name(&self) -> &'static str {
+        "Zero Account Reinit"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects #[account(zero)] accounts whose handler never sets an initialized flag"
+    }
+
+    fn message(&self) -> &'static str {
+        "`zero` only checks that the account data is still zeroed. If the handler never writes an initialized flag or discriminator, the same account can be initialized again. Set a flag (e.g. `is_initialized = true`) when initializing it."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "#[account(zero)] only checks that the discriminator is still zero. If the handler does not write the discriminator or an initialized flag, the account still looks uninitialized and the same instruction can initialize it again, overwriting its state."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::INFORMATION
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+        self.initialized_fields.clear();
+
+        // Handlers may follow the structs they use, so collect them first
+        let mut collector = HandlerCollector { detector: self };
+        collector.visit_file(syntax_tree);
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for ZeroAccountReinitDetector {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        let Fields::Named(fields) = &node.fields else {
+            return;
+        };
+
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+        let initialized = self.initialized_fields.get(&node.ident.to_string());
+
+        for field in &fields.named {
+            let Some(field_name) = field.ident.as_ref().map(|ident| ident.to_string()) else {
+                continue;
+            };
+            if !AnchorPatterns::has_constraint(field, "zero")
+                || initialized.is_some_and(|fields| fields.contains(&field_name))
+            {
+                continue;
+            }
+
+            self.diagnostics.push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(field.span(), &self.lines),
+                format!(
+                    "Account '{}' uses #[account(zero)] but no handler in this file marks it as initialized. {}",
+                    field_name,
+                    self.message()
+                ),
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+        }
+    }
+}
+
+/// Feeds every handler of a file to the detector before structs are checked
+struct HandlerCollector<'a> {
+    detector: &'a mut ZeroAccountReinitDetector,
+}
+
+impl<'ast> Visit<'ast> for HandlerCollector<'_> {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if AstAnalyzer::is_test_only(&node.attrs) {
+            return;
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.detector.collect_handler(&node.sig, &node.block);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.detector.collect_handler(&node.sig, &node.block);
+    }
+}
+
+/// Collects `ctx.accounts.<field>` accounts whose initialized flag is written
+#[derive(Default)]
+struct InitFlagFinder {
+    fields: HashSet<String>,
+    /// Locals bound to an account field (e.g. `let data = ctx.accounts.data.load_init()?`)
+    aliases: HashMap<String, String>,
+}
+
+impl InitFlagFinder {
+    /// Get the account field an expression reads, either `accounts.<field>` or an alias of it
+    fn account_field(&self, root: Option<&str>, members: &[String]) -> Option<(String, usize)> {
+        if let Some(accounts) = members.iter().position(|member| member == "accounts") {
+            return Some((members.get(accounts + 1)?.clone(), accounts + 2));
+        }
+
+        let field = self.aliases.get(root?)?;
+        Some((field.clone(), 0))
+    }
+
+    /// Get the account field whose initialized flag an expression refers to
+    fn flagged_account(&self, expr: &Expr) -> Option<String> {
+        let (root, members) = AstAnalyzer::member_chain(expr);
+        let (field, rest) = self.account_field(root.as_deref(), &members)?;
+        members[rest..]
+            .iter()
+            .any(|member| INIT_FLAG_FIELDS.contains(&member.as_str()))
+            .then_some(field)
+    }
+}
+
+impl<'ast> Visit<'ast> for InitFlagFinder {
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        if let Some(init) = &node.init
+            && let syn::Pat::Ident(pat_ident) = &node.pat
+        {
+            let (root, members) = AstAnalyzer::member_chain(&init.expr);
+            if let Some((field, _)) = self.account_field(root.as_deref(), &members) {
+                self.aliases.insert(pat_ident.ident.to_string(), field);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_expr_assign(&mut self, node: &'ast syn::ExprAssign) {
+        if let Some(field) = self.flagged_account(&node.left) {
+            self.fields.insert(field);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_assign(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        // e.g. `ctx.accounts.data.load_init()?.discriminator.copy_from_slice(..)`
+        if let Some(field) = self.flagged_account(&node.receiver) {
+            self.fields.insert(field);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
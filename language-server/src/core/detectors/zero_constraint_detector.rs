@@ -0,0 +1,139 @@
+use super::detector::Detector;
+use super::detector_config::DetectorConfig;
+use crate::core::utilities::{DiagnosticBuilder, LineIndex, anchor_patterns::AnchorPatterns};
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Fields, visit::Visit};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+#[derive(Default)]
+pub struct ZeroConstraintDetector {
+    diagnostics: Vec<Diagnostic>,
+    config: DetectorConfig,
+    lines: LineIndex,
+}
+
+impl ZeroConstraintDetector {
+    #[allow(dead_code)]
+    pub fn with_config(config: DetectorConfig) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            config,
+            lines: LineIndex::default(),
+        }
+    }
+
+    /// Find constraints that conflict with `zero` (the account is already allocated off-chain)
+    fn conflicting_constraints(&self, field: &syn::Field) -> Vec<String> {
+        AnchorPatterns::parse_account_constraints(field)
+            .into_iter()
+            .map(|constraint| constraint.key)
+            .filter(|key| matches!(key.as_str(), "init" | "init_if_needed" | "space"))
+            .collect()
+    }
+
+    /// Large pre-allocated accounts should be loaded with `AccountLoader` (zero-copy)
+    fn is_zero_copy_field(&self, field: &syn::Field) -> bool {
+        AnchorPatterns::account_wrapper_name(field).as_deref() == Some("AccountLoader")
+    }
+
+    fn push_diagnostic(&mut self, field: &syn::Field, message: String) {
+        let severity = self
+            .config
+            .severity_override
+            .unwrap_or(self.default_severity());
+
+        self.diagnostics
+            .push(DiagnosticBuilder::create_with_code_description(
+                DiagnosticBuilder::create_range_from_span_with_lines(field.span(), &self.lines),
+                message,
+                severity,
+                self.id().to_string(),
+                None,
+            ));
+    }
+}
+
+impl Detector for ZeroConstraintDetector {
+    fn id(&self) -> &'static str {
+        "ZERO_CONSTRAINT_MISUSE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Zero Constraint Misuse"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects #[account(zero)] combined with conflicting constraints or used on accounts that are not zero-copy"
+    }
+
+    fn message(&self) -> &'static str {
+        "#[account(zero)] is meant for large accounts pre-allocated off-chain. Use it with AccountLoader<'info, T> and #[account(zero_copy)] and without init/space."
+    }
+
+    fn rationale(&self) -> &'static str {
+        "`zero` expects an account that was created beforehand, for example by a separate System Program instruction, and checks that its discriminator is still unset. Combined with init or on an Account<'info, T> that is deserialized in full, it contradicts how the account is created or loaded."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.lines = lines.clone();
+        self.diagnostics.clear();
+
+        self.visit_file(syntax_tree);
+
+        self.diagnostics.clone()
+    }
+}
+
+impl<'ast> Visit<'ast> for ZeroConstraintDetector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        // Only check structs with #[derive(Accounts)]
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                if !AnchorPatterns::has_constraint(field, "zero") {
+                    continue;
+                }
+
+                let field_name = field
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+
+                let conflicts = self.conflicting_constraints(field);
+                if !conflicts.is_empty() {
+                    let message = format!(
+                        "Account '{}' uses #[account(zero)] together with `{}`. `zero` expects an account that was already allocated off-chain, so it cannot be combined with init/space.",
+                        field_name,
+                        conflicts.join("`, `")
+                    );
+                    self.push_diagnostic(field, message);
+                } else if !self.is_zero_copy_field(field) {
+                    let message = format!(
+                        "Account '{}' uses #[account(zero)] but is not zero-copy. {}",
+                        field_name,
+                        self.message()
+                    );
+                    self.push_diagnostic(field, message);
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
@@ -0,0 +1,125 @@
+use log::{info, warn};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Url};
+
+/// Environment variable naming the file published diagnostics are appended to
+pub const DIAGNOSTICS_LOG_ENV: &str = "SOLANA_LSP_DIAGNOSTICS_LOG";
+
+/// Receives every set of diagnostics the server publishes
+pub trait DiagnosticSink: Send + Sync + std::fmt::Debug {
+    fn record(&self, uri: &Url, diagnostics: &[Diagnostic]);
+}
+
+/// Sink used when no diagnostics log is configured
+#[derive(Debug)]
+pub struct NoopSink;
+
+impl DiagnosticSink for NoopSink {
+    fn record(&self, _uri: &Url, _diagnostics: &[Diagnostic]) {}
+}
+
+/// Appends one JSON object per diagnostic to a file (newline-delimited JSON)
+#[derive(Debug)]
+pub struct NdjsonFileSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl NdjsonFileSink {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    fn severity_name(severity: Option<DiagnosticSeverity>) -> &'static str {
+        match severity {
+            Some(DiagnosticSeverity::ERROR) => "error",
+            Some(DiagnosticSeverity::WARNING) => "warning",
+            Some(DiagnosticSeverity::INFORMATION) => "info",
+            Some(DiagnosticSeverity::HINT) => "hint",
+            _ => "unknown",
+        }
+    }
+
+    fn line(uri: &Url, diagnostic: &Diagnostic, timestamp_ms: u128) -> serde_json::Value {
+        let file = uri
+            .to_file_path()
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|_| uri.to_string());
+        let code = match &diagnostic.code {
+            Some(NumberOrString::String(code)) => Some(code.clone()),
+            Some(NumberOrString::Number(code)) => Some(code.to_string()),
+            None => None,
+        };
+
+        serde_json::json!({
+            "timestamp_ms": timestamp_ms,
+            "file": file,
+            "range": diagnostic.range,
+            "code": code,
+            "severity": Self::severity_name(diagnostic.severity),
+            "source": diagnostic.source,
+            "message": diagnostic.message,
+        })
+    }
+}
+
+impl DiagnosticSink for NdjsonFileSink {
+    fn record(&self, uri: &Url, diagnostics: &[Diagnostic]) {
+        if diagnostics.is_empty() {
+            return;
+        }
+
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+        let mut lines = String::new();
+        for diagnostic in diagnostics {
+            lines.push_str(&Self::line(uri, diagnostic, timestamp_ms).to_string());
+            lines.push('\n');
+        }
+
+        // One write per publish keeps lines of concurrent publishes from interleaving
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+        if let Err(e) = file.write_all(lines.as_bytes()) {
+            warn!(
+                "Failed to write diagnostics log {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Build the sink configured by `SOLANA_LSP_DIAGNOSTICS_LOG`, a no-op when unset
+pub fn sink_from_env() -> Arc<dyn DiagnosticSink> {
+    let Some(path) = std::env::var_os(DIAGNOSTICS_LOG_ENV).filter(|path| !path.is_empty()) else {
+        return Arc::new(NoopSink);
+    };
+
+    let path = PathBuf::from(path);
+    match NdjsonFileSink::open(&path) {
+        Ok(sink) => {
+            info!("Logging published diagnostics to {}", path.display());
+            Arc::new(sink)
+        }
+        Err(e) => {
+            warn!(
+                "Cannot open diagnostics log {}: {}, logging disabled",
+                path.display(),
+                e
+            );
+            Arc::new(NoopSink)
+        }
+    }
+}
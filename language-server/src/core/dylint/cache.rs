@@ -1,21 +1,65 @@
 use crate::core::dylint::constants::REQUIRED_NIGHTLY_VERSION;
 use crate::core::dylint::scanner::DylintDetectorInfo;
+use crate::core::utilities::StableHasher;
 use anyhow::{Context, Result};
-use log::{debug, info};
-use std::collections::hash_map::DefaultHasher;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 
+/// Index entry for a compiled detector library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheIndexEntry {
+    pub crate_name: String,
+    pub nightly_version: String,
+    pub library_path: PathBuf,
+    /// Hash of the detector sources the library was built from
+    pub source_hash: u64,
+}
+
+/// On-disk index of compiled detectors, keyed by (detector, nightly) cache key
+/// Persisted so cached libraries survive restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheIndex {
+    pub entries: HashMap<String, CacheIndexEntry>,
+}
+
+impl CacheIndex {
+    /// File name of the index inside the cache directory
+    pub const FILE_NAME: &'static str = "index.json";
+
+    /// Load the index, starting empty if it is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Ignoring unreadable dylint cache index {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Write the index to disk
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
 /// Cache manager for compiled dylint detectors
 pub struct DylintDetectorCache {
     cache_dir: PathBuf,
+    index: CacheIndex,
 }
 
 impl std::fmt::Debug for DylintDetectorCache {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DylintDetectorCache")
             .field("cache_dir", &self.cache_dir)
+            .field("entries", &self.index.entries.len())
             .finish()
     }
 }
@@ -23,15 +67,24 @@ impl std::fmt::Debug for DylintDetectorCache {
 impl DylintDetectorCache {
     /// Create a new cache manager
     pub fn new() -> Result<Self> {
-        let cache_dir = Self::get_cache_directory()?;
+        Self::with_cache_dir(Self::get_cache_directory()?)
+    }
 
+    /// Create a cache manager for a specific directory, loading its index
+    pub fn with_cache_dir(cache_dir: PathBuf) -> Result<Self> {
         // Create cache directory if it doesn't exist
         if !cache_dir.exists() {
             fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
             info!("Created cache directory: {:?}", cache_dir);
         }
 
-        Ok(Self { cache_dir })
+        let index = CacheIndex::load(&cache_dir.join(CacheIndex::FILE_NAME));
+        debug!(
+            "Loaded dylint cache index with {} entries",
+            index.entries.len()
+        );
+
+        Ok(Self { cache_dir, index })
     }
 
     /// Get the cache directory path with version
@@ -59,27 +112,26 @@ impl DylintDetectorCache {
             // Check ../extension/package.json (language-server sibling)
             let package_json = path
                 .parent()
-                .and_then(|p| Some(p.join("extension").join("package.json")));
-
-            if let Some(pkg_path) = package_json {
-                if pkg_path.exists() {
-                    let content =
-                        fs::read_to_string(&pkg_path).context("Failed to read package.json")?;
-
-                    // Simple version extraction (avoiding serde_json dependency)
-                    if let Some(version_line) = content
-                        .lines()
-                        .find(|line| line.trim().starts_with("\"version\""))
-                    {
-                        if let Some(version) = version_line.split(':').nth(1).and_then(|v| {
-                            v.trim()
-                                .trim_matches(',')
-                                .trim_matches('"')
-                                .strip_prefix("")
-                        }) {
-                            return Ok(version.trim_matches('"').to_string());
-                        }
-                    }
+                .map(|p| p.join("extension").join("package.json"));
+
+            if let Some(pkg_path) = package_json
+                && pkg_path.exists()
+            {
+                let content =
+                    fs::read_to_string(&pkg_path).context("Failed to read package.json")?;
+
+                // Simple version extraction (avoiding serde_json dependency)
+                if let Some(version_line) = content
+                    .lines()
+                    .find(|line| line.trim().starts_with("\"version\""))
+                    && let Some(version) = version_line.split(':').nth(1).and_then(|v| {
+                        v.trim()
+                            .trim_matches(',')
+                            .trim_matches('"')
+                            .strip_prefix("")
+                    })
+                {
+                    return Ok(version.trim_matches('"').to_string());
                 }
             }
 
@@ -93,28 +145,50 @@ impl DylintDetectorCache {
     /// Get the cache key for a detector and nightly version
     fn get_cache_key(detector: &DylintDetectorInfo, nightly_version: &str) -> String {
         // Create a hash of the detector path and nightly version
-        let mut hasher = DefaultHasher::new();
-        detector.crate_path.hash(&mut hasher);
-        detector.crate_name.hash(&mut hasher);
-        nightly_version.hash(&mut hasher);
+        let mut hasher = StableHasher::new();
+        hasher.update_path(&detector.crate_path);
+        hasher.update(detector.crate_name.as_bytes());
+        hasher.update(nightly_version.as_bytes());
         format!("{:x}", hasher.finish())
     }
 
-    /// Get the cached library path for a detector
-    pub fn get_cached_library(
-        &self,
-        detector: &DylintDetectorInfo,
-        nightly_version: &str,
-    ) -> Option<PathBuf> {
-        // Try new format first (with nightly version in filename)
-        let extension = if cfg!(target_os = "macos") {
-            "dylib"
-        } else if cfg!(target_os = "windows") {
-            "dll"
-        } else {
-            "so"
+    /// Hash the detector sources (Cargo.toml and everything under src/)
+    /// A detector whose hash differs from the indexed one has changed and must be rebuilt
+    pub fn source_hash(detector: &DylintDetectorInfo) -> u64 {
+        let mut files = vec![detector.cargo_toml_path.clone()];
+        Self::collect_source_files(&detector.crate_path.join("src"), &mut files);
+        files.sort();
+
+        let mut hasher = StableHasher::new();
+        for file in files {
+            if let Ok(content) = fs::read(&file) {
+                hasher.update_path(file.strip_prefix(&detector.crate_path).unwrap_or(&file));
+                hasher.update(&content);
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Recursively collect the files of a source directory
+    fn collect_source_files(dir: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
         };
 
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                Self::collect_source_files(&path, files);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    /// Get the library filename for a detector
+    /// Format: lib<detector_name>@<nightly_version>-<platform>.<ext>
+    /// This format matches the pre-compiled lints and allows dylint runner to detect toolchain
+    fn library_filename(detector: &DylintDetectorInfo, extension: &str) -> String {
         // Always use the extension's required nightly version
         let platform = std::env::consts::ARCH;
         let os = match std::env::consts::OS {
@@ -124,39 +198,55 @@ impl DylintDetectorCache {
             _ => "unknown",
         };
 
-        let filename = format!(
+        format!(
             "lib{}@{}-{}-{}.{}",
             detector.crate_name.replace("-", "_"),
             REQUIRED_NIGHTLY_VERSION,
             platform,
             os,
             extension
-        );
+        )
+    }
+
+    /// Get the cached library path for a detector
+    /// Only returns a hit if the index records the detector with its current source hash
+    pub fn get_cached_library(
+        &self,
+        detector: &DylintDetectorInfo,
+        nightly_version: &str,
+    ) -> Option<PathBuf> {
+        let cache_key = Self::get_cache_key(detector, nightly_version);
+        let Some(entry) = self.index.entries.get(&cache_key) else {
+            debug!("No cache index entry for detector {}", detector.crate_name);
+            return None;
+        };
 
-        let lib_path = self.cache_dir.join(&filename);
-        if lib_path.exists() {
-            debug!("Found cached library (new format): {:?}", lib_path);
-            return Some(lib_path);
+        if entry.source_hash != Self::source_hash(detector) {
+            info!(
+                "Detector {} sources changed since it was cached, rebuilding",
+                detector.crate_name
+            );
+            return None;
         }
 
-        // Fallback: try old hash-based format
-        let cache_key = Self::get_cache_key(detector, nightly_version);
-        let cached_path = self.cache_dir.join(&cache_key);
-        let lib_path = cached_path.with_extension(extension);
-        if lib_path.exists() {
-            debug!("Found cached library (old format): {:?}", lib_path);
-            return Some(lib_path);
+        if !entry.library_path.exists() {
+            debug!(
+                "Indexed library for {} is missing: {:?}",
+                detector.crate_name, entry.library_path
+            );
+            return None;
         }
 
-        None
+        debug!("Found cached library: {:?}", entry.library_path);
+        Some(entry.library_path.clone())
     }
 
-    /// Store a compiled library in the cache
+    /// Store a compiled library in the cache and record it in the index
     /// The filename includes the detector name and nightly version for easy identification
     pub fn cache_library(
-        &self,
+        &mut self,
         detector: &DylintDetectorInfo,
-        _nightly_version: &str,
+        nightly_version: &str,
         compiled_lib: &Path,
     ) -> Result<PathBuf> {
         let extension = compiled_lib
@@ -164,31 +254,26 @@ impl DylintDetectorCache {
             .and_then(|e| e.to_str())
             .unwrap_or("so");
 
-        // Always use the extension's required nightly version
-        // Create filename: lib<detector_name>@<nightly_version>-<platform>.<ext>
-        // This format matches the pre-compiled lints and allows dylint runner to detect toolchain
-        let platform = std::env::consts::ARCH;
-        let os = match std::env::consts::OS {
-            "macos" => "apple-darwin",
-            "linux" => "unknown-linux-gnu",
-            "windows" => "pc-windows-msvc",
-            _ => "unknown",
-        };
-
-        let filename = format!(
-            "lib{}@{}-{}-{}.{}",
-            detector.crate_name.replace("-", "_"),
-            REQUIRED_NIGHTLY_VERSION,
-            platform,
-            os,
-            extension
-        );
-
-        let cached_path = self.cache_dir.join(filename);
+        let cached_path = self
+            .cache_dir
+            .join(Self::library_filename(detector, extension));
 
         // Copy the compiled library to cache
         fs::copy(compiled_lib, &cached_path).context("Failed to copy library to cache")?;
 
+        self.index.entries.insert(
+            Self::get_cache_key(detector, nightly_version),
+            CacheIndexEntry {
+                crate_name: detector.crate_name.clone(),
+                nightly_version: nightly_version.to_string(),
+                library_path: cached_path.clone(),
+                source_hash: Self::source_hash(detector),
+            },
+        );
+        self.index
+            .save(&self.cache_dir.join(CacheIndex::FILE_NAME))
+            .context("Failed to write cache index")?;
+
         info!("Cached library to: {:?}", cached_path);
         Ok(cached_path)
     }
@@ -7,17 +7,31 @@ use std::process::Command;
 use tokio::process::Command as TokioCommand;
 
 /// Compiler for dylint detector crates
-pub struct DylintDetectorCompiler;
+pub struct DylintDetectorCompiler {
+    /// Program run in place of `cargo` to build detectors
+    cargo_command: String,
+}
 
 impl std::fmt::Debug for DylintDetectorCompiler {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("DylintDetectorCompiler").finish()
+        f.debug_struct("DylintDetectorCompiler")
+            .field("cargo_command", &self.cargo_command)
+            .finish()
     }
 }
 
 impl DylintDetectorCompiler {
     pub fn new() -> Self {
-        Self
+        Self {
+            cargo_command: "cargo".to_string(),
+        }
+    }
+
+    /// Run a different program in place of `cargo`
+    #[allow(dead_code)]
+    pub fn with_cargo_command(mut self, command: impl Into<String>) -> Self {
+        self.cargo_command = command.into();
+        self
     }
 
     /// Compile a dylint detector crate with the extension's required nightly Rust version
@@ -49,7 +63,7 @@ impl DylintDetectorCompiler {
 
         // Build the detector using cargo with the required nightly
         // Use debug mode for faster builds during development
-        let output = TokioCommand::new("cargo")
+        let output = TokioCommand::new(&self.cargo_command)
             .arg(&toolchain_arg)
             .arg("build")
             // .arg("--release")  // Commented out for faster development builds
@@ -1,11 +1,27 @@
 use crate::core::dylint::{
-    cache::DylintDetectorCache, compiler::DylintDetectorCompiler, scanner::DylintDetectorScanner,
+    cache::DylintDetectorCache, compiler::DylintDetectorCompiler,
+    constants::REQUIRED_NIGHTLY_VERSION, scanner::DylintDetectorScanner,
 };
+use crate::core::notifications::{DetectorStatus, DetectorStatusNotification};
 use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
 use log::{info, warn};
+use serde::Serialize;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tower_lsp::Client;
+
+/// Outcome of building every detector ahead of use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PrebuildSummary {
+    /// Detectors compiled by this run
+    pub compiled: usize,
+    /// Detectors whose cached build was still current
+    pub cached: usize,
+    /// Detectors that failed to compile
+    pub failed: usize,
+}
 
 /// Manager for dylint detectors - handles scanning, compilation, and caching
 /// Compiled detectors are added to dylint_runner which runs them via cargo +nightly dylint
@@ -19,20 +35,77 @@ pub struct DylintDetectorManager {
     initialized: bool,
     /// Cached list of compiled detector paths
     compiled_paths: Vec<PathBuf>,
+    /// Maximum number of detectors compiled at the same time
+    compile_concurrency: usize,
+}
+
+/// What building a single detector ahead of use did
+enum PrebuildOutcome {
+    Compiled,
+    Cached,
+    Failed,
 }
 
 impl DylintDetectorManager {
+    /// Environment variable overriding how many detectors are compiled at the same time
+    pub const COMPILE_CONCURRENCY_ENV: &'static str = "SOLANA_LSP_COMPILE_CONCURRENCY";
+
     pub fn new() -> Result<Self> {
-        let cache = Arc::new(Mutex::new(DylintDetectorCache::new()?));
+        Ok(Self::with_cache(DylintDetectorCache::new()?))
+    }
 
-        Ok(Self {
+    /// Create a manager storing compiled detectors in the given cache
+    pub fn with_cache(cache: DylintDetectorCache) -> Self {
+        Self {
             scanner: DylintDetectorScanner::new(),
             compiler: DylintDetectorCompiler::new(),
-            cache,
+            cache: Arc::new(Mutex::new(cache)),
             nightly_version: None,
             initialized: false,
             compiled_paths: Vec::new(),
-        })
+            compile_concurrency: Self::compile_concurrency_from_env(),
+        }
+    }
+
+    /// Use a specific compiler, e.g. one running a different `cargo`
+    #[allow(dead_code)]
+    pub fn with_compiler(mut self, compiler: DylintDetectorCompiler) -> Self {
+        self.compiler = compiler;
+        self
+    }
+
+    /// Override how many detectors are compiled at the same time (at least one)
+    #[allow(dead_code)]
+    pub fn with_compile_concurrency(mut self, limit: usize) -> Self {
+        self.compile_concurrency = limit.max(1);
+        self
+    }
+
+    /// Default concurrency: half the CPUs, as each detector build runs a parallel cargo itself
+    fn default_compile_concurrency() -> usize {
+        std::thread::available_parallelism()
+            .map(|cpus| cpus.get() / 2)
+            .unwrap_or(1)
+            .max(1)
+    }
+
+    fn compile_concurrency_from_env() -> usize {
+        match std::env::var(Self::COMPILE_CONCURRENCY_ENV) {
+            Ok(value) => match value.trim().parse::<usize>() {
+                Ok(limit) if limit > 0 => limit,
+                _ => {
+                    let default = Self::default_compile_concurrency();
+                    warn!(
+                        "Invalid {} value '{}', using default of {}",
+                        Self::COMPILE_CONCURRENCY_ENV,
+                        value,
+                        default
+                    );
+                    default
+                }
+            },
+            Err(_) => Self::default_compile_concurrency(),
+        }
     }
 
     /// Check if nightly Rust is available
@@ -40,6 +113,48 @@ impl DylintDetectorManager {
         DylintDetectorCompiler::is_nightly_available()
     }
 
+    /// Check for the required nightly and tell the extension how to install it if missing
+    /// Returns whether the toolchain is available
+    pub async fn ensure_nightly_or_report(client: &Client) -> bool {
+        if Self::check_nightly_available() {
+            return true;
+        }
+
+        warn!(
+            "[Extension Dylint] Required nightly Rust version not available: {}",
+            REQUIRED_NIGHTLY_VERSION
+        );
+        Self::report_missing_toolchain(client, REQUIRED_NIGHTLY_VERSION).await;
+        false
+    }
+
+    /// Send a `missing_toolchain` status carrying the install command for `version`
+    pub async fn report_missing_toolchain(client: &Client, version: &str) {
+        let status = DetectorStatus::missing_toolchain(version);
+        if let Some(command) = &status.install_command {
+            warn!("[Extension Dylint] Install with: {}", command);
+        }
+
+        client
+            .send_notification::<DetectorStatusNotification>(status)
+            .await;
+    }
+
+    /// Send a `cargo_missing` status telling the extension how to install Rust
+    pub async fn report_cargo_missing(client: &Client) {
+        let status = DetectorStatus::cargo_missing();
+        if let Some(command) = &status.install_command {
+            warn!(
+                "[Extension Dylint] cargo not found, install Rust with: {}",
+                command
+            );
+        }
+
+        client
+            .send_notification::<DetectorStatusNotification>(status)
+            .await;
+    }
+
     /// Check if dylint-driver is available
     pub fn check_dylint_driver_available() -> bool {
         DylintDetectorCompiler::is_dylint_driver_available()
@@ -92,21 +207,26 @@ impl DylintDetectorManager {
             detectors.len()
         );
 
-        // Compile each detector (will reuse cached builds) and collect paths
-        let mut compiled_paths = Vec::new();
-        for detector in detectors {
-            match self
-                .build_and_cache_detector(&detector, &nightly_version)
-                .await
-            {
-                Ok(path) => {
-                    compiled_paths.push(path);
-                }
-                Err(e) => {
-                    warn!("Failed to compile detector {}: {}", detector.crate_name, e);
-                }
-            }
-        }
+        // Compile the detectors (reusing cached builds) a few at a time; `buffered` keeps scan order
+        // The futures are collected up front, as a lazy `map` inside the stream is not `Send`
+        let builds: Vec<_> = detectors
+            .iter()
+            .map(|detector| async {
+                self.build_and_cache_detector(detector, &nightly_version)
+                    .await
+                    .inspect_err(|e| {
+                        warn!("Failed to compile detector {}: {}", detector.crate_name, e)
+                    })
+                    .ok()
+            })
+            .collect();
+        let compiled_paths: Vec<PathBuf> = stream::iter(builds)
+            .buffered(self.compile_concurrency)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
 
         // Mark as initialized and cache paths
         self.initialized = true;
@@ -119,6 +239,87 @@ impl DylintDetectorManager {
         Ok(compiled_paths)
     }
 
+    /// Compile every detector that has no current cached build, without loading any
+    /// Sends a "building" status per detector to the client, if given
+    pub async fn prebuild_detectors(&self, client: Option<&Client>) -> Result<PrebuildSummary> {
+        let nightly_version = DylintDetectorCompiler::get_nightly_version()
+            .context("Failed to get nightly Rust version. Make sure nightly is installed.")?;
+
+        let detectors = self.scanner.scan_detectors();
+        let builds: Vec<_> = detectors
+            .iter()
+            .enumerate()
+            .map(|(index, detector)| {
+                self.prebuild_detector(detector, &nightly_version, client, index, detectors.len())
+            })
+            .collect();
+        let outcomes: Vec<PrebuildOutcome> = stream::iter(builds)
+            .buffer_unordered(self.compile_concurrency)
+            .collect()
+            .await;
+
+        let mut summary = PrebuildSummary::default();
+        for outcome in outcomes {
+            match outcome {
+                PrebuildOutcome::Compiled => summary.compiled += 1,
+                PrebuildOutcome::Cached => summary.cached += 1,
+                PrebuildOutcome::Failed => summary.failed += 1,
+            }
+        }
+
+        info!(
+            "[Extension Dylint] Prebuilt detectors: {} compiled, {} cached, {} failed",
+            summary.compiled, summary.cached, summary.failed
+        );
+        Ok(summary)
+    }
+
+    /// Build one detector for `prebuild_detectors` unless its cached build is current
+    async fn prebuild_detector(
+        &self,
+        detector: &crate::core::dylint::scanner::DylintDetectorInfo,
+        nightly_version: &str,
+        client: Option<&Client>,
+        index: usize,
+        total: usize,
+    ) -> PrebuildOutcome {
+        let cached = self
+            .cache
+            .lock()
+            .await
+            .get_cached_library(detector, nightly_version)
+            .is_some();
+        if cached {
+            return PrebuildOutcome::Cached;
+        }
+
+        if let Some(client) = client {
+            client
+                .send_notification::<DetectorStatusNotification>(DetectorStatus {
+                    status: "building".to_string(),
+                    message: format!(
+                        "Building detector {} ({}/{})",
+                        detector.crate_name,
+                        index + 1,
+                        total
+                    ),
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        match self
+            .build_and_cache_detector(detector, nightly_version)
+            .await
+        {
+            Ok(_) => PrebuildOutcome::Compiled,
+            Err(e) => {
+                warn!("Failed to compile detector {}: {}", detector.crate_name, e);
+                PrebuildOutcome::Failed
+            }
+        }
+    }
+
     /// Build and cache a detector (without loading it)
     async fn build_and_cache_detector(
         &self,
@@ -150,7 +351,7 @@ impl DylintDetectorManager {
             .context("Failed to compile detector")?;
 
         // Cache the compiled version for future reuse
-        let cache = self.cache.lock().await;
+        let mut cache = self.cache.lock().await;
         let cached_path = cache
             .cache_library(detector, nightly_version, &compiled)
             .context("Failed to cache compiled detector")?;
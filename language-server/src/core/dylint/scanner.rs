@@ -84,13 +84,13 @@ impl DylintDetectorScanner {
 
             if path.is_dir() {
                 // Skip common directories
-                if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                    if matches!(
+                if let Some(dir_name) = path.file_name().and_then(|n| n.to_str())
+                    && matches!(
                         dir_name,
                         "target" | "node_modules" | ".git" | ".vscode" | "out" | ".anchor"
-                    ) {
-                        continue;
-                    }
+                    )
+                {
+                    continue;
                 }
                 self.find_cargo_toml_recursive(&path, files)?;
             } else if path.file_name().and_then(|n| n.to_str()) == Some("Cargo.toml") {
@@ -140,13 +140,13 @@ impl DylintDetectorScanner {
         // Look for [package] name = "..."
         for line in content.lines() {
             let line = line.trim();
-            if line.starts_with("name =") {
-                if let Some(name) = line.strip_prefix("name =") {
-                    let name = name.trim();
-                    // Remove quotes
-                    let name = name.trim_matches('"').trim_matches('\'');
-                    return Some(name.to_string());
-                }
+            if line.starts_with("name =")
+                && let Some(name) = line.strip_prefix("name =")
+            {
+                let name = name.trim();
+                // Remove quotes
+                let name = name.trim_matches('"').trim_matches('\'');
+                return Some(name.to_string());
             }
         }
         None
@@ -0,0 +1,125 @@
+use crate::core::registry::REGISTRY_VERSION;
+use crate::core::utilities::StableHasher;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::Diagnostic;
+
+/// Cached analysis result for a single file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFile {
+    pub content_hash: u64,
+    /// `WorkspaceFiles::content_hash` of the scan, since detectors such as
+    /// UNKNOWN_CONTEXT_ACCOUNTS resolve names in other files of the workspace
+    pub workspace_hash: u64,
+    pub diagnostics: Vec<Diagnostic>,
+    pub is_anchor_program: bool,
+}
+
+/// Persisted results of a whole-workspace scan, keyed by file path
+///
+/// Entries are only valid for the workspace contents they were produced from, so a change to
+/// any Rust file invalidates every entry, not just the changed file's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCache {
+    /// Detector registry version the results were produced with
+    pub registry_version: u32,
+    pub files: HashMap<PathBuf, CachedFile>,
+}
+
+impl Default for ScanCache {
+    fn default() -> Self {
+        Self {
+            registry_version: REGISTRY_VERSION,
+            files: HashMap::new(),
+        }
+    }
+}
+
+impl ScanCache {
+    /// Default location of the scan cache for a workspace
+    pub fn default_path(workspace_root: &Path) -> Option<PathBuf> {
+        let mut hasher = StableHasher::new();
+        hasher.update_path(workspace_root);
+
+        dirs::cache_dir().map(|dir| {
+            dir.join("solana-vscode")
+                .join("scan-cache")
+                .join(format!("{:016x}.json", hasher.finish()))
+        })
+    }
+
+    /// Load a cache file
+    /// Returns `None` (and removes the file) if it was written by a different registry version
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+
+        let cache: Self = match serde_json::from_str(&content) {
+            Ok(cache) => cache,
+            Err(e) => {
+                warn!("Discarding unreadable scan cache {:?}: {}", path, e);
+                let _ = fs::remove_file(path);
+                return None;
+            }
+        };
+
+        if cache.registry_version != REGISTRY_VERSION {
+            info!(
+                "Discarding scan cache {:?} (registry version {} != {})",
+                path, cache.registry_version, REGISTRY_VERSION
+            );
+            let _ = fs::remove_file(path);
+            return None;
+        }
+
+        debug!("Loaded scan cache with {} file(s)", cache.files.len());
+        Some(cache)
+    }
+
+    /// Write the cache to disk
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        fs::write(path, content)
+    }
+
+    /// Get cached results for a file if neither its content nor the rest of the workspace changed
+    pub fn get(&self, path: &Path, content: &str, workspace_hash: u64) -> Option<&CachedFile> {
+        self.files.get(path).filter(|cached| {
+            cached.workspace_hash == workspace_hash
+                && cached.content_hash == Self::hash_content(content)
+        })
+    }
+
+    /// Record results for a file
+    pub fn insert(
+        &mut self,
+        path: PathBuf,
+        content: &str,
+        workspace_hash: u64,
+        diagnostics: Vec<Diagnostic>,
+        is_anchor_program: bool,
+    ) {
+        self.files.insert(
+            path,
+            CachedFile {
+                content_hash: Self::hash_content(content),
+                workspace_hash,
+                diagnostics,
+                is_anchor_program,
+            },
+        );
+    }
+
+    /// Hash file content for change detection
+    pub fn hash_content(content: &str) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.update(content.as_bytes());
+        hasher.finish()
+    }
+}
@@ -0,0 +1,133 @@
+use log::debug;
+use std::fs;
+use std::path::Path;
+
+/// A single gitignore-style pattern
+#[derive(Debug, Clone)]
+struct IgnorePattern {
+    /// Glob without the leading `/`, trailing `/` and `!`
+    glob: String,
+    /// `!pattern` re-includes a previously ignored path
+    negated: bool,
+    /// `pattern/` only matches directories
+    dir_only: bool,
+    /// Patterns containing a `/` match from the root, others match any path component
+    anchored: bool,
+}
+
+/// Minimal gitignore-style matcher
+///
+/// Supports comments, `!` negation, trailing `/` for directories, anchoring via `/`,
+/// and the `*`, `?` and `**` wildcards. Only the workspace root `.gitignore` is read.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMatcher {
+    patterns: Vec<IgnorePattern>,
+}
+
+impl IgnoreMatcher {
+    /// Build a matcher from gitignore-style lines
+    pub fn from_lines<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns = lines
+            .into_iter()
+            .filter_map(|line| Self::parse_line(line.as_ref()))
+            .collect();
+        Self { patterns }
+    }
+
+    /// Load `.gitignore` from the workspace root (empty if there is none)
+    pub fn from_gitignore(root: &Path) -> Self {
+        match fs::read_to_string(root.join(".gitignore")) {
+            Ok(content) => {
+                let matcher = Self::from_lines(content.lines());
+                debug!("Loaded {} .gitignore pattern(s)", matcher.patterns.len());
+                matcher
+            }
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Append the patterns of another matcher (later patterns take precedence)
+    pub fn extend(&mut self, other: IgnoreMatcher) {
+        self.patterns.extend(other.patterns);
+    }
+
+    /// Check if a path relative to the workspace root is ignored
+    pub fn is_ignored(&self, relative_path: &Path, is_dir: bool) -> bool {
+        let path = relative_path.to_string_lossy().replace('\\', "/");
+        let name = path.rsplit('/').next().unwrap_or(&path);
+
+        // The last matching pattern decides, as in git
+        self.patterns
+            .iter()
+            .rev()
+            .find(|pattern| {
+                (!pattern.dir_only || is_dir)
+                    && if pattern.anchored {
+                        Self::glob_match(&pattern.glob, &path)
+                    } else {
+                        Self::glob_match(&pattern.glob, name)
+                    }
+            })
+            .is_some_and(|pattern| !pattern.negated)
+    }
+
+    fn parse_line(line: &str) -> Option<IgnorePattern> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let glob = line.trim_start_matches('/').to_string();
+
+        (!glob.is_empty()).then_some(IgnorePattern {
+            glob,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    /// Match a glob against a `/`-separated path
+    /// `*` and `?` do not cross `/`; `**` matches any number of components
+    fn glob_match(glob: &str, path: &str) -> bool {
+        let glob: Vec<char> = glob.chars().collect();
+        let path: Vec<char> = path.chars().collect();
+        Self::glob_match_chars(&glob, &path)
+    }
+
+    fn glob_match_chars(glob: &[char], path: &[char]) -> bool {
+        match glob {
+            [] => path.is_empty(),
+            ['*', '*', rest @ ..] => {
+                // `**/` also matches zero directories
+                let rest = rest.strip_prefix(&['/']).unwrap_or(rest);
+                rest.is_empty()
+                    || (0..=path.len()).any(|i| {
+                        (i == 0 || path[i - 1] == '/') && Self::glob_match_chars(rest, &path[i..])
+                    })
+            }
+            ['*', rest @ ..] => (0..=path.len())
+                .take_while(|&i| i == 0 || path[i - 1] != '/')
+                .any(|i| Self::glob_match_chars(rest, &path[i..])),
+            ['?', rest @ ..] => {
+                matches!(path, [c, ..] if *c != '/') && Self::glob_match_chars(rest, &path[1..])
+            }
+            [c, rest @ ..] => {
+                matches!(path, [p, ..] if p == c) && Self::glob_match_chars(rest, &path[1..])
+            }
+        }
+    }
+}
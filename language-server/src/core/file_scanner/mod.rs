@@ -1,5 +1,10 @@
+pub mod cache;
+pub mod ignore;
+pub mod revision;
 pub mod scanner;
 pub mod types;
 
+pub use cache::*;
+pub use revision::*;
 pub use scanner::*;
 pub use types::*;
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Source of file contents at a given revision
+pub trait RevisionContentProvider {
+    /// List files tracked at the revision, relative to the workspace root
+    fn list_files(&self, revision: &str) -> Result<Vec<PathBuf>>;
+
+    /// Read a file's content at the revision
+    fn read_file(&self, revision: &str, path: &Path) -> Result<String>;
+}
+
+/// Reads file contents at a revision from git without touching the working tree
+#[derive(Debug, Clone)]
+pub struct GitContentProvider {
+    workspace_root: PathBuf,
+}
+
+impl GitContentProvider {
+    pub fn new(workspace_root: PathBuf) -> Self {
+        Self { workspace_root }
+    }
+
+    /// Run a git command in the workspace root and return its stdout
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&self.workspace_root)
+            .output()
+            .context("Failed to execute git")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+impl RevisionContentProvider for GitContentProvider {
+    fn list_files(&self, revision: &str) -> Result<Vec<PathBuf>> {
+        // Without --full-name, paths are relative to the current directory (the workspace root)
+        let stdout = self.git(&["ls-tree", "-r", "--name-only", revision])?;
+        Ok(stdout.lines().map(PathBuf::from).collect())
+    }
+
+    fn read_file(&self, revision: &str, path: &Path) -> Result<String> {
+        // `./` makes git resolve the path relative to the workspace root rather than the repo root
+        let object = format!("{}:./{}", revision, path.to_string_lossy());
+        self.git(&["show", &object])
+    }
+}
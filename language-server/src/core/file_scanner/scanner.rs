@@ -1,22 +1,120 @@
-use crate::core::{DetectorRegistry, file_scanner::types::*};
+use crate::core::{
+    DetectorRegistry, WorkDoneProgressReporter,
+    file_scanner::{
+        cache::ScanCache, ignore::IgnoreMatcher, revision::RevisionContentProvider, types::*,
+    },
+    utilities::WorkspaceFiles,
+};
 use log::{debug, info, warn};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tower_lsp::Client;
 
 /// File scanner for analyzing workspace files on startup
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct FileScanner {
     workspace_root: Option<PathBuf>,
+    /// Extra ignore patterns from settings (gitignore syntax)
+    ignore_globs: Vec<String>,
+    /// Workspace `.gitignore` combined with `ignore_globs`
+    ignore: IgnoreMatcher,
+    /// Also scan test files and test directories
+    scan_tests: bool,
+    /// Set to stop a running scan after the current file
+    cancelled: Arc<AtomicBool>,
 }
 
 impl FileScanner {
+    /// Create a scanner for a directory, honoring its `.gitignore`
+    pub fn new(root: PathBuf) -> Self {
+        let mut scanner = Self {
+            workspace_root: Some(root),
+            ..Self::default()
+        };
+        scanner.rebuild_ignore();
+        scanner
+    }
+
     /// Set the workspace root directory
     pub fn set_workspace_root(&mut self, root: PathBuf) {
         self.workspace_root = Some(root.clone());
+        self.rebuild_ignore();
         info!("Workspace root set to: {:?}", root);
     }
 
+    /// Set extra ignore patterns (gitignore syntax, relative to the workspace root)
+    /// These apply on top of the workspace `.gitignore` and the built-in skipped directories
+    pub fn set_ignore_globs(&mut self, globs: Vec<String>) {
+        self.ignore_globs = globs;
+        self.rebuild_ignore();
+    }
+
+    /// Include test files and test directories (`tests/`, `*_test.rs`, ...) in scans
+    pub fn set_scan_tests(&mut self, scan_tests: bool) {
+        self.scan_tests = scan_tests;
+    }
+
+    /// Share a flag that stops running and future scans once set
+    /// The flag is checked between files, so a scan returns the files analyzed so far
+    pub fn set_cancel_flag(&mut self, cancelled: Arc<AtomicBool>) {
+        self.cancelled = cancelled;
+    }
+
+    /// Check if scanning has been cancelled
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Rebuild the ignore matcher from `.gitignore` and the configured globs
+    fn rebuild_ignore(&mut self) {
+        let mut ignore = self
+            .workspace_root
+            .as_deref()
+            .map(IgnoreMatcher::from_gitignore)
+            .unwrap_or_default();
+        ignore.extend(IgnoreMatcher::from_lines(&self.ignore_globs));
+        self.ignore = ignore;
+    }
+
+    /// Check if a path is excluded by `.gitignore` or the configured globs
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let relative = self
+            .workspace_root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        self.ignore.is_ignored(relative, is_dir)
+    }
+
+    /// Check if a file would be left out of a workspace scan
+    /// Covers paths outside the root, skipped directories, ignore patterns and test files
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let Some(relative_path) = self
+            .workspace_root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+        else {
+            return true;
+        };
+
+        self.is_excluded_relative(relative_path) || self.is_test_file(path)
+    }
+
+    /// Check a path relative to the root against the skipped directories and ignore patterns
+    fn is_excluded_relative(&self, relative_path: &Path) -> bool {
+        let skipped_dir = relative_path
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.ancestors())
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .any(|dir| self.should_skip_directory(dir) || self.ignore.is_ignored(dir, true));
+
+        skipped_dir || self.ignore.is_ignored(relative_path, false)
+    }
+
     /// Scan all relevant files in the workspace
     pub async fn scan_workspace(&self, detector_registry: &mut DetectorRegistry) -> ScanResult {
         self.scan_workspace_with_client(detector_registry, None)
@@ -40,7 +138,7 @@ impl FileScanner {
 
         // Scan for Rust files and run security analysis
         // Only .rs files in the workspace are analyzed (excludes external dependencies)
-        self.scan_rust_files_with_client(root, detector_registry, &mut result, client)
+        self.scan_rust_files_with_client(root, detector_registry, &mut result, client, None)
             .await;
 
         info!(
@@ -53,6 +151,144 @@ impl FileScanner {
         result
     }
 
+    /// Scan the workspace, reusing persisted results while no Rust file of it changed
+    /// The cache is all-or-nothing: detectors resolving names across files make every result
+    /// depend on all files, so editing, adding or removing any file re-analyzes all of them.
+    /// A cache written by a different registry version is discarded the same way
+    pub async fn scan_workspace_cached(
+        &self,
+        detector_registry: &mut DetectorRegistry,
+        cache_path: &Path,
+    ) -> ScanResult {
+        let Some(root) = &self.workspace_root else {
+            warn!("No workspace root set, skipping file scan");
+            return ScanResult::default();
+        };
+
+        let mut cache = ScanCache::load(cache_path).unwrap_or_default();
+        let mut result = ScanResult::default();
+
+        self.scan_rust_files_with_client(
+            root,
+            detector_registry,
+            &mut result,
+            None,
+            Some(&mut cache),
+        )
+        .await;
+
+        if let Err(e) = cache.save(cache_path) {
+            warn!("Failed to write scan cache {:?}: {}", cache_path, e);
+        }
+
+        result
+    }
+
+    /// List the workspace's Rust files, skipping dependency and build directories
+    pub fn rust_files(&self) -> Vec<PathBuf> {
+        self.workspace_root
+            .as_ref()
+            .and_then(|root| self.walk_directory(root, &["rs"]).ok())
+            .unwrap_or_default()
+    }
+
+    /// Read the workspace's Rust files once, for detectors resolving names across files
+    pub fn workspace_files(&self) -> Option<WorkspaceFiles> {
+        let root = self.workspace_root.as_ref()?;
+        Some(WorkspaceFiles::read(root.clone(), self.rust_files()))
+    }
+
+    /// Scan the workspace as it was at a revision (e.g. a git commit)
+    /// File contents come from `provider`, so the working tree is neither read nor modified
+    #[allow(dead_code)]
+    pub fn scan_revision(
+        &self,
+        provider: &dyn RevisionContentProvider,
+        revision: &str,
+        detector_registry: &mut DetectorRegistry,
+    ) -> anyhow::Result<ScanResult> {
+        let files = self.read_revision(provider, revision)?;
+        Ok(self.scan_revision_files(files, revision, detector_registry))
+    }
+
+    /// Read the Rust files of a revision, applying the same filters as a working tree scan
+    /// Providers may run subprocesses (e.g. git), so async callers run this on a blocking thread
+    pub fn read_revision(
+        &self,
+        provider: &dyn RevisionContentProvider,
+        revision: &str,
+    ) -> anyhow::Result<WorkspaceFiles> {
+        let Some(root) = &self.workspace_root else {
+            anyhow::bail!("No workspace root set");
+        };
+
+        info!("Reading revision {} of {:?}", revision, root);
+
+        let mut files = BTreeMap::new();
+        for relative_path in provider.list_files(revision)? {
+            if relative_path.extension().and_then(|e| e.to_str()) != Some("rs") {
+                continue;
+            }
+
+            // Apply the same directory filters as a working tree scan
+            if self.is_excluded_relative(&relative_path) {
+                continue;
+            }
+
+            match provider.read_file(revision, &relative_path) {
+                Ok(content) => {
+                    files.insert(root.join(&relative_path), content);
+                }
+                Err(e) => warn!("Failed to read {:?} at {}: {}", relative_path, revision, e),
+            }
+        }
+
+        Ok(WorkspaceFiles::from_contents(root.clone(), files))
+    }
+
+    /// Analyze the files of a revision read by `read_revision`
+    /// All files are read before the first is analyzed, so cross-file detectors see the same
+    /// revision
+    pub fn scan_revision_files(
+        &self,
+        files: WorkspaceFiles,
+        revision: &str,
+        detector_registry: &mut DetectorRegistry,
+    ) -> ScanResult {
+        let workspace = Arc::new(files);
+        let working_tree = detector_registry.workspace();
+        detector_registry.set_workspace(Some(workspace.clone()));
+
+        let mut result = ScanResult::default();
+        for (file_path, content) in workspace.files() {
+            if self.is_test_file(file_path) {
+                continue;
+            }
+
+            debug!("Analyzing {:?} at {}", file_path, revision);
+            let file_path = file_path.to_path_buf();
+            let diagnostics = detector_registry.analyze(content, Some(&file_path));
+            let is_anchor_program = self.is_anchor_program(content);
+
+            result.rust_files.push(RustFileInfo {
+                path: file_path,
+                diagnostics,
+                is_anchor_program,
+            });
+        }
+
+        // Later single-file analysis resolves names against the working tree again
+        detector_registry.set_workspace(working_tree);
+
+        info!(
+            "Revision scan completed. Found {} Rust files ({} with issues)",
+            result.rust_files.len(),
+            result.files_with_issues().len()
+        );
+
+        result
+    }
+
     /// Scan for Rust files and analyze them with optional progress notifications
     /// Only scans .rs files within the workspace, excluding external dependencies
     async fn scan_rust_files_with_client(
@@ -60,23 +296,80 @@ impl FileScanner {
         root: &Path,
         detector_registry: &mut DetectorRegistry,
         result: &mut ScanResult,
-        _client: Option<&Client>,
+        client: Option<&Client>,
+        mut cache: Option<&mut ScanCache>,
     ) {
         // Only scan .rs files (Rust source files), excluding test files
         if let Ok(entries) = self.walk_directory(root, &["rs"]) {
-            for file_path in entries {
+            // Drop cache entries for files that no longer exist
+            if let Some(cache) = cache.as_deref_mut() {
+                cache.files.retain(|path, _| entries.contains(path));
+            }
+
+            // Read every file once; detectors resolving names across files share the contents
+            let workspace = Arc::new(WorkspaceFiles::read(root.to_path_buf(), entries.clone()));
+            detector_registry.set_workspace(Some(workspace.clone()));
+            let workspace_hash = workspace.content_hash();
+
+            let total_files = entries.len();
+            let mut progress = match client {
+                Some(client) => Some(
+                    WorkDoneProgressReporter::begin(
+                        client,
+                        "Scanning workspace",
+                        Some(format!("0/{} files", total_files)),
+                    )
+                    .await,
+                ),
+                None => None,
+            };
+
+            for (index, file_path) in entries.into_iter().enumerate() {
+                if self.is_cancelled() {
+                    info!(
+                        "Workspace scan cancelled after {}/{} files",
+                        index, total_files
+                    );
+                    result.cancelled = true;
+                    break;
+                }
+
+                if let Some(progress) = progress.as_mut() {
+                    progress
+                        .report(
+                            index,
+                            total_files,
+                            Some(format!("{}/{} files", index, total_files)),
+                        )
+                        .await;
+                }
+
                 // Skip dedicated test files (in tests/ directories or with test in filename)
                 if self.is_test_file(&file_path) {
                     debug!("Skipping test file: {:?}", file_path);
                     continue;
                 }
 
-                if let Ok(content) = fs::read_to_string(&file_path) {
+                if let Some(content) = workspace.content(&file_path) {
+                    // Reuse cached results if the file and the rest of the workspace are unchanged
+                    if let Some(cached) = cache
+                        .as_deref()
+                        .and_then(|cache| cache.get(&file_path, content, workspace_hash))
+                    {
+                        debug!("Using cached results for: {:?}", file_path);
+                        result.rust_files.push(RustFileInfo {
+                            path: file_path,
+                            diagnostics: cached.diagnostics.clone(),
+                            is_anchor_program: cached.is_anchor_program,
+                        });
+                        continue;
+                    }
+
                     debug!("Analyzing Rust file: {:?}", file_path);
 
                     // Run security analysis on Rust source code
                     // Detectors will naturally skip test modules (#[cfg(test)]) during AST analysis
-                    let diagnostics = detector_registry.analyze(&content, Some(&file_path));
+                    let diagnostics = detector_registry.analyze(content, Some(&file_path));
 
                     if !diagnostics.is_empty() {
                         info!(
@@ -86,7 +379,17 @@ impl FileScanner {
                         );
                     }
 
-                    let is_anchor_program = self.is_anchor_program(&content);
+                    let is_anchor_program = self.is_anchor_program(content);
+
+                    if let Some(cache) = cache.as_deref_mut() {
+                        cache.insert(
+                            file_path.clone(),
+                            content,
+                            workspace_hash,
+                            diagnostics.clone(),
+                            is_anchor_program,
+                        );
+                    }
 
                     result.rust_files.push(RustFileInfo {
                         path: file_path,
@@ -97,6 +400,12 @@ impl FileScanner {
                     warn!("Failed to read file: {:?}", file_path);
                 }
             }
+
+            if let Some(progress) = progress {
+                progress
+                    .end(Some(format!("Scanned {} files", total_files)))
+                    .await;
+            }
         }
     }
 
@@ -112,7 +421,6 @@ impl FileScanner {
     }
 
     /// Recursive helper for walking directories
-    #[allow(clippy::only_used_in_recursion)]
     fn walk_directory_recursive(
         &self,
         dir: &Path,
@@ -129,14 +437,15 @@ impl FileScanner {
 
             if path.is_dir() {
                 // Skip directories containing external dependencies and build artifacts
-                if self.should_skip_directory(&path) {
+                if self.should_skip_directory(&path) || self.is_ignored(&path, true) {
                     continue;
                 }
                 self.walk_directory_recursive(&path, extensions, files)?;
-            } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                if extensions.contains(&ext) {
-                    files.push(path);
-                }
+            } else if let Some(ext) = path.extension().and_then(|e| e.to_str())
+                && extensions.contains(&ext)
+                && !self.is_ignored(&path, false)
+            {
+                files.push(path);
             }
         }
 
@@ -154,7 +463,12 @@ impl FileScanner {
     /// Check if a file is a dedicated test file based on path
     /// Files with test modules inside production files will still be analyzed
     /// (detectors will naturally skip test modules during AST analysis)
+    /// Always false when test scanning is enabled
     fn is_test_file(&self, path: &Path) -> bool {
+        if self.scan_tests {
+            return false;
+        }
+
         let path_str = path.to_string_lossy();
 
         // Check if in tests/ directory (dedicated test files)
@@ -185,13 +499,16 @@ impl FileScanner {
             return true;
         }
 
+        // Test directories (skip all test code unless requested)
+        if !self.scan_tests && matches!(dir_name, "tests" | "test" | "trident-tests") {
+            return true;
+        }
+
         // Skip known directories containing external dependencies or build artifacts
         matches!(
             dir_name,
             // Rust build artifacts and dependencies
             "target" | "debug" | "release" | "deps" | "build" | "incremental" |
-            // Test directories (skip all test code)
-            "tests" | "test" | "trident-tests" |
             // JavaScript/TypeScript dependencies and build outputs
             "node_modules" | "dist" | "out" | "coverage" | ".nyc_output" |
             // Version control
@@ -1,11 +1,14 @@
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tower_lsp::lsp_types::Diagnostic;
 
 /// Result of workspace scanning
 /// Only Rust (.rs) files are scanned for security issues
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ScanResult {
     pub rust_files: Vec<RustFileInfo>,
+    /// The scan was cancelled before every file was analyzed
+    pub cancelled: bool,
 }
 
 impl ScanResult {
@@ -33,7 +36,7 @@ impl ScanResult {
 
 /// Information about a scanned Rust file
 /// Test files are excluded from scanning
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RustFileInfo {
     pub path: PathBuf,
     pub diagnostics: Vec<Diagnostic>,
@@ -0,0 +1,280 @@
+use crate::core::utilities::PositionEncoding;
+use std::collections::HashMap;
+use syn::{parse_str, visit::Visit};
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, Diagnostic, NumberOrString, Position, Range, TextEdit, Url,
+    WorkspaceEdit,
+};
+
+/// Diagnostic code of the sysvar account detector
+const SYSVAR_DIAGNOSTIC_CODE: &str = "INEFFICIENT_SYSVAR_ACCOUNT";
+
+/// Diagnostic code of the missing `/// CHECK:` comment detector
+const CHECK_COMMENT_DIAGNOSTIC_CODE: &str = "MISSING_CHECK_COMMENT";
+
+/// Placeholder inserted for unchecked accounts, to be replaced with the actual reasoning
+const CHECK_COMMENT_PLACEHOLDER: &str = "/// CHECK: TODO explain why this is safe";
+
+/// Builds quick fixes for detector diagnostics
+pub struct CodeActionProvider;
+
+impl CodeActionProvider {
+    /// Compute code actions for the given diagnostics in a document
+    /// Diagnostic ranges and the returned edits count columns in the negotiated `encoding`
+    pub fn code_actions(
+        content: &str,
+        uri: &Url,
+        diagnostics: &[Diagnostic],
+        encoding: PositionEncoding,
+    ) -> Vec<CodeAction> {
+        diagnostics
+            .iter()
+            .flat_map(|diagnostic| match &diagnostic.code {
+                Some(NumberOrString::String(code)) if code == SYSVAR_DIAGNOSTIC_CODE => {
+                    Self::sysvar_field_actions(content, uri, diagnostic, encoding)
+                }
+                Some(NumberOrString::String(code)) if code == CHECK_COMMENT_DIAGNOSTIC_CODE => {
+                    vec![Self::check_comment_action(
+                        content, uri, diagnostic, encoding,
+                    )]
+                }
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Quick fix inserting a `/// CHECK:` placeholder above an unchecked account field
+    fn check_comment_action(
+        content: &str,
+        uri: &Url,
+        diagnostic: &Diagnostic,
+        encoding: PositionEncoding,
+    ) -> CodeAction {
+        let start = diagnostic.range.start;
+        let line = content.lines().nth(start.line as usize).unwrap_or_default();
+        let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+
+        // A field sharing its line with other code gets the comment on a line of its own
+        let edit = if Self::before(line, start.character, encoding)
+            .trim()
+            .is_empty()
+        {
+            TextEdit {
+                range: Range::new(Position::new(start.line, 0), Position::new(start.line, 0)),
+                new_text: format!("{}{}\n", indent, CHECK_COMMENT_PLACEHOLDER),
+            }
+        } else {
+            TextEdit {
+                range: Range::new(start, start),
+                new_text: format!("{}\n{}", CHECK_COMMENT_PLACEHOLDER, indent),
+            }
+        };
+
+        Self::quick_fix(
+            "Add `/// CHECK:` comment".to_string(),
+            uri,
+            vec![edit],
+            diagnostic,
+            true,
+        )
+    }
+
+    /// Quick fixes replacing a `Sysvar<'info, T>` field with `T::get()?`
+    ///
+    /// The first action only removes the field; if handlers read the field through
+    /// `ctx.accounts`, a second action also inserts a reminder comment in each of them
+    fn sysvar_field_actions(
+        content: &str,
+        uri: &Url,
+        diagnostic: &Diagnostic,
+        encoding: PositionEncoding,
+    ) -> Vec<CodeAction> {
+        let Some(data) = &diagnostic.data else {
+            return Vec::new();
+        };
+        let (Some(sysvar_type), Some(field_name)) = (
+            data.get("sysvar_type").and_then(|value| value.as_str()),
+            data.get("field_name").and_then(|value| value.as_str()),
+        ) else {
+            return Vec::new();
+        };
+
+        let removal = TextEdit {
+            range: Self::field_removal_range(content, diagnostic.range, encoding),
+            new_text: String::new(),
+        };
+
+        let mut actions = vec![Self::quick_fix(
+            format!("Remove `{}` and use `{}::get()?`", field_name, sysvar_type),
+            uri,
+            vec![removal.clone()],
+            diagnostic,
+            true,
+        )];
+
+        let reminders = Self::handler_reminders(content, field_name, sysvar_type);
+        if !reminders.is_empty() {
+            let mut edits = vec![removal];
+            edits.extend(reminders);
+            actions.push(Self::quick_fix(
+                format!(
+                    "Remove `{}` and add `{}::get()?` reminders to handlers",
+                    field_name, sysvar_type
+                ),
+                uri,
+                edits,
+                diagnostic,
+                false,
+            ));
+        }
+
+        actions
+    }
+
+    fn quick_fix(
+        title: String,
+        uri: &Url,
+        edits: Vec<TextEdit>,
+        diagnostic: &Diagnostic,
+        is_preferred: bool,
+    ) -> CodeAction {
+        CodeAction {
+            title,
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(vec![diagnostic.clone()]),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(uri.clone(), edits)])),
+                ..Default::default()
+            }),
+            is_preferred: Some(is_preferred),
+            ..Default::default()
+        }
+    }
+
+    /// Extend a field range so removing it also removes its trailing comma and,
+    /// if the field occupies whole lines, the lines themselves
+    pub fn field_removal_range(content: &str, range: Range, encoding: PositionEncoding) -> Range {
+        let lines: Vec<&str> = content.lines().collect();
+        let line_text = |line: u32| lines.get(line as usize).copied().unwrap_or_default();
+
+        let mut start = range.start;
+        let mut end = range.end;
+
+        // Swallow whitespace and the trailing comma after the field
+        let end_line = line_text(end.line);
+        let rest = Self::after(end_line, end.character, encoding);
+        let trimmed = rest.trim_start();
+        if let Some(after_comma) = trimmed.strip_prefix(',') {
+            let consumed = rest.len() - after_comma.len();
+            end.character += encoding.len(&rest[..consumed]) as u32;
+        }
+
+        // Remove whole lines when nothing else shares them
+        let before = Self::before(line_text(start.line), start.character, encoding);
+        let after = Self::after(line_text(end.line), end.character, encoding);
+        if before.trim().is_empty() && after.trim().is_empty() {
+            start = Position::new(start.line, 0);
+            end = Position::new(end.line + 1, 0);
+        }
+
+        Range::new(start, end)
+    }
+
+    /// Insert a reminder at the top of every function reading `ctx.accounts.<field>`
+    fn handler_reminders(content: &str, field_name: &str, sysvar_type: &str) -> Vec<TextEdit> {
+        let Ok(syntax_tree) = parse_str::<syn::File>(content) else {
+            return Vec::new();
+        };
+
+        let mut finder = HandlerFinder {
+            field_name,
+            open_brace_lines: Vec::new(),
+        };
+        finder.visit_file(&syntax_tree);
+
+        let lines: Vec<&str> = content.lines().collect();
+        finder
+            .open_brace_lines
+            .into_iter()
+            .map(|line| {
+                // Insert on the line after the opening brace, matching its indentation
+                let next_line = lines.get(line).copied().unwrap_or_default();
+                let indent: String = next_line
+                    .chars()
+                    .take_while(|c| c.is_whitespace())
+                    .collect();
+
+                TextEdit {
+                    range: Range::new(Position::new(line as u32, 0), Position::new(line as u32, 0)),
+                    new_text: format!(
+                        "{}// TODO: replace ctx.accounts.{} with `let {} = {}::get()?;`\n",
+                        indent, field_name, field_name, sysvar_type
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Part of a line before a column counted in `encoding` code units
+    fn before(line: &str, character: u32, encoding: PositionEncoding) -> &str {
+        &line[..encoding.byte_offset(line, character)]
+    }
+
+    /// Part of a line from a column counted in `encoding` code units
+    fn after(line: &str, character: u32, encoding: PositionEncoding) -> &str {
+        &line[encoding.byte_offset(line, character)..]
+    }
+}
+
+/// Finds functions whose body reads `ctx.accounts.<field>`
+struct HandlerFinder<'a> {
+    field_name: &'a str,
+    /// 1-based lines of the opening braces of matching function bodies
+    open_brace_lines: Vec<usize>,
+}
+
+impl HandlerFinder<'_> {
+    fn check_block(&mut self, block: &syn::Block) {
+        let mut reads = FieldReadFinder {
+            field_name: self.field_name,
+            found: false,
+        };
+        reads.visit_block(block);
+
+        if reads.found {
+            self.open_brace_lines
+                .push(block.brace_token.span.open().start().line);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for HandlerFinder<'_> {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.check_block(&node.block);
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.check_block(&node.block);
+        syn::visit::visit_impl_item_fn(self, node);
+    }
+}
+
+/// Finds `<..>.accounts.<field>` expressions
+struct FieldReadFinder<'a> {
+    field_name: &'a str,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for FieldReadFinder<'_> {
+    fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+        if let syn::Member::Named(ident) = &node.member
+            && ident == self.field_name
+            && let syn::Expr::Field(base) = &*node.base
+            && matches!(&base.member, syn::Member::Named(base_ident) if base_ident == "accounts")
+        {
+            self.found = true;
+        }
+        syn::visit::visit_expr_field(self, node);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::core::utilities::DiagnosticBuilder;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::str::FromStr;
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, Documentation, InsertTextFormat, MarkupContent, MarkupKind,
+    Position,
+};
+
+/// `#[account(...)]` constraint keywords: label, snippet inserted, documentation
+const ACCOUNT_CONSTRAINTS: &[(&str, &str, &str)] = &[
+    (
+        "mut",
+        "mut",
+        "Marks the account writable and persists changes at the end of the instruction.",
+    ),
+    (
+        "init",
+        "init",
+        "Creates the account via a System Program CPI. Requires `payer` and `space`.",
+    ),
+    (
+        "init_if_needed",
+        "init_if_needed",
+        "Like `init`, but skips creation if the account already exists. Guard against reinitialization in the handler.",
+    ),
+    (
+        "seeds",
+        "seeds = [$0]",
+        "Checks that the account is the PDA derived from these seeds and the program ID. Use together with `bump`.",
+    ),
+    (
+        "bump",
+        "bump",
+        "Bump of the PDA given by `seeds`. Without a value Anchor finds the canonical bump.",
+    ),
+    (
+        "has_one",
+        "has_one = $0",
+        "Checks that the account's field of this name equals the key of the account of the same name.",
+    ),
+    (
+        "close",
+        "close = $0",
+        "Closes the account at the end of the instruction and sends its lamports to the given account.",
+    ),
+    (
+        "payer",
+        "payer = $0",
+        "Account that pays rent for `init`. Must be a mutable signer.",
+    ),
+    (
+        "space",
+        "space = $0",
+        "Number of bytes to allocate for `init`, including the 8-byte discriminator.",
+    ),
+    (
+        "realloc",
+        "realloc = $0, realloc::payer = $1, realloc::zero = ${2:false}",
+        "Resizes the account to the given number of bytes.",
+    ),
+    (
+        "constraint",
+        "constraint = $0",
+        "Custom check that must evaluate to true. Add `@ ErrorCode::X` for a custom error.",
+    ),
+    (
+        "owner",
+        "owner = $0",
+        "Checks that the account is owned by the given program.",
+    ),
+    (
+        "address",
+        "address = $0",
+        "Checks that the account key equals the given public key.",
+    ),
+    (
+        "token::mint",
+        "token::mint = $0",
+        "Checks (or sets on `init`) the mint of the token account.",
+    ),
+    (
+        "associated_token::mint",
+        "associated_token::mint = $0",
+        "Checks that the account is the associated token account for this mint.",
+    ),
+];
+
+/// Completes Anchor constraint keywords inside `#[account(...)]`
+pub struct CompletionProvider;
+
+impl CompletionProvider {
+    /// Completion items for the position, if it is where a constraint keyword goes
+    ///
+    /// Works on tokens rather than a parsed file, so a document that is mid-edit elsewhere
+    /// still gets completions as long as its delimiters are balanced.
+    pub fn completions(content: &str, position: Position) -> Option<Vec<CompletionItem>> {
+        let tokens = TokenStream::from_str(content).ok()?;
+        if !Self::at_constraint_key(tokens, position) {
+            return None;
+        }
+
+        Some(
+            ACCOUNT_CONSTRAINTS
+                .iter()
+                .map(|(label, snippet, documentation)| CompletionItem {
+                    label: label.to_string(),
+                    kind: Some(CompletionItemKind::KEYWORD),
+                    detail: Some("Anchor account constraint".to_string()),
+                    documentation: Some(Documentation::MarkupContent(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: documentation.to_string(),
+                    })),
+                    insert_text: Some(snippet.to_string()),
+                    insert_text_format: Some(InsertTextFormat::SNIPPET),
+                    ..CompletionItem::default()
+                })
+                .collect(),
+        )
+    }
+
+    /// Check if the position is inside the parentheses of an `account` attribute, at the
+    /// start of a constraint rather than in a value (`payer = |`)
+    fn at_constraint_key(tokens: TokenStream, position: Position) -> bool {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+
+        for (index, token) in tokens.iter().enumerate() {
+            let TokenTree::Group(group) = token else {
+                continue;
+            };
+
+            // `#[account(...)]`: a bracket group after `#` holding `account` and a paren group
+            let after_pound = index > 0
+                && matches!(&tokens[index - 1], TokenTree::Punct(punct) if punct.as_char() == '#');
+            if after_pound && group.delimiter() == Delimiter::Bracket {
+                let mut attr = group.stream().into_iter();
+                if let (Some(TokenTree::Ident(name)), Some(TokenTree::Group(args))) =
+                    (attr.next(), attr.next())
+                    && name == "account"
+                    && args.delimiter() == Delimiter::Parenthesis
+                    && Self::contains(&args, position)
+                {
+                    return Self::in_key_position(args.stream(), position);
+                }
+            }
+
+            if Self::contains(group, position) {
+                return Self::at_constraint_key(group.stream(), position);
+            }
+        }
+
+        false
+    }
+
+    /// Check if the position lies between a group's delimiters
+    fn contains(group: &proc_macro2::Group, position: Position) -> bool {
+        let open = DiagnosticBuilder::create_range_from_span(group.span_open()).end;
+        let close = DiagnosticBuilder::create_range_from_span(group.span_close()).start;
+        open <= position && position <= close
+    }
+
+    /// Check if no `=` precedes the position within its comma-separated constraint
+    fn in_key_position(args: TokenStream, position: Position) -> bool {
+        let mut in_value = false;
+        for token in args {
+            if DiagnosticBuilder::create_range_from_span(token.span()).start >= position {
+                break;
+            }
+            if let TokenTree::Punct(punct) = &token {
+                match punct.as_char() {
+                    ',' => in_value = false,
+                    '=' => in_value = true,
+                    _ => {}
+                }
+            }
+        }
+        !in_value
+    }
+}
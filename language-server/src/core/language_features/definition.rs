@@ -0,0 +1,130 @@
+use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{parse_str, visit::Visit};
+use tower_lsp::lsp_types::{Position, Range};
+
+/// Resolves go-to-definition requests for Anchor types
+///
+/// Parsed files are cached for the lifetime of the provider, so create one per request
+#[derive(Default)]
+pub struct DefinitionProvider {
+    parsed: HashMap<PathBuf, Option<syn::File>>,
+}
+
+impl DefinitionProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the accounts struct name under the cursor if it is the `Foo` in `Context<Foo>`
+    pub fn context_accounts_at(content: &str, position: Position) -> Option<String> {
+        let syntax_tree = parse_str::<syn::File>(content).ok()?;
+
+        let mut finder = ContextTypeFinder {
+            position,
+            found: None,
+        };
+        finder.visit_file(&syntax_tree);
+        finder.found
+    }
+
+    /// Find the `#[derive(Accounts)]` struct referenced by `Context<Foo>` at the position
+    ///
+    /// The current document is searched first (using its in-memory content), then the
+    /// given workspace files. Returns the file and the range of the struct name.
+    pub fn find_definition(
+        &mut self,
+        current_path: &Path,
+        content: &str,
+        position: Position,
+        workspace_files: &[PathBuf],
+    ) -> Option<(PathBuf, Range)> {
+        let name = Self::context_accounts_at(content, position)?;
+
+        self.parsed.insert(
+            current_path.to_path_buf(),
+            parse_str::<syn::File>(content).ok(),
+        );
+
+        std::iter::once(current_path)
+            .chain(workspace_files.iter().map(PathBuf::as_path))
+            .find_map(|path| {
+                self.accounts_struct_range(path, &name)
+                    .map(|range| (path.to_path_buf(), range))
+            })
+    }
+
+    /// Range of the accounts struct with the given name in a file, if it defines one
+    fn accounts_struct_range(&mut self, path: &Path, name: &str) -> Option<Range> {
+        let syntax_tree = self
+            .parsed
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| parse_str::<syn::File>(&content).ok())
+            })
+            .as_ref()?;
+
+        let mut finder = AccountsStructFinder { name, found: None };
+        finder.visit_file(syntax_tree);
+        finder.found
+    }
+}
+
+/// Finds the `Foo` of a `Context<Foo>` type containing a position
+struct ContextTypeFinder {
+    position: Position,
+    found: Option<String>,
+}
+
+impl ContextTypeFinder {
+    fn contains(range: &Range, position: Position) -> bool {
+        range.start <= position && position <= range.end
+    }
+}
+
+impl<'ast> Visit<'ast> for ContextTypeFinder {
+    fn visit_path_segment(&mut self, node: &'ast syn::PathSegment) {
+        if node.ident == "Context"
+            && let syn::PathArguments::AngleBracketed(args) = &node.arguments
+            // `Context<'_, '_, '_, 'info, Foo<'info>>`: the accounts type is the last type argument
+            && let Some(syn::GenericArgument::Type(syn::Type::Path(type_path))) = args
+                .args
+                .iter()
+                .rfind(|arg| matches!(arg, syn::GenericArgument::Type(_)))
+            && let Some(segment) = type_path.path.segments.last()
+            && Self::contains(
+                &DiagnosticBuilder::create_range_from_span(segment.ident.span()),
+                self.position,
+            )
+        {
+            self.found = Some(segment.ident.to_string());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_path_segment(self, node);
+    }
+}
+
+/// Finds a `#[derive(Accounts)]` struct by name
+struct AccountsStructFinder<'a> {
+    name: &'a str,
+    found: Option<Range>,
+}
+
+impl<'ast> Visit<'ast> for AccountsStructFinder<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if self.found.is_none()
+            && node.ident == self.name
+            && AnchorPatterns::is_accounts_struct(node)
+        {
+            self.found = Some(DiagnosticBuilder::create_range_from_span(node.ident.span()));
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
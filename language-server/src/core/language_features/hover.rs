@@ -0,0 +1,65 @@
+use crate::core::registry::DetectorRegistry;
+use tower_lsp::lsp_types::{
+    Diagnostic, Hover, HoverContents, MarkupContent, MarkupKind, NumberOrString, Position,
+};
+
+/// Explains published diagnostics on hover
+pub struct HoverProvider;
+
+impl HoverProvider {
+    /// Build a hover for the diagnostics at `position`
+    ///
+    /// For every diagnostic overlapping the position whose code is a registered detector,
+    /// shows the detector's description and rationale. The rationale is left out when it is
+    /// just the diagnostic message, which the editor already shows. Diagnostics from other
+    /// sources (e.g. dylint lints) are skipped.
+    pub fn hover(
+        diagnostics: &[Diagnostic],
+        position: Position,
+        registry: &DetectorRegistry,
+    ) -> Option<Hover> {
+        let explained: Vec<(&Diagnostic, String)> = diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic.range.start <= position && position <= diagnostic.range.end
+            })
+            .filter_map(|diagnostic| {
+                let Some(NumberOrString::String(code)) = &diagnostic.code else {
+                    return None;
+                };
+                let detector = registry.detector(code)?;
+
+                let mut explanation = format!(
+                    "**{}** (`{}`)\n\n{}",
+                    detector.name(),
+                    detector.id(),
+                    detector.description()
+                );
+                if detector.rationale() != detector.message() {
+                    explanation.push_str(&format!(
+                        "\n\n**Why this matters:** {}",
+                        detector.rationale()
+                    ));
+                }
+
+                Some((diagnostic, explanation))
+            })
+            .collect();
+
+        // Highlight the first explained diagnostic, the one the cursor most likely targets
+        let range = explained.first()?.0.range;
+        let value = explained
+            .into_iter()
+            .map(|(_, explanation)| explanation)
+            .collect::<Vec<_>>()
+            .join("\n\n---\n\n");
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(range),
+        })
+    }
+}
@@ -0,0 +1,155 @@
+use crate::core::utilities::{DiagnosticBuilder, SizeEstimator, anchor_patterns::AnchorPatterns};
+use proc_macro2::TokenStream;
+use std::collections::HashMap;
+use syn::{BinOp, Expr, Fields, parse_str, visit::Visit};
+use tower_lsp::lsp_types::{InlayHint, InlayHintLabel, InlayHintTooltip};
+
+/// Size of the Anchor account discriminator
+const DISCRIMINATOR_SIZE: usize = 8;
+
+/// Builds inlay hints for Anchor account definitions
+pub struct InlayHintProvider;
+
+impl InlayHintProvider {
+    /// Compute inlay hints for a document
+    ///
+    /// For each `space = ...` of an `init`/`init_if_needed` field, shows the size of the
+    /// target `#[account]` struct (discriminator plus fields) next to the value, and
+    /// flags a mismatch when the value is a constant expression
+    pub fn inlay_hints(content: &str) -> Vec<InlayHint> {
+        let Ok(syntax_tree) = parse_str::<syn::File>(content) else {
+            return Vec::new();
+        };
+
+        let mut collector = AccountSizeCollector::default();
+        collector.visit_file(&syntax_tree);
+
+        let mut finder = SpaceConstraintFinder {
+            account_sizes: &collector.sizes,
+            hints: Vec::new(),
+        };
+        finder.visit_file(&syntax_tree);
+        finder.hints
+    }
+
+    /// Build the hint for a `space` value given the computed data size
+    fn space_hint(value: &TokenStream, data_size: usize) -> Option<InlayHint> {
+        let last_token = value.clone().into_iter().last()?;
+        let position = DiagnosticBuilder::create_range_from_span(last_token.span()).end;
+
+        let computed = DISCRIMINATOR_SIZE + data_size;
+        let declared = syn::parse2::<Expr>(value.clone())
+            .ok()
+            .and_then(|expr| Self::evaluate(&expr));
+
+        let (label, tooltip) = match declared {
+            Some(declared) if declared != computed => (
+                format!(
+                    "⚠ computed {} + {} = {} bytes",
+                    DISCRIMINATOR_SIZE, data_size, computed
+                ),
+                format!(
+                    "space is {} bytes, but the account needs {} (8-byte discriminator + {} bytes of fields)",
+                    declared, computed, data_size
+                ),
+            ),
+            _ => (
+                format!(
+                    "{} + {} = {} bytes",
+                    DISCRIMINATOR_SIZE, data_size, computed
+                ),
+                format!("8-byte discriminator + {} bytes of fields", data_size),
+            ),
+        };
+
+        Some(InlayHint {
+            position,
+            label: InlayHintLabel::String(label),
+            kind: None,
+            text_edits: None,
+            tooltip: Some(InlayHintTooltip::String(tooltip)),
+            padding_left: Some(true),
+            padding_right: None,
+            data: None,
+        })
+    }
+
+    /// Evaluate a constant integer expression (`8 + 4 * 10`)
+    fn evaluate(expr: &Expr) -> Option<usize> {
+        match expr {
+            Expr::Lit(expr_lit) => match &expr_lit.lit {
+                syn::Lit::Int(lit_int) => lit_int.base10_parse().ok(),
+                _ => None,
+            },
+            Expr::Paren(paren) => Self::evaluate(&paren.expr),
+            Expr::Group(group) => Self::evaluate(&group.expr),
+            Expr::Binary(binary) => {
+                let left = Self::evaluate(&binary.left)?;
+                let right = Self::evaluate(&binary.right)?;
+                match binary.op {
+                    BinOp::Add(_) => left.checked_add(right),
+                    BinOp::Sub(_) => left.checked_sub(right),
+                    BinOp::Mul(_) => left.checked_mul(right),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Collects the field size of every `#[account]` struct with a fully known layout
+#[derive(Default)]
+struct AccountSizeCollector {
+    sizes: HashMap<String, usize>,
+}
+
+impl<'ast> Visit<'ast> for AccountSizeCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_account_struct(node)
+            && let Some(size) = SizeEstimator::exact_fields_size(&node.fields)
+        {
+            self.sizes.insert(node.ident.to_string(), size);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+/// Finds `space = ...` constraints on initialized accounts
+struct SpaceConstraintFinder<'a> {
+    account_sizes: &'a HashMap<String, usize>,
+    hints: Vec<InlayHint>,
+}
+
+impl<'ast> Visit<'ast> for SpaceConstraintFinder<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if !AnchorPatterns::is_accounts_struct(node) {
+            return;
+        }
+
+        if let Fields::Named(fields) = &node.fields {
+            for field in &fields.named {
+                let is_init = AnchorPatterns::has_constraint(field, "init")
+                    || AnchorPatterns::has_constraint(field, "init_if_needed");
+                let Some(data_size) = AnchorPatterns::account_data_type_name(field)
+                    .and_then(|name| self.account_sizes.get(&name))
+                    .filter(|_| is_init)
+                else {
+                    continue;
+                };
+
+                let hint = AnchorPatterns::parse_account_constraints(field)
+                    .into_iter()
+                    .find(|constraint| constraint.key == "space")
+                    .and_then(|constraint| constraint.value)
+                    .and_then(|value| InlayHintProvider::space_hint(&value, *data_size));
+                self.hints.extend(hint);
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
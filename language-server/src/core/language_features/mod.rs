@@ -0,0 +1,19 @@
+pub mod code_actions;
+pub mod completion;
+pub mod definition;
+pub mod hover;
+pub mod inlay_hints;
+pub mod rename;
+pub mod selection_range;
+pub mod semantic_tokens;
+pub mod workspace_symbols;
+
+pub use code_actions::*;
+pub use completion::*;
+pub use definition::*;
+pub use hover::*;
+pub use inlay_hints::*;
+pub use rename::*;
+pub use selection_range::*;
+pub use semantic_tokens::*;
+pub use workspace_symbols::*;
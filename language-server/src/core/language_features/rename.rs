@@ -0,0 +1,211 @@
+use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::{parse_str, visit::Visit};
+use tower_lsp::lsp_types::{Position, Range, TextEdit};
+
+/// Where an accounts struct name occurs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReferenceKind {
+    /// `#[derive(Accounts)] pub struct Foo`
+    Definition,
+    /// `Context<Foo>` in a handler signature
+    Context,
+    /// A composite field `pub inner: Foo<'info>` or `impl Foo<'info>`
+    TypeUse,
+}
+
+/// An identifier that may refer to an accounts struct
+#[derive(Debug, Clone)]
+struct Reference {
+    name: String,
+    range: Range,
+    kind: ReferenceKind,
+}
+
+/// Renames `#[derive(Accounts)]` structs across the workspace
+///
+/// Parsed files are cached for the lifetime of the provider, so create one per request
+#[derive(Default)]
+pub struct RenameProvider {
+    references: HashMap<PathBuf, Vec<Reference>>,
+}
+
+impl RenameProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the range and name of the renameable identifier at the position
+    ///
+    /// Renameable are an accounts struct definition, the `Foo` of `Context<Foo>`, and
+    /// uses of an accounts struct defined in the same document
+    pub fn prepare_rename(content: &str, position: Position) -> Option<(Range, String)> {
+        let references = Self::collect_references(content);
+        let defined = |name: &str| {
+            references.iter().any(|reference| {
+                reference.kind == ReferenceKind::Definition && reference.name == name
+            })
+        };
+
+        references
+            .iter()
+            .find(|reference| {
+                reference.range.start <= position
+                    && position <= reference.range.end
+                    && (reference.kind != ReferenceKind::TypeUse || defined(&reference.name))
+            })
+            .map(|reference| (reference.range, reference.name.clone()))
+    }
+
+    /// Compute the edits renaming the accounts struct at the position to `new_name`
+    ///
+    /// The current document is read from `content`, other workspace files from disk.
+    /// Returns `None` if the position is not renameable or `new_name` is not an identifier.
+    pub fn rename(
+        &mut self,
+        current_path: &Path,
+        content: &str,
+        position: Position,
+        new_name: &str,
+        workspace_files: &[PathBuf],
+    ) -> Option<HashMap<PathBuf, Vec<TextEdit>>> {
+        parse_str::<syn::Ident>(new_name).ok()?;
+        let (_, old_name) = Self::prepare_rename(content, position)?;
+
+        self.references.insert(
+            current_path.to_path_buf(),
+            Self::collect_references(content),
+        );
+
+        // The walked copy of the current document (possibly under a canonical path) is skipped
+        let current_canonical = fs::canonicalize(current_path).ok();
+        let is_current = |path: &Path| {
+            path == current_path
+                || current_canonical.is_some() && fs::canonicalize(path).ok() == current_canonical
+        };
+
+        let mut changes = HashMap::new();
+        for path in std::iter::once(current_path).chain(
+            workspace_files
+                .iter()
+                .map(PathBuf::as_path)
+                .filter(|path| !is_current(path)),
+        ) {
+            let edits: Vec<TextEdit> = self
+                .file_references(path)
+                .iter()
+                .filter(|reference| reference.name == old_name)
+                .map(|reference| TextEdit {
+                    range: reference.range,
+                    new_text: new_name.to_string(),
+                })
+                .collect();
+
+            if !edits.is_empty() {
+                changes.insert(path.to_path_buf(), edits);
+            }
+        }
+
+        Some(changes)
+    }
+
+    /// References in a file, parsing it on first use
+    fn file_references(&mut self, path: &Path) -> &[Reference] {
+        self.references
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                fs::read_to_string(path)
+                    .map(|content| Self::collect_references(&content))
+                    .unwrap_or_default()
+            })
+    }
+
+    fn collect_references(content: &str) -> Vec<Reference> {
+        let Ok(syntax_tree) = parse_str::<syn::File>(content) else {
+            return Vec::new();
+        };
+
+        let mut collector = ReferenceCollector::default();
+        collector.visit_file(&syntax_tree);
+        collector.references
+    }
+}
+
+/// Collects identifiers that may refer to accounts structs
+#[derive(Default)]
+struct ReferenceCollector {
+    references: Vec<Reference>,
+}
+
+impl ReferenceCollector {
+    fn push(&mut self, ident: &syn::Ident, kind: ReferenceKind) {
+        self.references.push(Reference {
+            name: ident.to_string(),
+            range: DiagnosticBuilder::create_range_from_span(ident.span()),
+            kind,
+        });
+    }
+
+    /// Last path segment of a type, unwrapping `Box<T>`
+    fn type_ident(ty: &syn::Type) -> Option<&syn::Ident> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+
+        if segment.ident == "Box"
+            && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+            && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
+        {
+            return Self::type_ident(inner_ty);
+        }
+
+        Some(&segment.ident)
+    }
+}
+
+impl<'ast> Visit<'ast> for ReferenceCollector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) {
+            self.push(&node.ident, ReferenceKind::Definition);
+
+            // Composite accounts: fields typed with another accounts struct
+            for field in &node.fields {
+                if let Some(ident) = Self::type_ident(&field.ty) {
+                    self.push(ident, ReferenceKind::TypeUse);
+                }
+            }
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if let Some(ident) = Self::type_ident(&node.self_ty) {
+            self.push(ident, ReferenceKind::TypeUse);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_impl(self, node);
+    }
+
+    fn visit_path_segment(&mut self, node: &'ast syn::PathSegment) {
+        if node.ident == "Context"
+            && let syn::PathArguments::AngleBracketed(args) = &node.arguments
+            // `Context<'_, '_, '_, 'info, Foo<'info>>`: the accounts type is the last type argument
+            && let Some(syn::GenericArgument::Type(syn::Type::Path(type_path))) = args
+                .args
+                .iter()
+                .rfind(|arg| matches!(arg, syn::GenericArgument::Type(_)))
+            && let Some(segment) = type_path.path.segments.last()
+        {
+            self.push(&segment.ident, ReferenceKind::Context);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_path_segment(self, node);
+    }
+}
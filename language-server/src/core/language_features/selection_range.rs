@@ -0,0 +1,120 @@
+use crate::core::utilities::DiagnosticBuilder;
+use syn::spanned::Spanned;
+use syn::{parse_str, visit::Visit};
+use tower_lsp::lsp_types::{Position, Range, SelectionRange};
+
+/// Builds selection ranges that expand along syntax tree boundaries
+/// (identifier, expression, statement, block, function, struct or module)
+pub struct SelectionRangeProvider;
+
+impl SelectionRangeProvider {
+    /// Selection range for each position, innermost first with its ancestors as parents
+    ///
+    /// Returns `None` if the document does not parse. A position outside any node gets an
+    /// empty range at the position, as the client expects one result per position.
+    pub fn selection_ranges(content: &str, positions: &[Position]) -> Option<Vec<SelectionRange>> {
+        let syntax_tree = parse_str::<syn::File>(content).ok()?;
+
+        Some(
+            positions
+                .iter()
+                .map(|&position| {
+                    let mut collector = EnclosingRangeCollector {
+                        position,
+                        ranges: Vec::new(),
+                    };
+                    collector.visit_file(&syntax_tree);
+                    Self::nest(collector.ranges).unwrap_or(SelectionRange {
+                        range: Range::new(position, position),
+                        parent: None,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Nest ranges ordered outermost first, skipping repeats of the same range
+    fn nest(mut ranges: Vec<Range>) -> Option<SelectionRange> {
+        ranges.dedup();
+        ranges.into_iter().fold(None, |parent, range| {
+            Some(SelectionRange {
+                range,
+                parent: parent.map(Box::new),
+            })
+        })
+    }
+}
+
+/// Collects the ranges of nodes containing a position, from the outermost in
+struct EnclosingRangeCollector {
+    position: Position,
+    ranges: Vec<Range>,
+}
+
+impl EnclosingRangeCollector {
+    /// Record the node's range if it contains the position
+    /// Nodes that do not contain it are not descended into
+    fn enter(&mut self, node: &impl Spanned) -> bool {
+        let range = DiagnosticBuilder::create_range_from_span(node.span());
+        let contains = range.start <= self.position && self.position <= range.end;
+        if contains {
+            self.ranges.push(range);
+        }
+        contains
+    }
+}
+
+impl<'ast> Visit<'ast> for EnclosingRangeCollector {
+    fn visit_item(&mut self, node: &'ast syn::Item) {
+        if self.enter(node) {
+            // Continue visiting children
+            syn::visit::visit_item(self, node);
+        }
+    }
+
+    fn visit_impl_item(&mut self, node: &'ast syn::ImplItem) {
+        if self.enter(node) {
+            // Continue visiting children
+            syn::visit::visit_impl_item(self, node);
+        }
+    }
+
+    fn visit_field(&mut self, node: &'ast syn::Field) {
+        if self.enter(node) {
+            // Continue visiting children
+            syn::visit::visit_field(self, node);
+        }
+    }
+
+    fn visit_block(&mut self, node: &'ast syn::Block) {
+        if self.enter(node) {
+            // Continue visiting children
+            syn::visit::visit_block(self, node);
+        }
+    }
+
+    fn visit_stmt(&mut self, node: &'ast syn::Stmt) {
+        if self.enter(node) {
+            // Continue visiting children
+            syn::visit::visit_stmt(self, node);
+        }
+    }
+
+    fn visit_expr(&mut self, node: &'ast syn::Expr) {
+        if self.enter(node) {
+            // Continue visiting children
+            syn::visit::visit_expr(self, node);
+        }
+    }
+
+    fn visit_type(&mut self, node: &'ast syn::Type) {
+        if self.enter(node) {
+            // Continue visiting children
+            syn::visit::visit_type(self, node);
+        }
+    }
+
+    fn visit_ident(&mut self, node: &'ast proc_macro2::Ident) {
+        self.enter(node);
+    }
+}
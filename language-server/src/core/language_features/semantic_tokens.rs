@@ -0,0 +1,233 @@
+use crate::core::utilities::anchor_patterns::AnchorPatterns;
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use syn::{Fields, Type, parse_str, visit::Visit};
+use tower_lsp::lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend,
+};
+
+/// Token type index for `#[account(...)]` constraint keywords
+pub const TOKEN_TYPE_CONSTRAINT: u32 = 0;
+/// Token type index for Anchor account wrapper types
+pub const TOKEN_TYPE_ACCOUNT_WRAPPER: u32 = 1;
+/// Token type index for instruction handler names
+pub const TOKEN_TYPE_HANDLER: u32 = 2;
+
+/// Token modifier bit for declarations
+pub const TOKEN_MODIFIER_DECLARATION: u32 = 1 << 0;
+
+/// Keywords recognized inside `#[account(...)]` attributes
+const CONSTRAINT_KEYWORDS: &[&str] = &[
+    "mut",
+    "init",
+    "init_if_needed",
+    "zero",
+    "seeds",
+    "bump",
+    "payer",
+    "space",
+    "has_one",
+    "constraint",
+    "close",
+    "address",
+    "owner",
+    "executable",
+    "rent_exempt",
+    "realloc",
+    "signer",
+    "token",
+    "mint",
+    "authority",
+    "associated_token",
+    "decimals",
+    "freeze_authority",
+    "seeds_program",
+];
+
+/// Anchor account wrapper types
+const ACCOUNT_WRAPPER_TYPES: &[&str] = &[
+    "Account",
+    "AccountInfo",
+    "AccountLoader",
+    "InterfaceAccount",
+    "Interface",
+    "Program",
+    "Signer",
+    "SystemAccount",
+    "Sysvar",
+    "UncheckedAccount",
+];
+
+/// A token before delta encoding (0-based line, UTF-16 column)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct AbsoluteToken {
+    line: u32,
+    start: u32,
+    length: u32,
+    token_type: u32,
+    modifiers: u32,
+}
+
+/// Computes semantic tokens for Anchor-specific constructs
+pub struct SemanticTokenizer {
+    tokens: Vec<AbsoluteToken>,
+}
+
+impl SemanticTokenizer {
+    /// Legend advertised in the server capabilities; indices match the `TOKEN_*` constants
+    pub fn legend() -> SemanticTokensLegend {
+        SemanticTokensLegend {
+            token_types: vec![
+                SemanticTokenType::KEYWORD,
+                SemanticTokenType::TYPE,
+                SemanticTokenType::FUNCTION,
+            ],
+            token_modifiers: vec![SemanticTokenModifier::DECLARATION],
+        }
+    }
+
+    /// Compute delta-encoded semantic tokens for the given content
+    /// Returns an empty list if the content cannot be parsed
+    pub fn tokenize(content: &str) -> Vec<SemanticToken> {
+        let Ok(syntax_tree) = parse_str::<syn::File>(content) else {
+            return Vec::new();
+        };
+
+        let mut tokenizer = Self { tokens: Vec::new() };
+        tokenizer.visit_file(&syntax_tree);
+        tokenizer.encode()
+    }
+
+    /// Record a token covering the given identifier
+    fn push_ident(&mut self, ident: &Ident, token_type: u32, modifiers: u32) {
+        let start = ident.span().start();
+        if start.line == 0 {
+            return;
+        }
+
+        self.tokens.push(AbsoluteToken {
+            line: (start.line - 1) as u32,
+            start: start.column as u32,
+            length: ident.to_string().encode_utf16().count() as u32,
+            token_type,
+            modifiers,
+        });
+    }
+
+    /// Tokenize constraint keywords in the top level of an `#[account(...)]` token stream
+    /// Only identifiers in key position (start of a constraint or after `::`) are considered
+    fn collect_constraint_keywords(&mut self, tokens: &TokenStream) {
+        let mut in_key_position = true;
+
+        for token in tokens.clone() {
+            match token {
+                TokenTree::Ident(ident) => {
+                    if in_key_position && CONSTRAINT_KEYWORDS.contains(&ident.to_string().as_str())
+                    {
+                        self.push_ident(&ident, TOKEN_TYPE_CONSTRAINT, 0);
+                    }
+                }
+                TokenTree::Punct(punct) => match punct.as_char() {
+                    ',' => in_key_position = true,
+                    ':' => {}
+                    _ => in_key_position = false,
+                },
+                TokenTree::Group(_) | TokenTree::Literal(_) => in_key_position = false,
+            }
+        }
+    }
+
+    /// Tokenize account wrapper types, including ones nested in generics (e.g. `Box<Account<..>>`)
+    fn collect_wrapper_types(&mut self, ty: &Type) {
+        if let Type::Path(type_path) = ty {
+            for segment in &type_path.path.segments {
+                if ACCOUNT_WRAPPER_TYPES.contains(&segment.ident.to_string().as_str()) {
+                    self.push_ident(&segment.ident, TOKEN_TYPE_ACCOUNT_WRAPPER, 0);
+                }
+
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let syn::GenericArgument::Type(inner_ty) = arg {
+                            self.collect_wrapper_types(inner_ty);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sort collected tokens and convert them to the LSP delta-encoded format
+    fn encode(mut self) -> Vec<SemanticToken> {
+        self.tokens.sort();
+        self.tokens
+            .dedup_by(|a, b| a.line == b.line && a.start == b.start);
+
+        let mut previous_line = 0;
+        let mut previous_start = 0;
+
+        self.tokens
+            .iter()
+            .map(|token| {
+                let delta_line = token.line - previous_line;
+                let delta_start = if delta_line == 0 {
+                    token.start - previous_start
+                } else {
+                    token.start
+                };
+
+                previous_line = token.line;
+                previous_start = token.start;
+
+                SemanticToken {
+                    delta_line,
+                    delta_start,
+                    length: token.length,
+                    token_type: token.token_type,
+                    token_modifiers_bitset: token.modifiers,
+                }
+            })
+            .collect()
+    }
+}
+
+impl<'ast> Visit<'ast> for SemanticTokenizer {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node)
+            && let Fields::Named(fields) = &node.fields
+        {
+            for field in &fields.named {
+                for attr in &field.attrs {
+                    if attr.path().is_ident("account")
+                        && let syn::Meta::List(meta_list) = &attr.meta
+                    {
+                        self.collect_constraint_keywords(&meta_list.tokens);
+                    }
+                }
+
+                self.collect_wrapper_types(&field.ty);
+            }
+        }
+
+        syn::visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        let is_program_module = node
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("program"));
+
+        if is_program_module && let Some((_, items)) = &node.content {
+            for item in items {
+                if let syn::Item::Fn(item_fn) = item {
+                    self.push_ident(
+                        &item_fn.sig.ident,
+                        TOKEN_TYPE_HANDLER,
+                        TOKEN_MODIFIER_DECLARATION,
+                    );
+                }
+            }
+        }
+
+        syn::visit::visit_item_mod(self, node);
+    }
+}
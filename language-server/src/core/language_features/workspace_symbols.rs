@@ -0,0 +1,138 @@
+use crate::core::utilities::{DiagnosticBuilder, anchor_patterns::AnchorPatterns};
+use std::collections::HashMap;
+use syn::{parse_str, visit::Visit};
+use tower_lsp::lsp_types::{Location, SymbolInformation, SymbolKind, Url};
+
+/// A handler or Anchor struct definition in the index
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedSymbol {
+    pub kind: SymbolKind,
+    /// Program module of a handler, `None` for structs
+    pub container: Option<String>,
+    pub location: Location,
+}
+
+/// Index of instruction handlers, `#[derive(Accounts)]` structs and `#[account]` state
+/// structs across the workspace, for `workspace/symbol`
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceSymbolIndex {
+    symbols: HashMap<String, Vec<IndexedSymbol>>,
+}
+
+impl WorkspaceSymbolIndex {
+    /// Replace the symbols of a file with those defined in its content
+    /// A file that does not parse keeps its previous symbols
+    pub fn index_file(&mut self, uri: &Url, content: &str) {
+        let Ok(syntax_tree) = parse_str::<syn::File>(content) else {
+            return;
+        };
+
+        self.remove_file(uri);
+
+        let mut collector = SymbolCollector {
+            uri,
+            program: None,
+            found: Vec::new(),
+        };
+        collector.visit_file(&syntax_tree);
+
+        for (name, symbol) in collector.found {
+            self.symbols.entry(name).or_default().push(symbol);
+        }
+    }
+
+    /// Drop all symbols defined in a file
+    pub fn remove_file(&mut self, uri: &Url) {
+        self.symbols.retain(|_, symbols| {
+            symbols.retain(|symbol| &symbol.location.uri != uri);
+            !symbols.is_empty()
+        });
+    }
+
+    /// Symbols whose name contains the query (case-insensitive), sorted by name
+    /// An empty query matches every symbol
+    pub fn query(&self, query: &str) -> Vec<SymbolInformation> {
+        let query = query.to_lowercase();
+
+        let mut names: Vec<&String> = self
+            .symbols
+            .keys()
+            .filter(|name| name.to_lowercase().contains(&query))
+            .collect();
+        names.sort();
+
+        names
+            .into_iter()
+            .flat_map(|name| {
+                self.symbols[name].iter().map(move |symbol| {
+                    #[allow(deprecated)] // `deprecated` has no default and must be set
+                    SymbolInformation {
+                        name: name.clone(),
+                        kind: symbol.kind,
+                        tags: None,
+                        deprecated: None,
+                        location: symbol.location.clone(),
+                        container_name: symbol.container.clone(),
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+/// Collects handlers of `#[program]` modules and Anchor structs in a file
+struct SymbolCollector<'a> {
+    uri: &'a Url,
+    /// Name of the enclosing `#[program]` module
+    program: Option<String>,
+    found: Vec<(String, IndexedSymbol)>,
+}
+
+impl SymbolCollector<'_> {
+    fn push(&mut self, ident: &syn::Ident, kind: SymbolKind, container: Option<String>) {
+        self.found.push((
+            ident.to_string(),
+            IndexedSymbol {
+                kind,
+                container,
+                location: Location {
+                    uri: self.uri.clone(),
+                    range: DiagnosticBuilder::create_range_from_span(ident.span()),
+                },
+            },
+        ));
+    }
+}
+
+impl<'ast> Visit<'ast> for SymbolCollector<'_> {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        let outer = self.program.take();
+        if AnchorPatterns::is_program_module(node) {
+            self.program = Some(node.ident.to_string());
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_mod(self, node);
+        self.program = outer;
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if let Some(program) = self.program.clone() {
+            self.push(&node.sig.ident, SymbolKind::FUNCTION, Some(program));
+        }
+
+        // Nested items are not part of the program module's interface
+        let outer = self.program.take();
+        syn::visit::visit_item_fn(self, node);
+        self.program = outer;
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if AnchorPatterns::is_accounts_struct(node) || AnchorPatterns::is_account_struct(node) {
+            self.push(&node.ident, SymbolKind::STRUCT, None);
+        }
+
+        // Continue visiting children
+        syn::visit::visit_item_struct(self, node);
+    }
+}
@@ -1,13 +1,18 @@
 pub mod backend_stats;
 pub mod detectors;
+pub mod diagnostic_sink;
 pub mod dylint;
 pub mod file_scanner;
+pub mod language_features;
 pub mod notifications;
+pub mod progress;
 pub mod registry;
 pub mod utilities;
 
 pub use detectors::*;
 pub use dylint::DylintDetectorManager;
 pub use file_scanner::*;
+pub use language_features::*;
 pub use notifications::*;
+pub use progress::*;
 pub use registry::*;
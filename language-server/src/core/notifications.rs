@@ -1,5 +1,7 @@
-use crate::core::ScanResult;
+use crate::core::{DetectorInfo, ScanResult};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tower_lsp::lsp_types::{DiagnosticSeverity, NumberOrString};
 
 /// Custom notification for sending scan results to the extension
 #[derive(Debug)]
@@ -39,6 +41,16 @@ impl tower_lsp::lsp_types::notification::Notification for DetectorStatusNotifica
     const METHOD: &'static str = "solana/detectorStatus";
 }
 
+/// Custom request listing every registered detector with its current enabled state
+#[derive(Debug)]
+pub enum ListDetectorsRequest {}
+
+impl tower_lsp::lsp_types::request::Request for ListDetectorsRequest {
+    type Params = ();
+    type Result = Vec<DetectorInfo>;
+    const METHOD: &'static str = "solana/listDetectors";
+}
+
 /// Summary of scan results to send to the extension
 /// Only Rust files are scanned for security issues
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +60,8 @@ pub struct ScanSummary {
     pub files_with_issues: usize,
     pub total_issues: usize,
     pub issues_by_file: Vec<FileIssueInfo>,
+    /// Issue count per detector ID, taken from the diagnostic codes
+    pub by_detector: BTreeMap<String, usize>,
     pub is_manual_scan: bool,
 }
 
@@ -63,17 +77,51 @@ impl ScanSummary {
             })
             .collect();
 
+        let mut by_detector = BTreeMap::new();
+        for diagnostic in scan_result.rust_files.iter().flat_map(|f| &f.diagnostics) {
+            let code = match &diagnostic.code {
+                Some(NumberOrString::String(code)) => code.clone(),
+                Some(NumberOrString::Number(code)) => code.to_string(),
+                None => continue,
+            };
+            *by_detector.entry(code).or_insert(0) += 1;
+        }
+
         Self {
             total_rust_files: scan_result.rust_files.len(),
             anchor_program_files: scan_result.anchor_program_files().len(),
             files_with_issues: scan_result.files_with_issues().len(),
             total_issues: scan_result.total_issues(),
             issues_by_file,
+            by_detector,
             is_manual_scan,
         }
     }
 }
 
+/// Issue counts per severity across a scan
+/// Hints are counted as info
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    pub error: usize,
+    pub warning: usize,
+    pub info: usize,
+}
+
+impl SeverityCounts {
+    pub fn from_scan_result(scan_result: &ScanResult) -> Self {
+        let mut counts = Self::default();
+        for diagnostic in scan_result.rust_files.iter().flat_map(|f| &f.diagnostics) {
+            match diagnostic.severity {
+                Some(DiagnosticSeverity::ERROR) => counts.error += 1,
+                Some(DiagnosticSeverity::WARNING) => counts.warning += 1,
+                _ => counts.info += 1,
+            }
+        }
+        counts
+    }
+}
+
 /// Information about a file with security issues
 /// Test files are excluded from scanning
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,8 +151,47 @@ pub struct FileAnalysisResult {
 }
 
 /// Status of detector operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DetectorStatus {
-    pub status: String, // "initializing", "building", "running", "complete", "idle"
+    /// "initializing", "building", "running", "complete", "idle", "missing_toolchain",
+    /// "cargo_missing", or a failed dylint run: "missing_driver", "timeout", "parse_failed",
+    /// "compile_failed", "driver_crashed", "driver_unstable" (see `DylintError::status`)
+    pub status: String,
     pub message: String,
+    /// Toolchain that has to be installed, set for "missing_toolchain", "missing_driver" and
+    /// "driver_unstable"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_version: Option<String>,
+    /// Command that installs the missing toolchain, set for "missing_toolchain", "cargo_missing",
+    /// "missing_driver" and "driver_unstable"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_command: Option<String>,
+}
+
+impl DetectorStatus {
+    /// Status telling the extension which nightly toolchain to install
+    pub fn missing_toolchain(version: &str) -> Self {
+        Self {
+            status: "missing_toolchain".to_string(),
+            message: format!(
+                "Rust {} is required for dylint detectors but is not installed",
+                version
+            ),
+            required_version: Some(version.to_string()),
+            install_command: Some(format!("rustup toolchain install {}", version)),
+        }
+    }
+
+    /// Status telling the extension that cargo itself could not be run
+    /// Dylint detectors stay disabled for the session; syn detectors keep working
+    pub fn cargo_missing() -> Self {
+        Self {
+            status: "cargo_missing".to_string(),
+            message: "cargo was not found, so dylint detectors are disabled. Install Rust with rustup and restart the language server".to_string(),
+            required_version: None,
+            install_command: Some(
+                "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh".to_string(),
+            ),
+        }
+    }
 }
@@ -0,0 +1,102 @@
+use log::debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tower_lsp::Client;
+use tower_lsp::lsp_types::{
+    NumberOrString, ProgressParams, ProgressParamsValue, WorkDoneProgress, WorkDoneProgressBegin,
+    WorkDoneProgressCreateParams, WorkDoneProgressEnd, WorkDoneProgressReport,
+    notification::Progress, request::WorkDoneProgressCreate,
+};
+
+/// Counter for unique progress tokens within the server process
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// Server-initiated `$/progress` reporting for a long-running operation
+///
+/// The token is created with `window/workDoneProgress/create`. If the client rejects it
+/// (no progress support, or the server is not initialized yet) all reports are dropped.
+pub struct WorkDoneProgressReporter {
+    client: Client,
+    token: NumberOrString,
+    enabled: bool,
+    /// Last reported percentage, so unchanged values are not re-sent
+    last_percentage: u32,
+}
+
+impl WorkDoneProgressReporter {
+    /// Create a progress token and send `begin`
+    pub async fn begin(client: &Client, title: &str, message: Option<String>) -> Self {
+        let token = NumberOrString::String(format!(
+            "solana/{}",
+            NEXT_TOKEN.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let enabled = match client
+            .send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
+                token: token.clone(),
+            })
+            .await
+        {
+            Ok(()) => true,
+            Err(e) => {
+                debug!("Client rejected progress token {:?}: {}", token, e);
+                false
+            }
+        };
+
+        let reporter = Self {
+            client: client.clone(),
+            token,
+            enabled,
+            last_percentage: 0,
+        };
+
+        reporter
+            .send(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+                title: title.to_string(),
+                cancellable: Some(false),
+                message,
+                percentage: Some(0),
+            }))
+            .await;
+
+        reporter
+    }
+
+    /// Report `done` of `total` items, skipped if the percentage did not change
+    pub async fn report(&mut self, done: usize, total: usize, message: Option<String>) {
+        // An empty operation is complete
+        let percentage = (done.min(total) * 100)
+            .checked_div(total)
+            .map_or(100, |percentage| percentage as u32);
+        if self.last_percentage == percentage {
+            return;
+        }
+        self.last_percentage = percentage;
+
+        self.send(WorkDoneProgress::Report(WorkDoneProgressReport {
+            cancellable: Some(false),
+            message,
+            percentage: Some(percentage),
+        }))
+        .await;
+    }
+
+    /// Send `end`, consuming the reporter
+    pub async fn end(self, message: Option<String>) {
+        self.send(WorkDoneProgress::End(WorkDoneProgressEnd { message }))
+            .await;
+    }
+
+    async fn send(&self, value: WorkDoneProgress) {
+        if !self.enabled {
+            return;
+        }
+
+        self.client
+            .send_notification::<Progress>(ProgressParams {
+                token: self.token.clone(),
+                value: ProgressParamsValue::WorkDone(value),
+            })
+            .await;
+    }
+}
@@ -1,13 +1,36 @@
 use crate::core::detector::Detector;
 use crate::core::detector_config::DetectorConfig;
+use crate::core::utilities::{
+    CustomRegexPatterns, LineIndex, WorkspaceFiles, ast_analyzer::AstAnalyzer,
+};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use tower_lsp::lsp_types::Diagnostic;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use syn::parse_str;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+/// Version of the detector set, persisted alongside cached scan results
+/// Bump this whenever detectors are added or removed, their matching logic changes, or the
+/// diagnostics they produce gain or change fields (e.g. `data`, `code_description`), so stale
+/// cached diagnostics are discarded
+pub const REGISTRY_VERSION: u32 = 42;
+
+/// Abbreviations whose trailing period does not end a sentence in terse messages
+const SENTENCE_ABBREVIATIONS: &[&str] = &["e.g", "i.e", "etc", "vs", "cf"];
 
 /// Registry that manages all security detectors
 pub struct DetectorRegistry {
     detectors: Vec<Box<dyn Detector>>,
     configs: HashMap<String, DetectorConfig>,
+    /// Compiled `custom_regex_patterns` of each configured detector
+    custom_patterns: HashMap<String, CustomRegexPatterns>,
+    /// Whether diagnostics carry the full remediation text or a terse one-line form
+    verbose_messages: bool,
+    /// Workspace files of the last scan, shared with detectors that resolve names across files
+    workspace: Option<Arc<WorkspaceFiles>>,
 }
 
 impl std::fmt::Debug for DetectorRegistry {
@@ -15,6 +38,7 @@ impl std::fmt::Debug for DetectorRegistry {
         f.debug_struct("DetectorRegistry")
             .field("detector_count", &self.detectors.len())
             .field("configs", &self.configs)
+            .field("verbose_messages", &self.verbose_messages)
             .finish()
     }
 }
@@ -25,19 +49,42 @@ impl DetectorRegistry {
         Self {
             detectors: Vec::new(),
             configs: HashMap::new(),
+            custom_patterns: HashMap::new(),
+            verbose_messages: true,
+            workspace: None,
         }
     }
 
     /// Register a detector with the registry
     pub fn register<D: Detector + 'static>(&mut self, detector: D) {
         let id = detector.id().to_string();
+        let mut detector = Box::new(detector);
+        detector.set_workspace(self.workspace.clone());
         self.configs.insert(id, DetectorConfig::default());
-        self.detectors.push(Box::new(detector));
+        self.detectors.push(detector);
+    }
+
+    /// Share the workspace files of a scan with all detectors
+    /// They stay in use for later single-file analysis until the next scan replaces them
+    pub fn set_workspace(&mut self, workspace: Option<Arc<WorkspaceFiles>>) {
+        for detector in &mut self.detectors {
+            detector.set_workspace(workspace.clone());
+        }
+        self.workspace = workspace;
+    }
+
+    /// Workspace files currently shared with the detectors
+    pub fn workspace(&self) -> Option<Arc<WorkspaceFiles>> {
+        self.workspace.clone()
     }
 
     /// Configure a specific detector
     #[allow(dead_code)]
     pub fn configure(&mut self, detector_id: &str, config: DetectorConfig) {
+        self.custom_patterns.insert(
+            detector_id.to_string(),
+            CustomRegexPatterns::compile(&config.custom_regex_patterns),
+        );
         self.configs.insert(detector_id.to_string(), config);
     }
 
@@ -57,8 +104,203 @@ impl DetectorRegistry {
         }
     }
 
+    /// Let a detector analyze test-only code as well
+    pub fn set_include_tests(&mut self, detector_id: &str, include_tests: bool) {
+        if let Some(config) = self.configs.get_mut(detector_id) {
+            config.include_tests = include_tests;
+        }
+    }
+
+    /// Switch between full diagnostic messages and terse one-line messages
+    pub fn set_verbose_messages(&mut self, verbose: bool) {
+        self.verbose_messages = verbose;
+    }
+
+    /// Check if diagnostics carry full messages
+    #[allow(dead_code)]
+    pub fn verbose_messages(&self) -> bool {
+        self.verbose_messages
+    }
+
+    /// Raise every diagnostic less severe than `floor` to `floor`
+    ///
+    /// Applied to finished diagnostics (e.g. by `--scan --severity-floor`), independent of any
+    /// per-detector severity override. Diagnostics without a severity are raised as well.
+    pub fn apply_severity_floor(diagnostics: &mut [Diagnostic], floor: DiagnosticSeverity) {
+        for diagnostic in diagnostics.iter_mut() {
+            // LSP severities are ordered from ERROR (1) to HINT (4)
+            if diagnostic.severity.is_none_or(|severity| severity > floor) {
+                diagnostic.severity = Some(floor);
+            }
+        }
+    }
+
+    /// Shorten a message to the detector name and the first sentence
+    fn terse_message(detector_name: &str, message: &str) -> String {
+        let first_line = message.lines().next().unwrap_or_default();
+        let first_sentence = match Self::first_sentence_end(first_line) {
+            Some(end) => &first_line[..=end],
+            None => first_line,
+        };
+        format!("{}: {}", detector_name, first_sentence.trim())
+    }
+
+    /// Byte offset of the period ending the first sentence, if the line has several
+    ///
+    /// A `. ` after an abbreviation such as "e.g." or followed by a lowercase word does not
+    /// end a sentence.
+    fn first_sentence_end(line: &str) -> Option<usize> {
+        line.match_indices(". ").map(|(end, _)| end).find(|&end| {
+            let word = line[..end]
+                .rsplit(char::is_whitespace)
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches(['(', '"', '`'])
+                .to_ascii_lowercase();
+            let next = line[end + 2..].trim_start().chars().next();
+            !SENTENCE_ABBREVIATIONS.contains(&word.as_str())
+                && !next.is_some_and(char::is_lowercase)
+        })
+    }
+
+    /// Parse content for detectors, keeping test-only code visible if requested
+    fn parse(content: &str, include_tests: bool) -> Option<syn::File> {
+        let mut syntax_tree = parse_str::<syn::File>(content).ok()?;
+        if include_tests {
+            AstAnalyzer::strip_test_attrs(&mut syntax_tree);
+        }
+        Some(syntax_tree)
+    }
+
+    /// Apply a detector's severity override and the message verbosity to its diagnostics
+    fn apply_config(
+        detector: &dyn Detector,
+        config: &DetectorConfig,
+        verbose_messages: bool,
+        diagnostics: &mut [Diagnostic],
+    ) {
+        // Apply severity override if configured
+        if let Some(severity_override) = config.severity_override {
+            for diagnostic in diagnostics.iter_mut() {
+                diagnostic.severity = Some(severity_override);
+            }
+        }
+
+        // Trim messages down to the rule and a single line if requested
+        if !verbose_messages {
+            for diagnostic in diagnostics.iter_mut() {
+                diagnostic.message = Self::terse_message(detector.name(), &diagnostic.message);
+            }
+        }
+    }
+
+    /// Diagnostics for matches of a detector's custom regex patterns
+    /// They take the detector's severity (or its override) and are not shortened
+    fn custom_pattern_diagnostics(
+        detector: &dyn Detector,
+        config: &DetectorConfig,
+        patterns: Option<&CustomRegexPatterns>,
+        content: &str,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        let Some(patterns) = patterns.filter(|patterns| !patterns.is_empty()) else {
+            return Vec::new();
+        };
+
+        patterns.diagnostics(
+            content,
+            lines,
+            &format!("{}_CUSTOM_REGEX", detector.id()),
+            config
+                .severity_override
+                .unwrap_or(detector.default_severity()),
+        )
+    }
+
     /// Run all enabled detectors on the given content
+    ///
+    /// Detectors are split into one chunk per worker thread and the chunks run in parallel.
+    /// syn ASTs are not `Sync`, so each chunk parses the content once and shares that tree
+    /// across its detectors (plus a second tree with test code visible, if any detector opts
+    /// in). Diagnostics are returned in registration order.
     pub fn analyze(&mut self, content: &str, file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+        let lines = LineIndex::new(content);
+        let configs = &self.configs;
+        let custom_patterns = &self.custom_patterns;
+        let verbose_messages = self.verbose_messages;
+        let chunk_size = self
+            .detectors
+            .len()
+            .div_ceil(rayon::current_num_threads())
+            .max(1);
+
+        self.detectors
+            .par_chunks_mut(chunk_size)
+            .map(|chunk| {
+                let mut syntax_tree = None;
+                let mut test_syntax_tree = None;
+                let mut chunk_diagnostics = Vec::new();
+
+                for detector in chunk {
+                    let config = configs.get(detector.id()).cloned().unwrap_or_default();
+
+                    if !config.enabled {
+                        continue;
+                    }
+
+                    // Unparsable content still goes to detectors that work on raw text
+                    let tree = if config.include_tests {
+                        &mut test_syntax_tree
+                    } else {
+                        &mut syntax_tree
+                    };
+                    let mut diagnostics = match tree
+                        .get_or_insert_with(|| Self::parse(content, config.include_tests))
+                    {
+                        Some(syntax_tree) => {
+                            detector.analyze_ast(syntax_tree, content, file_path, &lines)
+                        }
+                        None => detector.analyze(content, file_path),
+                    };
+
+                    Self::apply_config(
+                        detector.as_ref(),
+                        &config,
+                        verbose_messages,
+                        &mut diagnostics,
+                    );
+                    chunk_diagnostics.extend(diagnostics);
+                    chunk_diagnostics.extend(Self::custom_pattern_diagnostics(
+                        detector.as_ref(),
+                        &config,
+                        custom_patterns.get(detector.id()),
+                        content,
+                        &lines,
+                    ));
+                }
+
+                chunk_diagnostics
+            })
+            .collect::<Vec<_>>()
+            .concat()
+    }
+
+    /// Run all enabled detectors on a file the caller has already parsed
+    ///
+    /// Unlike `analyze`, which parses the content once per worker chunk (up to one parse per
+    /// rayon thread, plus one more per chunk with test code visible), this performs no parse
+    /// at all. The tree with test code visible is derived from `syntax_tree` by stripping test
+    /// attributes from a clone. Detectors run sequentially on the calling thread because syn
+    /// ASTs cannot be shared across threads. Diagnostics are returned in registration order.
+    #[allow(dead_code)]
+    pub fn analyze_parsed(
+        &mut self,
+        syntax_tree: &syn::File,
+        content: &str,
+        file_path: Option<&PathBuf>,
+    ) -> Vec<Diagnostic> {
+        let lines = LineIndex::new(content);
+        let mut test_syntax_tree = None;
         let mut all_diagnostics = Vec::new();
 
         for detector in &mut self.detectors {
@@ -68,23 +310,112 @@ impl DetectorRegistry {
                 continue;
             }
 
-            let mut diagnostics = detector.analyze(content, file_path);
+            let tree = if config.include_tests {
+                test_syntax_tree.get_or_insert_with(|| {
+                    let mut test_syntax_tree = syntax_tree.clone();
+                    AstAnalyzer::strip_test_attrs(&mut test_syntax_tree);
+                    test_syntax_tree
+                })
+            } else {
+                syntax_tree
+            };
+            let mut diagnostics = detector.analyze_ast(tree, content, file_path, &lines);
+
+            Self::apply_config(
+                detector.as_ref(),
+                &config,
+                self.verbose_messages,
+                &mut diagnostics,
+            );
+            all_diagnostics.extend(diagnostics);
+            all_diagnostics.extend(Self::custom_pattern_diagnostics(
+                detector.as_ref(),
+                &config,
+                self.custom_patterns.get(detector.id()),
+                content,
+                &lines,
+            ));
+        }
+
+        all_diagnostics
+    }
+
+    /// Run each detector on the given content sequentially and time it
+    ///
+    /// Returns one entry per registered detector, in registration order, each followed by a
+    /// `<ID>_CUSTOM_REGEX` entry if the detector has custom regex patterns, so the counts add
+    /// up to what `analyze` publishes. Disabled detectors are not run and have no duration.
+    /// The parse is shared and not counted towards any detector.
+    pub fn analyze_file(
+        &mut self,
+        content: &str,
+        file_path: Option<&PathBuf>,
+    ) -> Vec<DetectorTiming> {
+        let lines = LineIndex::new(content);
+        let syntax_tree = Self::parse(content, false);
+        let mut test_syntax_tree = None;
+        let mut timings = Vec::with_capacity(self.detectors.len());
 
-            // Apply severity override if configured
-            if let Some(severity_override) = config.severity_override {
-                for diagnostic in &mut diagnostics {
-                    diagnostic.severity = Some(severity_override);
+        for detector in self.detectors.iter_mut() {
+            let config = self.configs.get(detector.id()).cloned().unwrap_or_default();
+            let patterns = self
+                .custom_patterns
+                .get(detector.id())
+                .filter(|patterns| !patterns.is_empty());
+
+            if !config.enabled {
+                timings.push(DetectorTiming {
+                    id: detector.id().to_string(),
+                    duration: None,
+                    count: 0,
+                });
+                if patterns.is_some() {
+                    timings.push(DetectorTiming {
+                        id: format!("{}_CUSTOM_REGEX", detector.id()),
+                        duration: None,
+                        count: 0,
+                    });
                 }
+                continue;
             }
 
-            all_diagnostics.extend(diagnostics);
+            let start = Instant::now();
+            let tree = if config.include_tests {
+                test_syntax_tree.get_or_insert_with(|| Self::parse(content, true))
+            } else {
+                &syntax_tree
+            };
+            let diagnostics = match tree {
+                Some(syntax_tree) => detector.analyze_ast(syntax_tree, content, file_path, &lines),
+                None => detector.analyze(content, file_path),
+            };
+            timings.push(DetectorTiming {
+                id: detector.id().to_string(),
+                duration: Some(start.elapsed()),
+                count: diagnostics.len(),
+            });
+
+            if let Some(patterns) = patterns {
+                let start = Instant::now();
+                let diagnostics = Self::custom_pattern_diagnostics(
+                    detector.as_ref(),
+                    &config,
+                    Some(patterns),
+                    content,
+                    &lines,
+                );
+                timings.push(DetectorTiming {
+                    id: format!("{}_CUSTOM_REGEX", detector.id()),
+                    duration: Some(start.elapsed()),
+                    count: diagnostics.len(),
+                });
+            }
         }
 
-        all_diagnostics
+        timings
     }
 
     /// Get information about all registered detectors
-    #[allow(dead_code)]
     pub fn list_detectors(&self) -> Vec<DetectorInfo> {
         self.detectors
             .iter()
@@ -102,6 +433,14 @@ impl DetectorRegistry {
             .collect()
     }
 
+    /// Get a registered detector by ID, whether enabled or not
+    pub fn detector(&self, detector_id: &str) -> Option<&dyn Detector> {
+        self.detectors
+            .iter()
+            .find(|detector| detector.id() == detector_id)
+            .map(|detector| detector.as_ref())
+    }
+
     /// Get the number of registered detectors
     #[allow(dead_code)]
     pub fn count(&self) -> usize {
@@ -129,9 +468,8 @@ impl Default for DetectorRegistry {
     }
 }
 
-/// Information about a detector
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+/// Information about a detector, returned to the extension by `solana/listDetectors`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectorInfo {
     pub id: String,
     pub name: String,
@@ -140,6 +478,15 @@ pub struct DetectorInfo {
     pub default_severity: tower_lsp::lsp_types::DiagnosticSeverity,
 }
 
+/// Time spent and diagnostics produced by one detector
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectorTiming {
+    pub id: String,
+    /// `None` if the detector is disabled and was not run
+    pub duration: Option<Duration>,
+    pub count: usize,
+}
+
 /// Builder for creating and configuring a detector registry
 pub struct DetectorRegistryBuilder {
     registry: DetectorRegistry,
@@ -1,3 +1,18 @@
+use crate::core::utilities::ast_analyzer::AstAnalyzer;
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+/// A single constraint inside an `#[account(...)]` attribute, e.g. `payer = authority`
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct AccountConstraint {
+    /// Constraint key, e.g. `mut`, `seeds` or `token::mint`
+    pub key: String,
+    /// Tokens after the first `=`, if the constraint has a value
+    pub value: Option<TokenStream>,
+    /// Span of the first token of the constraint
+    pub span: Span,
+}
+
 // Anchor-specific AST patterns and utilities
 pub struct AnchorPatterns;
 
@@ -22,6 +37,29 @@ impl AnchorPatterns {
             .any(|attr| attr.path().is_ident("account"))
     }
 
+    /// Check if an account struct is zero-copy (`#[account(zero_copy)]` or `#[zero_copy]`)
+    pub fn is_zero_copy_struct(item_struct: &syn::ItemStruct) -> bool {
+        item_struct.attrs.iter().any(|attr| {
+            if attr.path().is_ident("zero_copy") {
+                return true;
+            }
+
+            attr.path().is_ident("account")
+                && matches!(&attr.meta, syn::Meta::List(meta_list)
+                if meta_list.tokens.clone().into_iter().any(|token| {
+                    matches!(token, TokenTree::Ident(ident) if ident == "zero_copy")
+                }))
+        })
+    }
+
+    /// Check if a module has the #[program] attribute
+    pub fn is_program_module(item_mod: &syn::ItemMod) -> bool {
+        item_mod
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("program"))
+    }
+
     /// Check if a function has the #[access_control] attribute
     #[allow(dead_code)]
     pub fn has_access_control(item_fn: &syn::ItemFn) -> bool {
@@ -37,16 +75,229 @@ impl AnchorPatterns {
         let mut constraints = Vec::new();
 
         for attr in &field.attrs {
-            if attr.path().is_ident("account") {
-                if let syn::Meta::List(meta_list) = &attr.meta {
-                    constraints.push(meta_list.tokens.to_string());
+            if attr.path().is_ident("account")
+                && let syn::Meta::List(meta_list) = &attr.meta
+            {
+                constraints.push(meta_list.tokens.to_string());
+            }
+        }
+
+        constraints
+    }
+
+    /// Parse all `#[account(...)]` attributes of a field into individual constraints
+    ///
+    /// Constraints are split on top-level commas; the key is everything before the
+    /// first `=` and the value everything after it (so `constraint = a == b` has the
+    /// value `a == b`)
+    pub fn parse_account_constraints(field: &syn::Field) -> Vec<AccountConstraint> {
+        let mut constraints = Vec::new();
+
+        for attr in &field.attrs {
+            if !attr.path().is_ident("account") {
+                continue;
+            }
+            let syn::Meta::List(meta_list) = &attr.meta else {
+                continue;
+            };
+
+            let mut current: Vec<TokenTree> = Vec::new();
+            for token in meta_list.tokens.clone() {
+                if let TokenTree::Punct(punct) = &token
+                    && punct.as_char() == ','
+                {
+                    constraints.extend(Self::build_constraint(&current));
+                    current.clear();
+                } else {
+                    current.push(token);
                 }
             }
+            constraints.extend(Self::build_constraint(&current));
         }
 
         constraints
     }
 
+    /// Build a constraint from the tokens between two top-level commas
+    fn build_constraint(tokens: &[TokenTree]) -> Option<AccountConstraint> {
+        let first = tokens.first()?;
+
+        let eq_index = tokens
+            .iter()
+            .position(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == '='));
+
+        let key_tokens = &tokens[..eq_index.unwrap_or(tokens.len())];
+        let key = key_tokens
+            .iter()
+            .map(|token| token.to_string())
+            .collect::<String>();
+        let value = eq_index.map(|index| tokens[index + 1..].iter().cloned().collect());
+
+        Some(AccountConstraint {
+            key,
+            value,
+            span: first.span(),
+        })
+    }
+
+    /// Check if a field has an `#[account(...)]` constraint with the given key
+    pub fn has_constraint(field: &syn::Field, key: &str) -> bool {
+        Self::parse_account_constraints(field)
+            .iter()
+            .any(|constraint| constraint.key == key)
+    }
+
+    /// Check if a field is required to sign (`Signer<'info>` or `#[account(signer)]`)
+    pub fn is_signer_field(field: &syn::Field) -> bool {
+        Self::account_wrapper_name(field).as_deref() == Some("Signer")
+            || Self::has_constraint(field, "signer")
+    }
+
+    /// Get the Anchor wrapper type name of a field (e.g. `Account`, `Signer`),
+    /// looking through `Box<...>`
+    pub fn account_wrapper_name(field: &syn::Field) -> Option<String> {
+        let segment = Self::unboxed_last_segment(&field.ty)?;
+        Some(segment.ident.to_string())
+    }
+
+    /// Get the account data type of a field (e.g. `Vault` for `Account<'info, Vault>`),
+    /// looking through `Box<...>`
+    pub fn account_data_type_name(field: &syn::Field) -> Option<String> {
+        Self::account_data_type(field).map(ToString::to_string)
+    }
+
+    /// Get the identifier of the account data type of a field, looking through `Box<...>`
+    pub fn account_data_type(field: &syn::Field) -> Option<&syn::Ident> {
+        let segment = Self::unboxed_last_segment(&field.ty)?;
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+
+        args.args.iter().rev().find_map(|arg| match arg {
+            syn::GenericArgument::Type(syn::Type::Path(type_path)) => {
+                type_path.path.segments.last().map(|segment| &segment.ident)
+            }
+            _ => None,
+        })
+    }
+
+    /// The `Foo` of a `Context<Foo>` type
+    pub fn context_accounts_ident(ty: &syn::Type) -> Option<&syn::Ident> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != "Context" {
+            return None;
+        }
+
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+
+        // `Context<'_, '_, '_, 'info, Foo<'info>>`: the accounts type is the last type argument
+        match args
+            .args
+            .iter()
+            .rfind(|arg| matches!(arg, syn::GenericArgument::Type(_)))?
+        {
+            syn::GenericArgument::Type(syn::Type::Path(accounts_type)) => {
+                Some(&accounts_type.path.segments.last()?.ident)
+            }
+            _ => None,
+        }
+    }
+
+    /// Comparable shape of PDA seeds: literal seeds are kept, any other seed becomes `_`
+    /// String and byte string literals compare equal, so `"vault".as_bytes()` matches `b"vault"`
+    pub fn seed_shape<'a>(seeds: impl IntoIterator<Item = &'a syn::Expr>) -> String {
+        seeds
+            .into_iter()
+            .map(|seed| match Self::strip_seed_conversions(seed) {
+                syn::Expr::Lit(lit) => Self::seed_literal_text(&lit.lit),
+                _ => "_".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Strip `&` and byte conversions from a seed, e.g. `b"vault".as_ref()` -> `b"vault"`
+    pub fn strip_seed_conversions(expr: &syn::Expr) -> &syn::Expr {
+        match expr {
+            syn::Expr::Reference(reference) => Self::strip_seed_conversions(&reference.expr),
+            syn::Expr::Paren(paren) => Self::strip_seed_conversions(&paren.expr),
+            syn::Expr::MethodCall(call)
+                if call.args.is_empty()
+                    && matches!(call.method.to_string().as_str(), "as_ref" | "as_bytes") =>
+            {
+                Self::strip_seed_conversions(&call.receiver)
+            }
+            _ => expr,
+        }
+    }
+
+    /// Text of a literal seed; literals of other kinds compare as `_`
+    fn seed_literal_text(lit: &syn::Lit) -> String {
+        match lit {
+            syn::Lit::Str(lit) => format!("b{:?}", lit.value()),
+            syn::Lit::ByteStr(lit) => format!("b{:?}", String::from_utf8_lossy(&lit.value())),
+            syn::Lit::Byte(lit) => lit.token().to_string(),
+            syn::Lit::Int(lit) => lit.token().to_string(),
+            _ => "_".to_string(),
+        }
+    }
+
+    /// Return true if the expression represents an access to lamports:
+    /// - foo.lamports
+    /// - foo.lamports()   (some code uses a method accessor)
+    /// - foo.lamports.borrow_mut()
+    /// - foo.try_borrow_mut_lamports()
+    pub fn is_lamports_access(expr: &syn::Expr) -> bool {
+        use syn::{Expr, ExprField, ExprMethodCall};
+
+        match AstAnalyzer::strip_wrappers(expr) {
+            // 1) foo.lamports
+            Expr::Field(ExprField {
+                member: syn::Member::Named(ident),
+                ..
+            }) if ident == "lamports" => true,
+
+            // 2) foo.lamports() — allow method named `lamports`
+            Expr::MethodCall(ExprMethodCall { method, .. }) if method == "lamports" => true,
+
+            // 3) foo.lamports.borrow_mut() — the receiver of borrow_mut() must be a lamports access
+            Expr::MethodCall(ExprMethodCall {
+                method, receiver, ..
+            }) if method == "borrow_mut" => Self::is_lamports_access(receiver),
+
+            // 4) foo.try_borrow_mut_lamports()
+            Expr::MethodCall(ExprMethodCall { method, .. })
+                if method == "try_borrow_mut_lamports" =>
+            {
+                true
+            }
+
+            _ => false,
+        }
+    }
+
+    /// Last path segment of a type, unwrapping `Box<T>`
+    fn unboxed_last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+
+        if segment.ident == "Box"
+            && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+            && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
+        {
+            return Self::unboxed_last_segment(inner_ty);
+        }
+
+        Some(segment)
+    }
+
     /// Check if an expression contains security checks
     #[allow(dead_code)]
     pub fn has_security_check(expr: &syn::Expr) -> bool {
@@ -76,6 +327,120 @@ impl AnchorPatterns {
         }
     }
 
+    /// Extract parameters from an instruction handler function and return (context_struct_name, parameters)
+    /// The `Context` parameter itself is not included
+    pub fn extract_handler_parameters(
+        item_fn: &syn::ItemFn,
+    ) -> Option<(String, Vec<(String, String)>)> {
+        let mut parameters = Vec::new();
+        let mut context_struct_name: Option<String> = None;
+
+        for input in &item_fn.sig.inputs {
+            if let syn::FnArg::Typed(syn::PatType { pat, ty, .. }) = input
+                && let syn::Pat::Ident(pat_ident) = &**pat
+            {
+                let param_name = pat_ident.ident.to_string();
+
+                // Check if this is a Context parameter
+                if let syn::Type::Path(syn::TypePath { path, .. }) = &**ty
+                    && let Some(segment) = path.segments.first()
+                    && segment.ident == "Context"
+                {
+                    // Extract the context struct name from Context<StructName>
+                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+                        && let Some(syn::GenericArgument::Type(syn::Type::Path(context_type))) =
+                            args.args.first()
+                        && let Some(context_segment) = context_type.path.segments.first()
+                    {
+                        context_struct_name = Some(context_segment.ident.to_string());
+                    }
+                    continue; // Skip the Context parameter itself
+                }
+
+                // Extract type as string with better formatting
+                let type_str = Self::type_to_string(ty);
+                parameters.push((param_name, type_str));
+            }
+        }
+
+        // Only return if we found a context struct name
+        context_struct_name.map(|struct_name| (struct_name, parameters))
+    }
+
+    /// Format a type as a compact string, e.g. `Vec<u8>` or `&'a str`
+    pub fn type_to_string(ty: &syn::Type) -> String {
+        match ty {
+            syn::Type::Path(type_path) => {
+                let mut result = String::new();
+                for (i, segment) in type_path.path.segments.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str("::");
+                    }
+                    result.push_str(&segment.ident.to_string());
+
+                    // Handle generic arguments
+                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                        result.push('<');
+                        for (j, arg) in args.args.iter().enumerate() {
+                            if j > 0 {
+                                result.push_str(", ");
+                            }
+                            match arg {
+                                syn::GenericArgument::Type(inner_ty) => {
+                                    result.push_str(&Self::type_to_string(inner_ty));
+                                }
+                                syn::GenericArgument::Lifetime(lifetime) => {
+                                    result.push_str(&format!("'{}", lifetime.ident));
+                                }
+                                _ => {
+                                    result.push_str(&format!("{:?}", arg));
+                                }
+                            }
+                        }
+                        result.push('>');
+                    }
+                }
+                result
+            }
+            syn::Type::Reference(type_ref) => {
+                let mut result = String::from("&");
+                if let Some(lifetime) = &type_ref.lifetime {
+                    result.push_str(&format!("'{} ", lifetime.ident));
+                }
+                if type_ref.mutability.is_some() {
+                    result.push_str("mut ");
+                }
+                result.push_str(&Self::type_to_string(&type_ref.elem));
+                result
+            }
+            syn::Type::Slice(type_slice) => {
+                format!("[{}]", Self::type_to_string(&type_slice.elem))
+            }
+            syn::Type::Array(type_array) => {
+                format!(
+                    "[{}; {:?}]",
+                    Self::type_to_string(&type_array.elem),
+                    type_array.len
+                )
+            }
+            syn::Type::Tuple(type_tuple) => {
+                let mut result = String::from("(");
+                for (i, elem) in type_tuple.elems.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(", ");
+                    }
+                    result.push_str(&Self::type_to_string(elem));
+                }
+                result.push(')');
+                result
+            }
+            _ => {
+                // Fallback to debug format
+                format!("{:?}", ty)
+            }
+        }
+    }
+
     /// Extract parameter names and types from the #[instruction(...)] attribute
     ///
     /// Returns a vector of tuples, where each tuple contains:
@@ -88,80 +453,77 @@ impl AnchorPatterns {
         let mut parameters = Vec::new();
 
         for attr in &item_struct.attrs {
-            if attr.path().is_ident("instruction") {
-                if let syn::Meta::List(meta_list) = &attr.meta {
-                    // Parse the tokens to get individual parameter spans
-                    let tokens = &meta_list.tokens;
-                    let mut token_iter = tokens.clone().into_iter();
-                    let mut current_param = String::new();
-                    let mut current_type = String::new();
-                    let mut param_start_span: Option<proc_macro2::Span> = None;
-                    let mut parsing_type = false;
-
-                    while let Some(token) = token_iter.next() {
-                        match token {
-                            proc_macro2::TokenTree::Ident(ident) => {
-                                if current_param.is_empty() && !parsing_type {
-                                    // This is the start of a parameter name
-                                    current_param = ident.to_string();
-                                    param_start_span = Some(ident.span());
-                                } else if parsing_type {
-                                    // This is part of the type
-                                    current_type.push_str(&ident.to_string());
-                                }
-                            }
-                            proc_macro2::TokenTree::Punct(punct) => {
-                                if punct.as_char() == ':'
-                                    && !current_param.is_empty()
-                                    && !parsing_type
-                                {
-                                    parsing_type = true;
-                                } else if punct.as_char() == ',' && parsing_type {
-                                    // End of this parameter
-                                    if let Some(span) = param_start_span {
-                                        parameters.push((
-                                            current_param.clone(),
-                                            current_type.trim().to_string(),
-                                            span,
-                                        ));
-                                    }
-                                    current_param.clear();
-                                    current_type.clear();
-                                    param_start_span = None;
-                                    parsing_type = false;
-                                } else if parsing_type {
-                                    // Add punctuation to type (for things like &str, Vec<T>, etc.)
-                                    current_type.push(punct.as_char());
-                                }
+            if attr.path().is_ident("instruction")
+                && let syn::Meta::List(meta_list) = &attr.meta
+            {
+                // Parse the tokens to get individual parameter spans
+                let tokens = &meta_list.tokens;
+                let token_iter = tokens.clone().into_iter();
+                let mut current_param = String::new();
+                let mut current_type = String::new();
+                let mut param_start_span: Option<proc_macro2::Span> = None;
+                let mut parsing_type = false;
+
+                for token in token_iter {
+                    match token {
+                        proc_macro2::TokenTree::Ident(ident) => {
+                            if current_param.is_empty() && !parsing_type {
+                                // This is the start of a parameter name
+                                current_param = ident.to_string();
+                                param_start_span = Some(ident.span());
+                            } else if parsing_type {
+                                // This is part of the type
+                                current_type.push_str(&ident.to_string());
                             }
-                            proc_macro2::TokenTree::Group(group) => {
-                                if parsing_type {
-                                    // Add group content to type (for generics, etc.)
-                                    let (open_char, close_char) = match group.delimiter() {
-                                        proc_macro2::Delimiter::Parenthesis => ('(', ')'),
-                                        proc_macro2::Delimiter::Brace => ('{', '}'),
-                                        proc_macro2::Delimiter::Bracket => ('[', ']'),
-                                        proc_macro2::Delimiter::None => (' ', ' '),
-                                    };
-                                    current_type.push_str(&format!(
-                                        "{}{}{}",
-                                        open_char,
-                                        group.stream().to_string(),
-                                        close_char
+                        }
+                        proc_macro2::TokenTree::Punct(punct) => {
+                            if punct.as_char() == ':' && !current_param.is_empty() && !parsing_type
+                            {
+                                parsing_type = true;
+                            } else if punct.as_char() == ',' && parsing_type {
+                                // End of this parameter
+                                if let Some(span) = param_start_span {
+                                    parameters.push((
+                                        current_param.clone(),
+                                        current_type.trim().to_string(),
+                                        span,
                                     ));
                                 }
+                                current_param.clear();
+                                current_type.clear();
+                                param_start_span = None;
+                                parsing_type = false;
+                            } else if parsing_type {
+                                // Add punctuation to type (for things like &str, Vec<T>, etc.)
+                                current_type.push(punct.as_char());
                             }
-                            _ => {}
                         }
-                    }
-
-                    // Handle the last parameter if we ended without a comma
-                    if !current_param.is_empty() && parsing_type {
-                        if let Some(span) = param_start_span {
-                            parameters.push((current_param, current_type.trim().to_string(), span));
+                        proc_macro2::TokenTree::Group(group) if parsing_type => {
+                            // Add group content to type (for generics, etc.)
+                            let (open_char, close_char) = match group.delimiter() {
+                                proc_macro2::Delimiter::Parenthesis => ('(', ')'),
+                                proc_macro2::Delimiter::Brace => ('{', '}'),
+                                proc_macro2::Delimiter::Bracket => ('[', ']'),
+                                proc_macro2::Delimiter::None => (' ', ' '),
+                            };
+                            current_type.push_str(&format!(
+                                "{}{}{}",
+                                open_char,
+                                group.stream(),
+                                close_char
+                            ));
                         }
+                        _ => {}
                     }
                 }
+
+                // Handle the last parameter if we ended without a comma
+                if !current_param.is_empty()
+                    && parsing_type
+                    && let Some(span) = param_start_span
+                {
+                    parameters.push((current_param, current_type.trim().to_string(), span));
+                }
             }
         }
 
@@ -170,12 +532,12 @@ impl AnchorPatterns {
 
     /// Check if a field type is AccountInfo or UncheckedAccount
     pub fn is_unchecked_account_type(field: &syn::Field) -> Option<String> {
-        if let syn::Type::Path(syn::TypePath { path, .. }) = &field.ty {
-            if let Some(segment) = path.segments.last() {
-                let type_name = segment.ident.to_string();
-                if type_name == "AccountInfo" || type_name == "UncheckedAccount" {
-                    return Some(type_name);
-                }
+        if let syn::Type::Path(syn::TypePath { path, .. }) = &field.ty
+            && let Some(segment) = path.segments.last()
+        {
+            let type_name = segment.ident.to_string();
+            if type_name == "AccountInfo" || type_name == "UncheckedAccount" {
+                return Some(type_name);
             }
         }
         None
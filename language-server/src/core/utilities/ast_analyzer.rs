@@ -1,3 +1,5 @@
+use crate::core::utilities::line_index::LineIndex;
+use crate::core::utilities::position_encoding::PositionEncoding;
 use proc_macro2::Span;
 use syn::File;
 use syn::spanned::Spanned;
@@ -13,22 +15,27 @@ impl AstAnalyzer {
         syn::parse_str::<File>(content)
     }
 
-    /// Convert a proc_macro2::Span to LSP Range (simplified for now)
-    pub fn span_to_range(_content: &str, _span: Span) -> Range {
-        // For now, return a simple range. In a full implementation,
-        // you would need to track source positions more carefully
-        Range {
-            start: Position {
-                line: 0,
-                character: 0,
-            },
-            end: Position {
-                line: 0,
-                character: 10,
-            },
+    /// Convert a proc_macro2::Span to LSP Range
+    /// Builds a line index for the content; use `span_to_range_with_lines` to reuse one
+    #[allow(dead_code)]
+    pub fn span_to_range(content: &str, span: Span) -> Range {
+        Self::span_to_range_with_lines(content, span, None)
+    }
+
+    /// Convert a proc_macro2::Span to LSP Range using a precomputed line index if given
+    pub fn span_to_range_with_lines(content: &str, span: Span, lines: Option<&LineIndex>) -> Range {
+        match lines {
+            Some(lines) => lines.span_to_range(span),
+            None => LineIndex::new(content).span_to_range(span),
         }
     }
 
+    /// Convert a proc_macro2::Span to LSP Range with columns in the negotiated encoding
+    #[allow(dead_code)]
+    pub fn span_to_range_encoded(content: &str, span: Span, encoding: PositionEncoding) -> Range {
+        LineIndex::new(content).span_to_range_encoded(content, span, encoding)
+    }
+
     /// Get the span of any spanned AST node
     pub fn get_span<T: Spanned>(node: &T) -> Span {
         node.span()
@@ -37,22 +44,98 @@ impl AstAnalyzer {
     /// Convert byte offset to line/column position
     #[allow(dead_code)]
     pub fn byte_offset_to_position(content: &str, offset: usize) -> Position {
-        let mut line = 0u32;
-        let mut character = 0u32;
+        LineIndex::new(content).position(offset)
+    }
 
-        for (i, ch) in content.char_indices() {
-            if i >= offset {
-                break;
+    /// Check if attributes mark an item as test-only (`#[cfg(test)]`, `#[test]`, `#[tokio::test]`)
+    pub fn is_test_only(attrs: &[syn::Attribute]) -> bool {
+        attrs.iter().any(|attr| {
+            let path = attr.path();
+            if path.is_ident("cfg") {
+                return attr
+                    .parse_args::<syn::Ident>()
+                    .is_ok_and(|ident| ident == "test");
             }
-            if ch == '\n' {
-                line += 1;
-                character = 0;
-            } else {
-                character += 1;
+
+            path.segments
+                .last()
+                .is_some_and(|segment| segment.ident == "test")
+        })
+    }
+
+    /// Peel common wrappers so we can reason about the "real" expression:
+    /// - Parentheses: ( .. )
+    /// - References: &expr
+    /// - Deref: *expr
+    /// - Try: expr?  (Expr::Try)
+    pub fn strip_wrappers(mut expr: &syn::Expr) -> &syn::Expr {
+        use syn::{Expr, UnOp};
+
+        loop {
+            expr = match expr {
+                Expr::Paren(p) => &p.expr,
+                Expr::Reference(r) => &r.expr,
+                Expr::Unary(u) if matches!(u.op, UnOp::Deref(_)) => &u.expr, // *
+                Expr::Try(t) => &t.expr,                                     // ?
+                _ => break expr,
+            };
+        }
+    }
+
+    /// Split a place expression into its root local and the members accessed on it, in order
+    /// e.g. `ctx.accounts.vault.load_mut()?.owner` is `(Some("ctx"), ["accounts", "vault", "load_mut", "owner"])`
+    pub fn member_chain(mut expr: &syn::Expr) -> (Option<String>, Vec<String>) {
+        use syn::Expr;
+
+        let mut members = Vec::new();
+        let root = loop {
+            expr = match expr {
+                Expr::Field(field) => {
+                    if let syn::Member::Named(name) = &field.member {
+                        members.push(name.to_string());
+                    }
+                    &field.base
+                }
+                Expr::MethodCall(call) => {
+                    members.push(call.method.to_string());
+                    &call.receiver
+                }
+                Expr::Paren(paren) => &paren.expr,
+                Expr::Reference(reference) => &reference.expr,
+                Expr::Unary(unary) => &unary.expr,
+                Expr::Try(expr_try) => &expr_try.expr,
+                Expr::Path(path) => break path.path.get_ident().map(|ident| ident.to_string()),
+                _ => break None,
+            };
+        };
+
+        // Members were collected from the outside in
+        members.reverse();
+        (root, members)
+    }
+
+    /// Remove test-only attributes from modules and functions
+    /// Detectors then analyze test code like any other code
+    pub fn strip_test_attrs(file: &mut File) {
+        use syn::visit_mut::VisitMut;
+
+        struct TestAttrStripper;
+
+        impl VisitMut for TestAttrStripper {
+            fn visit_item_mod_mut(&mut self, node: &mut syn::ItemMod) {
+                node.attrs
+                    .retain(|attr| !AstAnalyzer::is_test_only(std::slice::from_ref(attr)));
+                syn::visit_mut::visit_item_mod_mut(self, node);
+            }
+
+            fn visit_item_fn_mut(&mut self, node: &mut syn::ItemFn) {
+                node.attrs
+                    .retain(|attr| !AstAnalyzer::is_test_only(std::slice::from_ref(attr)));
+                syn::visit_mut::visit_item_fn_mut(self, node);
             }
         }
 
-        Position { line, character }
+        TestAttrStripper.visit_file_mut(file);
     }
 
     /// Check if a string contains any of the given patterns
@@ -0,0 +1,106 @@
+use super::{DiagnosticBuilder, LineIndex};
+use log::warn;
+use regex::Regex;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+
+/// Organization-specific regex rules configured for a detector
+/// (see `DetectorConfig::custom_regex_patterns`)
+#[derive(Debug, Clone, Default)]
+pub struct CustomRegexPatterns {
+    patterns: Vec<Regex>,
+}
+
+impl CustomRegexPatterns {
+    /// Compile the patterns, logging and skipping any that are not valid regexes
+    pub fn compile(patterns: &[String]) -> Self {
+        let patterns = patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!("Ignoring invalid custom regex pattern '{}': {}", pattern, e);
+                    None
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Check if a line is a comment or an import, which custom patterns never match
+    /// Lines inside a multi-line block comment are tracked by `ends_in_block_comment`
+    fn is_skipped_line(line: &str) -> bool {
+        let line = line.trim_start();
+        ["//", "/*", "use ", "pub use ", "extern crate "]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+    }
+
+    /// Whether a `/* ... */` comment is still open at the end of `line`
+    fn ends_in_block_comment(line: &str, mut in_comment: bool) -> bool {
+        let mut rest = line;
+        loop {
+            if in_comment {
+                let Some(end) = rest.find("*/") else {
+                    return true;
+                };
+                in_comment = false;
+                rest = &rest[end + 2..];
+            } else {
+                let line_comment = rest.find("//");
+                match rest.find("/*") {
+                    Some(start) if line_comment.is_none_or(|comment| start < comment) => {
+                        in_comment = true;
+                        rest = &rest[start + 2..];
+                    }
+                    _ => return false,
+                }
+            }
+        }
+    }
+
+    /// One diagnostic per match on a code line, with `code` as its diagnostic code
+    pub fn diagnostics(
+        &self,
+        content: &str,
+        lines: &LineIndex,
+        code: &str,
+        severity: DiagnosticSeverity,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut line_start = 0;
+        let mut in_block_comment = false;
+
+        for line in content.split_inclusive('\n') {
+            let offset = line_start;
+            line_start += line.len();
+            let skipped = in_block_comment || Self::is_skipped_line(line);
+            in_block_comment = Self::ends_in_block_comment(line, in_block_comment);
+            if skipped {
+                continue;
+            }
+
+            let line = line.trim_end_matches(['\n', '\r']);
+            for regex in &self.patterns {
+                for found in regex.find_iter(line) {
+                    let range = Range::new(
+                        lines.position(offset + found.start()),
+                        lines.position(offset + found.end()),
+                    );
+                    diagnostics.push(DiagnosticBuilder::create(
+                        range,
+                        format!("Matches custom pattern `{}`", regex.as_str()),
+                        severity,
+                        code.to_string(),
+                        None,
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
@@ -1,11 +1,18 @@
 use crate::core::utilities::ast_analyzer::AstAnalyzer;
+use crate::core::utilities::line_index::LineIndex;
+use crate::core::utilities::position_encoding::PositionEncoding;
 use proc_macro2::Span;
 use std::path::Path;
 use syn::spanned::Spanned;
 use tower_lsp::lsp_types::{
-    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Position, Range, Url,
+    CodeDescription, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location,
+    NumberOrString, Position, Range, Url,
 };
 
+/// Page documenting the detectors, with a heading per detector whose anchor is its `doc_slug`
+pub const DETECTOR_DOCS_URL: &str =
+    "https://github.com/Ackee-Blockchain/solana-vscode/blob/main/README.md";
+
 /// Enhanced diagnostic builder with span-aware functionality
 pub struct DiagnosticBuilder;
 
@@ -28,7 +35,36 @@ impl DiagnosticBuilder {
         }
     }
 
+    /// Create a diagnostic linking to the documentation of its detector
+    pub fn create_with_code_description(
+        range: Range,
+        message: String,
+        severity: DiagnosticSeverity,
+        code: String,
+        source: Option<String>,
+    ) -> Diagnostic {
+        let code_description = Self::code_description(&code);
+        Diagnostic {
+            code_description,
+            ..Self::create(range, message, severity, code, source)
+        }
+    }
+
+    /// Documentation anchor of a detector, e.g. `missing-signer` for `MISSING_SIGNER`
+    /// Derived from the id so links stay valid as long as the id does; dylint lint
+    /// names (`missing_signer`) map to the same slug
+    pub fn doc_slug(code: &str) -> String {
+        code.to_lowercase().replace('_', "-")
+    }
+
+    /// Link to the documentation of a detector
+    pub fn code_description(code: &str) -> Option<CodeDescription> {
+        let href = Url::parse(&format!("{}#{}", DETECTOR_DOCS_URL, Self::doc_slug(code))).ok()?;
+        Some(CodeDescription { href })
+    }
+
     /// Create a diagnostic with related information
+    #[allow(clippy::too_many_arguments)]
     pub fn create_with_related(
         range: Range,
         message: String,
@@ -88,22 +124,25 @@ impl DiagnosticBuilder {
         (primary, related)
     }
 
-    /// Create a diagnostic from a span
+    /// Create a diagnostic from a span of `content`
+    /// `lines` is the caller's index of `content`; without one, an index is built for the call
     pub fn create_from_span(
         content: &str,
+        lines: Option<&LineIndex>,
         span: Span,
         message: String,
         severity: DiagnosticSeverity,
         code: String,
     ) -> Diagnostic {
-        let range = AstAnalyzer::span_to_range(content, span);
+        let range = AstAnalyzer::span_to_range_with_lines(content, span, lines);
         Self::create(range, message, severity, code, None)
     }
 
     /// Create a diagnostic from a span with related information
-    #[allow(dead_code)]
+    #[allow(dead_code, clippy::too_many_arguments)]
     pub fn create_from_span_with_related(
         content: &str,
+        lines: Option<&LineIndex>,
         span: Span,
         message: String,
         severity: DiagnosticSeverity,
@@ -112,8 +151,8 @@ impl DiagnosticBuilder {
         related_message: String,
         file_path: &Path,
     ) -> Diagnostic {
-        let range = AstAnalyzer::span_to_range(content, span);
-        let related_range = AstAnalyzer::span_to_range(content, related_span);
+        let range = AstAnalyzer::span_to_range_with_lines(content, span, lines);
+        let related_range = AstAnalyzer::span_to_range_with_lines(content, related_span, lines);
         Self::create_with_related(
             range,
             message,
@@ -130,6 +169,7 @@ impl DiagnosticBuilder {
     #[allow(dead_code, clippy::too_many_arguments)]
     pub fn create_from_spans_with_bidirectional_relation(
         content: &str,
+        lines: Option<&LineIndex>,
         primary_span: Span,
         primary_message: String,
         related_span: Span,
@@ -140,8 +180,8 @@ impl DiagnosticBuilder {
         code: String,
         file_path: &Path,
     ) -> (Diagnostic, Diagnostic) {
-        let primary_range = AstAnalyzer::span_to_range(content, primary_span);
-        let related_range = AstAnalyzer::span_to_range(content, related_span);
+        let primary_range = AstAnalyzer::span_to_range_with_lines(content, primary_span, lines);
+        let related_range = AstAnalyzer::span_to_range_with_lines(content, related_span, lines);
         Self::create_with_bidirectional_relation(
             primary_range,
             primary_message,
@@ -160,13 +200,14 @@ impl DiagnosticBuilder {
     #[allow(dead_code)]
     pub fn from_spanned<T: Spanned>(
         content: &str,
+        lines: Option<&LineIndex>,
         node: &T,
         message: String,
         severity: DiagnosticSeverity,
         code: String,
     ) -> Diagnostic {
         let span = AstAnalyzer::get_span(node);
-        Self::create_from_span(content, span, message, severity, code)
+        Self::create_from_span(content, lines, span, message, severity, code)
     }
 
     /// Create a range from line and character positions
@@ -187,6 +228,12 @@ impl DiagnosticBuilder {
         Self::create_range(line, 0, line, 200)
     }
 
+    /// Create a range from a span using the line index of its file, with columns counted in chars
+    /// Detectors use the index handed to `Detector::analyze_ast`, so each lookup is a binary search
+    pub fn create_range_from_span_with_lines(span: Span, lines: &LineIndex) -> Range {
+        lines.span_to_range(span)
+    }
+
     pub fn create_range_from_span(span: Span) -> Range {
         Range {
             start: Position {
@@ -211,4 +258,65 @@ impl DiagnosticBuilder {
         let range = Self::create_range(line, 0, line, 100);
         Self::create(range, message, severity, code, None)
     }
+
+    /// Convert diagnostic ranges of `content` from char columns to `encoding` code units
+    /// Applied once, right before diagnostics leave the server
+    pub fn encode_positions(
+        diagnostics: &mut [Diagnostic],
+        content: &str,
+        encoding: PositionEncoding,
+    ) {
+        if content.is_ascii() {
+            return;
+        }
+
+        let lines = LineIndex::new(content);
+        let encode = |position: &mut Position| {
+            if let Some(line) = lines.line_text(content, position.line) {
+                position.character = encoding.column(line, position.character);
+            }
+        };
+        for diagnostic in diagnostics {
+            encode(&mut diagnostic.range.start);
+            encode(&mut diagnostic.range.end);
+        }
+    }
+
+    /// Combine syn and dylint diagnostics for one file into the list to publish
+    /// See `normalize`; on duplicates the syn diagnostic is kept
+    pub fn merge_and_normalize(syn: Vec<Diagnostic>, dylint: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let mut diagnostics = syn;
+        diagnostics.extend(dylint);
+        Self::normalize(diagnostics)
+    }
+
+    /// Drop duplicates (same range, code and message) and sort by position, then code
+    /// Publishing the same set in the same order every time keeps the editor from flickering
+    pub fn normalize(mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        fn code(diagnostic: &Diagnostic) -> String {
+            match &diagnostic.code {
+                Some(NumberOrString::String(code)) => code.clone(),
+                Some(NumberOrString::Number(code)) => code.to_string(),
+                None => String::new(),
+            }
+        }
+
+        // Stable, so the first of several duplicates stays in front
+        diagnostics.sort_by_cached_key(|diagnostic| {
+            (
+                diagnostic.range.start.line,
+                diagnostic.range.start.character,
+                code(diagnostic),
+                diagnostic.range.end.line,
+                diagnostic.range.end.character,
+                diagnostic.message.clone(),
+            )
+        });
+        diagnostics.dedup_by(|later, earlier| {
+            later.range == earlier.range
+                && later.code == earlier.code
+                && later.message == earlier.message
+        });
+        diagnostics
+    }
 }
@@ -0,0 +1,95 @@
+use crate::core::utilities::position_encoding::PositionEncoding;
+use std::collections::HashMap;
+use tower_lsp::lsp_types::{Position, TextDocumentContentChangeEvent, Url};
+
+/// Latest content and version of a document the client has open
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    pub text: String,
+    /// Version of the last applied change, as sent by the client
+    pub version: i32,
+}
+
+/// In-memory contents of open documents, kept current with incremental changes
+#[derive(Debug, Clone, Default)]
+pub struct DocumentStore {
+    documents: HashMap<Url, Document>,
+}
+
+impl DocumentStore {
+    /// Store the full content of a document, replacing any previous content
+    pub fn insert(&mut self, uri: Url, text: String, version: i32) {
+        self.documents.insert(uri, Document { text, version });
+    }
+
+    /// Replace the content of a document, keeping its version (e.g. on save)
+    pub fn set_text(&mut self, uri: Url, text: String) {
+        self.documents.entry(uri).or_default().text = text;
+    }
+
+    pub fn get(&self, uri: &Url) -> Option<&Document> {
+        self.documents.get(uri)
+    }
+
+    pub fn remove(&mut self, uri: &Url) -> Option<Document> {
+        self.documents.remove(uri)
+    }
+
+    /// Apply changes in order and return the updated document
+    /// Change ranges count columns in the negotiated `encoding`. Changes to a document that was
+    /// never opened are ignored unless one replaces the full text
+    pub fn apply_changes(
+        &mut self,
+        uri: &Url,
+        version: i32,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        encoding: PositionEncoding,
+    ) -> Option<&Document> {
+        if !self.documents.contains_key(uri) && changes.iter().any(|change| change.range.is_none())
+        {
+            self.documents.insert(uri.clone(), Document::default());
+        }
+
+        let document = self.documents.get_mut(uri)?;
+        for change in changes {
+            apply_change(&mut document.text, change, encoding);
+        }
+        document.version = version;
+        Some(document)
+    }
+}
+
+/// Apply a single change: a range edit, or a full replacement if it has no range
+pub fn apply_change(
+    text: &mut String,
+    change: TextDocumentContentChangeEvent,
+    encoding: PositionEncoding,
+) {
+    let Some(range) = change.range else {
+        *text = change.text;
+        return;
+    };
+
+    let start = byte_offset(text, range.start, encoding);
+    // Clients never send reversed ranges, but a bad one must not panic
+    let end = byte_offset(text, range.end, encoding).max(start);
+    text.replace_range(start..end, &change.text);
+}
+
+/// Convert a position with a column in `encoding` code units to a byte offset
+/// Positions past the end of a line or the document are clamped to it
+pub fn byte_offset(text: &str, position: Position, encoding: PositionEncoding) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |newline| line_start + newline);
+
+    line_start + encoding.byte_offset(&text[line_start..line_end], position.character)
+}
@@ -0,0 +1,141 @@
+use crate::core::utilities::position_encoding::PositionEncoding;
+use proc_macro2::Span;
+use std::sync::Arc;
+use tower_lsp::lsp_types::{Position, Range};
+
+/// Byte offsets of line starts and multi-byte chars, built once per file
+///
+/// Converting an offset to a position is a binary search over the line starts
+/// instead of a scan from the start of the file. Columns are counted in chars
+/// from the recorded multi-byte chars, so no lookup needs the content itself.
+/// Clones share the tables, so detectors can keep the index of the file they visit.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Arc<[usize]>,
+    /// Byte offset of each multi-byte char and the extra bytes of all chars up to and including it
+    wide_chars: Arc<[(usize, usize)]>,
+    len: usize,
+}
+
+impl Default for LineIndex {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl LineIndex {
+    pub fn new(content: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            content
+                .bytes()
+                .enumerate()
+                .filter(|(_, byte)| *byte == b'\n')
+                .map(|(i, _)| i + 1),
+        );
+
+        let mut extra = 0;
+        let wide_chars: Vec<_> = content
+            .char_indices()
+            .filter(|(_, ch)| !ch.is_ascii())
+            .map(|(i, ch)| {
+                extra += ch.len_utf8() - 1;
+                (i, extra)
+            })
+            .collect();
+
+        Self {
+            line_starts: line_starts.into(),
+            wide_chars: wide_chars.into(),
+            len: content.len(),
+        }
+    }
+
+    /// Number of lines in the indexed content
+    #[allow(dead_code)]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Extra bytes of the multi-byte chars starting before `offset`
+    fn extra_bytes_before(&self, offset: usize) -> usize {
+        match self
+            .wide_chars
+            .partition_point(|&(start, _)| start < offset)
+        {
+            0 => 0,
+            i => self.wide_chars[i - 1].1,
+        }
+    }
+
+    /// Convert a byte offset to a line/column position (column counted in chars)
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.len);
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1);
+        let line_start = self.line_starts[line];
+        let extra = self.extra_bytes_before(offset) - self.extra_bytes_before(line_start);
+        let character = (offset - line_start).saturating_sub(extra);
+
+        Position {
+            line: line as u32,
+            character: character as u32,
+        }
+    }
+
+    /// Convert a byte offset to a position with the column counted in `encoding` code units
+    pub fn position_encoded(
+        &self,
+        content: &str,
+        offset: usize,
+        encoding: PositionEncoding,
+    ) -> Position {
+        let position = self.position(offset);
+        let line_start = self.line_starts[position.line as usize];
+        let character = content
+            .get(line_start..offset.min(content.len()))
+            .map_or(position.character, |text| encoding.len(text) as u32);
+
+        Position {
+            line: position.line,
+            character,
+        }
+    }
+
+    /// Text of a line without its line ending
+    pub fn line_text<'a>(&self, content: &'a str, line: u32) -> Option<&'a str> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = self
+            .line_starts
+            .get(line as usize + 1)
+            .map_or(content.len(), |next| next - 1);
+        content
+            .get(start..end)
+            .map(|text| text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// Convert a span of the indexed content to a range
+    pub fn span_to_range(&self, span: Span) -> Range {
+        let bytes = span.byte_range();
+        Range {
+            start: self.position(bytes.start),
+            end: self.position(bytes.end),
+        }
+    }
+
+    /// Convert a span of the indexed content to a range with columns in `encoding` code units
+    pub fn span_to_range_encoded(
+        &self,
+        content: &str,
+        span: Span,
+        encoding: PositionEncoding,
+    ) -> Range {
+        let bytes = span.byte_range();
+        Range {
+            start: self.position_encoded(content, bytes.start, encoding),
+            end: self.position_encoded(content, bytes.end, encoding),
+        }
+    }
+}
@@ -1,5 +1,23 @@
 pub mod anchor_patterns;
 pub mod ast_analyzer;
+pub mod custom_patterns;
 pub mod diagnostic_builder;
+pub mod document_store;
+pub mod line_index;
+pub mod path_normalizer;
+pub mod position_encoding;
+pub mod publish_versions;
+pub mod size_estimator;
+pub mod stable_hash;
+pub mod workspace_files;
 
+pub use custom_patterns::*;
 pub use diagnostic_builder::*;
+pub use document_store::*;
+pub use line_index::*;
+pub use path_normalizer::*;
+pub use position_encoding::*;
+pub use publish_versions::*;
+pub use size_estimator::*;
+pub use stable_hash::*;
+pub use workspace_files::*;
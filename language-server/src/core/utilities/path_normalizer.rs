@@ -0,0 +1,77 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::Url;
+
+/// Maps file paths to URIs that match the ones the client opened documents with
+///
+/// The workspace root is canonicalized (symlinks resolved) so that paths coming from
+/// the scanner, cargo and dylint agree with each other. When publishing, canonical paths
+/// under the workspace are rewritten back onto the root the client reported, so a
+/// symlinked workspace still produces URIs matching the client's open documents.
+#[derive(Debug, Clone)]
+pub struct PathNormalizer {
+    /// Workspace root as reported by the client
+    client_root: Option<PathBuf>,
+    /// Canonicalized workspace root
+    canonical_root: Option<PathBuf>,
+    /// Whether canonicalization is enabled
+    enabled: bool,
+}
+
+impl Default for PathNormalizer {
+    fn default() -> Self {
+        Self {
+            client_root: None,
+            canonical_root: None,
+            enabled: true,
+        }
+    }
+}
+
+impl PathNormalizer {
+    /// Create a normalizer for the given client workspace root
+    /// With `enabled` set to false, paths are passed through unchanged
+    pub fn new(client_root: PathBuf, enabled: bool) -> Self {
+        let canonical_root = if enabled {
+            fs::canonicalize(&client_root).ok()
+        } else {
+            None
+        };
+
+        Self {
+            client_root: Some(client_root),
+            canonical_root,
+            enabled,
+        }
+    }
+
+    /// Workspace root to use for scanning and running cargo
+    /// This is the canonical root when canonicalization is enabled
+    pub fn workspace_root(&self) -> Option<PathBuf> {
+        self.canonical_root
+            .clone()
+            .or_else(|| self.client_root.clone())
+    }
+
+    /// Map a path onto the workspace root the client knows about
+    pub fn to_client_path(&self, path: &Path) -> PathBuf {
+        if !self.enabled {
+            return path.to_path_buf();
+        }
+
+        let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+        if let (Some(client_root), Some(canonical_root)) = (&self.client_root, &self.canonical_root)
+            && let Ok(relative) = canonical.strip_prefix(canonical_root)
+        {
+            return client_root.join(relative);
+        }
+
+        canonical
+    }
+
+    /// Convert a path to the URI published to the client
+    pub fn to_uri(&self, path: &Path) -> Option<Url> {
+        Url::from_file_path(self.to_client_path(path)).ok()
+    }
+}
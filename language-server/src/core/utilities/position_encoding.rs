@@ -0,0 +1,125 @@
+use tower_lsp::lsp_types::{Position, PositionEncodingKind, Range};
+
+/// Unit in which LSP position columns are counted, negotiated with the client in `initialize`
+///
+/// Detectors count columns in chars; positions are converted to this encoding before publishing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PositionEncoding {
+    Utf8,
+    /// The LSP default, used when the client offers nothing else
+    #[default]
+    Utf16,
+}
+
+impl PositionEncoding {
+    /// Pick UTF-8 if the client offers it (`general.positionEncodings`), otherwise UTF-16
+    pub fn negotiate(offered: Option<&[PositionEncodingKind]>) -> Self {
+        if offered.is_some_and(|kinds| kinds.contains(&PositionEncodingKind::UTF8)) {
+            Self::Utf8
+        } else {
+            Self::Utf16
+        }
+    }
+
+    /// Kind announced to the client in the server capabilities
+    pub fn kind(self) -> PositionEncodingKind {
+        match self {
+            Self::Utf8 => PositionEncodingKind::UTF8,
+            Self::Utf16 => PositionEncodingKind::UTF16,
+        }
+    }
+
+    /// Length of `text` in code units of this encoding
+    pub fn len(self, text: &str) -> usize {
+        match self {
+            Self::Utf8 => text.len(),
+            Self::Utf16 => text.encode_utf16().count(),
+        }
+    }
+
+    /// Length of `ch` in code units of this encoding
+    fn char_len(self, ch: char) -> usize {
+        match self {
+            Self::Utf8 => ch.len_utf8(),
+            Self::Utf16 => ch.len_utf16(),
+        }
+    }
+
+    /// Byte offset in `line` of a column counted in this encoding
+    /// A column inside a character moves to the end of it; columns past the end are clamped
+    pub fn byte_offset(self, line: &str, column: u32) -> usize {
+        let mut units = 0;
+        for (offset, ch) in line.char_indices() {
+            if units >= column as usize {
+                return offset;
+            }
+            units += self.char_len(ch);
+        }
+        line.len()
+    }
+
+    /// Convert a column counted in this encoding on `line` to a column counted in chars
+    /// The inverse of `column`; columns past the end of the line are kept as they are
+    pub fn char_column(self, line: &str, column: u32) -> u32 {
+        if line.is_ascii() {
+            return column;
+        }
+
+        let offset = self.byte_offset(line, column);
+        let chars = line[..offset].chars().count() as u32;
+        if offset == line.len() {
+            chars + column.saturating_sub(self.len(line) as u32)
+        } else {
+            chars
+        }
+    }
+
+    /// Convert a client position in this encoding on `content` to one with a char column
+    pub fn decode_position(self, content: &str, position: Position) -> Position {
+        match line_text(content, position.line) {
+            Some(line) => Position::new(position.line, self.char_column(line, position.character)),
+            None => position,
+        }
+    }
+
+    /// Convert a position on `content` with a char column to one with a column in this encoding
+    pub fn encode_position(self, content: &str, position: Position) -> Position {
+        match line_text(content, position.line) {
+            Some(line) => Position::new(position.line, self.column(line, position.character)),
+            None => position,
+        }
+    }
+
+    /// Convert a range on `content` with char columns to one with columns in this encoding
+    pub fn encode_range(self, content: &str, range: Range) -> Range {
+        Range::new(
+            self.encode_position(content, range.start),
+            self.encode_position(content, range.end),
+        )
+    }
+
+    /// Convert a column counted in chars on `line` to a column in this encoding
+    /// Columns past the end of the line are kept as they are
+    pub fn column(self, line: &str, char_column: u32) -> u32 {
+        // Cheap path for the common all-ASCII line
+        if line.is_ascii() {
+            return char_column;
+        }
+
+        match line.char_indices().nth(char_column as usize) {
+            Some((byte_offset, _)) => self.len(&line[..byte_offset]) as u32,
+            None => {
+                let chars = line.chars().count() as u32;
+                self.len(line) as u32 + char_column.saturating_sub(chars)
+            }
+        }
+    }
+}
+
+/// Text of a line of `content` without its line ending
+fn line_text(content: &str, line: u32) -> Option<&str> {
+    content
+        .split('\n')
+        .nth(line as usize)
+        .map(|text| text.strip_suffix('\r').unwrap_or(text))
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+use tower_lsp::lsp_types::{Diagnostic, Url};
+
+/// Per-file counter of published diagnostics
+///
+/// Every publish for a file gets the next version, so a result computed against older
+/// diagnostics (e.g. a slow dylint run) can tell that it would overwrite newer ones.
+/// The counter stays in the server; it is not the document version sent to the client.
+/// The diagnostics of the last publish are kept too, so hovers match what the client shows.
+#[derive(Debug, Clone, Default)]
+pub struct PublishVersions {
+    versions: HashMap<Url, i32>,
+    published: HashMap<Url, Vec<Diagnostic>>,
+}
+
+impl PublishVersions {
+    /// Version of the last diagnostics published for a file, 0 if none were
+    pub fn current(&self, uri: &Url) -> i32 {
+        self.versions.get(uri).copied().unwrap_or(0)
+    }
+
+    /// Advance the version of a file and return it
+    pub fn next(&mut self, uri: &Url) -> i32 {
+        let version = self.versions.entry(uri.clone()).or_insert(0);
+        *version += 1;
+        *version
+    }
+
+    /// Advance the version only if nothing was published since `expected`
+    /// Returns `None` if the file has moved on and the result should be dropped
+    pub fn next_if_current(&mut self, uri: &Url, expected: i32) -> Option<i32> {
+        (self.current(uri) == expected).then(|| self.next(uri))
+    }
+
+    /// Keep the diagnostics just published for a file, replacing the previous ones
+    pub fn record(&mut self, uri: &Url, diagnostics: &[Diagnostic]) {
+        self.published.insert(uri.clone(), diagnostics.to_vec());
+    }
+
+    /// Diagnostics of the last publish for a file, in the client's position encoding
+    pub fn published(&self, uri: &Url) -> &[Diagnostic] {
+        self.published
+            .get(uri)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,114 @@
+use syn::{Expr, Fields, GenericArgument, Lit, PathArguments, Type};
+
+/// Estimates the Borsh-serialized size of Anchor account data
+pub struct SizeEstimator;
+
+impl SizeEstimator {
+    /// Estimate the serialized size of a struct's fields
+    /// Fields of unknown size count as 0, so the result is a lower bound
+    pub fn estimate_fields(fields: &Fields) -> usize {
+        fields
+            .iter()
+            .map(|field| Self::estimate_field(field).unwrap_or(0))
+            .sum()
+    }
+
+    /// Compute the serialized size of a struct's fields
+    /// Returns `None` if any field's size is unknown
+    pub fn exact_fields_size(fields: &Fields) -> Option<usize> {
+        fields.iter().map(Self::estimate_field).sum()
+    }
+
+    /// Estimate the serialized size of a field, taking `#[max_len(..)]` into account
+    pub fn estimate_field(field: &syn::Field) -> Option<usize> {
+        let max_lens = Self::max_len(field);
+        Self::estimate_type_with_max_len(&field.ty, &max_lens)
+    }
+
+    /// Estimate the serialized size of a type with a known fixed size
+    #[allow(dead_code)]
+    pub fn estimate_type(ty: &Type) -> Option<usize> {
+        Self::estimate_type_with_max_len(ty, &[])
+    }
+
+    /// Estimate a type, consuming one `max_len` entry per level of `Vec`/`String` nesting
+    fn estimate_type_with_max_len(ty: &Type, max_lens: &[usize]) -> Option<usize> {
+        match ty {
+            Type::Array(array) => {
+                let len = Self::literal_usize(&array.len)?;
+                Some(Self::estimate_type_with_max_len(&array.elem, max_lens)? * len)
+            }
+            Type::Tuple(tuple) => tuple
+                .elems
+                .iter()
+                .map(|elem| Self::estimate_type_with_max_len(elem, max_lens))
+                .sum(),
+            Type::Paren(paren) => Self::estimate_type_with_max_len(&paren.elem, max_lens),
+            Type::Path(type_path) => {
+                let segment = type_path.path.segments.last()?;
+                let inner = Self::first_type_argument(&segment.arguments);
+
+                match segment.ident.to_string().as_str() {
+                    "bool" | "u8" | "i8" => Some(1),
+                    "u16" | "i16" => Some(2),
+                    "u32" | "i32" | "f32" => Some(4),
+                    "u64" | "i64" | "f64" => Some(8),
+                    "u128" | "i128" => Some(16),
+                    "Pubkey" => Some(32),
+                    "Option" => Some(1 + Self::estimate_type_with_max_len(inner?, max_lens)?),
+                    "Box" => Self::estimate_type_with_max_len(inner?, max_lens),
+                    // Length prefix plus `max_len` elements
+                    "String" => Some(4 + max_lens.first()?),
+                    "Vec" => {
+                        let (len, rest) = max_lens.split_first()?;
+                        Some(4 + len * Self::estimate_type_with_max_len(inner?, rest)?)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Read `#[max_len(a, b, ..)]` from a field
+    fn max_len(field: &syn::Field) -> Vec<usize> {
+        field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("max_len"))
+            .filter_map(|attr| {
+                attr.parse_args_with(
+                    syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated,
+                )
+                .ok()
+            })
+            .flat_map(|lens| lens.into_iter().collect::<Vec<_>>())
+            .filter_map(|len| Self::literal_usize(&len))
+            .collect()
+    }
+
+    /// Get the first generic type argument (e.g. `T` in `Vec<T>`)
+    fn first_type_argument(arguments: &PathArguments) -> Option<&Type> {
+        let PathArguments::AngleBracketed(args) = arguments else {
+            return None;
+        };
+
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+    }
+
+    /// Evaluate an integer literal expression
+    fn literal_usize(expr: &Expr) -> Option<usize> {
+        match expr {
+            Expr::Lit(expr_lit) => match &expr_lit.lit {
+                Lit::Int(lit_int) => lit_int.base10_parse().ok(),
+                _ => None,
+            },
+            Expr::Paren(paren) => Self::literal_usize(&paren.expr),
+            Expr::Group(group) => Self::literal_usize(&group.expr),
+            _ => None,
+        }
+    }
+}
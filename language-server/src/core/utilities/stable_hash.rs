@@ -0,0 +1,48 @@
+/// 64-bit FNV-1a hasher whose output is fixed across Rust releases and platforms
+///
+/// Use it for hashes that are persisted (cache keys, source and content hashes); the std
+/// `DefaultHasher` algorithm may change between releases. Input is fed as raw byte strings
+/// rather than through `Hash`, whose byte layout is not guaranteed either.
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    /// Add a byte string, prefixed with its length so `("ab", "c")` and `("a", "bc")` differ
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.write_bytes(&(bytes.len() as u64).to_le_bytes());
+        self.write_bytes(bytes);
+    }
+
+    /// Add a path, using its textual form so the hash does not depend on `OsStr` internals
+    pub fn update_path(&mut self, path: &std::path::Path) {
+        self.update(path.to_string_lossy().as_bytes());
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
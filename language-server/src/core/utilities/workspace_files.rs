@@ -0,0 +1,194 @@
+use crate::core::file_scanner::FileScanner;
+use crate::core::utilities::StableHasher;
+use log::warn;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use syn::parse_str;
+
+/// Rust files of a workspace read once, shared by detectors that resolve names across files
+///
+/// A workspace scan loads it before analyzing the first file and hands it to every detector,
+/// so analyzing a file never walks or reads the rest of the tree again.
+#[derive(Debug, Default)]
+pub struct WorkspaceFiles {
+    root: PathBuf,
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl WorkspaceFiles {
+    /// Read the given files of the workspace at `root`, skipping unreadable ones
+    pub fn read(root: PathBuf, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .filter_map(|path| match fs::read_to_string(&path) {
+                Ok(content) => Some((path, content)),
+                Err(e) => {
+                    warn!("Failed to read file {:?}: {}", path, e);
+                    None
+                }
+            })
+            .collect();
+
+        Self { root, files }
+    }
+
+    /// Read all Rust files under `root`, skipping dependency and build directories
+    pub fn load(root: &Path) -> Self {
+        let paths = FileScanner::new(root.to_path_buf()).rust_files();
+        Self::read(root.to_path_buf(), paths)
+    }
+
+    /// Files with contents that do not come from disk (e.g. a git revision)
+    pub fn from_contents(root: PathBuf, files: BTreeMap<PathBuf, String>) -> Self {
+        Self { root, files }
+    }
+
+    /// Root whose files a cross-file detector resolves names in: the Anchor workspace if
+    /// there is one, otherwise the nearest crate
+    pub fn workspace_root(file_path: &Path) -> Option<PathBuf> {
+        let ancestors = || file_path.ancestors().skip(1);
+
+        ancestors()
+            .find(|dir| dir.join("Anchor.toml").is_file())
+            .or_else(|| ancestors().find(|dir| dir.join("Cargo.toml").is_file()))
+            .map(Path::to_path_buf)
+    }
+
+    /// Cheap stamp of the Rust files under `root`, from their paths, sizes and modification
+    /// times, which changes whenever a file is added, removed or saved
+    pub fn revision(root: &Path) -> u64 {
+        let mut hasher = StableHasher::new();
+        for path in FileScanner::new(root.to_path_buf()).rust_files() {
+            hasher.update_path(&path);
+            if let Ok(metadata) = fs::metadata(&path) {
+                hasher.update(&metadata.len().to_le_bytes());
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .unwrap_or_default();
+                hasher.update(&modified.as_nanos().to_le_bytes());
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Check if every file under `root` was loaded
+    pub fn covers(&self, root: &Path) -> bool {
+        root.starts_with(&self.root)
+    }
+
+    /// Content of a loaded file
+    pub fn content(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// Loaded files with their contents, ordered by path
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.files
+            .iter()
+            .map(|(path, content)| (path.as_path(), content.as_str()))
+    }
+
+    /// Hash of the paths and contents of all loaded files
+    /// Changes whenever any file is added, removed or edited, so it keys results that
+    /// depend on the whole workspace
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        for (path, content) in &self.files {
+            hasher.update_path(path);
+            hasher.update(content.as_bytes());
+        }
+        hasher.finish()
+    }
+}
+
+/// Facts a detector collects from each workspace file (e.g. the accounts structs it defines)
+///
+/// Files are parsed once per `WorkspaceFiles` snapshot; later lookups only filter the
+/// collected facts by the workspace of the analyzed file. Workspaces the shared snapshot does
+/// not cover are loaded by the detector itself and parsed again only once their revision changes.
+#[derive(Debug)]
+pub struct WorkspaceFacts<T> {
+    /// Snapshot the shared facts were collected from
+    source: Option<Arc<WorkspaceFiles>>,
+    facts: Vec<(PathBuf, T)>,
+    /// Facts of workspaces loaded by root, with the `WorkspaceFiles::revision` they were
+    /// collected at
+    loaded: HashMap<PathBuf, (u64, Vec<(PathBuf, T)>)>,
+}
+
+impl<T> Default for WorkspaceFacts<T> {
+    fn default() -> Self {
+        Self {
+            source: None,
+            facts: Vec::new(),
+            loaded: HashMap::new(),
+        }
+    }
+}
+
+impl<T> WorkspaceFacts<T> {
+    /// Facts of the other files in the workspace of `file_path`
+    ///
+    /// Uses `shared` if it covers that workspace, otherwise the facts collected from the
+    /// workspace on disk, reloading it only if one of its files changed since.
+    /// Unparsable files contribute no facts.
+    pub fn for_file(
+        &mut self,
+        shared: Option<&Arc<WorkspaceFiles>>,
+        file_path: &Path,
+        collect: impl Fn(&syn::File) -> T,
+    ) -> impl Iterator<Item = &T> {
+        let root = WorkspaceFiles::workspace_root(file_path);
+
+        let facts: &[(PathBuf, T)] = match (&root, shared) {
+            (Some(root), Some(files)) if files.covers(root) => {
+                if !self
+                    .source
+                    .as_ref()
+                    .is_some_and(|source| Arc::ptr_eq(source, files))
+                {
+                    self.facts = Self::collect_all(files, &collect);
+                    self.source = Some(files.clone());
+                }
+                &self.facts
+            }
+            (Some(root), _) => {
+                let revision = WorkspaceFiles::revision(root);
+                if self
+                    .loaded
+                    .get(root)
+                    .is_none_or(|(collected_at, _)| *collected_at != revision)
+                {
+                    let facts = Self::collect_all(&WorkspaceFiles::load(root), &collect);
+                    self.loaded.insert(root.clone(), (revision, facts));
+                }
+                &self.loaded[root].1
+            }
+            (None, _) => &[],
+        };
+
+        // The analyzed file itself is collected from its AST, which may be newer than the disk
+        facts
+            .iter()
+            .filter(move |(path, _)| {
+                root.as_ref().is_some_and(|root| path.starts_with(root)) && path != file_path
+            })
+            .map(|(_, facts)| facts)
+    }
+
+    /// Parse every file of a snapshot and collect its facts
+    fn collect_all(files: &WorkspaceFiles, collect: impl Fn(&syn::File) -> T) -> Vec<(PathBuf, T)> {
+        files
+            .files()
+            .filter_map(|(path, content)| {
+                let syntax_tree = parse_str::<syn::File>(content).ok()?;
+                Some((path.to_path_buf(), collect(&syntax_tree)))
+            })
+            .collect()
+    }
+}
@@ -1,10 +1,17 @@
+use crate::core::utilities::{DiagnosticBuilder, PathNormalizer};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Lints reported as errors whatever their rustc level
+/// They are declared as warnings because a deny lint fails `cargo check` for the crate,
+/// which then stops crates depending on it from being linted
+const ERROR_LINTS: &[&str] = &["unchecked_math_balance"];
 
 /// Related information for a diagnostic (e.g., pointing to a field declaration or mutation site)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DylintRelatedInfo {
-    pub file_name: String,
+    /// Canonical path of the file, which may differ from the diagnostic's own file
+    pub path: PathBuf,
     pub line_start: usize,
     pub line_end: usize,
     pub column_start: usize,
@@ -15,7 +22,8 @@ pub struct DylintRelatedInfo {
 /// A diagnostic message from dylint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DylintDiagnostic {
-    pub file_name: String,
+    /// Canonical path of the file (see `parse_json_output`)
+    pub path: PathBuf,
     pub line_start: usize,
     pub line_end: usize,
     pub column_start: usize,
@@ -27,18 +35,25 @@ pub struct DylintDiagnostic {
 }
 
 impl DylintDiagnostic {
+    /// Check if the diagnostic belongs to a file, comparing canonical paths
+    pub fn is_in_file(&self, canonical_file: &Path) -> bool {
+        self.path == canonical_file
+    }
+
     /// Convert to LSP Diagnostic.
     /// `workspace_root` is used to resolve relative file paths in related information.
+    /// Related locations are published through `normalizer`, like the diagnostic's own URI.
     pub fn to_lsp_diagnostic(
         &self,
         workspace_root: Option<&Path>,
+        normalizer: &PathNormalizer,
     ) -> tower_lsp::lsp_types::Diagnostic {
         use tower_lsp::lsp_types::{
-            Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Position,
-            Range, Url,
+            Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Position, Range,
         };
 
         let severity = match self.level.as_str() {
+            _ if ERROR_LINTS.contains(&self.code.as_str()) => Some(DiagnosticSeverity::ERROR),
             "error" => Some(DiagnosticSeverity::ERROR),
             "warning" => Some(DiagnosticSeverity::WARNING),
             "note" | "help" => Some(DiagnosticSeverity::INFORMATION),
@@ -62,15 +77,14 @@ impl DylintDiagnostic {
                 .related_information
                 .iter()
                 .filter_map(|info| {
-                    let path = Path::new(&info.file_name);
-                    let abs_path = if path.is_absolute() {
-                        path.to_path_buf()
+                    let abs_path = if info.path.is_absolute() {
+                        info.path.clone()
                     } else if let Some(root) = workspace_root {
-                        root.join(path)
+                        root.join(&info.path)
                     } else {
                         return None;
                     };
-                    let uri = Url::from_file_path(&abs_path).ok()?;
+                    let uri = normalizer.to_uri(&abs_path)?;
                     Some(DiagnosticRelatedInformation {
                         location: Location {
                             uri,
@@ -98,6 +112,7 @@ impl DylintDiagnostic {
             code: Some(tower_lsp::lsp_types::NumberOrString::String(
                 self.code.clone(),
             )),
+            code_description: DiagnosticBuilder::code_description(&self.code),
             source: Some("solana".to_string()),
             message: self.message.clone(),
             related_information,
@@ -105,3 +120,16 @@ impl DylintDiagnostic {
         }
     }
 }
+
+/// Diagnostics of a file, matched by canonical path so equally named files
+/// (e.g. `lib.rs` of two crates) never receive each other's diagnostics
+pub fn diagnostics_for_file<'a>(
+    diagnostics: &'a [DylintDiagnostic],
+    file: &Path,
+) -> Vec<&'a DylintDiagnostic> {
+    let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_in_file(&file))
+        .collect()
+}
@@ -0,0 +1,123 @@
+use crate::core::notifications::DetectorStatus;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Why a dylint run produced no diagnostics
+#[derive(Debug)]
+pub enum DylintError {
+    /// The nightly toolchain the lints were built with is not installed
+    NightlyMissing { toolchain: String },
+    /// `dylint-driver` for the toolchain was not found at the expected path
+    DriverMissing { path: PathBuf, toolchain: String },
+    /// `cargo check` could not be started
+    CargoSpawn(String),
+    /// `cargo check` did not finish in time and was killed
+    Timeout(Duration),
+    /// `cargo check` output could not be read as JSON messages
+    ParseFailed(String),
+    /// `cargo check` failed without reporting any lint, e.g. the program does not compile
+    CompileFailed { stderr: String },
+    /// `dylint-driver` crashed (killed by a signal or panicked) instead of reporting lints
+    DriverCrashed { stderr: String },
+    /// `dylint-driver` crashed on several runs in a row, so dylint is disabled for the session
+    DriverUnstable { crashes: usize, toolchain: String },
+}
+
+/// Result of a dylint run
+pub type DylintResult<T> = std::result::Result<T, DylintError>;
+
+impl std::fmt::Display for DylintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NightlyMissing { toolchain } => {
+                write!(f, "Rust {} is not installed", toolchain)
+            }
+            Self::DriverMissing { path, .. } => {
+                write!(f, "dylint-driver not found at {}", path.display())
+            }
+            Self::CargoSpawn(reason) => write!(f, "Failed to spawn cargo check: {}", reason),
+            Self::Timeout(timeout) => {
+                write!(f, "cargo check timed out after {}s", timeout.as_secs())
+            }
+            Self::ParseFailed(reason) => {
+                write!(f, "Failed to parse cargo check output: {}", reason)
+            }
+            Self::CompileFailed { stderr } => {
+                write!(
+                    f,
+                    "cargo check failed: {}",
+                    stderr.lines().next().unwrap_or("")
+                )
+            }
+            Self::DriverCrashed { stderr } => {
+                write!(
+                    f,
+                    "dylint-driver crashed: {}",
+                    stderr.lines().next().unwrap_or("")
+                )
+            }
+            Self::DriverUnstable { crashes, .. } => {
+                write!(
+                    f,
+                    "dylint-driver crashed {} times in a row, dylint detectors are disabled",
+                    crashes
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DylintError {}
+
+impl DylintError {
+    /// Status telling the extension why detectors did not run
+    pub fn status(&self) -> DetectorStatus {
+        match self {
+            Self::NightlyMissing { toolchain } => DetectorStatus::missing_toolchain(toolchain),
+            Self::DriverMissing { toolchain, .. } => DetectorStatus {
+                status: "missing_driver".to_string(),
+                message: "dylint-driver is required for dylint detectors but is not installed"
+                    .to_string(),
+                required_version: Some(toolchain.clone()),
+                install_command: Some(format!(
+                    "cargo install cargo-dylint dylint-link && cargo +{} dylint --list",
+                    toolchain
+                )),
+            },
+            Self::CargoSpawn(_) => DetectorStatus::cargo_missing(),
+            Self::Timeout(_) => DetectorStatus {
+                status: "timeout".to_string(),
+                message: self.to_string(),
+                ..Default::default()
+            },
+            Self::ParseFailed(_) => DetectorStatus {
+                status: "parse_failed".to_string(),
+                message: self.to_string(),
+                ..Default::default()
+            },
+            Self::CompileFailed { .. } => DetectorStatus {
+                status: "compile_failed".to_string(),
+                message: "Dylint detectors did not run because the program does not compile"
+                    .to_string(),
+                ..Default::default()
+            },
+            Self::DriverCrashed { .. } => DetectorStatus {
+                status: "driver_crashed".to_string(),
+                message: self.to_string(),
+                ..Default::default()
+            },
+            Self::DriverUnstable { toolchain, .. } => DetectorStatus {
+                status: "driver_unstable".to_string(),
+                message: format!(
+                    "{} for this session. This usually means dylint-driver was built for a different nightly than {}; reinstall it and restart the language server",
+                    self, toolchain
+                ),
+                required_version: Some(toolchain.clone()),
+                install_command: Some(format!(
+                    "cargo install cargo-dylint dylint-link && cargo +{} dylint --list",
+                    toolchain
+                )),
+            },
+        }
+    }
+}
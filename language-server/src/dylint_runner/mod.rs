@@ -1,5 +1,8 @@
 mod diagnostics;
-mod parser;
+mod error;
+pub mod parser;
 mod runner;
 
+pub use diagnostics::*;
+pub use error::*;
 pub use runner::DylintRunner;
@@ -2,12 +2,16 @@ use super::diagnostics::{DylintDiagnostic, DylintRelatedInfo};
 use anyhow::{Context, Result};
 use log::debug;
 use serde_json::Value;
+use std::path::{Path, PathBuf};
 
 /// Parse cargo check JSON output and extract lint diagnostics
 /// Only accepts diagnostics from the specified lint codes (whitelist approach)
+/// Span file names are relative to the directory cargo ran in, so they are resolved against
+/// `workspace_root`
 pub fn parse_json_output(
     stdout: &str,
     allowed_lint_codes: &[String],
+    workspace_root: &Path,
 ) -> Result<Vec<DylintDiagnostic>> {
     let mut diagnostics = Vec::new();
 
@@ -47,7 +51,11 @@ pub fn parse_json_output(
                 .any(|allowed| code.contains(allowed))
         {
             // Parse into DylintDiagnostic
-            if let Ok(diagnostic) = parse_diagnostic(message) {
+            let manifest_dir = json
+                .get("manifest_path")
+                .and_then(|m| m.as_str())
+                .and_then(|m| Path::new(m).parent());
+            if let Ok(diagnostic) = parse_diagnostic(message, workspace_root, manifest_dir) {
                 diagnostics.push(diagnostic);
             }
         }
@@ -56,8 +64,31 @@ pub fn parse_json_output(
     Ok(diagnostics)
 }
 
+/// Resolve a span's file name to a canonical path
+/// Relative names are tried against the workspace root, then the package directory
+/// (cargo reports paths relative to the package for crates outside a workspace)
+pub fn resolve_span_path(
+    file_name: &str,
+    workspace_root: &Path,
+    manifest_dir: Option<&Path>,
+) -> PathBuf {
+    let path = Path::new(file_name);
+    if path.is_absolute() {
+        return path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    }
+
+    std::iter::once(workspace_root)
+        .chain(manifest_dir)
+        .find_map(|base| base.join(path).canonicalize().ok())
+        .unwrap_or_else(|| workspace_root.join(path))
+}
+
 /// Parse a single diagnostic message
-fn parse_diagnostic(message: &Value) -> Result<DylintDiagnostic> {
+fn parse_diagnostic(
+    message: &Value,
+    workspace_root: &Path,
+    manifest_dir: Option<&Path>,
+) -> Result<DylintDiagnostic> {
     // Get primary span
     let spans = message
         .get("spans")
@@ -79,8 +110,8 @@ fn parse_diagnostic(message: &Value) -> Result<DylintDiagnostic> {
     let file_name = primary_span
         .get("file_name")
         .and_then(|f| f.as_str())
-        .context("No file_name")?
-        .to_string();
+        .context("No file_name")?;
+    let path = resolve_span_path(file_name, workspace_root, manifest_dir);
 
     let line_start = primary_span
         .get("line_start")
@@ -122,7 +153,7 @@ fn parse_diagnostic(message: &Value) -> Result<DylintDiagnostic> {
         .to_string();
 
     // Parse children (notes/help) that have spans for related information
-    let related_information = parse_related_information(message);
+    let related_information = parse_related_information(message, workspace_root, manifest_dir);
     debug!(
         "[Dylint Parser] Diagnostic '{}' has {} related info(s), children: {}",
         msg,
@@ -133,7 +164,7 @@ fn parse_diagnostic(message: &Value) -> Result<DylintDiagnostic> {
     );
 
     Ok(DylintDiagnostic {
-        file_name,
+        path,
         line_start,
         line_end,
         column_start,
@@ -146,7 +177,12 @@ fn parse_diagnostic(message: &Value) -> Result<DylintDiagnostic> {
 }
 
 /// Parse children of a diagnostic message to extract related information with spans
-fn parse_related_information(message: &Value) -> Vec<DylintRelatedInfo> {
+/// Their file names are resolved like the primary span's, as notes may point into other files
+fn parse_related_information(
+    message: &Value,
+    workspace_root: &Path,
+    manifest_dir: Option<&Path>,
+) -> Vec<DylintRelatedInfo> {
     let mut related = Vec::new();
 
     let children = match message.get("children").and_then(|c| c.as_array()) {
@@ -168,8 +204,8 @@ fn parse_related_information(message: &Value) -> Vec<DylintRelatedInfo> {
 
         // Use the first span from the child
         if let Some(span) = child_spans.first() {
-            let file_name = match span.get("file_name").and_then(|f| f.as_str()) {
-                Some(f) => f.to_string(),
+            let path = match span.get("file_name").and_then(|f| f.as_str()) {
+                Some(f) => resolve_span_path(f, workspace_root, manifest_dir),
                 None => continue,
             };
 
@@ -182,7 +218,7 @@ fn parse_related_information(message: &Value) -> Vec<DylintRelatedInfo> {
             let column_end = span.get("column_end").and_then(|c| c.as_u64()).unwrap_or(1) as usize;
 
             related.push(DylintRelatedInfo {
-                file_name,
+                path,
                 line_start,
                 line_end,
                 column_start,
@@ -1,25 +1,79 @@
 use super::diagnostics::DylintDiagnostic;
+use super::error::{DylintError, DylintResult};
 use super::parser::parse_json_output;
+use crate::core::detectors::detector_config::DetectorConfig;
 use anyhow::{Context, Result};
 use log::{debug, info, warn};
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
+use std::process::{Output, Stdio};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::sync::{Mutex, OnceCell};
+
+/// Messages rustc prints on stderr when the compiler (here `dylint-driver`) crashes
+const DRIVER_CRASH_MARKERS: &[&str] = &[
+    "error: internal compiler error",
+    "thread 'rustc' panicked at",
+    "the compiler unexpectedly panicked",
+];
 
 #[derive(Debug)]
 pub struct DylintRunner {
     /// Path to pre-compiled lint libraries (e.g., lints_compiled/macos-arm64/)
     lint_libs_dir: PathBuf,
 
+    /// All directories searched for pre-compiled lints, most preferred first
+    lint_lib_dirs: Vec<PathBuf>,
+
     /// List of lint library files to load (pre-compiled + workspace detectors)
     lint_libs: Arc<std::sync::Mutex<Vec<PathBuf>>>,
 
     /// Cache of last run results per workspace
     cache: Arc<Mutex<std::collections::HashMap<PathBuf, Vec<DylintDiagnostic>>>>,
+
+    /// Maximum duration of a single lint run before the cargo process is killed
+    timeout: Duration,
+
+    /// Program run as `cargo` (overridable so a missing cargo can be simulated)
+    cargo_command: String,
+
+    /// Whether `cargo --version` succeeded, checked once per runner
+    cargo_available: Arc<OnceCell<bool>>,
+
+    /// `dylint-driver` to use instead of the one installed for the toolchain
+    dylint_driver: Option<PathBuf>,
+
+    /// Receivers trusted by the `unchecked_math` lint, passed to it as dylint configuration
+    trusted_math_receivers: Arc<std::sync::Mutex<Vec<String>>>,
+
+    /// Runs in a row on which `dylint-driver` crashed
+    consecutive_crashes: Arc<AtomicUsize>,
+
+    /// Consecutive crashes after which dylint is disabled for the session
+    crash_threshold: usize,
+
+    /// Set once the driver crashed `crash_threshold` times in a row; lint runs then do nothing
+    disabled: Arc<AtomicBool>,
 }
 
 impl DylintRunner {
+    /// Default upper bound for a single `cargo check` run
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+    /// Environment variable overriding the timeout (in seconds)
+    pub const TIMEOUT_ENV: &'static str = "SOLANA_DYLINT_TIMEOUT_SECS";
+
+    /// Environment variable listing extra lint library directories (`PATH` syntax)
+    pub const LIB_DIRS_ENV: &'static str = "SOLANA_DYLINT_LIB_DIRS";
+
+    /// Default number of consecutive driver crashes before dylint is disabled
+    pub const DEFAULT_CRASH_THRESHOLD: usize = 3;
+
+    /// Upper bound for the `cargo --version` availability check
+    const CARGO_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
     /// Add workspace detector libraries to the runner
     pub fn add_workspace_detectors(&self, detector_libs: Vec<PathBuf>) {
         let mut libs = self.lint_libs.lock().unwrap();
@@ -33,57 +87,367 @@ impl DylintRunner {
 
     /// Initialize the runner with pre-compiled lints (if available)
     /// Can start empty and have detectors added later via add_workspace_detectors
+    ///
+    /// Libraries are loaded from `lints_compiled/<platform>`, any directories listed in
+    /// `SOLANA_DYLINT_LIB_DIRS`, and `lints_compiled/common`, in that order of preference.
     pub fn new(extension_path: &Path) -> Result<Self> {
         // 1. Detect platform
         let platform = Self::detect_platform()?;
 
-        // 2. Find pre-compiled lints directory
-        let lint_libs_dir = extension_path.join("lints_compiled").join(platform);
-
-        // 3. Discover all .dylib/.so files in the directory (if it exists)
-        let lint_libs = if lint_libs_dir.exists() {
-            match Self::discover_lint_libs(&lint_libs_dir) {
-                Ok(libs) => {
-                    if libs.is_empty() {
-                        info!(
-                            "No pre-compiled lints found in {}. Runner will start empty.",
-                            lint_libs_dir.display()
-                        );
-                        Vec::new()
-                    } else {
-                        info!(
-                            "Dylint runner initialized with {} pre-compiled lints from {}",
-                            libs.len(),
-                            lint_libs_dir.display()
-                        );
-                        libs
-                    }
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to discover pre-compiled lints: {}. Starting with empty runner.",
-                        e
-                    );
-                    Vec::new()
-                }
-            }
+        // 2. Collect pre-compiled lint directories, platform-specific first
+        let lints_compiled = extension_path.join("lints_compiled");
+        let lint_libs_dir = lints_compiled.join(platform);
+        let mut lint_lib_dirs = vec![lint_libs_dir.clone()];
+        lint_lib_dirs.extend(Self::lib_dirs_from_env());
+        lint_lib_dirs.push(lints_compiled.join("common"));
+
+        // 3. Discover all .dylib/.so files in those directories
+        let lint_libs = Self::discover_lint_libs(&lint_lib_dirs);
+        if lint_libs.is_empty() {
+            info!(
+                "No pre-compiled lints found in {:?}. Runner will start empty and can have detectors added later.",
+                lint_lib_dirs
+            );
         } else {
             info!(
-                "Pre-compiled lints directory not found: {}. Runner will start empty and can have detectors added later.",
-                lint_libs_dir.display()
+                "Dylint runner initialized with {} pre-compiled lints from {:?}",
+                lint_libs.len(),
+                lint_lib_dirs
             );
-            Vec::new()
-        };
+        }
 
         Ok(Self {
             lint_libs_dir,
+            lint_lib_dirs,
             lint_libs: Arc::new(std::sync::Mutex::new(lint_libs)),
             cache: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            timeout: Self::timeout_from_env(),
+            cargo_command: "cargo".to_string(),
+            cargo_available: Arc::new(OnceCell::new()),
+            dylint_driver: None,
+            trusted_math_receivers: Arc::new(std::sync::Mutex::new(Vec::new())),
+            consecutive_crashes: Arc::new(AtomicUsize::new(0)),
+            crash_threshold: Self::DEFAULT_CRASH_THRESHOLD,
+            disabled: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Also load pre-compiled lints from extra directories
+    /// They take precedence over `lints_compiled/common` but not over the platform directory
+    #[allow(dead_code)]
+    pub fn with_additional_lib_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        let common = self.lint_lib_dirs.pop();
+        self.lint_lib_dirs.extend(dirs);
+        self.lint_lib_dirs.extend(common);
+
+        let lint_libs = Self::discover_lint_libs(&self.lint_lib_dirs);
+        info!(
+            "Dylint runner now has {} pre-compiled lint(s) from {:?}",
+            lint_libs.len(),
+            self.lint_lib_dirs
+        );
+        *self.lint_libs.lock().unwrap() = lint_libs;
+        self
+    }
+
+    /// Read extra lint directories from the environment
+    fn lib_dirs_from_env() -> Vec<PathBuf> {
+        std::env::var_os(Self::LIB_DIRS_ENV)
+            .map(|dirs| std::env::split_paths(&dirs).collect())
+            .unwrap_or_default()
+    }
+
+    /// Override the per-run timeout
+    #[allow(dead_code)]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run a different program in place of `cargo`
+    #[allow(dead_code)]
+    pub fn with_cargo_command(mut self, command: impl Into<String>) -> Self {
+        self.cargo_command = command.into();
+        self.cargo_available = Arc::new(OnceCell::new());
+        self
+    }
+
+    /// Use a specific `dylint-driver` instead of looking it up under `~/.dylint_drivers`
+    #[allow(dead_code)]
+    pub fn with_dylint_driver(mut self, driver: PathBuf) -> Self {
+        self.dylint_driver = Some(driver);
+        self
+    }
+
+    /// Override the number of consecutive driver crashes before dylint is disabled
+    #[allow(dead_code)]
+    pub fn with_crash_threshold(mut self, threshold: usize) -> Self {
+        self.crash_threshold = threshold.max(1);
+        self
+    }
+
+    /// Whether dylint was disabled for the session after repeated driver crashes
+    #[allow(dead_code)]
+    pub fn is_disabled(&self) -> bool {
+        self.disabled.load(Ordering::SeqCst)
+    }
+
+    /// Apply the `UNCHECKED_MATH` detector config to the `unchecked_math` lint
+    pub fn set_unchecked_math_config(&self, config: &DetectorConfig) {
+        *self.trusted_math_receivers.lock().unwrap() = config.trusted_math_receivers.clone();
+    }
+
+    /// Lint configuration passed to dylint through `DYLINT_TOML`, if any is set
+    /// It replaces the workspace `dylint.toml`, so nothing is passed without settings
+    pub fn dylint_toml(&self) -> Option<String> {
+        let receivers = self.trusted_math_receivers.lock().unwrap();
+        if receivers.is_empty() {
+            return None;
+        }
+
+        // JSON strings are valid TOML basic strings
+        let receivers = receivers
+            .iter()
+            .map(|receiver| serde_json::Value::from(receiver.as_str()).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(format!(
+            "[unchecked_math]\ntrusted_math_receivers = [{}]\n",
+            receivers
+        ))
+    }
+
+    /// Check whether cargo can be run, caching the result for the lifetime of the runner
+    /// When it cannot, lint runs return no diagnostics instead of failing on every save
+    pub async fn cargo_available(&self) -> bool {
+        *self
+            .cargo_available
+            .get_or_init(|| async {
+                let mut command = tokio::process::Command::new(&self.cargo_command);
+                command
+                    .arg("--version")
+                    .env("PATH", Self::cargo_search_path());
+
+                match Self::run_command_with_timeout(command, Self::CARGO_CHECK_TIMEOUT).await {
+                    Ok(Some(output)) if output.status.success() => {
+                        debug!("Found {}", String::from_utf8_lossy(&output.stdout).trim());
+                        true
+                    }
+                    Ok(Some(output)) => {
+                        warn!(
+                            "'{} --version' failed with {}, dylint integration disabled",
+                            self.cargo_command, output.status
+                        );
+                        false
+                    }
+                    Ok(None) => {
+                        warn!(
+                            "'{} --version' timed out, dylint integration disabled",
+                            self.cargo_command
+                        );
+                        false
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Could not run '{}' ({:#}), dylint integration disabled",
+                            self.cargo_command, e
+                        );
+                        false
+                    }
+                }
+            })
+            .await
+    }
+
+    /// `PATH` with the cargo bin directories in front, as rustup installs cargo there
+    /// without every editor inheriting a shell `PATH` that includes it
+    fn cargo_search_path() -> String {
+        let current_path = std::env::var("PATH").unwrap_or_default();
+        let cargo_bin = dirs::home_dir()
+            .map(|home| format!("{}:", home.join(".cargo").join("bin").display()))
+            .unwrap_or_default();
+        format!("{cargo_bin}/usr/local/bin:/usr/bin:{current_path}")
+    }
+
+    /// Get the per-run timeout
+    #[allow(dead_code)]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Read the timeout from the environment, falling back to the default
+    fn timeout_from_env() -> Duration {
+        match std::env::var(Self::TIMEOUT_ENV) {
+            Ok(value) => match value.trim().parse::<u64>() {
+                Ok(secs) if secs > 0 => Duration::from_secs(secs),
+                _ => {
+                    warn!(
+                        "Invalid {} value '{}', using default of {}s",
+                        Self::TIMEOUT_ENV,
+                        value,
+                        Self::DEFAULT_TIMEOUT.as_secs()
+                    );
+                    Self::DEFAULT_TIMEOUT
+                }
+            },
+            Err(_) => Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Run a command, killing it if it does not finish within `timeout`
+    /// Returns `Ok(None)` if the command timed out
+    ///
+    /// On unix the command runs in its own process group, so a timeout also kills the
+    /// processes it started (cargo's rustc and `dylint-driver` children)
+    pub async fn run_command_with_timeout(
+        mut command: tokio::process::Command,
+        timeout: Duration,
+    ) -> Result<Option<Output>> {
+        #[cfg(unix)]
+        command.process_group(0);
+
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to spawn command")?;
+
+        // Drain pipes concurrently so a chatty process cannot block on a full pipe
+        let mut stdout_pipe = child.stdout.take();
+        let mut stderr_pipe = child.stderr.take();
+        let stdout_task = tokio::spawn(async move {
+            let mut buffer = Vec::new();
+            if let Some(pipe) = stdout_pipe.as_mut() {
+                let _ = pipe.read_to_end(&mut buffer).await;
+            }
+            buffer
+        });
+        let stderr_task = tokio::spawn(async move {
+            let mut buffer = Vec::new();
+            if let Some(pipe) = stderr_pipe.as_mut() {
+                let _ = pipe.read_to_end(&mut buffer).await;
+            }
+            buffer
+        });
+
+        match tokio::time::timeout(timeout, child.wait()).await {
+            Ok(status) => {
+                let status = status.context("Failed to wait for command")?;
+                Ok(Some(Output {
+                    status,
+                    stdout: stdout_task.await.unwrap_or_default(),
+                    stderr: stderr_task.await.unwrap_or_default(),
+                }))
+            }
+            Err(_) => {
+                #[cfg(unix)]
+                if let Some(pid) = child.id() {
+                    // SAFETY: only sends a signal to the group created for this child
+                    if unsafe { libc::killpg(pid as libc::pid_t, libc::SIGKILL) } != 0 {
+                        warn!(
+                            "Failed to kill process group of timed out command: {}",
+                            std::io::Error::last_os_error()
+                        );
+                    }
+                }
+
+                // Kill and reap the child so it does not linger in the background
+                if let Err(e) = child.kill().await {
+                    warn!("Failed to kill timed out command: {}", e);
+                }
+                stdout_task.abort();
+                stderr_task.abort();
+                Ok(None)
+            }
+        }
+    }
+
     /// Run lints on a workspace
-    pub async fn run_lints(&self, workspace_path: &Path) -> Result<Vec<DylintDiagnostic>> {
+    pub async fn run_lints(&self, workspace_path: &Path) -> DylintResult<Vec<DylintDiagnostic>> {
+        self.run_cargo_check(workspace_path, None).await
+    }
+
+    /// Run lints only on the package containing `file_path`
+    /// Falls back to the whole workspace if the owning package cannot be determined
+    pub async fn run_lints_for_file(
+        &self,
+        workspace_path: &Path,
+        file_path: &Path,
+    ) -> DylintResult<Vec<DylintDiagnostic>> {
+        match Self::package_for_file(file_path) {
+            Some(package) => {
+                debug!(
+                    "Running dylint on package '{}' for {}",
+                    package,
+                    file_path.display()
+                );
+                self.run_cargo_check(workspace_path, Some(&package)).await
+            }
+            None => {
+                debug!(
+                    "Could not determine package for {}, running dylint on workspace",
+                    file_path.display()
+                );
+                self.run_cargo_check(workspace_path, None).await
+            }
+        }
+    }
+
+    /// Find the name of the package owning a file
+    /// Walks up to the nearest `Cargo.toml` and reads its `[package] name`
+    pub fn package_for_file(file_path: &Path) -> Option<String> {
+        let manifest = file_path
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join("Cargo.toml"))
+            .find(|manifest| manifest.is_file())?;
+
+        let content = std::fs::read_to_string(&manifest).ok()?;
+        Self::parse_package_name(&content)
+    }
+
+    /// Read `name` from the `[package]` section of a manifest
+    /// Returns `None` for virtual manifests (workspace roots without a package)
+    fn parse_package_name(manifest: &str) -> Option<String> {
+        let mut in_package = false;
+
+        for line in manifest.lines() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                in_package = line == "[package]";
+                continue;
+            }
+
+            if in_package
+                && let Some((key, value)) = line.split_once('=')
+                && key.trim() == "name"
+            {
+                let name = value.trim().trim_matches('"').trim_matches('\'');
+                return (!name.is_empty()).then(|| name.to_string());
+            }
+        }
+
+        None
+    }
+
+    /// Build the `cargo check` arguments for a workspace or a single package
+    pub fn cargo_check_args(toolchain: &str, package: Option<&str>) -> Vec<String> {
+        let mut args = vec![format!("+{}", toolchain), "check".to_string()];
+        match package {
+            Some(package) => args.extend(["-p".to_string(), package.to_string()]),
+            None => args.push("--workspace".to_string()),
+        }
+        args.push("--message-format=json".to_string());
+        args
+    }
+
+    /// Run `cargo check` with dylint on a workspace or a single package
+    async fn run_cargo_check(
+        &self,
+        workspace_path: &Path,
+        package: Option<&str>,
+    ) -> DylintResult<Vec<DylintDiagnostic>> {
         // Clone the lint libs list while holding the lock, then release it
         let lint_libs: Vec<PathBuf> = {
             let libs = self.lint_libs.lock().unwrap();
@@ -93,6 +457,10 @@ impl DylintRunner {
             libs.clone()
         };
 
+        if self.is_disabled() || !self.cargo_available().await {
+            return Ok(Vec::new());
+        }
+
         debug!(
             "Running dylint lints on workspace: {}",
             workspace_path.display()
@@ -106,22 +474,23 @@ impl DylintRunner {
         }
 
         // Build PATH with cargo bin directories
-        let current_path = std::env::var("PATH").unwrap_or_default();
-        let home = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-
-        let cargo_bin = home.join(".cargo").join("bin");
-        let new_path = format!(
-            "{}:/usr/local/bin:/usr/bin:{current_path}",
-            cargo_bin.display()
-        );
+        let new_path = Self::cargo_search_path();
 
         // Detect toolchain from lint's rust-toolchain file
-        let toolchain = Self::detect_lint_toolchain(&self.lint_libs_dir)?;
+        let toolchain = Self::detect_lint_toolchain(&self.lint_libs_dir);
         debug!("Using toolchain: {}", toolchain);
 
         // Get dylint-driver path
-        let dylint_driver = Self::find_dylint_driver(&home, &toolchain)?;
+        let dylint_driver = match &self.dylint_driver {
+            Some(driver) if driver.exists() => driver.clone(),
+            Some(driver) => {
+                return Err(DylintError::DriverMissing {
+                    path: driver.clone(),
+                    toolchain,
+                });
+            }
+            None => Self::find_dylint_driver(&toolchain)?,
+        };
         debug!("Using dylint-driver: {}", dylint_driver.display());
 
         // Build DYLINT_LIBS JSON array with absolute paths
@@ -130,24 +499,35 @@ impl DylintRunner {
                 .iter()
                 .map(|p| p.to_string_lossy().to_string())
                 .collect::<Vec<_>>(),
-        )?;
+        )
+        .map_err(|e| DylintError::ParseFailed(e.to_string()))?;
 
         debug!("DYLINT_LIBS: {}", dylint_libs_json);
 
         // Run cargo check with dylint
-        // --workspace flag ensures we only check workspace members, not external dependencies
-        let output = tokio::process::Command::new("cargo")
-            .arg(format!("+{}", toolchain))
-            .args(&["check", "--workspace", "--message-format=json"])
+        // --workspace (or -p <package>) ensures we only check workspace members, not external dependencies
+        let mut command = tokio::process::Command::new(&self.cargo_command);
+        command
+            .args(Self::cargo_check_args(&toolchain, package))
             .current_dir(workspace_path)
             .env("PATH", new_path)
             .env("RUSTC_WORKSPACE_WRAPPER", &dylint_driver)
-            .env("DYLINT_LIBS", dylint_libs_json)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+            .env("DYLINT_LIBS", dylint_libs_json);
+        if let Some(dylint_toml) = self.dylint_toml() {
+            command.env("DYLINT_TOML", dylint_toml);
+        }
+
+        let Some(output) = Self::run_command_with_timeout(command, self.timeout)
             .await
-            .context("Failed to spawn cargo check")?;
+            .map_err(|e| DylintError::CargoSpawn(format!("{:#}", e)))?
+        else {
+            warn!(
+                "Dylint run on {} timed out after {}s, cargo check was killed",
+                workspace_path.display(),
+                self.timeout.as_secs()
+            );
+            return Err(DylintError::Timeout(self.timeout));
+        };
 
         // Extract lint names from loaded libraries
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -173,13 +553,41 @@ impl DylintRunner {
             })
             .collect();
 
+        if Self::is_driver_crash(&output, &stderr) {
+            return Err(self.record_crash(&stderr, toolchain));
+        }
+        self.consecutive_crashes.store(0, Ordering::SeqCst);
+
+        if !output.status.success() && Self::is_toolchain_missing(&stderr) {
+            return Err(DylintError::NightlyMissing { toolchain });
+        }
+
+        // `--message-format=json` output has one JSON message per line
+        if !stdout.trim().is_empty()
+            && !stdout
+                .lines()
+                .any(|line| serde_json::from_str::<serde_json::Value>(line).is_ok())
+        {
+            return Err(DylintError::ParseFailed(
+                "no JSON messages in cargo output".to_string(),
+            ));
+        }
+
         // Parse JSON output
         debug!("[Dylint] Parsing output for lint codes: {:?}", lint_codes);
-        let diagnostics = parse_json_output(&stdout, &lint_codes)?;
+        let diagnostics = parse_json_output(&stdout, &lint_codes, workspace_path)
+            .map_err(|e| DylintError::ParseFailed(format!("{:#}", e)))?;
         debug!("[Dylint] Parsed {} diagnostic(s)", diagnostics.len());
 
-        // Update cache
-        {
+        // Lints still report on the crates that compiled, so only fail if none did
+        if !output.status.success() && diagnostics.is_empty() {
+            return Err(DylintError::CompileFailed {
+                stderr: stderr.trim().to_string(),
+            });
+        }
+
+        // Update cache (only whole-workspace runs are complete)
+        if package.is_none() {
             let mut cache = self.cache.lock().await;
             cache.insert(workspace_path.to_path_buf(), diagnostics.clone());
         }
@@ -204,8 +612,61 @@ impl DylintRunner {
         }
     }
 
+    /// Check if the run ended in a crash rather than a normal (possibly failed) compilation:
+    /// cargo itself was killed by a signal, or it reports that the compiler (i.e. the
+    /// driver) panicked or was killed. Cargo still prints JSON messages and exits with 101
+    /// in that case, so stdout cannot tell a crash apart from a compile error
+    fn is_driver_crash(output: &Output, stderr: &str) -> bool {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if output.status.signal().is_some() {
+                return true;
+            }
+        }
+
+        !output.status.success()
+            && stderr.lines().any(|line| {
+                DRIVER_CRASH_MARKERS
+                    .iter()
+                    .any(|marker| line.contains(marker))
+                    || (line.contains("process didn't exit successfully")
+                        && line.contains("(signal:"))
+            })
+    }
+
+    /// Count a driver crash, disabling dylint once the threshold is reached
+    fn record_crash(&self, stderr: &str, toolchain: String) -> DylintError {
+        let crashes = self.consecutive_crashes.fetch_add(1, Ordering::SeqCst) + 1;
+        warn!(
+            "dylint-driver crashed ({} of {} before disabling): {}",
+            crashes,
+            self.crash_threshold,
+            stderr.lines().take(10).collect::<Vec<_>>().join("\n")
+        );
+
+        if crashes >= self.crash_threshold && !self.disabled.swap(true, Ordering::SeqCst) {
+            warn!(
+                "dylint-driver crashed {} times in a row, dylint integration disabled",
+                crashes
+            );
+            return DylintError::DriverUnstable { crashes, toolchain };
+        }
+
+        DylintError::DriverCrashed {
+            stderr: stderr.trim().to_string(),
+        }
+    }
+
+    /// Check if cargo failed because the requested toolchain is not installed
+    fn is_toolchain_missing(stderr: &str) -> bool {
+        stderr
+            .lines()
+            .any(|line| line.contains("toolchain") && line.contains("is not installed"))
+    }
+
     /// Detect the Rust toolchain from the lint library filename
-    fn detect_lint_toolchain(_lints_dir: &Path) -> Result<String> {
+    fn detect_lint_toolchain(_lints_dir: &Path) -> String {
         use crate::core::dylint::constants::REQUIRED_NIGHTLY_VERSION;
 
         // Simply return the required nightly version - all detectors use this version
@@ -213,11 +674,11 @@ impl DylintRunner {
             "Using extension's required nightly version: {}",
             REQUIRED_NIGHTLY_VERSION
         );
-        Ok(REQUIRED_NIGHTLY_VERSION.to_string())
+        REQUIRED_NIGHTLY_VERSION.to_string()
     }
 
     /// Find dylint-driver executable
-    fn find_dylint_driver(home: &Path, toolchain: &str) -> Result<PathBuf> {
+    fn find_dylint_driver(toolchain: &str) -> DylintResult<PathBuf> {
         let arch = std::env::consts::ARCH;
         let os = match std::env::consts::OS {
             "macos" => "apple-darwin",
@@ -226,26 +687,61 @@ impl DylintRunner {
         };
 
         let toolchain_target = format!("{}-{}-{}", toolchain, arch, os);
-        let dylint_driver = home
-            .join(".dylint_drivers")
-            .join(&toolchain_target)
-            .join("dylint-driver");
+        let drivers_dir = dirs::home_dir().unwrap_or_default().join(".dylint_drivers");
+        let dylint_driver = drivers_dir.join(&toolchain_target).join("dylint-driver");
 
         if !dylint_driver.exists() {
-            anyhow::bail!(
-                "dylint-driver not found at {:?}.\n\
-                Install it by running: cargo install cargo-dylint dylint-link\n\
-                Then run: cargo +{} dylint --list",
-                dylint_driver,
-                toolchain
+            warn!(
+                "dylint-driver not found at {:?}. Install it by running: \
+                cargo install cargo-dylint dylint-link, then: cargo +{} dylint --list",
+                dylint_driver, toolchain
             );
+            return Err(DylintError::DriverMissing {
+                path: dylint_driver,
+                toolchain: toolchain.to_string(),
+            });
         }
 
         Ok(dylint_driver)
     }
 
+    /// Discover lint libraries in several directories, most preferred first
+    /// A library found in more than one directory is taken from the first one; missing or
+    /// unreadable directories are skipped
+    pub fn discover_lint_libs(dirs: &[PathBuf]) -> Vec<PathBuf> {
+        let mut libs: Vec<PathBuf> = Vec::new();
+
+        for dir in dirs {
+            if !dir.exists() {
+                debug!("Lint directory not found: {}", dir.display());
+                continue;
+            }
+
+            let dir_libs = match Self::discover_lint_libs_in(dir) {
+                Ok(dir_libs) => dir_libs,
+                Err(e) => {
+                    warn!("Failed to discover pre-compiled lints: {}", e);
+                    continue;
+                }
+            };
+
+            for lib in dir_libs {
+                if libs
+                    .iter()
+                    .any(|existing| existing.file_name() == lib.file_name())
+                {
+                    debug!("Skipping {} (already loaded)", lib.display());
+                    continue;
+                }
+                libs.push(lib);
+            }
+        }
+
+        libs
+    }
+
     /// Discover all lint libraries in the directory
-    fn discover_lint_libs(dir: &Path) -> Result<Vec<PathBuf>> {
+    fn discover_lint_libs_in(dir: &Path) -> Result<Vec<PathBuf>> {
         let mut libs = Vec::new();
 
         for entry in std::fs::read_dir(dir)
@@ -266,9 +762,17 @@ impl DylintRunner {
             }
         }
 
+        // Keep the load order stable across file systems
+        libs.sort();
         Ok(libs)
     }
 
+    /// Get the paths of all loaded lint libraries
+    #[allow(dead_code)]
+    pub fn lint_libs(&self) -> Vec<PathBuf> {
+        self.lint_libs.lock().unwrap().clone()
+    }
+
     /// Get list of loaded lint names
     pub fn loaded_lints(&self) -> Vec<String> {
         let lint_libs = self.lint_libs.lock().unwrap();
@@ -1,4 +1,5 @@
 pub mod backend;
+pub mod cli;
 pub mod core;
 pub mod dylint_runner;
 pub mod server;
@@ -1,17 +1,36 @@
 mod backend;
+mod cli;
 mod core;
 mod dylint_runner;
 mod server;
 
+use cli::Mode;
 use log::debug;
 
 #[tokio::main]
 async fn main() {
     env_logger::init();
 
-    let (service, socket) = server::create_service();
+    let mode = match Mode::from_args(std::env::args().skip(1)) {
+        Ok(mode) => mode,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
 
-    debug!("Starting server: {:#?} on socket: {:#?}", service, socket);
+    match mode {
+        Mode::Server => {
+            let (service, socket) = server::create_service();
 
-    server::start_server(service, socket).await;
+            debug!("Starting server: {:#?} on socket: {:#?}", service, socket);
+
+            server::start_server(service, socket).await;
+        }
+        Mode::Scan {
+            root,
+            severity_floor,
+        } => std::process::exit(cli::run_scan(&root, severity_floor).await),
+        Mode::Watch(root) => std::process::exit(cli::run_watch(&root).await),
+    }
 }
@@ -1,10 +1,17 @@
 use tokio::io::{Stdin, Stdout};
+use tower_lsp::lsp_types::request::Request;
 use tower_lsp::{ClientSocket, LspService, Server};
 
 use crate::backend::Backend;
+use crate::core::ListDetectorsRequest;
 
 pub fn create_service() -> (LspService<Backend>, ClientSocket) {
-    let (service, socket) = LspService::new(Backend::new);
+    let (service, socket) = LspService::build(Backend::new)
+        .custom_method(
+            ListDetectorsRequest::METHOD,
+            Backend::list_detectors_request,
+        )
+        .finish();
     (service, socket)
 }
 
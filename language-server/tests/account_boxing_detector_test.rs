@@ -0,0 +1,83 @@
+use language_server::core::detectors::{
+    account_boxing_detector::AccountBoxingDetector, detector::Detector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+/// An Accounts struct with `count` `Account<'info, Vault>` fields, boxed or not
+fn accounts_struct(count: usize, boxed: bool) -> String {
+    let fields: String = (0..count)
+        .map(|i| {
+            if boxed {
+                format!("    pub vault_{}: Box<Account<'info, Vault>>,\n", i)
+            } else {
+                format!("    pub vault_{}: Account<'info, Vault>,\n", i)
+            }
+        })
+        .collect();
+
+    format!(
+        "#[derive(Accounts)]\npub struct Rebalance<'info> {{\n    pub authority: Signer<'info>,\n{}}}\n",
+        fields
+    )
+}
+
+#[test]
+fn test_detector_metadata() {
+    let detector = AccountBoxingDetector::default();
+
+    assert_eq!(detector.id(), "ACCOUNT_BOXING");
+    assert_eq!(detector.name(), "Account Boxing");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_many_unboxed_accounts() {
+    let mut detector = AccountBoxingDetector::default();
+
+    let diagnostics = detector.analyze(&accounts_struct(9, false), None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 1);
+    assert!(diagnostics[0].message.contains("deserializes 9 accounts"));
+}
+
+#[test]
+fn test_boxed_or_few_accounts_not_flagged() {
+    let mut detector = AccountBoxingDetector::default();
+
+    assert!(detector.analyze(&accounts_struct(9, true), None).is_empty());
+    assert!(
+        detector
+            .analyze(&accounts_struct(8, false), None)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_detects_boxed_tiny_account() {
+    let mut detector = AccountBoxingDetector::default();
+
+    let code = r#"
+        #[account]
+        pub struct Counter {
+            pub count: u64,
+        }
+
+        #[account]
+        pub struct Registry {
+            pub entries: [Pubkey; 64],
+        }
+
+        #[derive(Accounts)]
+        pub struct Increment<'info> {
+            pub counter: Box<Account<'info, Counter>>,
+            pub registry: Box<Account<'info, Registry>>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 13);
+    assert!(diagnostics[0].message.contains("'counter'"));
+    assert!(diagnostics[0].message.contains("8 bytes"));
+}
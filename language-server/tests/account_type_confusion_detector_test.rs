@@ -0,0 +1,68 @@
+use language_server::core::detectors::{
+    account_type_confusion_detector::AccountTypeConfusionDetector, detector::Detector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = AccountTypeConfusionDetector::default();
+
+    assert_eq!(detector.id(), "ACCOUNT_TYPE_CONFUSION");
+    assert_eq!(detector.name(), "Account Type Confusion");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_account_deserialized_as_two_types() {
+    let mut detector = AccountTypeConfusionDetector::default();
+
+    let code = r#"
+        pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+            let account = &accounts[0];
+            let vault = Vault::try_from_slice(&account.data.borrow())?;
+            let config = Config::try_from_slice(&account.data.borrow()[..])?;
+            let user = <User as AccountDeserialize>::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 4);
+    assert_eq!(diagnostics[0].range.start.character, 25);
+    assert!(
+        diagnostics[0]
+            .message
+            .contains("as Config after being deserialized as Vault")
+    );
+    assert_eq!(diagnostics[1].range.start.line, 5);
+    assert!(diagnostics[1].message.contains("as User"));
+}
+
+#[test]
+fn test_consistent_deserialization_not_flagged() {
+    let mut detector = AccountTypeConfusionDetector::default();
+
+    let code = r#"
+        pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+            let vault = Vault::try_from_slice(&accounts[0].data.borrow())?;
+            let source = Vault::try_from_slice(&source.data.borrow())?;
+            let config = Config::try_from_slice(&config_info.data.borrow())?;
+            let again = BorshDeserialize::try_from_slice::<Vault>(&source.data.borrow())?;
+            Ok(())
+        }
+
+        pub fn other(account: &AccountInfo) -> ProgramResult {
+            let config = Config::try_from_slice(&account.data.borrow())?;
+            Ok(())
+        }
+
+        pub fn another(account: &AccountInfo) -> ProgramResult {
+            let vault = Vault::try_from_slice(&account.data.borrow())?;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
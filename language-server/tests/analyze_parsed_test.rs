@@ -0,0 +1,143 @@
+use language_server::core::detectors::{
+    detector::Detector, insecure_randomness_detector::InsecureRandomnessDetector,
+    manual_lamports_zeroing::ManualLamportsZeroingDetector,
+    msg_in_loop_detector::MsgInLoopDetector, unwrap_in_program_detector::UnwrapInProgramDetector,
+};
+use language_server::core::registry::DetectorRegistry;
+use language_server::core::utilities::LineIndex;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn payout(ctx: Context<Payout>, amounts: Vec<u64>) -> Result<()> {
+        let seed = Clock::get().unwrap().unix_timestamp;
+        for amount in amounts.iter() {
+            msg!("Paying {}", amount);
+        }
+        msg!("Seed {}", seed % 10);
+        Ok(())
+    }
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        **ctx.accounts.vault.lamports.borrow_mut() = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper(ctx: Context<Close>) {
+        **ctx.accounts.vault.lamports.borrow_mut() = 0;
+    }
+}
+"#;
+
+/// Wraps a detector and counts how often it has to parse the content itself
+struct ParseCounting {
+    inner: Box<dyn Detector>,
+    parses: Arc<AtomicUsize>,
+}
+
+impl Detector for ParseCounting {
+    fn id(&self) -> &'static str {
+        self.inner.id()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.inner.description()
+    }
+
+    fn message(&self) -> &'static str {
+        self.inner.message()
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        self.inner.default_severity()
+    }
+
+    fn analyze(&mut self, content: &str, file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+        self.parses.fetch_add(1, Ordering::SeqCst);
+        self.inner.analyze(content, file_path)
+    }
+
+    fn analyze_ast(
+        &mut self,
+        syntax_tree: &syn::File,
+        content: &str,
+        file_path: Option<&PathBuf>,
+        lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.inner
+            .analyze_ast(syntax_tree, content, file_path, lines)
+    }
+}
+
+fn registry(parses: &Arc<AtomicUsize>) -> DetectorRegistry {
+    let detectors: Vec<Box<dyn Detector>> = vec![
+        Box::new(InsecureRandomnessDetector::default()),
+        Box::new(ManualLamportsZeroingDetector::default()),
+        Box::new(MsgInLoopDetector::default()),
+        Box::new(UnwrapInProgramDetector::default()),
+    ];
+
+    let mut registry = DetectorRegistry::new();
+    for inner in detectors {
+        registry.register(ParseCounting {
+            inner,
+            parses: Arc::clone(parses),
+        });
+    }
+    registry
+}
+
+/// Parse the content, counting each parse
+fn parse_counted(content: &str, parses: &AtomicUsize) -> syn::File {
+    parses.fetch_add(1, Ordering::SeqCst);
+    syn::parse_str(content).expect("program parses")
+}
+
+#[test]
+fn test_analyze_parsed_parses_once_with_same_diagnostics() {
+    let parses = Arc::new(AtomicUsize::new(0));
+    let mut registry = registry(&parses);
+    let file_path = PathBuf::from("programs/vault/src/lib.rs");
+
+    let syntax_tree = parse_counted(PROGRAM, &parses);
+    let parsed = registry.analyze_parsed(&syntax_tree, PROGRAM, Some(&file_path));
+    assert_eq!(parses.load(Ordering::SeqCst), 1);
+
+    let expected = registry.analyze(PROGRAM, Some(&file_path));
+    assert!(!parsed.is_empty());
+    assert_eq!(parsed, expected);
+}
+
+#[test]
+fn test_analyze_parsed_includes_tests_without_reparsing() {
+    let parses = Arc::new(AtomicUsize::new(0));
+    let mut registry = registry(&parses);
+    registry.set_include_tests("MANUAL_LAMPORTS_ZEROING", true);
+
+    let syntax_tree = parse_counted(PROGRAM, &parses);
+    let parsed = registry.analyze_parsed(&syntax_tree, PROGRAM, None);
+    assert_eq!(parses.load(Ordering::SeqCst), 1);
+
+    // The zeroing inside `mod tests` is only visible with test code included
+    assert!(
+        parsed
+            .iter()
+            .any(|diagnostic| diagnostic.range.start.line == 25)
+    );
+    assert_eq!(parsed, registry.analyze(PROGRAM, None));
+}
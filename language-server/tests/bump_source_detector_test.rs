@@ -0,0 +1,100 @@
+use language_server::core::detectors::{
+    bump_source_detector::BumpSourceDetector, detector::Detector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = BumpSourceDetector::default();
+
+    assert_eq!(detector.id(), "UNVERIFIED_BUMP_SOURCE");
+    assert_eq!(detector.name(), "Unverified Bump Source");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_unrelated_bump_value() {
+    let mut detector = BumpSourceDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        #[instruction(some_unrelated_u8: u8)]
+        pub struct Withdraw<'info> {
+            #[account(mut, seeds = [b"vault", authority.key().as_ref()], bump = some_unrelated_u8)]
+            pub vault: SystemAccount<'info>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].severity,
+        Some(DiagnosticSeverity::INFORMATION)
+    );
+    assert!(diagnostics[0].message.contains("some_unrelated_u8"));
+}
+
+#[test]
+fn test_bump_from_validated_account_not_flagged() {
+    let mut detector = BumpSourceDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut, seeds = [b"vault", authority.key().as_ref()], bump = vault.bump)]
+            pub vault: Account<'info, Vault>,
+            #[account(seeds = [b"config"], bump = config.load()?.bump)]
+            pub config: AccountLoader<'info, Config>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_bump_from_unvalidated_account_flagged() {
+    let mut detector = BumpSourceDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut, seeds = [b"vault"], bump = state.bump)]
+            pub vault: SystemAccount<'info>,
+            /// CHECK: not validated
+            pub state: UncheckedAccount<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_bare_bump_not_flagged() {
+    let mut detector = BumpSourceDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(init, payer = payer, space = 8 + 1, seeds = [b"vault"], bump)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub payer: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
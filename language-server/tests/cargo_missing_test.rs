@@ -0,0 +1,93 @@
+use language_server::core::notifications::DetectorStatus;
+use language_server::dylint_runner::DylintRunner;
+use std::path::PathBuf;
+
+/// Workspace with a manifest and a runner that has a (fake) lint library loaded,
+/// so lint runs get past the early returns and reach cargo
+fn runner_with_workspace(name: &str, cargo_command: &str) -> (DylintRunner, PathBuf) {
+    let root =
+        std::env::temp_dir().join(format!("solana-ls-cargo-{}-{}", name, std::process::id()));
+    let workspace = root.join("workspace");
+    std::fs::create_dir_all(&workspace).unwrap();
+    std::fs::write(
+        workspace.join("Cargo.toml"),
+        "[package]\nname = \"program\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let runner = DylintRunner::new(&root.join("extension"))
+        .unwrap()
+        .with_cargo_command(cargo_command);
+    runner.add_workspace_detectors(vec![root.join("libfake_lint@nightly.so")]);
+    (runner, workspace)
+}
+
+#[tokio::test]
+async fn test_missing_cargo_disables_lint_runs() {
+    let (runner, workspace) = runner_with_workspace("missing", "solana-ls-no-such-cargo");
+
+    assert!(!runner.cargo_available().await);
+
+    // Every run is skipped quietly instead of failing to spawn cargo
+    for _ in 0..3 {
+        assert!(runner.run_lints(&workspace).await.unwrap().is_empty());
+        assert!(
+            runner
+                .run_lints_for_file(&workspace, &workspace.join("src").join("lib.rs"))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_cargo_availability_is_checked_once() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir().join(format!("solana-ls-cargo-stub-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let calls = dir.join("calls");
+    let _ = std::fs::remove_file(&calls);
+
+    // Stub cargo that records each call and fails like a broken installation
+    let stub = dir.join("cargo-stub");
+    std::fs::write(
+        &stub,
+        format!("#!/bin/sh\necho call >> {}\nexit 1\n", calls.display()),
+    )
+    .unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let (runner, workspace) = runner_with_workspace("stub", stub.to_str().unwrap());
+
+    assert!(!runner.cargo_available().await);
+    assert!(!runner.cargo_available().await);
+    assert!(runner.run_lints(&workspace).await.unwrap().is_empty());
+
+    let recorded = std::fs::read_to_string(&calls).unwrap();
+    assert_eq!(recorded.lines().count(), 1);
+}
+
+#[tokio::test]
+async fn test_working_cargo_is_available() {
+    // `true` ignores `--version` and always succeeds
+    let (runner, _) = runner_with_workspace("present", "true");
+
+    assert!(runner.cargo_available().await);
+}
+
+#[test]
+fn test_cargo_missing_status_payload() {
+    let status = serde_json::to_value(DetectorStatus::cargo_missing()).unwrap();
+
+    assert_eq!(status["status"], "cargo_missing");
+    assert!(status.get("required_version").is_none());
+    assert!(
+        status["install_command"]
+            .as_str()
+            .unwrap()
+            .contains("rustup")
+    );
+}
@@ -0,0 +1,131 @@
+use language_server::cli::{Mode, watch_loop};
+use language_server::core::utilities::DiagnosticBuilder;
+use language_server::core::{RustFileInfo, ScanResult};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc::unbounded_channel;
+use tower_lsp::lsp_types::{DiagnosticSeverity, Position, Range};
+
+/// Scan result with one warning per given line of `src/lib.rs`
+fn scan_result(lines: &[u32]) -> ScanResult {
+    let diagnostics = lines
+        .iter()
+        .map(|&line| {
+            DiagnosticBuilder::create(
+                Range::new(Position::new(line, 4), Position::new(line, 8)),
+                "Unchecked arithmetic".to_string(),
+                DiagnosticSeverity::WARNING,
+                "UNSAFE_MATH".to_string(),
+                None,
+            )
+        })
+        .collect();
+
+    ScanResult {
+        rust_files: vec![RustFileInfo {
+            path: PathBuf::from("/workspace/src/lib.rs"),
+            diagnostics,
+            is_anchor_program: true,
+        }],
+        ..ScanResult::default()
+    }
+}
+
+#[test]
+fn test_mode_from_args() {
+    let args = |args: &[&str]| Mode::from_args(args.iter().map(|arg| arg.to_string()));
+
+    assert_eq!(args(&[]), Ok(Mode::Server));
+    // Language clients launch the server with `--stdio`
+    assert_eq!(args(&["--stdio"]), Ok(Mode::Server));
+    assert_eq!(
+        args(&["--watch", "programs"]),
+        Ok(Mode::Watch(PathBuf::from("programs")))
+    );
+    assert_eq!(
+        args(&["--scan", "."]),
+        Ok(Mode::Scan {
+            root: PathBuf::from("."),
+            severity_floor: None,
+        })
+    );
+    assert!(args(&["--watch"]).is_err());
+}
+
+#[tokio::test]
+async fn test_file_events_trigger_debounced_rescan() {
+    let (sender, events) = unbounded_channel();
+    let scans = AtomicUsize::new(0);
+    let mut out = Vec::new();
+
+    // A burst of saves, then the watcher goes away
+    for _ in 0..5 {
+        sender.send(()).unwrap();
+    }
+    drop(sender);
+
+    watch_loop(
+        events,
+        Duration::from_millis(20),
+        || {
+            let scan = scans.fetch_add(1, Ordering::SeqCst);
+            async move {
+                match scan {
+                    0 => scan_result(&[3, 10]),
+                    _ => scan_result(&[10, 20]),
+                }
+            }
+        },
+        Path::new("/workspace"),
+        &mut out,
+    )
+    .await;
+
+    // Baseline plus a single rescan for the whole burst
+    assert_eq!(scans.load(Ordering::SeqCst), 2);
+
+    let output = String::from_utf8(out).unwrap();
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        vec![
+            "2 issues",
+            "+ src/lib.rs:21:5 warning [UNSAFE_MATH] Unchecked arithmetic",
+            "- src/lib.rs:4:5 warning [UNSAFE_MATH] Unchecked arithmetic",
+            "1 new, 1 resolved, 2 issues",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_events_after_quiet_period_rescan_again() {
+    let (sender, events) = unbounded_channel();
+    let scans = AtomicUsize::new(0);
+    let mut out = Vec::new();
+
+    let events_task = tokio::spawn(async move {
+        sender.send(()).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        sender.send(()).unwrap();
+    });
+
+    watch_loop(
+        events,
+        Duration::from_millis(20),
+        || {
+            scans.fetch_add(1, Ordering::SeqCst);
+            async { scan_result(&[3]) }
+        },
+        Path::new("/workspace"),
+        &mut out,
+    )
+    .await;
+    events_task.await.unwrap();
+
+    assert_eq!(scans.load(Ordering::SeqCst), 3);
+    assert!(
+        String::from_utf8(out)
+            .unwrap()
+            .ends_with("0 new, 0 resolved, 1 issues\n")
+    );
+}
@@ -0,0 +1,63 @@
+use language_server::core::detectors::{
+    close_destination_not_mut_detector::CloseDestinationNotMutDetector, detector::Detector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = CloseDestinationNotMutDetector::default();
+
+    assert_eq!(detector.id(), "CLOSE_DESTINATION_NOT_MUT");
+    assert_eq!(detector.name(), "Close Destination Not Mutable");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::ERROR);
+}
+
+#[test]
+fn test_detects_immutable_close_destination() {
+    let mut detector = CloseDestinationNotMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct CloseVault<'info> {
+            #[account(mut, close = dest, has_one = dest)]
+            pub vault: Account<'info, Vault>,
+            pub dest: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 5);
+    assert_eq!(diagnostics[0].range.start.character, 27);
+    assert!(diagnostics[0].message.contains("'dest'"));
+}
+
+#[test]
+fn test_mutable_close_destination_not_flagged() {
+    let mut detector = CloseDestinationNotMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct CloseVault<'info> {
+            #[account(mut, close = dest @ ErrorCode::WrongDestination)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub dest: Signer<'info>,
+        }
+
+        #[derive(Accounts)]
+        pub struct CloseToPayer<'info> {
+            #[account(mut, close = payer)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut @ ErrorCode::NotWritable)]
+            pub payer: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
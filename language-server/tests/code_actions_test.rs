@@ -0,0 +1,163 @@
+use language_server::core::detectors::{
+    detector::Detector, missing_check_comment::MissingCheckCommentDetector,
+    sysvar_account_detector::SysvarAccountDetector,
+};
+use language_server::core::language_features::CodeActionProvider;
+use language_server::core::utilities::PositionEncoding;
+use tower_lsp::lsp_types::{Position, Range, TextEdit, Url};
+
+const PROGRAM: &str = r#"use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn deposit(ctx: Context<Deposit>) -> Result<()> {
+        let now = ctx.accounts.clock.unix_timestamp;
+        ctx.accounts.vault.last_deposit = now;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    pub clock: Sysvar<'info, Clock>,
+    pub authority: Signer<'info>,
+}
+"#;
+
+/// Convert an LSP position to a byte offset (ASCII content)
+fn offset(content: &str, position: Position) -> usize {
+    content
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(|line| line.len())
+        .sum::<usize>()
+        + position.character as usize
+}
+
+/// Apply non-overlapping edits to content
+fn apply(content: &str, edits: &[TextEdit]) -> String {
+    let mut edits = edits.to_vec();
+    edits.sort_by_key(|edit| std::cmp::Reverse(offset(content, edit.range.start)));
+
+    let mut result = content.to_string();
+    for edit in edits {
+        let start = offset(content, edit.range.start);
+        let end = offset(content, edit.range.end);
+        result.replace_range(start..end, &edit.new_text);
+    }
+    result
+}
+
+fn uri() -> Url {
+    Url::parse("file:///project/programs/vault/src/lib.rs").unwrap()
+}
+
+#[test]
+fn test_sysvar_diagnostic_records_type() {
+    let diagnostics = SysvarAccountDetector::default().analyze(PROGRAM, None);
+    assert_eq!(diagnostics.len(), 1);
+
+    let data = diagnostics[0].data.as_ref().expect("diagnostic data");
+    assert_eq!(data["sysvar_type"], "Clock");
+    assert_eq!(data["field_name"], "clock");
+}
+
+#[test]
+fn test_removal_edit_removes_exactly_the_field() {
+    let diagnostics = SysvarAccountDetector::default().analyze(PROGRAM, None);
+    let actions =
+        CodeActionProvider::code_actions(PROGRAM, &uri(), &diagnostics, PositionEncoding::Utf16);
+
+    let primary = &actions[0];
+    assert_eq!(primary.is_preferred, Some(true));
+
+    let edits = &primary.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri()];
+    assert_eq!(edits.len(), 1);
+
+    let expected = PROGRAM.replace("    pub clock: Sysvar<'info, Clock>,\n", "");
+    assert_eq!(apply(PROGRAM, edits), expected);
+}
+
+#[test]
+fn test_reminder_action_inserts_comment_in_handlers() {
+    let diagnostics = SysvarAccountDetector::default().analyze(PROGRAM, None);
+    let actions =
+        CodeActionProvider::code_actions(PROGRAM, &uri(), &diagnostics, PositionEncoding::Utf16);
+    assert_eq!(actions.len(), 2);
+
+    let edits = &actions[1].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri()];
+    let updated = apply(PROGRAM, edits);
+
+    assert!(!updated.contains("Sysvar<'info, Clock>"));
+    assert!(updated.contains(
+        "    pub fn deposit(ctx: Context<Deposit>) -> Result<()> {\n        // TODO: replace ctx.accounts.clock with `let clock = Clock::get()?;`\n"
+    ));
+}
+
+#[test]
+fn test_last_field_without_trailing_comma() {
+    let code = "#[derive(Accounts)]\npub struct Tick<'info> {\n    pub payer: Signer<'info>,\n    pub rent: Sysvar<'info, Rent>\n}\n";
+
+    let diagnostics = SysvarAccountDetector::default().analyze(code, None);
+    let actions =
+        CodeActionProvider::code_actions(code, &uri(), &diagnostics, PositionEncoding::Utf16);
+    assert_eq!(actions.len(), 1);
+
+    let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri()];
+    assert_eq!(
+        apply(code, edits),
+        "#[derive(Accounts)]\npub struct Tick<'info> {\n    pub payer: Signer<'info>,\n}\n"
+    );
+}
+
+#[test]
+fn test_check_comment_action_inserts_doc_comment_above_field() {
+    let code = "#[derive(Accounts)]\npub struct Close<'info> {\n    #[account(mut)]\n    pub vault: Account<'info, Vault>,\n    #[account(mut)]\n    pub destination: UncheckedAccount<'info>,\n}\n";
+
+    let mut detector = MissingCheckCommentDetector::default();
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+
+    let actions =
+        CodeActionProvider::code_actions(code, &uri(), &diagnostics, PositionEncoding::Utf16);
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].is_preferred, Some(true));
+
+    let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri()];
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range.start, Position::new(4, 0));
+
+    let updated = apply(code, edits);
+    assert_eq!(
+        updated,
+        "#[derive(Accounts)]\npub struct Close<'info> {\n    #[account(mut)]\n    pub vault: Account<'info, Vault>,\n    /// CHECK: TODO explain why this is safe\n    #[account(mut)]\n    pub destination: UncheckedAccount<'info>,\n}\n"
+    );
+    assert!(detector.analyze(&updated, None).is_empty());
+}
+
+#[test]
+fn test_removal_range_after_multibyte_char() {
+    // `🦀` is 2 UTF-16 code units and 4 UTF-8 bytes
+    let code = "    /* 🦀 */ pub rent: Sysvar<'info, Rent>  , pub b: u8,\n";
+
+    assert_eq!(
+        CodeActionProvider::field_removal_range(
+            code,
+            Range::new(Position::new(0, 15), Position::new(0, 44)),
+            PositionEncoding::Utf8
+        ),
+        Range::new(Position::new(0, 15), Position::new(0, 47))
+    );
+    assert_eq!(
+        CodeActionProvider::field_removal_range(
+            code,
+            Range::new(Position::new(0, 13), Position::new(0, 42)),
+            PositionEncoding::Utf16
+        ),
+        Range::new(Position::new(0, 13), Position::new(0, 45))
+    );
+}
@@ -0,0 +1,115 @@
+use language_server::backend::create_default_registry;
+use language_server::core::detectors::{
+    detector::Detector, pda_typed_as_signer_detector::PdaTypedAsSignerDetector,
+};
+use language_server::core::utilities::DiagnosticBuilder;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+/// Anchors GitHub generates for the headings of a markdown file
+fn heading_anchors(markdown: &str) -> HashSet<String> {
+    markdown
+        .lines()
+        .filter_map(|line| line.strip_prefix('#'))
+        .map(|heading| {
+            heading
+                .trim_start_matches('#')
+                .trim()
+                .to_lowercase()
+                .chars()
+                .filter(|ch| ch.is_alphanumeric() || matches!(ch, ' ' | '-' | '_'))
+                .map(|ch| if ch == ' ' { '-' } else { ch })
+                .collect()
+        })
+        .collect()
+}
+
+/// Names of the lints declared by the dylint detector crates
+fn dylint_lint_names(detectors_dir: &Path) -> Vec<String> {
+    let mut names = Vec::new();
+    for entry in fs::read_dir(detectors_dir).unwrap() {
+        let Ok(source) = fs::read_to_string(entry.unwrap().path().join("src/lib.rs")) else {
+            continue;
+        };
+        names.extend(source.lines().filter_map(|line| {
+            let name = line.trim().strip_prefix("pub ")?.strip_suffix(',')?;
+            name.chars()
+                .all(|ch| ch.is_ascii_uppercase() || ch == '_')
+                .then(|| name.to_lowercase())
+        }));
+    }
+    names
+}
+
+#[test]
+fn test_diagnostic_links_to_detector_docs() {
+    let diagnostic = DiagnosticBuilder::create_with_code_description(
+        DiagnosticBuilder::create_range(3, 4, 3, 20),
+        "Missing signer".to_string(),
+        DiagnosticSeverity::WARNING,
+        "MISSING_SIGNER".to_string(),
+        None,
+    );
+
+    let href = diagnostic.code_description.unwrap().href;
+    assert_eq!(
+        href.as_str(),
+        "https://github.com/Ackee-Blockchain/solana-vscode/blob/main/README.md#missing-signer"
+    );
+    // Dylint lint names share the slug of the detector id
+    assert_eq!(
+        DiagnosticBuilder::code_description("missing_signer")
+            .unwrap()
+            .href,
+        href
+    );
+}
+
+#[test]
+fn test_detector_diagnostics_carry_code_description() {
+    let mut detector = PdaTypedAsSignerDetector::default();
+
+    let code = r#"
+        #[derive(Accounts)]
+        pub struct Sign<'info> {
+            #[account(seeds = [b"vault"], bump)]
+            pub vault: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]
+            .code_description
+            .as_ref()
+            .and_then(|description| description.href.fragment()),
+        Some("pda-typed-as-signer")
+    );
+}
+
+#[test]
+fn test_every_detector_code_has_a_readme_section() {
+    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let anchors = heading_anchors(&fs::read_to_string(repo_root.join("README.md")).unwrap());
+
+    let lint_names = dylint_lint_names(&repo_root.join("extension/detectors"));
+    assert!(lint_names.contains(&"missing_signer".to_string()));
+
+    let codes = create_default_registry()
+        .list_detectors()
+        .into_iter()
+        .map(|info| info.id)
+        .chain(lint_names);
+    for code in codes {
+        let slug = DiagnosticBuilder::doc_slug(&code);
+        assert!(
+            anchors.contains(&slug),
+            "README.md has no heading for {} (#{})",
+            code,
+            slug
+        );
+    }
+}
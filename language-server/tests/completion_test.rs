@@ -0,0 +1,68 @@
+use language_server::core::language_features::CompletionProvider;
+use tower_lsp::lsp_types::{CompletionItemKind, Position};
+
+const EXPECTED_KEYWORDS: &[&str] = &[
+    "mut",
+    "init",
+    "init_if_needed",
+    "seeds",
+    "bump",
+    "has_one",
+    "close",
+    "payer",
+    "space",
+    "realloc",
+    "constraint",
+    "owner",
+    "address",
+    "token::mint",
+    "associated_token::mint",
+];
+
+const CODE: &str = r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account()]
+    pub vault: Account<'info, Vault>,
+    #[account(mut, payer = authority)]
+    pub authority: Signer<'info>,
+}
+"#;
+
+#[test]
+fn test_completion_inside_empty_account_attribute() {
+    // Between the parentheses of `#[account()]`
+    let items = CompletionProvider::completions(CODE, Position::new(4, 14)).unwrap();
+
+    let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(labels, EXPECTED_KEYWORDS);
+    assert!(
+        items
+            .iter()
+            .all(|item| item.kind == Some(CompletionItemKind::KEYWORD)
+                && item.documentation.is_some())
+    );
+    let payer = items.iter().find(|item| item.label == "payer").unwrap();
+    assert_eq!(payer.insert_text.as_deref(), Some("payer = $0"));
+}
+
+#[test]
+fn test_completion_after_comma_in_account_attribute() {
+    // After `#[account(mut, `
+    let items = CompletionProvider::completions(CODE, Position::new(6, 19));
+    assert_eq!(
+        items.map(|items| items.len()),
+        Some(EXPECTED_KEYWORDS.len())
+    );
+}
+
+#[test]
+fn test_no_completion_outside_constraint_keys() {
+    // In the value of `payer = authority`
+    assert!(CompletionProvider::completions(CODE, Position::new(6, 28)).is_none());
+    // On the field type
+    assert!(CompletionProvider::completions(CODE, Position::new(5, 20)).is_none());
+    // Inside `#[derive(Accounts)]`
+    assert!(CompletionProvider::completions(CODE, Position::new(2, 12)).is_none());
+}
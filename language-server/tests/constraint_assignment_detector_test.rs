@@ -0,0 +1,95 @@
+use language_server::core::detectors::{
+    constraint_assignment_detector::ConstraintAssignmentDetector, detector::Detector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = ConstraintAssignmentDetector::default();
+
+    assert_eq!(detector.id(), "CONSTRAINT_ASSIGNMENT");
+    assert_eq!(detector.name(), "Constraint Assignment");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::ERROR);
+}
+
+#[test]
+fn test_detects_assignment_constraint() {
+    let mut detector = ConstraintAssignmentDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut, constraint = vault.owner = authority.key())]
+            pub vault: Account<'info, Vault>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(diagnostics[0].range.start.line, 5);
+}
+
+#[test]
+fn test_comparison_constraint_not_flagged() {
+    let mut detector = ConstraintAssignmentDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut, constraint = vault.owner == authority.key())]
+            pub vault: Account<'info, Vault>,
+            #[account(constraint = config.fee <= MAX_FEE @ ErrorCode::FeeTooHigh)]
+            pub config: Account<'info, Config>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_detects_assignment_with_custom_error() {
+    let mut detector = ConstraintAssignmentDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(constraint = vault.authority = authority.key() @ ErrorCode::Unauthorized)]
+            pub vault: Account<'info, Vault>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_other_constraints_not_flagged() {
+    let mut detector = ConstraintAssignmentDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Init<'info> {
+            #[account(init, payer = authority, space = 8 + 32, has_one = authority)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub authority: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
@@ -0,0 +1,79 @@
+use language_server::core::detectors::{
+    detector_config::DetectorConfig, unwrap_in_program_detector::UnwrapInProgramDetector,
+};
+use language_server::core::registry::DetectorRegistryBuilder;
+use tower_lsp::lsp_types::{DiagnosticSeverity, NumberOrString, Position};
+
+const CODE: &str = r#"use std::ptr; // unsafe { }
+
+pub fn copy(src: &[u8], dst: &mut [u8]) {
+    // unsafe { is fine in a comment
+    unsafe { ptr::copy(src.as_ptr(), dst.as_mut_ptr(), src.len()) }
+    let _ = unsafe{ dst.len() };
+}
+"#;
+
+fn custom_regex_matches(code: &str, patterns: &[&str]) -> Vec<Position> {
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(UnwrapInProgramDetector::default())
+        .with_config(
+            "UNWRAP_IN_PROGRAM",
+            DetectorConfig {
+                custom_regex_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+                ..Default::default()
+            },
+        )
+        .build();
+
+    registry
+        .analyze(code, None)
+        .into_iter()
+        .filter(|diagnostic| {
+            diagnostic.code
+                == Some(NumberOrString::String(
+                    "UNWRAP_IN_PROGRAM_CUSTOM_REGEX".to_string(),
+                ))
+        })
+        .map(|diagnostic| {
+            assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+            diagnostic.range.start
+        })
+        .collect()
+}
+
+#[test]
+fn test_regex_matches_code_lines() {
+    let matches = custom_regex_matches(CODE, &[r"unsafe\s*\{"]);
+
+    // Comment and import lines are skipped
+    assert_eq!(matches, vec![Position::new(4, 4), Position::new(5, 12)]);
+}
+
+#[test]
+fn test_invalid_regex_is_ignored() {
+    assert!(custom_regex_matches(CODE, &["unsafe\\s*(\\{"]).is_empty());
+    assert_eq!(
+        custom_regex_matches(CODE, &["unsafe\\s*(\\{", r"unsafe\s*\{"]).len(),
+        2
+    );
+}
+
+#[test]
+fn test_no_patterns_no_custom_diagnostics() {
+    assert!(custom_regex_matches(CODE, &[]).is_empty());
+}
+
+#[test]
+fn test_dereference_lines_are_matched() {
+    let code = r#"pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    /*
+     * lamports.borrow_mut() in a block comment is skipped
+     */
+    **ctx.accounts.vault.lamports.borrow_mut() -= amount;
+    Ok(())
+}
+"#;
+
+    let matches = custom_regex_matches(code, &[r"lamports\.borrow_mut\(\)"]);
+    assert_eq!(matches, vec![Position::new(4, 25)]);
+}
@@ -0,0 +1,130 @@
+use language_server::core::detectors::{
+    dangling_instruction_attribute_detector::DanglingInstructionAttributeDetector,
+    detector::Detector,
+};
+use std::fs;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+const HANDLERS: &str = r#"use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        Ok(())
+    }
+}
+"#;
+
+const DEPOSIT: &str = r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct Deposit<'info> {
+    #[account(mut, constraint = vault.amount >= amount)]
+    pub vault: Account<'info, Vault>,
+}
+"#;
+
+/// Create a crate whose handlers live in `lib.rs`
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-dangling-instruction-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("programs/vault/src")).unwrap();
+    fs::write(root.join("Anchor.toml"), "[programs.localnet]\n").unwrap();
+    fs::write(
+        root.join("programs/vault/Cargo.toml"),
+        "[package]\nname = \"vault\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("programs/vault/src/lib.rs"), HANDLERS).unwrap();
+    root
+}
+
+#[test]
+fn test_detector_metadata() {
+    let detector = DanglingInstructionAttributeDetector::default();
+
+    assert_eq!(detector.id(), "DANGLING_INSTRUCTION_ATTRIBUTE");
+    assert_eq!(detector.name(), "Dangling Instruction Attribute");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_dangling_attribute() {
+    let mut detector = DanglingInstructionAttributeDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        #[instruction(amount: u64)]
+        pub struct Withdraw<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 4);
+    assert_eq!(diagnostics[0].range.start.character, 8);
+    assert_eq!(diagnostics[0].range.end.character, 35);
+    assert!(diagnostics[0].message.contains("Context<Withdraw>"));
+}
+
+#[test]
+fn test_referenced_in_same_file_not_flagged() {
+    let mut detector = DanglingInstructionAttributeDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        #[instruction(amount: u64)]
+        pub struct Withdraw<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+        }
+
+        #[derive(Accounts)]
+        pub struct Close<'info> {
+            pub vault: Account<'info, Vault>,
+        }
+
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_referenced_in_another_file_not_flagged() {
+    let root = workspace("referenced");
+    let file_path = root.join("programs/vault/src/deposit.rs");
+    fs::write(&file_path, DEPOSIT).unwrap();
+
+    let mut detector = DanglingInstructionAttributeDetector::default();
+    let diagnostics = detector.analyze(DEPOSIT, Some(&file_path));
+    assert!(diagnostics.is_empty());
+
+    // Removing the handler makes the attribute dangle
+    fs::write(
+        root.join("programs/vault/src/lib.rs"),
+        "use anchor_lang::prelude::*;\n",
+    )
+    .unwrap();
+    let diagnostics = detector.analyze(DEPOSIT, Some(&file_path));
+    assert_eq!(diagnostics.len(), 1);
+
+    let _ = fs::remove_dir_all(&root);
+}
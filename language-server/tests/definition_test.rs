@@ -0,0 +1,131 @@
+use language_server::core::file_scanner::FileScanner;
+use language_server::core::language_features::DefinitionProvider;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::{Position, Range};
+
+const LIB: &str = r#"use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        Ok(())
+    }
+}
+"#;
+
+const INSTRUCTIONS: &str = r#"use anchor_lang::prelude::*;
+
+pub struct Initialize {
+    pub not_accounts: u8,
+}
+
+pub mod accounts {
+    use super::*;
+
+    #[derive(Accounts)]
+    pub struct Initialize<'info> {
+        #[account(mut)]
+        pub payer: Signer<'info>,
+    }
+}
+"#;
+
+/// Create a workspace with the handler and the accounts struct in separate files
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-definition-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("programs/vault/src")).unwrap();
+    fs::write(root.join("programs/vault/src/lib.rs"), LIB).unwrap();
+    fs::write(
+        root.join("programs/vault/src/instructions.rs"),
+        INSTRUCTIONS,
+    )
+    .unwrap();
+    root
+}
+
+fn workspace_files(root: &Path) -> Vec<PathBuf> {
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.to_path_buf());
+    scanner.rust_files()
+}
+
+#[test]
+fn test_context_type_under_cursor() {
+    // Inside `Initialize` of `Context<Initialize>`
+    let position = Position::new(6, 39);
+    assert_eq!(
+        DefinitionProvider::context_accounts_at(LIB, position),
+        Some("Initialize".to_string())
+    );
+
+    // On `Context` itself
+    assert_eq!(
+        DefinitionProvider::context_accounts_at(LIB, Position::new(6, 30)),
+        None
+    );
+}
+
+#[test]
+fn test_resolves_accounts_struct_in_other_file() {
+    let root = workspace("other-file");
+    let lib = root.join("programs/vault/src/lib.rs");
+
+    let (path, range) = DefinitionProvider::new()
+        .find_definition(&lib, LIB, Position::new(6, 39), &workspace_files(&root))
+        .expect("definition");
+
+    assert_eq!(path, root.join("programs/vault/src/instructions.rs"));
+    // The `#[derive(Accounts)]` struct, not the plain struct with the same name
+    assert_eq!(
+        range,
+        Range::new(Position::new(10, 15), Position::new(10, 25))
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_prefers_current_document() {
+    let root = workspace("current");
+    let lib = root.join("programs/vault/src/lib.rs");
+    let content = format!(
+        "{}\n#[derive(Accounts)]\npub struct Initialize<'info> {{\n    pub payer: Signer<'info>,\n}}\n",
+        LIB
+    );
+
+    let (path, range) = DefinitionProvider::new()
+        .find_definition(
+            &lib,
+            &content,
+            Position::new(6, 39),
+            &workspace_files(&root),
+        )
+        .expect("definition");
+
+    assert_eq!(path, lib);
+    assert_eq!(range.start.line, 12);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_no_definition_outside_context() {
+    let root = workspace("outside");
+    let lib = root.join("programs/vault/src/lib.rs");
+
+    assert!(
+        DefinitionProvider::new()
+            .find_definition(&lib, LIB, Position::new(0, 5), &workspace_files(&root))
+            .is_none()
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,129 @@
+use language_server::backend::create_default_registry;
+use language_server::core::FileScanner;
+use language_server::core::backend_stats::BackendManager;
+use language_server::core::detectors::detector_config::DetectorConfig;
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        msg!("Withdrawing {}", amount);
+        let balance = ctx.accounts.vault.amount.checked_sub(amount).unwrap();
+        require!(balance as u32 > 0, ErrorCode::Empty);
+        Ok(())
+    }
+}
+"#;
+
+#[test]
+fn test_analyze_file_times_every_detector() {
+    let mut registry = create_default_registry();
+    // Some detectors are off by default
+    for info in registry.list_detectors() {
+        registry.enable(&info.id);
+    }
+
+    let timings = registry.analyze_file(PROGRAM, None);
+
+    assert_eq!(timings.len(), registry.count());
+    let ids: Vec<String> = registry
+        .list_detectors()
+        .into_iter()
+        .map(|info| info.id)
+        .collect();
+    let timed_ids: Vec<String> = timings.iter().map(|timing| timing.id.clone()).collect();
+    assert_eq!(timed_ids, ids);
+    assert!(timings.iter().all(|timing| timing.duration.is_some()));
+
+    // Counts match what a regular analysis reports
+    let total: usize = timings.iter().map(|timing| timing.count).sum();
+    assert!(total > 0);
+    assert_eq!(total, registry.analyze(PROGRAM, None).len());
+}
+
+#[test]
+fn test_disabled_detector_is_not_timed() {
+    let mut registry = create_default_registry();
+    registry.disable("UNWRAP_IN_PROGRAM");
+
+    let timings = registry.analyze_file(PROGRAM, None);
+
+    assert_eq!(timings.len(), registry.count());
+    let disabled = timings
+        .iter()
+        .find(|timing| timing.id == "UNWRAP_IN_PROGRAM")
+        .unwrap();
+    assert_eq!(disabled.duration, None);
+    assert_eq!(disabled.count, 0);
+}
+
+#[test]
+fn test_custom_regex_patterns_are_timed() {
+    let mut registry = create_default_registry();
+    registry.configure(
+        "UNWRAP_IN_PROGRAM",
+        DetectorConfig {
+            custom_regex_patterns: vec![r"msg!\(".to_string()],
+            ..Default::default()
+        },
+    );
+
+    let timings = registry.analyze_file(PROGRAM, None);
+
+    assert_eq!(timings.len(), registry.count() + 1);
+    let position = timings
+        .iter()
+        .position(|timing| timing.id == "UNWRAP_IN_PROGRAM")
+        .unwrap();
+    let custom = &timings[position + 1];
+    assert_eq!(custom.id, "UNWRAP_IN_PROGRAM_CUSTOM_REGEX");
+    assert!(custom.duration.is_some());
+    assert_eq!(custom.count, 1);
+
+    let total: usize = timings.iter().map(|timing| timing.count).sum();
+    assert_eq!(total, registry.analyze(PROGRAM, None).len());
+
+    registry.disable("UNWRAP_IN_PROGRAM");
+    let timings = registry.analyze_file(PROGRAM, None);
+    let custom = timings
+        .iter()
+        .find(|timing| timing.id == "UNWRAP_IN_PROGRAM_CUSTOM_REGEX")
+        .unwrap();
+    assert_eq!(custom.duration, None);
+    assert_eq!(custom.count, 0);
+}
+
+#[tokio::test]
+async fn test_workspace_timings_sum_over_files() {
+    let dir = std::env::temp_dir().join(format!("detector_timing_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), PROGRAM).unwrap();
+    std::fs::write(dir.join("src/other.rs"), PROGRAM).unwrap();
+
+    let mut registry = create_default_registry();
+    let single: usize = registry
+        .analyze_file(PROGRAM, None)
+        .iter()
+        .map(|timing| timing.count)
+        .sum();
+
+    let scanner = FileScanner::new(dir.clone());
+    let timings = BackendManager::get_detector_timings(&mut registry, &scanner).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(timings.len(), registry.count());
+    assert_eq!(
+        timings.iter().map(|timing| timing.count).sum::<usize>(),
+        single * 2
+    );
+    // Slowest detector first
+    assert!(
+        timings
+            .windows(2)
+            .all(|pair| pair[0].duration >= pair[1].duration)
+    );
+}
@@ -0,0 +1,82 @@
+use language_server::core::utilities::DiagnosticBuilder;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+fn diagnostic(line: u32, character: u32, code: &str, message: &str) -> Diagnostic {
+    DiagnosticBuilder::create(
+        Range::new(
+            Position::new(line, character),
+            Position::new(line, character + 5),
+        ),
+        message.to_string(),
+        DiagnosticSeverity::WARNING,
+        code.to_string(),
+        None,
+    )
+}
+
+fn code(diagnostic: &Diagnostic) -> &str {
+    match &diagnostic.code {
+        Some(NumberOrString::String(code)) => code,
+        _ => "",
+    }
+}
+
+#[test]
+fn test_merge_dedupes_and_sorts() {
+    let syn = vec![
+        diagnostic(12, 4, "UNSAFE_MATH", "Unchecked arithmetic"),
+        diagnostic(3, 8, "MISSING_SIGNER", "Missing signer"),
+        diagnostic(3, 8, "MISSING_SIGNER", "Missing signer"),
+    ];
+    let mut dylint_duplicate = diagnostic(12, 4, "UNSAFE_MATH", "Unchecked arithmetic");
+    dylint_duplicate.source = Some("dylint".to_string());
+    let dylint = vec![
+        dylint_duplicate,
+        diagnostic(3, 8, "MISSING_OWNER_CHECK", "Missing owner check"),
+        diagnostic(3, 2, "UNSAFE_MATH", "Unchecked arithmetic"),
+    ];
+
+    let merged = DiagnosticBuilder::merge_and_normalize(syn, dylint);
+
+    let positions: Vec<(u32, u32, &str)> = merged
+        .iter()
+        .map(|d| (d.range.start.line, d.range.start.character, code(d)))
+        .collect();
+    assert_eq!(
+        positions,
+        vec![
+            (3, 2, "UNSAFE_MATH"),
+            (3, 8, "MISSING_OWNER_CHECK"),
+            (3, 8, "MISSING_SIGNER"),
+            (12, 4, "UNSAFE_MATH"),
+        ]
+    );
+    // The syn diagnostic wins over an identical dylint one
+    assert_eq!(merged[3].source.as_deref(), Some("solana-vscode"));
+}
+
+#[test]
+fn test_same_position_different_message_kept() {
+    let merged = DiagnosticBuilder::merge_and_normalize(
+        vec![diagnostic(5, 0, "UNSAFE_MATH", "Unchecked addition")],
+        vec![diagnostic(5, 0, "UNSAFE_MATH", "Unchecked subtraction")],
+    );
+
+    assert_eq!(merged.len(), 2);
+}
+
+#[test]
+fn test_order_does_not_depend_on_input_order() {
+    let diagnostics = vec![
+        diagnostic(7, 1, "B", "second"),
+        diagnostic(7, 1, "A", "first"),
+        diagnostic(1, 9, "C", "third"),
+    ];
+    let mut reversed = diagnostics.clone();
+    reversed.reverse();
+
+    assert_eq!(
+        DiagnosticBuilder::normalize(diagnostics),
+        DiagnosticBuilder::normalize(reversed)
+    );
+}
@@ -0,0 +1,77 @@
+use futures::StreamExt;
+use language_server::backend::Backend;
+use language_server::core::diagnostic_sink::DIAGNOSTICS_LOG_ENV;
+use serde_json::{Value, json};
+use std::fs;
+use tower::{Service, ServiceExt};
+use tower_lsp::LspService;
+use tower_lsp::jsonrpc::Request;
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        **ctx.accounts.vault.lamports.borrow_mut() = 0;
+        Ok(())
+    }
+}
+"#;
+
+#[tokio::test]
+async fn test_published_diagnostics_are_logged() {
+    let dir = std::env::temp_dir().join(format!("diagnostic_sink_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let log = dir.join("diagnostics.ndjson");
+    let _ = fs::remove_file(&log);
+    // Only test in this binary, so no other thread reads the environment
+    unsafe { std::env::set_var(DIAGNOSTICS_LOG_ENV, &log) };
+
+    let (mut service, socket) = LspService::new(Backend::new);
+    // Drain server-to-client messages so publishing never blocks
+    tokio::spawn(socket.for_each(|_| async {}));
+
+    let initialize = Request::build("initialize")
+        .params(json!({ "capabilities": {} }))
+        .id(1)
+        .finish();
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap();
+
+    let file = dir.join("lib.rs");
+    let did_open = Request::build("textDocument/didOpen")
+        .params(json!({
+            "textDocument": {
+                "uri": format!("file://{}", file.display()),
+                "languageId": "rust",
+                "version": 1,
+                "text": PROGRAM,
+            }
+        }))
+        .finish();
+    service.ready().await.unwrap().call(did_open).await.unwrap();
+
+    let content = fs::read_to_string(&log).unwrap();
+    let lines: Vec<Value> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let zeroing = lines
+        .iter()
+        .find(|line| line["code"] == "MANUAL_LAMPORTS_ZEROING")
+        .unwrap();
+    assert_eq!(zeroing["file"], file.to_string_lossy().as_ref());
+    assert_eq!(zeroing["severity"], "error");
+    assert_eq!(zeroing["range"]["start"]["line"], 8);
+    assert!(zeroing["timestamp_ms"].as_u64().unwrap() > 0);
+}
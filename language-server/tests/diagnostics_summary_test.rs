@@ -0,0 +1,50 @@
+use language_server::backend::create_default_registry;
+use language_server::core::{FileScanner, ScanSummary, SeverityCounts};
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let balance = ctx.accounts.vault.amount.checked_sub(amount).unwrap();
+        require!(balance as u32 > 0, ErrorCode::Empty);
+        Ok(())
+    }
+}
+"#;
+
+#[tokio::test]
+async fn test_summary_counts_per_detector() {
+    let dir = std::env::temp_dir().join(format!("diagnostics_summary_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), PROGRAM).unwrap();
+    std::fs::write(dir.join("src/other.rs"), PROGRAM).unwrap();
+    std::fs::write(dir.join("src/clean.rs"), "pub fn add(a: u8) -> u8 { a }\n").unwrap();
+
+    let mut registry = create_default_registry();
+    let scanner = FileScanner::new(dir.clone());
+    let scan_result = scanner.scan_workspace(&mut registry).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let summary = ScanSummary::from_scan_result(&scan_result, true);
+    assert_eq!(summary.total_rust_files, 3);
+    assert_eq!(summary.files_with_issues, 2);
+    assert_eq!(summary.by_detector.get("UNWRAP_IN_PROGRAM"), Some(&2));
+    assert_eq!(
+        summary.by_detector.get("LOSSY_REQUIRE_COMPARISON"),
+        Some(&2)
+    );
+    assert_eq!(
+        summary.by_detector.values().sum::<usize>(),
+        summary.total_issues
+    );
+
+    let severities = SeverityCounts::from_scan_result(&scan_result);
+    assert_eq!(
+        severities.error + severities.warning + severities.info,
+        summary.total_issues
+    );
+}
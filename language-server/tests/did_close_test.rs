@@ -0,0 +1,84 @@
+use futures::StreamExt;
+use language_server::backend::Backend;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use tower::{Service, ServiceExt};
+use tower_lsp::LspService;
+use tower_lsp::jsonrpc::Request;
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        **ctx.accounts.vault.lamports.borrow_mut() = 0;
+        Ok(())
+    }
+}
+"#;
+
+#[tokio::test]
+async fn test_close_clears_diagnostics() {
+    let (mut service, socket) = LspService::new(Backend::new);
+    // Record published diagnostics, draining everything else so publishing never blocks
+    let published: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let recorded = Arc::clone(&published);
+    tokio::spawn(socket.for_each(move |request| {
+        if request.method() == "textDocument/publishDiagnostics" {
+            recorded
+                .lock()
+                .unwrap()
+                .push(request.params().cloned().unwrap());
+        }
+        async {}
+    }));
+
+    let initialize = Request::build("initialize")
+        .params(json!({ "capabilities": {} }))
+        .id(1)
+        .finish();
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap();
+
+    let uri = "file:///tmp/did_close/lib.rs";
+    let did_open = Request::build("textDocument/didOpen")
+        .params(json!({
+            "textDocument": { "uri": uri, "languageId": "rust", "version": 7, "text": PROGRAM }
+        }))
+        .finish();
+    service.ready().await.unwrap().call(did_open).await.unwrap();
+
+    let did_close = Request::build("textDocument/didClose")
+        .params(json!({ "textDocument": { "uri": uri } }))
+        .finish();
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(did_close)
+        .await
+        .unwrap();
+
+    // Let the recorder catch up with the notifications sent so far
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let published = published.lock().unwrap();
+    let for_uri: Vec<&Value> = published
+        .iter()
+        .filter(|params| params["uri"] == uri)
+        .collect();
+    assert_eq!(for_uri.len(), 2);
+    assert!(!for_uri[0]["diagnostics"].as_array().unwrap().is_empty());
+    assert!(for_uri[1]["diagnostics"].as_array().unwrap().is_empty());
+    // The open document's version, not the server's publish counter; none once closed
+    assert_eq!(for_uri[0]["version"], 7);
+    assert!(for_uri[1]["version"].is_null());
+}
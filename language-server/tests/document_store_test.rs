@@ -0,0 +1,150 @@
+use language_server::core::utilities::{DocumentStore, PositionEncoding, byte_offset};
+use tower_lsp::lsp_types::{Position, Range, TextDocumentContentChangeEvent, Url};
+
+fn edit(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+    TextDocumentContentChangeEvent {
+        range: Some(Range::new(
+            Position::new(start.0, start.1),
+            Position::new(end.0, end.1),
+        )),
+        range_length: None,
+        text: text.to_string(),
+    }
+}
+
+fn uri() -> Url {
+    Url::parse("file:///workspace/src/lib.rs").unwrap()
+}
+
+#[test]
+fn test_incremental_edits_reconstruct_buffer() {
+    let mut store = DocumentStore::default();
+    store.insert(uri(), "fn main() {\n}\n".to_string(), 1);
+
+    let changes = vec![
+        // Insert a body line
+        edit((0, 11), (0, 11), "\n    let x = 1;"),
+        // Rename `x` to `amount`
+        edit((1, 8), (1, 9), "amount"),
+        // Append a statement after the first one
+        edit((1, 19), (1, 19), "\n    msg!(\"{}\", amount);"),
+    ];
+    let document = store
+        .apply_changes(&uri(), 2, changes, PositionEncoding::Utf16)
+        .unwrap();
+
+    assert_eq!(
+        document.text,
+        "fn main() {\n    let amount = 1;\n    msg!(\"{}\", amount);\n}\n"
+    );
+    assert_eq!(document.version, 2);
+
+    // Delete the msg! line across the line break
+    let document = store
+        .apply_changes(
+            &uri(),
+            3,
+            vec![edit((1, 19), (2, 23), "")],
+            PositionEncoding::Utf16,
+        )
+        .unwrap();
+    assert_eq!(document.text, "fn main() {\n    let amount = 1;\n}\n");
+    assert_eq!(document.version, 3);
+}
+
+#[test]
+fn test_utf16_columns_after_multibyte_chars() {
+    let mut store = DocumentStore::default();
+    // `é` is one UTF-16 unit (two bytes), `🦀` is two UTF-16 units (four bytes)
+    store.insert(uri(), "// é🦀 x\n".to_string(), 1);
+
+    let document = store
+        .apply_changes(
+            &uri(),
+            2,
+            vec![edit((0, 7), (0, 8), "y")],
+            PositionEncoding::Utf16,
+        )
+        .unwrap();
+    assert_eq!(document.text, "// é🦀 y\n");
+
+    assert_eq!(
+        byte_offset("é🦀x", Position::new(0, 1), PositionEncoding::Utf16),
+        2
+    );
+    assert_eq!(
+        byte_offset("é🦀x", Position::new(0, 3), PositionEncoding::Utf16),
+        6
+    );
+    // Positions past the end of a line or the document are clamped
+    assert_eq!(
+        byte_offset("ab\ncd", Position::new(0, 10), PositionEncoding::Utf16),
+        2
+    );
+    assert_eq!(
+        byte_offset("ab\ncd", Position::new(5, 0), PositionEncoding::Utf16),
+        5
+    );
+}
+
+#[test]
+fn test_utf8_columns_after_multibyte_chars() {
+    let mut store = DocumentStore::default();
+    // `é` is two UTF-8 bytes, `🦀` is four
+    store.insert(uri(), "// é🦀 x\n".to_string(), 1);
+
+    let document = store
+        .apply_changes(
+            &uri(),
+            2,
+            vec![edit((0, 10), (0, 11), "y")],
+            PositionEncoding::Utf8,
+        )
+        .unwrap();
+    assert_eq!(document.text, "// é🦀 y\n");
+
+    assert_eq!(
+        byte_offset("é🦀x", Position::new(0, 2), PositionEncoding::Utf8),
+        2
+    );
+    // A column inside a character moves past it
+    assert_eq!(
+        byte_offset("é🦀x", Position::new(0, 3), PositionEncoding::Utf8),
+        6
+    );
+}
+
+#[test]
+fn test_full_replacement_and_unknown_documents() {
+    let mut store = DocumentStore::default();
+
+    // Range edits to a document that was never opened are dropped
+    assert!(
+        store
+            .apply_changes(
+                &uri(),
+                1,
+                vec![edit((0, 0), (0, 0), "x")],
+                PositionEncoding::Utf16
+            )
+            .is_none()
+    );
+
+    let replace = TextDocumentContentChangeEvent {
+        range: None,
+        range_length: None,
+        text: "pub fn a() {}\n".to_string(),
+    };
+    let document = store
+        .apply_changes(
+            &uri(),
+            2,
+            vec![replace, edit((0, 7), (0, 8), "b")],
+            PositionEncoding::Utf16,
+        )
+        .unwrap();
+    assert_eq!(document.text, "pub fn b() {}\n");
+
+    assert!(store.remove(&uri()).is_some());
+    assert!(store.get(&uri()).is_none());
+}
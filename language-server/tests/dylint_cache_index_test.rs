@@ -0,0 +1,116 @@
+use language_server::core::dylint::cache::{CacheIndex, DylintDetectorCache};
+use language_server::core::dylint::scanner::DylintDetectorInfo;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const NIGHTLY: &str = "nightly-2025-01-09";
+
+/// Create a fresh temporary directory for a test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "solana-ls-dylint-cache-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Create a detector crate and a fake compiled library for it
+fn detector(root: &Path) -> (DylintDetectorInfo, PathBuf) {
+    let crate_path = root.join("detectors/unchecked_math");
+    fs::create_dir_all(crate_path.join("src")).unwrap();
+    fs::write(
+        crate_path.join("Cargo.toml"),
+        "[package]\nname = \"unchecked_math\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(crate_path.join("src/lib.rs"), "// lint v1\n").unwrap();
+
+    let compiled = root.join("libunchecked_math.so");
+    fs::write(&compiled, b"compiled").unwrap();
+
+    let info = DylintDetectorInfo {
+        cargo_toml_path: crate_path.join("Cargo.toml"),
+        crate_name: "unchecked_math".to_string(),
+        crate_path,
+    };
+    (info, compiled)
+}
+
+#[test]
+fn test_index_persists_across_instances() {
+    let root = temp_dir("persist");
+    let cache_dir = root.join("cache");
+    let (info, compiled) = detector(&root);
+
+    let mut cache = DylintDetectorCache::with_cache_dir(cache_dir.clone()).unwrap();
+    assert!(cache.get_cached_library(&info, NIGHTLY).is_none());
+    let cached_path = cache.cache_library(&info, NIGHTLY, &compiled).unwrap();
+
+    assert!(cache_dir.join(CacheIndex::FILE_NAME).exists());
+
+    // A new instance (e.g. after a restart) reuses the library from the index
+    let reloaded = DylintDetectorCache::with_cache_dir(cache_dir).unwrap();
+    assert_eq!(
+        reloaded.get_cached_library(&info, NIGHTLY),
+        Some(cached_path)
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_changed_sources_reject_cached_library() {
+    let root = temp_dir("stale");
+    let cache_dir = root.join("cache");
+    let (info, compiled) = detector(&root);
+
+    let mut cache = DylintDetectorCache::with_cache_dir(cache_dir.clone()).unwrap();
+    cache.cache_library(&info, NIGHTLY, &compiled).unwrap();
+
+    fs::write(info.crate_path.join("src/lib.rs"), "// lint v2\n").unwrap();
+
+    let reloaded = DylintDetectorCache::with_cache_dir(cache_dir).unwrap();
+    assert!(reloaded.get_cached_library(&info, NIGHTLY).is_none());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_stale_hash_in_index_is_rejected() {
+    let root = temp_dir("index-hash");
+    let cache_dir = root.join("cache");
+    let (info, compiled) = detector(&root);
+
+    let mut cache = DylintDetectorCache::with_cache_dir(cache_dir.clone()).unwrap();
+    cache.cache_library(&info, NIGHTLY, &compiled).unwrap();
+
+    // Tamper with the persisted hash
+    let index_path = cache_dir.join(CacheIndex::FILE_NAME);
+    let mut index = CacheIndex::load(&index_path);
+    for entry in index.entries.values_mut() {
+        entry.source_hash = entry.source_hash.wrapping_add(1);
+    }
+    index.save(&index_path).unwrap();
+
+    let reloaded = DylintDetectorCache::with_cache_dir(cache_dir).unwrap();
+    assert!(reloaded.get_cached_library(&info, NIGHTLY).is_none());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_unreadable_index_starts_empty() {
+    let root = temp_dir("corrupt");
+    let cache_dir = root.join("cache");
+    fs::create_dir_all(&cache_dir).unwrap();
+    fs::write(cache_dir.join(CacheIndex::FILE_NAME), "{not json").unwrap();
+    let (info, _) = detector(&root);
+
+    let cache = DylintDetectorCache::with_cache_dir(cache_dir).unwrap();
+    assert!(cache.get_cached_library(&info, NIGHTLY).is_none());
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,128 @@
+#![cfg(unix)]
+
+use language_server::core::dylint::cache::DylintDetectorCache;
+use language_server::core::dylint::compiler::DylintDetectorCompiler;
+use language_server::core::dylint::{DylintDetectorManager, PrebuildSummary};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+const DETECTORS: &[&str] = &["lint_a", "lint_b", "lint_c", "lint_d", "lint_e"];
+
+/// Create a fresh temporary directory for a test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "solana-ls-dylint-concurrency-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A cargo stub that "builds" the detector in its working directory
+/// While building, it holds a slot in `running/` and logs how many slots were held at once
+fn cargo_stub(root: &Path) -> PathBuf {
+    let running = root.join("running");
+    fs::create_dir_all(&running).unwrap();
+    let stub = root.join("cargo-stub");
+    fs::write(
+        &stub,
+        format!(
+            "#!/bin/sh\n\
+             touch \"{running}/$$\"\n\
+             ls \"{running}\" | wc -l >> \"{log}\"\n\
+             sleep 0.5\n\
+             name=$(basename \"$PWD\")\n\
+             mkdir -p target/debug\n\
+             touch \"target/debug/lib$name.so\" \"target/debug/lib$name.dylib\"\n\
+             rm \"{running}/$$\"\n",
+            running = running.display(),
+            log = root.join("concurrency.log").display(),
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+    stub
+}
+
+/// A manager over `DETECTORS` under `<root>/extension/detectors/`, building with the stub
+fn manager(root: &Path, limit: usize) -> DylintDetectorManager {
+    let extension = root.join("extension");
+    for name in DETECTORS {
+        let crate_path = extension.join("detectors").join(name);
+        fs::create_dir_all(crate_path.join("src")).unwrap();
+        fs::write(
+            crate_path.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n\n[dependencies]\n# dylint_linting omitted in the fixture\n",
+                name
+            ),
+        )
+        .unwrap();
+        fs::write(crate_path.join("src/lib.rs"), "// lint\n").unwrap();
+    }
+
+    let compiler =
+        DylintDetectorCompiler::new().with_cargo_command(cargo_stub(root).to_str().unwrap());
+    let cache = DylintDetectorCache::with_cache_dir(root.join("cache")).unwrap();
+    let mut manager = DylintDetectorManager::with_cache(cache)
+        .with_compiler(compiler)
+        .with_compile_concurrency(limit);
+    manager.set_extension_path(extension);
+    manager
+}
+
+/// Highest number of builds seen running at the same time
+fn max_concurrent_builds(root: &Path) -> usize {
+    fs::read_to_string(root.join("concurrency.log"))
+        .unwrap()
+        .lines()
+        .map(|line| line.trim().parse::<usize>().unwrap())
+        .max()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_prebuild_respects_concurrency_limit() {
+    let root = temp_dir("prebuild");
+    let manager = manager(&root, 2);
+
+    let summary = manager.prebuild_detectors(None).await.unwrap();
+    assert_eq!(
+        summary,
+        PrebuildSummary {
+            compiled: DETECTORS.len(),
+            cached: 0,
+            failed: 0,
+        }
+    );
+    assert_eq!(max_concurrent_builds(&root), 2);
+
+    // Everything is cached now, so nothing is built again
+    let summary = manager.prebuild_detectors(None).await.unwrap();
+    assert_eq!(summary.cached, DETECTORS.len());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_initialize_compiles_all_detectors() {
+    let root = temp_dir("initialize");
+    let mut manager = manager(&root, 3);
+
+    let paths = manager.initialize().await.unwrap();
+    assert_eq!(paths.len(), DETECTORS.len());
+    assert!(paths.iter().all(|path| path.exists()));
+    for name in DETECTORS {
+        assert!(
+            paths
+                .iter()
+                .any(|path| path.to_string_lossy().contains(name))
+        );
+    }
+    assert!(max_concurrent_builds(&root) <= 3);
+
+    let _ = fs::remove_dir_all(&root);
+}
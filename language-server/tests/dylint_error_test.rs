@@ -0,0 +1,183 @@
+#![cfg(unix)]
+
+use language_server::dylint_runner::{DylintError, DylintRunner};
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A runner whose cargo is a stub script running `body` (it answers `--version` itself),
+/// with a fake lint library and driver so runs reach cargo
+fn stub_runner(name: &str, body: &str) -> (DylintRunner, PathBuf) {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-dylint-error-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&root);
+    let workspace = root.join("workspace");
+    std::fs::create_dir_all(&workspace).unwrap();
+    std::fs::write(
+        workspace.join("Cargo.toml"),
+        "[package]\nname = \"program\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let stub = root.join("cargo-stub");
+    std::fs::write(
+        &stub,
+        format!(
+            "#!/bin/sh\nif [ \"$1\" = \"--version\" ]; then echo \"cargo 1.0.0 (stub)\"; exit 0; fi\n{}\n",
+            body
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let driver = root.join("dylint-driver");
+    std::fs::write(&driver, "").unwrap();
+
+    let runner = DylintRunner::new(&root.join("extension"))
+        .unwrap()
+        .with_cargo_command(stub.to_str().unwrap())
+        .with_dylint_driver(driver);
+    runner.add_workspace_detectors(vec![root.join("libmissing_signer@nightly.so")]);
+    (runner, workspace)
+}
+
+#[tokio::test]
+async fn test_toolchain_not_installed_is_nightly_missing() {
+    let (runner, workspace) = stub_runner(
+        "nightly",
+        "echo \"error: toolchain 'nightly-2025-09-18-x86_64-unknown-linux-gnu' is not installed\" >&2\nexit 1",
+    );
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(error, DylintError::NightlyMissing { .. }));
+    assert_eq!(error.status().status, "missing_toolchain");
+}
+
+#[tokio::test]
+async fn test_missing_driver_is_driver_missing() {
+    let (runner, workspace) = stub_runner("driver", "exit 0");
+    let runner = runner.with_dylint_driver(PathBuf::from("/nonexistent/dylint-driver"));
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(error, DylintError::DriverMissing { .. }));
+
+    let status = error.status();
+    assert_eq!(status.status, "missing_driver");
+    assert!(status.install_command.unwrap().contains("cargo-dylint"));
+}
+
+#[tokio::test]
+async fn test_unspawnable_cargo_is_cargo_spawn() {
+    // The stub is removed after answering `--version`, so the actual run cannot start
+    let (runner, workspace) = stub_runner("spawn", "exit 0");
+    let stub = workspace.parent().unwrap().join("cargo-stub");
+    assert!(runner.cargo_available().await);
+    std::fs::remove_file(&stub).unwrap();
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(error, DylintError::CargoSpawn(_)));
+    assert_eq!(error.status().status, "cargo_missing");
+}
+
+#[tokio::test]
+async fn test_slow_cargo_is_timeout() {
+    let (runner, workspace) = stub_runner("timeout", "exec sleep 30");
+    let runner = runner.with_timeout(Duration::from_millis(300));
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(
+        matches!(error, DylintError::Timeout(timeout) if timeout == Duration::from_millis(300))
+    );
+    assert_eq!(error.status().status, "timeout");
+}
+
+#[tokio::test]
+async fn test_non_json_output_is_parse_failed() {
+    let (runner, workspace) = stub_runner("parse", "echo 'Checking program v0.1.0'\nexit 0");
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(error, DylintError::ParseFailed(_)));
+    assert_eq!(error.status().status, "parse_failed");
+}
+
+#[tokio::test]
+async fn test_failed_build_is_compile_failed() {
+    let (runner, workspace) = stub_runner(
+        "compile",
+        "echo '{\"reason\":\"build-finished\",\"success\":false}'\necho 'error[E0425]: cannot find value `x` in this scope' >&2\nexit 101",
+    );
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    match &error {
+        DylintError::CompileFailed { stderr } => assert!(stderr.contains("E0425")),
+        other => panic!("expected CompileFailed, got {:?}", other),
+    }
+    assert_eq!(error.status().status, "compile_failed");
+}
+
+#[tokio::test]
+async fn test_successful_run_is_ok() {
+    let (runner, workspace) = stub_runner(
+        "ok",
+        "echo '{\"reason\":\"build-finished\",\"success\":true}'\nexit 0",
+    );
+
+    assert!(runner.run_lints(&workspace).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_driver_panic_is_driver_crashed() {
+    // Like real cargo, the stub still reports the build on stdout and exits with 101
+    let (runner, workspace) = stub_runner(
+        "panic",
+        "echo '{\"reason\":\"build-finished\",\"success\":false}'\necho \"thread 'rustc' panicked at compiler/rustc_middle/src/ty/mod.rs:42:5\" >&2\necho 'error: the compiler unexpectedly panicked. this is a bug.' >&2\nexit 101",
+    );
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(error, DylintError::DriverCrashed { .. }));
+    assert_eq!(error.status().status, "driver_crashed");
+    assert!(!runner.is_disabled());
+}
+
+#[tokio::test]
+async fn test_compile_error_is_not_a_crash() {
+    let (runner, workspace) = stub_runner(
+        "no-crash",
+        "echo '{\"reason\":\"build-finished\",\"success\":false}'\necho 'error: could not compile `program` (lib) due to 1 previous error' >&2\nexit 101",
+    );
+    let runner = runner.with_crash_threshold(1);
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(error, DylintError::CompileFailed { .. }));
+    assert!(!runner.is_disabled());
+}
+
+#[tokio::test]
+async fn test_repeated_crashes_disable_dylint() {
+    // The stub logs each real run, then fails like cargo does when rustc segfaults
+    let (runner, workspace) = stub_runner(
+        "segfault",
+        "echo run >> \"$0.log\"\necho '{\"reason\":\"build-finished\",\"success\":false}'\necho 'error: could not compile `program` (lib)' >&2\necho 'Caused by:' >&2\necho \"  process didn't exit successfully: \\`dylint-driver rustc --crate-name program\\` (signal: 11, SIGSEGV: invalid memory reference)\" >&2\nexit 101",
+    );
+    let runner = runner.with_crash_threshold(2);
+    let log = workspace.parent().unwrap().join("cargo-stub.log");
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(error, DylintError::DriverCrashed { .. }));
+    assert!(!runner.is_disabled());
+
+    let error = runner.run_lints(&workspace).await.unwrap_err();
+    assert!(matches!(
+        error,
+        DylintError::DriverUnstable { crashes: 2, .. }
+    ));
+    assert_eq!(error.status().status, "driver_unstable");
+    assert!(runner.is_disabled());
+
+    // Once disabled, runs return nothing without starting cargo
+    assert!(runner.run_lints(&workspace).await.unwrap().is_empty());
+    assert_eq!(std::fs::read_to_string(&log).unwrap().lines().count(), 2);
+}
@@ -0,0 +1,198 @@
+use language_server::core::utilities::PathNormalizer;
+use language_server::dylint_runner::diagnostics_for_file;
+use language_server::dylint_runner::parser::parse_json_output;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::{Position, Url};
+
+/// Create a workspace with two crates that both have a `src/lib.rs`
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-dylint-files-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    for program in ["vault", "escrow"] {
+        fs::create_dir_all(root.join("programs").join(program).join("src")).unwrap();
+        fs::write(root.join("programs").join(program).join("src/lib.rs"), "").unwrap();
+    }
+    root
+}
+
+/// A cargo `compiler-message` line for a lint at the given file and line
+fn message(file_name: &str, manifest_path: &str, line: usize) -> String {
+    serde_json::json!({
+        "reason": "compiler-message",
+        "manifest_path": manifest_path,
+        "message": {
+            "message": "account is not checked",
+            "code": { "code": "missing_signer" },
+            "level": "warning",
+            "spans": [{
+                "file_name": file_name,
+                "line_start": line,
+                "line_end": line,
+                "column_start": 5,
+                "column_end": 10,
+                "is_primary": true,
+                "expansion": null
+            }],
+            "children": []
+        }
+    })
+    .to_string()
+}
+
+#[test]
+fn test_diagnostics_land_on_the_right_lib_rs() {
+    let root = workspace("two-libs");
+    let stdout = [
+        message(
+            "programs/vault/src/lib.rs",
+            &root.join("programs/vault/Cargo.toml").to_string_lossy(),
+            3,
+        ),
+        message(
+            "programs/escrow/src/lib.rs",
+            &root.join("programs/escrow/Cargo.toml").to_string_lossy(),
+            7,
+        ),
+    ]
+    .join("\n");
+
+    let diagnostics = parse_json_output(&stdout, &["missing_signer".to_string()], &root).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+
+    let vault = diagnostics_for_file(&diagnostics, &root.join("programs/vault/src/lib.rs"));
+    assert_eq!(vault.len(), 1);
+    assert_eq!(vault[0].line_start, 3);
+
+    let escrow = diagnostics_for_file(&diagnostics, &root.join("programs/escrow/src/lib.rs"));
+    assert_eq!(escrow.len(), 1);
+    assert_eq!(escrow[0].line_start, 7);
+
+    // A file whose path merely ends the same way gets nothing
+    assert!(diagnostics_for_file(&diagnostics, &root.join("src/lib.rs")).is_empty());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_package_relative_paths_resolved_against_manifest() {
+    let root = workspace("package-relative");
+    let manifest = root.join("programs/escrow/Cargo.toml");
+    let stdout = message("src/lib.rs", &manifest.to_string_lossy(), 1);
+
+    let diagnostics = parse_json_output(&stdout, &["missing_signer".to_string()], &root).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].path,
+        root.join("programs/escrow/src/lib.rs")
+            .canonicalize()
+            .unwrap()
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+/// `missing_signer` on a composite context, with a note on the component defined in
+/// another file of the package (paths relative to the package, as outside a workspace)
+fn composite_message(root: &Path) -> String {
+    serde_json::json!({
+        "reason": "compiler-message",
+        "manifest_path": root.join("programs/vault/Cargo.toml"),
+        "message": {
+            "message": "Accounts struct has no signer field",
+            "code": { "code": "missing_signer" },
+            "level": "warning",
+            "spans": [{
+                "file_name": "src/lib.rs",
+                "line_start": 12,
+                "line_end": 15,
+                "column_start": 1,
+                "column_end": 2,
+                "is_primary": true,
+                "expansion": null
+            }],
+            "children": [{
+                "message": "component 'Deposit' has no signer field",
+                "level": "note",
+                "spans": [{
+                    "file_name": "src/contexts.rs",
+                    "line_start": 4,
+                    "line_end": 8,
+                    "column_start": 1,
+                    "column_end": 2,
+                    "is_primary": true,
+                    "expansion": null
+                }],
+                "children": []
+            }]
+        }
+    })
+    .to_string()
+}
+
+#[test]
+fn test_composite_component_note_points_at_other_file() {
+    let root = workspace("composite");
+    let component_file = root.join("programs/vault/src/contexts.rs");
+    fs::write(&component_file, "").unwrap();
+
+    let stdout = composite_message(&root);
+    let diagnostics = parse_json_output(&stdout, &["missing_signer".to_string()], &root).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+
+    let normalizer = PathNormalizer::new(root.clone(), true);
+    let related = diagnostics[0]
+        .to_lsp_diagnostic(Some(&root), &normalizer)
+        .related_information
+        .unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(
+        related[0].location.uri,
+        Url::from_file_path(component_file.canonicalize().unwrap()).unwrap()
+    );
+    assert_eq!(related[0].location.range.start, Position::new(3, 0));
+    assert!(related[0].message.contains("'Deposit'"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_related_locations_use_symlinked_root() {
+    let real_root = workspace("symlinked-real");
+    fs::write(real_root.join("programs/vault/src/contexts.rs"), "").unwrap();
+    let link_root = real_root.with_file_name(format!(
+        "solana-ls-dylint-files-symlinked-link-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&link_root);
+    std::os::unix::fs::symlink(&real_root, &link_root).unwrap();
+
+    // The client opened the workspace through the symlink; cargo runs in the canonical root
+    let normalizer = PathNormalizer::new(link_root.clone(), true);
+    let workspace = normalizer.workspace_root().unwrap();
+    let stdout = composite_message(&workspace);
+    let diagnostics =
+        parse_json_output(&stdout, &["missing_signer".to_string()], &workspace).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+
+    // The related location lands on the same root as the diagnostic's own URI
+    assert_eq!(
+        normalizer.to_uri(&diagnostics[0].path).unwrap(),
+        Url::from_file_path(link_root.join("programs/vault/src/lib.rs")).unwrap()
+    );
+    let related = diagnostics[0]
+        .to_lsp_diagnostic(Some(&workspace), &normalizer)
+        .related_information
+        .unwrap();
+    assert_eq!(
+        related[0].location.uri,
+        Url::from_file_path(link_root.join("programs/vault/src/contexts.rs")).unwrap()
+    );
+
+    let _ = fs::remove_file(&link_root);
+    let _ = fs::remove_dir_all(&real_root);
+}
@@ -0,0 +1,71 @@
+use language_server::core::dylint::cache::DylintDetectorCache;
+use language_server::core::dylint::compiler::DylintDetectorCompiler;
+use language_server::core::dylint::scanner::DylintDetectorInfo;
+use language_server::core::dylint::{DylintDetectorManager, PrebuildSummary};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create a fresh temporary directory for a test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "solana-ls-dylint-prebuild-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Create a detector crate under `<extension>/detectors/`
+fn detector(extension: &Path, name: &str) -> DylintDetectorInfo {
+    let crate_path = extension.join("detectors").join(name);
+    fs::create_dir_all(crate_path.join("src")).unwrap();
+    fs::write(
+        crate_path.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n\n[dependencies]\n# dylint_linting omitted in the fixture\n",
+            name
+        ),
+    )
+    .unwrap();
+    fs::write(crate_path.join("src/lib.rs"), "// lint v1\n").unwrap();
+
+    DylintDetectorInfo {
+        cargo_toml_path: crate_path.join("Cargo.toml"),
+        crate_name: name.to_string(),
+        crate_path,
+    }
+}
+
+#[tokio::test]
+async fn test_prebuild_counts_cached_detectors() {
+    let root = temp_dir("counts");
+    let extension = root.join("extension");
+    // Compiling needs the pinned nightly, so the fixture only has a cached detector
+    let cached = detector(&extension, "unchecked_math");
+
+    // Pretend `unchecked_math` was built before
+    let nightly = DylintDetectorCompiler::get_nightly_version().unwrap();
+    let compiled = root.join("libunchecked_math.so");
+    fs::write(&compiled, b"compiled").unwrap();
+    let mut cache = DylintDetectorCache::with_cache_dir(root.join("cache")).unwrap();
+    cache.cache_library(&cached, &nightly, &compiled).unwrap();
+
+    let mut manager = DylintDetectorManager::with_cache(cache);
+    manager.set_extension_path(extension);
+
+    let summary = manager.prebuild_detectors(None).await.unwrap();
+    assert_eq!(
+        summary,
+        PrebuildSummary {
+            compiled: 0,
+            cached: 1,
+            failed: 0,
+        }
+    );
+    // Prebuilding does not load anything
+    assert!(!manager.is_initialized());
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,87 @@
+use language_server::dylint_runner::DylintRunner;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create a directory containing the given (empty) files
+fn lib_dir(root: &Path, name: &str, files: &[&str]) -> PathBuf {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for file in files {
+        fs::write(dir.join(file), b"").unwrap();
+    }
+    dir
+}
+
+fn test_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-lib-dirs-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    root
+}
+
+#[test]
+fn test_discover_merges_and_dedupes_by_file_name() {
+    let root = test_root("discover");
+    let platform = lib_dir(
+        &root,
+        "linux-x64",
+        &["libmissing_signer.so", "libunsafe_math.so", "README.md"],
+    );
+    let common = lib_dir(
+        &root,
+        "common",
+        &["libunsafe_math.so", "libowner_check.so", "helper.so"],
+    );
+
+    let libs = DylintRunner::discover_lint_libs(&[platform.clone(), common.clone()]);
+
+    assert_eq!(
+        libs,
+        vec![
+            platform.join("libmissing_signer.so"),
+            platform.join("libunsafe_math.so"),
+            common.join("libowner_check.so"),
+        ]
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_missing_directories_are_skipped() {
+    let root = test_root("missing");
+    let common = lib_dir(&root, "common", &["libowner_check.dylib"]);
+
+    let libs = DylintRunner::discover_lint_libs(&[root.join("does-not-exist"), common.clone()]);
+
+    assert_eq!(libs, vec![common.join("libowner_check.dylib")]);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_additional_lib_dirs_are_loaded() {
+    let root = test_root("builder");
+    let first = lib_dir(&root, "first", &["liba.so", "libshared.so"]);
+    let second = lib_dir(&root, "second", &["libshared.so", "libb.so"]);
+
+    // No lints_compiled directory, so only the additional directories contribute
+    let runner = DylintRunner::new(&root.join("extension"))
+        .unwrap()
+        .with_additional_lib_dirs(vec![first.clone(), second.clone()]);
+
+    assert_eq!(
+        runner.lint_libs(),
+        vec![
+            first.join("liba.so"),
+            first.join("libshared.so"),
+            second.join("libb.so"),
+        ]
+    );
+    assert_eq!(runner.loaded_lints(), vec!["liba", "libshared", "libb"]);
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,66 @@
+use language_server::dylint_runner::DylintRunner;
+use std::fs;
+use std::path::PathBuf;
+
+/// Create a fresh temporary workspace for a test
+fn temp_workspace(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("solana-ls-package-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_package_args_for_file_in_known_package() {
+    let workspace = temp_workspace("known");
+    fs::write(
+        workspace.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"programs/*\"]\n",
+    )
+    .unwrap();
+
+    let program = workspace.join("programs/vault");
+    fs::create_dir_all(program.join("src/instructions")).unwrap();
+    fs::write(
+        program.join("Cargo.toml"),
+        "[package]\nname = \"vault\"\nversion = \"0.1.0\"\n\n[dependencies]\nanchor-lang = \"0.31.0\"\n",
+    )
+    .unwrap();
+    let file = program.join("src/instructions/deposit.rs");
+    fs::write(&file, "").unwrap();
+
+    let package = DylintRunner::package_for_file(&file);
+    assert_eq!(package.as_deref(), Some("vault"));
+
+    let args = DylintRunner::cargo_check_args("nightly", package.as_deref());
+    assert_eq!(
+        args,
+        vec!["+nightly", "check", "-p", "vault", "--message-format=json"]
+    );
+
+    fs::remove_dir_all(&workspace).unwrap();
+}
+
+#[test]
+fn test_virtual_manifest_falls_back_to_workspace() {
+    let workspace = temp_workspace("virtual");
+    fs::write(
+        workspace.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"programs/*\"]\n",
+    )
+    .unwrap();
+    let file = workspace.join("build.rs");
+    fs::write(&file, "").unwrap();
+
+    let package = DylintRunner::package_for_file(&file);
+    assert_eq!(package, None);
+
+    let args = DylintRunner::cargo_check_args("nightly", package.as_deref());
+    assert_eq!(
+        args,
+        vec!["+nightly", "check", "--workspace", "--message-format=json"]
+    );
+
+    fs::remove_dir_all(&workspace).unwrap();
+}
@@ -0,0 +1,91 @@
+use language_server::dylint_runner::DylintRunner;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_default_timeout() {
+    let extension_path =
+        std::env::temp_dir().join(format!("solana-ls-timeout-test-{}", std::process::id()));
+    let runner = DylintRunner::new(&extension_path).unwrap();
+
+    assert_eq!(runner.timeout(), DylintRunner::DEFAULT_TIMEOUT);
+    assert_eq!(
+        runner.with_timeout(Duration::from_secs(5)).timeout(),
+        Duration::from_secs(5)
+    );
+}
+
+#[tokio::test]
+async fn test_command_finishing_in_time_returns_output() {
+    let mut command = tokio::process::Command::new("sh");
+    command.args(["-c", "echo done"]);
+
+    let output = DylintRunner::run_command_with_timeout(command, Duration::from_secs(10))
+        .await
+        .unwrap()
+        .expect("command should finish before the timeout");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "done");
+}
+
+/// Whether a process is still running (zombies waiting to be reaped do not count)
+#[cfg(unix)]
+fn is_running(pid: &str) -> bool {
+    if let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        // The state follows the parenthesized command name
+        return stat
+            .rsplit_once(") ")
+            .is_some_and(|(_, rest)| !rest.starts_with('Z'));
+    }
+
+    std::process::Command::new("kill")
+        .args(["-0", pid])
+        .status()
+        .unwrap()
+        .success()
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_long_running_command_is_killed_on_timeout() {
+    let pid_file =
+        std::env::temp_dir().join(format!("solana-ls-timeout-test-{}.pid", std::process::id()));
+    let child_pid_file = pid_file.with_extension("child.pid");
+    let _ = std::fs::remove_file(&pid_file);
+    let _ = std::fs::remove_file(&child_pid_file);
+
+    // Like cargo running rustc, the command starts a process of its own
+    let mut command = tokio::process::Command::new("sh");
+    command.args([
+        "-c",
+        &format!(
+            "sleep 30 & echo $! > {}; echo $$ > {}; exec sleep 30",
+            child_pid_file.display(),
+            pid_file.display()
+        ),
+    ]);
+
+    let started = Instant::now();
+    let output = DylintRunner::run_command_with_timeout(command, Duration::from_millis(500))
+        .await
+        .unwrap();
+
+    assert!(output.is_none());
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    // Neither the command nor the process it started may still exist
+    for file in [&pid_file, &child_pid_file] {
+        let pid = std::fs::read_to_string(file).unwrap();
+        let pid = pid.trim();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while is_running(pid) && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(
+            !is_running(pid),
+            "timed out process {} is still running",
+            pid
+        );
+        let _ = std::fs::remove_file(file);
+    }
+}
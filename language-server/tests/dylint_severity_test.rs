@@ -0,0 +1,36 @@
+use language_server::core::utilities::PathNormalizer;
+use language_server::dylint_runner::DylintDiagnostic;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+fn diagnostic(code: &str, level: &str) -> DylintDiagnostic {
+    DylintDiagnostic {
+        path: PathBuf::from("/workspace/programs/vault/src/lib.rs"),
+        line_start: 7,
+        line_end: 7,
+        column_start: 21,
+        column_end: 38,
+        message: "unchecked subtraction".to_string(),
+        code: code.to_string(),
+        level: level.to_string(),
+        related_information: Vec::new(),
+    }
+}
+
+#[test]
+fn test_severity_follows_lint_level() {
+    let warning =
+        diagnostic("unchecked_math", "warning").to_lsp_diagnostic(None, &PathNormalizer::default());
+    assert_eq!(warning.severity, Some(DiagnosticSeverity::WARNING));
+
+    let error =
+        diagnostic("missing_signer", "error").to_lsp_diagnostic(None, &PathNormalizer::default());
+    assert_eq!(error.severity, Some(DiagnosticSeverity::ERROR));
+}
+
+#[test]
+fn test_balance_underflow_warning_is_reported_as_error() {
+    let balance = diagnostic("unchecked_math_balance", "warning")
+        .to_lsp_diagnostic(None, &PathNormalizer::default());
+    assert_eq!(balance.severity, Some(DiagnosticSeverity::ERROR));
+}
@@ -0,0 +1,79 @@
+use language_server::core::detectors::{
+    detector::Detector, empty_handler_detector::EmptyHandlerDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = EmptyHandlerDetector::default();
+
+    assert_eq!(detector.id(), "EMPTY_HANDLER");
+    assert_eq!(detector.name(), "Empty Handler");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_empty_handler_with_close_account() {
+    let mut detector = EmptyHandlerDetector::default();
+
+    let code = r#"
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn close_vault(_ctx: Context<CloseVault>) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct CloseVault<'info> {
+            #[account(mut, close = authority, has_one = authority)]
+            pub vault: Account<'info, Vault>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 5);
+    assert_eq!(diagnostics[0].range.start.character, 19);
+    assert!(diagnostics[0].message.contains("'close_vault'"));
+    assert!(diagnostics[0].message.contains("`close` on 'vault'"));
+}
+
+#[test]
+fn test_read_only_or_non_empty_handlers_not_flagged() {
+    let mut detector = EmptyHandlerDetector::default();
+
+    let code = r#"
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn ping(_ctx: Context<Ping>) -> Result<()> {
+                Ok(())
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                ctx.accounts.vault.balance += amount;
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Ping<'info> {
+            pub vault: Account<'info, Vault>,
+            pub authority: Signer<'info>,
+        }
+
+        #[derive(Accounts)]
+        pub struct Deposit<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
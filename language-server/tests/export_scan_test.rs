@@ -0,0 +1,43 @@
+use language_server::backend::create_default_registry;
+use language_server::core::{FileScanner, ScanResult};
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let balance = ctx.accounts.vault.amount.checked_sub(amount).unwrap();
+        require!(balance as u32 > 0, ErrorCode::Empty);
+        Ok(())
+    }
+}
+"#;
+
+#[tokio::test]
+async fn test_scan_result_json_round_trip() {
+    let dir = std::env::temp_dir().join(format!("export_scan_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), PROGRAM).unwrap();
+    std::fs::write(dir.join("src/clean.rs"), "pub fn add(a: u8) -> u8 { a }\n").unwrap();
+
+    let mut registry = create_default_registry();
+    let scanner = FileScanner::new(dir.clone());
+    let scan_result = scanner.scan_workspace(&mut registry).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(scan_result.total_issues() > 0);
+
+    let json = serde_json::to_string(&scan_result).unwrap();
+    let restored: ScanResult = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.cancelled, scan_result.cancelled);
+    assert_eq!(restored.total_issues(), scan_result.total_issues());
+    assert_eq!(restored.rust_files.len(), scan_result.rust_files.len());
+    for (restored_file, file) in restored.rust_files.iter().zip(&scan_result.rust_files) {
+        assert_eq!(restored_file.path, file.path);
+        assert_eq!(restored_file.is_anchor_program, file.is_anchor_program);
+        assert_eq!(restored_file.diagnostics, file.diagnostics);
+    }
+}
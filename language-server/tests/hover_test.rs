@@ -0,0 +1,189 @@
+use futures::StreamExt;
+use language_server::backend::{Backend, create_default_registry};
+use language_server::core::detectors::detector::Detector;
+use language_server::core::registry::DetectorRegistryBuilder;
+use language_server::core::utilities::LineIndex;
+use language_server::core::{HoverProvider, UncheckedAccountIndexDetector};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use tower::{Service, ServiceExt};
+use tower_lsp::LspService;
+use tower_lsp::jsonrpc::Request;
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticSeverity, HoverContents, NumberOrString, Position, Range,
+};
+
+const PROGRAM: &str = r#"
+pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+    let authority = &accounts[3];
+    Ok(())
+}
+"#;
+
+/// Detector relying on the default rationale, which repeats its message
+struct MessageOnlyDetector;
+
+impl Detector for MessageOnlyDetector {
+    fn id(&self) -> &'static str {
+        "MESSAGE_ONLY"
+    }
+
+    fn name(&self) -> &'static str {
+        "Message Only"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags nothing"
+    }
+
+    fn message(&self) -> &'static str {
+        "Something is off."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+
+    fn analyze_ast(
+        &mut self,
+        _syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        _lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+}
+
+async fn hover(service: &mut LspService<Backend>, uri: &str, line: u32, character: u32) -> Value {
+    let hover = Request::build("textDocument/hover")
+        .params(json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character },
+        }))
+        .id(2)
+        .finish();
+    let response = service
+        .ready()
+        .await
+        .unwrap()
+        .call(hover)
+        .await
+        .unwrap()
+        .unwrap();
+
+    response.result().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_hover_explains_published_diagnostic() {
+    let (mut service, socket) = LspService::new(Backend::new);
+    // Drain server-to-client messages so publishing never blocks
+    tokio::spawn(socket.for_each(|_| async {}));
+
+    let initialize = Request::build("initialize")
+        .params(json!({ "capabilities": {} }))
+        .id(1)
+        .finish();
+    let response = service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        response.result().unwrap()["capabilities"]["hoverProvider"],
+        true
+    );
+
+    let uri = "file:///tmp/hover/lib.rs";
+    let did_open = Request::build("textDocument/didOpen")
+        .params(json!({
+            "textDocument": {
+                "uri": uri,
+                "languageId": "rust",
+                "version": 1,
+                "text": PROGRAM,
+            }
+        }))
+        .finish();
+    service.ready().await.unwrap().call(did_open).await.unwrap();
+
+    // Inside `accounts[3]`
+    let result = hover(&mut service, uri, 2, 25).await;
+    let value = result["contents"]["value"].as_str().unwrap();
+    assert!(value.contains("UNCHECKED_ACCOUNT_INDEX"));
+    assert!(value.contains(UncheckedAccountIndexDetector::default().description()));
+    assert!(value.contains("Indexing past its end panics"));
+    assert_eq!(result["range"]["start"]["line"], 2);
+
+    // Outside any diagnostic
+    assert!(hover(&mut service, uri, 3, 4).await.is_null());
+}
+
+#[test]
+fn test_hover_skips_unknown_codes() {
+    let registry = DetectorRegistryBuilder::new()
+        .with_detector(UncheckedAccountIndexDetector::default())
+        .build();
+    let range = Range::new(Position::new(1, 0), Position::new(1, 10));
+    let diagnostics = vec![
+        Diagnostic {
+            range,
+            code: Some(NumberOrString::String("unchecked_math".to_string())),
+            ..Default::default()
+        },
+        Diagnostic {
+            range,
+            code: Some(NumberOrString::String(
+                "UNCHECKED_ACCOUNT_INDEX".to_string(),
+            )),
+            ..Default::default()
+        },
+    ];
+
+    let hover = HoverProvider::hover(&diagnostics, Position::new(1, 4), &registry).unwrap();
+    let HoverContents::Markup(markup) = hover.contents else {
+        panic!("expected markdown");
+    };
+    assert!(markup.value.starts_with("**Unchecked Account Index**"));
+    assert!(!markup.value.contains("---"));
+
+    assert!(HoverProvider::hover(&diagnostics[..1], Position::new(1, 4), &registry).is_none());
+}
+
+#[test]
+fn test_hover_omits_rationale_repeating_the_message() {
+    let registry = DetectorRegistryBuilder::new()
+        .with_detector(MessageOnlyDetector)
+        .build();
+    let diagnostics = vec![Diagnostic {
+        range: Range::new(Position::new(1, 0), Position::new(1, 10)),
+        code: Some(NumberOrString::String("MESSAGE_ONLY".to_string())),
+        ..Default::default()
+    }];
+
+    let hover = HoverProvider::hover(&diagnostics, Position::new(1, 4), &registry).unwrap();
+    let HoverContents::Markup(markup) = hover.contents else {
+        panic!("expected markdown");
+    };
+    assert!(markup.value.starts_with("**Message Only**"));
+    assert!(!markup.value.contains("Why this matters"));
+}
+
+#[test]
+fn test_every_detector_explains_why() {
+    let registry = create_default_registry();
+
+    for info in registry.list_detectors() {
+        let detector = registry.detector(&info.id).unwrap();
+        assert_ne!(
+            detector.rationale(),
+            detector.message(),
+            "{} has no rationale of its own",
+            info.id
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use language_server::core::detectors::{
+    detector::Detector, init_without_space_detector::InitWithoutSpaceDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = InitWithoutSpaceDetector::default();
+
+    assert_eq!(detector.id(), "INIT_WITHOUT_SPACE");
+    assert_eq!(detector.name(), "Init Without Space");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::ERROR);
+}
+
+#[test]
+fn test_detects_init_without_space() {
+    let mut detector = InitWithoutSpaceDetector::default();
+
+    let code = r#"
+        #[account]
+        pub struct Vault {
+            pub authority: Pubkey,
+        }
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(init, payer = authority)]
+            pub vault: Account<'info, Vault>,
+            #[account(init_if_needed, payer = authority, seeds = [b"config"], bump)]
+            pub config: Account<'info, Config>,
+            #[account(mut)]
+            pub authority: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 8);
+    assert_eq!(diagnostics[0].range.start.character, 22);
+    assert!(diagnostics[0].message.contains("'vault'"));
+    assert!(diagnostics[0].message.contains("'Vault'"));
+    assert!(diagnostics[1].message.contains("`init_if_needed`"));
+}
+
+#[test]
+fn test_sized_init_not_flagged() {
+    let mut detector = InitWithoutSpaceDetector::default();
+
+    let code = r#"
+        #[account]
+        #[derive(InitSpace)]
+        pub struct Config {
+            pub admin: Pubkey,
+        }
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(init, payer = authority, space = 8 + 32)]
+            pub vault: Account<'info, Vault>,
+            #[account(init, payer = authority)]
+            pub config: Account<'info, Config>,
+            #[account(init, payer = authority, token::mint = mint, token::authority = authority)]
+            pub escrow: Account<'info, TokenAccount>,
+            #[account(mut)]
+            pub vault_state: Account<'info, Vault>,
+            #[account(mut)]
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
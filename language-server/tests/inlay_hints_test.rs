@@ -0,0 +1,113 @@
+use language_server::core::language_features::InlayHintProvider;
+use tower_lsp::lsp_types::{InlayHintLabel, Position};
+
+fn label(hint: &tower_lsp::lsp_types::InlayHint) -> String {
+    match &hint.label {
+        InlayHintLabel::String(label) => label.clone(),
+        InlayHintLabel::LabelParts(parts) => parts.iter().map(|part| part.value.as_str()).collect(),
+    }
+}
+
+const VAULT: &str = r#"
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub created_at: i64,
+}
+"#;
+
+#[test]
+fn test_hint_flags_space_mismatch() {
+    let code = format!(
+        r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {{
+    #[account(init, payer = payer, space = 8 + 40)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}}
+{}"#,
+        VAULT
+    );
+
+    let hints = InlayHintProvider::inlay_hints(&code);
+    assert_eq!(hints.len(), 1);
+
+    // Right after `8 + 40`
+    assert_eq!(hints[0].position, Position::new(4, 49));
+    assert_eq!(label(&hints[0]), "⚠ computed 8 + 48 = 56 bytes");
+}
+
+#[test]
+fn test_hint_for_matching_space() {
+    let code = format!(
+        r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {{
+    #[account(init, payer = payer, space = 8 + 32 + 8 + 8)]
+    pub vault: Box<Account<'info, Vault>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}}
+{}"#,
+        VAULT
+    );
+
+    let hints = InlayHintProvider::inlay_hints(&code);
+    assert_eq!(hints.len(), 1);
+    assert_eq!(label(&hints[0]), "8 + 48 = 56 bytes");
+}
+
+#[test]
+fn test_non_constant_space_shows_computed_size() {
+    let code = format!(
+        r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {{
+    #[account(init, payer = payer, space = 8 + Vault::INIT_SPACE)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}}
+{}"#,
+        VAULT
+    );
+
+    let hints = InlayHintProvider::inlay_hints(&code);
+    assert_eq!(hints.len(), 1);
+    assert_eq!(label(&hints[0]), "8 + 48 = 56 bytes");
+}
+
+#[test]
+fn test_no_hint_without_init_or_known_layout() {
+    let code = r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    #[account(init, payer = payer, space = 8 + 64)]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+}
+
+#[account]
+pub struct Profile {
+    pub name: String,
+}
+"#;
+
+    assert!(InlayHintProvider::inlay_hints(code).is_empty());
+}
@@ -0,0 +1,90 @@
+use language_server::core::detectors::{
+    detector::Detector, insecure_randomness_detector::InsecureRandomnessDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = InsecureRandomnessDetector::default();
+
+    assert_eq!(detector.id(), "INSECURE_RANDOMNESS");
+    assert_eq!(detector.name(), "Insecure Randomness");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_timestamp_modulo_winner() {
+    let mut detector = InsecureRandomnessDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        pub fn pick_winner(ctx: Context<PickWinner>, players: Vec<Pubkey>) -> Result<()> {
+            let clock = Clock::get()?;
+            let winner = clock.unix_timestamp % players.len() as i64;
+            ctx.accounts.lottery.winner = players[winner as usize];
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+    assert!(diagnostics[0].message.contains("'winner'"));
+    assert!(diagnostics[0].message.contains("predictable"));
+}
+
+#[test]
+fn test_tracks_taint_through_locals() {
+    let mut detector = InsecureRandomnessDetector::default();
+
+    let code = r#"
+        pub fn draw(ctx: Context<Draw>) -> Result<()> {
+            let slot = Clock::get()?.slot;
+            let mixed = slot ^ 0x5f5f;
+            let index = mixed % 10;
+            ctx.accounts.game.index = index;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("Modulo"));
+}
+
+#[test]
+fn test_benign_timestamp_store_not_flagged() {
+    let mut detector = InsecureRandomnessDetector::default();
+
+    let code = r#"
+        pub fn update(ctx: Context<Update>) -> Result<()> {
+            let clock = Clock::get()?;
+            let vault = &mut ctx.accounts.vault;
+            vault.last_updated = clock.unix_timestamp;
+            vault.deadline = clock.unix_timestamp + 3600;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_modulo_of_untainted_value_not_flagged() {
+    let mut detector = InsecureRandomnessDetector::default();
+
+    let code = r#"
+        pub fn rotate(ctx: Context<Rotate>, round: u64) -> Result<()> {
+            let clock = Clock::get()?;
+            let index = round % 4;
+            ctx.accounts.state.index = index;
+            ctx.accounts.state.updated_at = clock.unix_timestamp;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
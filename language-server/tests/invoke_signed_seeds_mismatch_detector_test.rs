@@ -0,0 +1,110 @@
+use language_server::core::detectors::{
+    detector::Detector, invoke_signed_seeds_mismatch_detector::InvokeSignedSeedsMismatchDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+const ACCOUNTS: &str = r#"
+    #[derive(Accounts)]
+    pub struct Withdraw<'info> {
+        #[account(mut, seeds = [b"vault", user.key().as_ref()], bump)]
+        pub vault: SystemAccount<'info>,
+        #[account(mut)]
+        pub user: Signer<'info>,
+        pub system_program: Program<'info, System>,
+    }
+"#;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = InvokeSignedSeedsMismatchDetector::default();
+
+    assert_eq!(detector.id(), "INVOKE_SIGNED_SEEDS_MISMATCH");
+    assert_eq!(detector.name(), "Invoke Signed Seeds Mismatch");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_mismatched_signer_seeds() {
+    let mut detector = InvokeSignedSeedsMismatchDetector::default();
+
+    let code = format!(
+        "{}{}",
+        ACCOUNTS,
+        r#"
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let user_key = ctx.accounts.user.key();
+        let ix = system_instruction::transfer(&ctx.accounts.vault.key(), &user_key, amount);
+        invoke_signed(
+            &ix,
+            &[ctx.accounts.vault.to_account_info(), ctx.accounts.user.to_account_info()],
+            &[&[b"treasury", user_key.as_ref(), &[ctx.bumps.vault]]],
+        )?;
+        Ok(())
+    }
+"#
+    );
+
+    let diagnostics = detector.analyze(&code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 16);
+    assert!(diagnostics[0].message.contains("`[b\"treasury\", _]`"));
+    assert!(diagnostics[0].message.contains("'vault'"));
+}
+
+#[test]
+fn test_detects_missing_seed_through_bindings() {
+    let mut detector = InvokeSignedSeedsMismatchDetector::default();
+
+    let code = format!(
+        "{}{}",
+        ACCOUNTS,
+        r#"
+    impl<'info> Withdraw<'info> {
+        pub fn pay_out(&self, amount: u64, bump: u8) -> Result<()> {
+            let vault = &self.vault;
+            let seeds = &[b"vault".as_ref(), &[bump]];
+            let ix = system_instruction::transfer(&vault.key(), &self.user.key(), amount);
+            invoke_signed(&ix, &[vault.to_account_info()], &[&seeds[..]])?;
+            Ok(())
+        }
+    }
+"#
+    );
+
+    let diagnostics = detector.analyze(&code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 13);
+}
+
+#[test]
+fn test_matching_signer_seeds_not_flagged() {
+    let mut detector = InvokeSignedSeedsMismatchDetector::default();
+
+    let code = format!(
+        "{}{}",
+        ACCOUNTS,
+        r#"
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let user_key = ctx.accounts.user.key();
+        let bump = ctx.bumps.vault;
+        let signer_seeds: &[&[&[u8]]] = &[&["vault".as_bytes(), user_key.as_ref(), &[bump]]];
+        let ix = system_instruction::transfer(&ctx.accounts.vault.key(), &user_key, amount);
+        invoke_signed(
+            &ix,
+            &[ctx.accounts.vault.to_account_info(), ctx.accounts.user.to_account_info()],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+
+    pub fn forward(ctx: Context<Withdraw>, ix: Instruction, seeds: &[&[&[u8]]]) -> Result<()> {
+        // Seeds from the caller cannot be compared
+        invoke_signed(&ix, &[ctx.accounts.vault.to_account_info()], seeds)?;
+        Ok(())
+    }
+"#
+    );
+
+    let diagnostics = detector.analyze(&code, None);
+    assert!(diagnostics.is_empty());
+}
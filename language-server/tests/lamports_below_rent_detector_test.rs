@@ -0,0 +1,75 @@
+use language_server::core::detectors::{
+    detector::Detector, lamports_below_rent_detector::LamportsBelowRentDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = LamportsBelowRentDetector::default();
+
+    assert_eq!(detector.id(), "LAMPORTS_BELOW_RENT");
+    assert_eq!(detector.name(), "Lamports Below Rent");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_subtraction_without_rent_check() {
+    let mut detector = LamportsBelowRentDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            let vault = &ctx.accounts.vault;
+            let remaining = vault.lamports() - amount;
+            let checked = vault.to_account_info().lamports().checked_sub(amount);
+            **vault.try_borrow_mut_lamports()? -= amount;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0].range.start.line, 3);
+    assert_eq!(diagnostics[0].range.start.character, 28);
+    assert_eq!(diagnostics[1].range.start.line, 4);
+    assert_eq!(diagnostics[2].range.start.line, 5);
+}
+
+#[test]
+fn test_rent_aware_subtraction_not_flagged() {
+    let mut detector = LamportsBelowRentDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            let vault = &ctx.accounts.vault;
+            let minimum = Rent::get()?.minimum_balance(vault.data_len());
+            require!(vault.lamports() - amount >= minimum, ErrorCode::BelowRent);
+            **vault.try_borrow_mut_lamports()? -= amount;
+            Ok(())
+        }
+
+        pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+            let total = amount - ctx.accounts.fee;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_test_module_not_flagged() {
+    let mut detector = LamportsBelowRentDetector::default();
+
+    let code = r#"
+        #[cfg(test)]
+        mod tests {
+            fn drain(acc: &AccountInfo) -> u64 {
+                acc.lamports() - 1
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,103 @@
+use language_server::core::detectors::{
+    detector::Detector, large_account_no_zero_copy_detector::LargeAccountNoZeroCopyDetector,
+};
+use language_server::core::utilities::SizeEstimator;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = LargeAccountNoZeroCopyDetector::default();
+
+    assert_eq!(detector.id(), "LARGE_ACCOUNT_NO_ZERO_COPY");
+    assert_eq!(detector.name(), "Large Account Without Zero-Copy");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_large_account() {
+    let mut detector = LargeAccountNoZeroCopyDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[account]
+        pub struct OrderBook {
+            pub authority: Pubkey,
+            pub data: [u8; 20000],
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+    assert!(diagnostics[0].message.contains("'OrderBook'"));
+    assert!(diagnostics[0].message.contains("20032 bytes"));
+}
+
+#[test]
+fn test_zero_copy_account_not_flagged() {
+    let mut detector = LargeAccountNoZeroCopyDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[account(zero_copy)]
+        pub struct OrderBook {
+            pub authority: Pubkey,
+            pub data: [u8; 20000],
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_small_account_not_flagged() {
+    let mut detector = LargeAccountNoZeroCopyDetector::default();
+
+    let code = r#"
+        #[account]
+        #[derive(InitSpace)]
+        pub struct Vault {
+            pub authority: Pubkey,
+            pub balance: u64,
+            #[max_len(32)]
+            pub name: String,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_max_len_vec_counts_towards_size() {
+    let mut detector = LargeAccountNoZeroCopyDetector::default().with_threshold(1000);
+
+    let code = r#"
+        #[account]
+        pub struct Registry {
+            #[max_len(100)]
+            pub members: Vec<Pubkey>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("3204 bytes"));
+}
+
+#[test]
+fn test_size_estimator_types() {
+    let estimate = |ty: &str| SizeEstimator::estimate_type(&syn::parse_str(ty).unwrap());
+
+    assert_eq!(estimate("u8"), Some(1));
+    assert_eq!(estimate("u64"), Some(8));
+    assert_eq!(estimate("Pubkey"), Some(32));
+    assert_eq!(estimate("[u64; 4]"), Some(32));
+    assert_eq!(estimate("Option<Pubkey>"), Some(33));
+    assert_eq!(estimate("(u8, u128)"), Some(17));
+    assert_eq!(estimate("Vec<u8>"), None);
+    assert_eq!(estimate("CustomType"), None);
+}
@@ -0,0 +1,125 @@
+use language_server::core::detectors::manual_lamports_zeroing::ManualLamportsZeroingDetector;
+use language_server::core::registry::DetectorRegistryBuilder;
+use language_server::core::utilities::{DiagnosticBuilder, LineIndex};
+use std::time::{Duration, Instant};
+use syn::visit::Visit;
+use tower_lsp::lsp_types::Position;
+
+/// Generate a program of roughly `lines` lines with one finding per handler
+fn generate_program(lines: usize) -> String {
+    let mut content = String::from("use anchor_lang::prelude::*;\n\n");
+    let mut i = 0;
+    while content.lines().count() < lines {
+        content.push_str(&format!(
+            "pub fn close_{i}(ctx: Context<Close>) -> Result<()> {{\n    let label = \"véhicule-{i}\"; let copy = label;\n    **ctx.accounts.vault.to_account_info().lamports.borrow_mut() = 0;\n    Ok(())\n}}\n\n"
+        ));
+        i += 1;
+    }
+    content
+}
+
+/// Reference implementation: scan from the start of the content
+fn linear_position(content: &str, offset: usize) -> Position {
+    let mut line = 0u32;
+    let mut character = 0u32;
+
+    for (i, ch) in content.char_indices() {
+        if i >= offset {
+            break;
+        }
+        if ch == '\n' {
+            line += 1;
+            character = 0;
+        } else {
+            character += 1;
+        }
+    }
+
+    Position { line, character }
+}
+
+/// Collects the span of every identifier
+#[derive(Default)]
+struct IdentSpans(Vec<proc_macro2::Span>);
+
+impl<'ast> Visit<'ast> for IdentSpans {
+    fn visit_ident(&mut self, node: &'ast proc_macro2::Ident) {
+        self.0.push(node.span());
+    }
+}
+
+#[test]
+fn test_line_index_positions() {
+    let content = "ab\ncé d\n\nx";
+    let lines = LineIndex::new(content);
+
+    assert_eq!(lines.line_count(), 4);
+    assert_eq!(lines.position(0), Position::new(0, 0));
+    assert_eq!(lines.position(3), Position::new(1, 0));
+    // `d` follows a two-byte char
+    assert_eq!(lines.position(7), Position::new(1, 3));
+    assert_eq!(lines.position(9), Position::new(2, 0));
+    assert_eq!(lines.position(content.len()), Position::new(3, 1));
+
+    // Columns count a four-byte char once, and only on its own line
+    let content = "🦀 é\nfn";
+    let lines = LineIndex::new(content);
+    assert_eq!(lines.position(5), Position::new(0, 2));
+    assert_eq!(lines.position(content.len()), Position::new(1, 2));
+}
+
+#[test]
+fn test_indexed_ranges_match_span_locations() {
+    let content = generate_program(5000);
+    let syntax_tree = syn::parse_str::<syn::File>(&content).unwrap();
+
+    let mut idents = IdentSpans::default();
+    idents.visit_file(&syntax_tree);
+    assert!(idents.0.len() > 5000);
+
+    let lines = LineIndex::new(&content);
+    for span in idents.0 {
+        let indexed = DiagnosticBuilder::create_range_from_span_with_lines(span, &lines);
+
+        assert_eq!(indexed, DiagnosticBuilder::create_range_from_span(span));
+        assert_eq!(
+            indexed.start,
+            linear_position(&content, span.byte_range().start)
+        );
+        assert_eq!(
+            indexed.end,
+            linear_position(&content, span.byte_range().end)
+        );
+    }
+}
+
+#[test]
+fn test_large_file_analysis_completes_quickly() {
+    let content = generate_program(5000);
+    let handlers = content.matches("pub fn close_").count();
+
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .build();
+
+    let start = Instant::now();
+    let diagnostics = registry.analyze(&content, None);
+    let elapsed = start.elapsed();
+
+    assert_eq!(diagnostics.len(), handlers);
+    assert!(
+        elapsed < Duration::from_secs(10),
+        "analysis took {:?}",
+        elapsed
+    );
+
+    // Ranges come from the registry's shared index and match a scan from the start
+    let zeroing = "**ctx.accounts.vault.to_account_info().lamports.borrow_mut() = 0";
+    for (diagnostic, (offset, _)) in diagnostics.iter().zip(content.match_indices(zeroing)) {
+        assert_eq!(diagnostic.range.start, linear_position(&content, offset));
+        assert_eq!(
+            diagnostic.range.end,
+            linear_position(&content, offset + zeroing.len())
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use futures::StreamExt;
+use language_server::backend::create_default_registry;
+use language_server::core::registry::DetectorInfo;
+use language_server::server::create_service;
+use serde_json::json;
+use tower::{Service, ServiceExt};
+use tower_lsp::jsonrpc::Request;
+
+#[tokio::test]
+async fn test_list_detectors_returns_registered_detectors() {
+    let (mut service, socket) = create_service();
+    tokio::spawn(socket.for_each(|_| async {}));
+
+    let initialize = Request::build("initialize")
+        .params(json!({ "capabilities": {} }))
+        .id(1)
+        .finish();
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap();
+
+    let list = Request::build("solana/listDetectors").id(2).finish();
+    let response = service
+        .ready()
+        .await
+        .unwrap()
+        .call(list)
+        .await
+        .unwrap()
+        .expect("request has a response");
+    let (_, result) = response.into_parts();
+    let detectors: Vec<DetectorInfo> = serde_json::from_value(result.unwrap()).unwrap();
+
+    let expected = create_default_registry().list_detectors();
+    assert_eq!(detectors.len(), expected.len());
+    for (detector, expected) in detectors.iter().zip(&expected) {
+        assert_eq!(detector.id, expected.id);
+        assert_eq!(detector.name, expected.name);
+        assert_eq!(detector.description, expected.description);
+        assert_eq!(detector.default_severity, expected.default_severity);
+        assert_eq!(detector.enabled, expected.enabled);
+    }
+
+    let zeroing = detectors
+        .iter()
+        .find(|detector| detector.id == "MANUAL_LAMPORTS_ZEROING")
+        .unwrap();
+    assert!(zeroing.enabled);
+    let redundant_log = detectors
+        .iter()
+        .find(|detector| detector.id == "REDUNDANT_ERROR_LOG")
+        .unwrap();
+    assert!(!redundant_log.enabled);
+}
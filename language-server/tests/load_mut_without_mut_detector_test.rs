@@ -0,0 +1,78 @@
+use language_server::core::detectors::{
+    detector::Detector, load_mut_without_mut_detector::LoadMutWithoutMutDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = LoadMutWithoutMutDetector::default();
+
+    assert_eq!(detector.id(), "LOAD_MUT_WITHOUT_MUT");
+    assert_eq!(detector.name(), "Load Mut Without Mut");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::ERROR);
+}
+
+#[test]
+fn test_detects_load_mut_on_immutable_field() {
+    let mut detector = LoadMutWithoutMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod orderbook {
+            use super::*;
+
+            pub fn update(ctx: Context<Update>, price: u64) -> Result<()> {
+                let mut book = ctx.accounts.book.load_mut()?;
+                book.price = price;
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Update<'info> {
+            pub book: AccountLoader<'info, OrderBook>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 16);
+    assert_eq!(diagnostics[0].range.start.character, 16);
+    assert!(diagnostics[0].message.contains("'book'"));
+}
+
+#[test]
+fn test_load_mut_on_mutable_field_not_flagged() {
+    let mut detector = LoadMutWithoutMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod orderbook {
+            use super::*;
+
+            pub fn update(ctx: Context<Update>, price: u64) -> Result<()> {
+                let mut book = ctx.accounts.book.load_mut()?;
+                book.price = price;
+                let config = ctx.accounts.config.load()?;
+                require!(config.enabled, ErrorCode::Disabled);
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Update<'info> {
+            #[account(mut)]
+            pub book: AccountLoader<'info, OrderBook>,
+            pub config: AccountLoader<'info, Config>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
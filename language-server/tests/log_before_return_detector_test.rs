@@ -0,0 +1,69 @@
+use language_server::core::detectors::{
+    detector::Detector, log_before_return_detector::LogBeforeReturnDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = LogBeforeReturnDetector::default();
+
+    assert_eq!(detector.id(), "REDUNDANT_ERROR_LOG");
+    assert_eq!(detector.name(), "Redundant Error Log");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::HINT);
+}
+
+#[test]
+fn test_detects_msg_before_return_err() {
+    let mut detector = LogBeforeReturnDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            if amount > ctx.accounts.vault.balance {
+                msg!("Insufficient funds");
+                return Err(ErrorCode::InsufficientFunds.into());
+            }
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::HINT));
+    assert_eq!(diagnostics[0].range.start.line, 3);
+}
+
+#[test]
+fn test_detects_msg_before_trailing_err() {
+    let mut detector = LogBeforeReturnDetector::default();
+
+    let code = r#"
+        pub fn check(amount: u64) -> Result<()> {
+            if amount == 0 {
+                msg!("Amount must be positive");
+                Err(error!(ErrorCode::InvalidAmount))
+            } else {
+                Ok(())
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_standalone_log_not_flagged() {
+    let mut detector = LogBeforeReturnDetector::default();
+
+    let code = r#"
+        pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+            msg!("Depositing {} lamports", amount);
+            ctx.accounts.vault.balance += amount;
+            msg!("Deposit complete");
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
@@ -0,0 +1,85 @@
+use language_server::core::detectors::{
+    detector::Detector, lossy_require_comparison_detector::LossyRequireComparisonDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = LossyRequireComparisonDetector::default();
+
+    assert_eq!(detector.id(), "LOSSY_REQUIRE_COMPARISON");
+    assert_eq!(detector.name(), "Lossy Require Comparison");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_cast_in_require() {
+    let mut detector = LossyRequireComparisonDetector::default();
+
+    let code = r#"
+        pub fn set_fee(ctx: Context<SetFee>, x: u64, y: u8) -> Result<()> {
+            require!(x as u8 == y, ErrorCode::InvalidFee);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.start.character, 21);
+    assert_eq!(diagnostics[0].range.end.character, 28);
+}
+
+#[test]
+fn test_detects_float_and_comparison_macros() {
+    let mut detector = LossyRequireComparisonDetector::default();
+
+    let code = r#"
+        pub fn check(ratio: f64, amount: u64, limit: u128) -> Result<()> {
+            require!(amount > 0 && ratio <= 0.5, ErrorCode::InvalidRatio);
+            require_eq!(amount, limit as u64, ErrorCode::LimitMismatch);
+            require_gte!((amount as u32) + 1, 10, ErrorCode::TooSmall);
+            assert!(ratio != 1.0);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 4);
+}
+
+#[test]
+fn test_plain_comparison_not_flagged() {
+    let mut detector = LossyRequireComparisonDetector::default();
+
+    let code = r#"
+        pub fn set_fee(ctx: Context<SetFee>, x: u8, y: u8) -> Result<()> {
+            require!(x == y, ErrorCode::InvalidFee);
+            require_eq!(ctx.accounts.config.admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+            require!(u8::try_from(x).is_ok(), ErrorCode::InvalidFee);
+            msg!("fee {}", x as u64);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_skips_test_modules() {
+    let mut detector = LossyRequireComparisonDetector::default();
+
+    let code = r#"
+        #[cfg(test)]
+        mod tests {
+            #[test]
+            fn test_ratio() {
+                assert_eq!(compute() as u8, 3);
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
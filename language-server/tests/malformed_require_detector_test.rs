@@ -0,0 +1,83 @@
+use language_server::core::detectors::{
+    detector::Detector, malformed_require_detector::MalformedRequireDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = MalformedRequireDetector::default();
+
+    assert_eq!(detector.id(), "MALFORMED_REQUIRE");
+    assert_eq!(detector.name(), "Malformed Require");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_reversed_arguments() {
+    let mut detector = MalformedRequireDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, x: u64) -> Result<()> {
+            require!(ErrorCode::Bad, x > 0);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert!(diagnostics[0].message.contains("condition"));
+}
+
+#[test]
+fn test_detects_missing_error_code() {
+    let mut detector = MalformedRequireDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, x: u64) -> Result<()> {
+            require!(x > 0);
+            anchor_lang::require!(error!(ErrorCode::Bad), x > 0);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0].message.contains("without an error code"));
+}
+
+#[test]
+fn test_well_formed_require_not_flagged() {
+    let mut detector = MalformedRequireDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, x: u64, ok: bool) -> Result<()> {
+            require!(x > 0, ErrorCode::Bad);
+            require!(ok, ErrorCode::Bad);
+            require!(ctx.accounts.vault.is_active(), error!(ErrorCode::Inactive));
+            require_eq!(x, 1, ErrorCode::Bad);
+            assert!(x > 0);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_skips_test_modules() {
+    let mut detector = MalformedRequireDetector::default();
+
+    let code = r#"
+        #[cfg(test)]
+        mod tests {
+            fn check(x: u64) {
+                require!(x > 0);
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
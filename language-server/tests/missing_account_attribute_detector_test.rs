@@ -0,0 +1,116 @@
+use language_server::core::detectors::{
+    detector::Detector, missing_account_attribute_detector::MissingAccountAttributeDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = MissingAccountAttributeDetector::default();
+
+    assert_eq!(detector.id(), "MISSING_ACCOUNT_ATTRIBUTE");
+    assert_eq!(detector.name(), "Missing Account Attribute");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_plain_struct_in_account() {
+    let mut detector = MissingAccountAttributeDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Update<'info> {
+            #[account(mut)]
+            pub state: Account<'info, PlainStruct>,
+            pub boxed: Box<Account<'info, PlainStruct>>,
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+        pub struct PlainStruct {
+            pub value: u64,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 6);
+    assert_eq!(diagnostics[0].range.start.character, 38);
+    assert!(diagnostics[0].message.contains("'PlainStruct'"));
+    assert_eq!(diagnostics[1].range.start.line, 7);
+}
+
+#[test]
+fn test_account_struct_not_flagged() {
+    let mut detector = MissingAccountAttributeDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Update<'info> {
+            #[account(mut)]
+            pub state: Account<'info, ProperState>,
+            pub big: AccountLoader<'info, PlainStruct>,
+        }
+
+        #[account]
+        pub struct ProperState {
+            pub value: u64,
+        }
+
+        pub struct PlainStruct {
+            pub value: u64,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_external_and_spl_types_not_flagged() {
+    let mut detector = MissingAccountAttributeDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+        use anchor_spl::token::{Mint, TokenAccount};
+        use other_program::Config;
+
+        #[derive(Accounts)]
+        pub struct Swap<'info> {
+            pub mint: Account<'info, Mint>,
+            pub vault: Account<'info, TokenAccount>,
+            pub config: Account<'info, Config>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_manual_account_impl_not_flagged() {
+    let mut detector = MissingAccountAttributeDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Read<'info> {
+            pub oracle: Account<'info, OracleWrapper>,
+        }
+
+        #[derive(Clone)]
+        pub struct OracleWrapper(oracle::PriceAccount);
+
+        impl anchor_lang::AccountDeserialize for OracleWrapper {
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+                unimplemented!()
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,63 @@
+use language_server::core::detectors::{
+    detector::Detector, missing_max_len_detector::MissingMaxLenDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = MissingMaxLenDetector::default();
+
+    assert_eq!(detector.id(), "MISSING_MAX_LEN");
+    assert_eq!(detector.name(), "Missing Max Len");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_vec_without_max_len() {
+    let mut detector = MissingMaxLenDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[account]
+        #[derive(InitSpace)]
+        pub struct Profile {
+            pub owner: Pubkey,
+            pub data: Vec<u8>,
+            pub nickname: Option<String>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 7);
+    assert!(diagnostics[0].message.contains("Vec field 'data'"));
+    assert_eq!(diagnostics[1].range.start.line, 8);
+    assert!(diagnostics[1].message.contains("String field 'nickname'"));
+}
+
+#[test]
+fn test_vec_with_max_len_not_flagged() {
+    let mut detector = MissingMaxLenDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[account]
+        #[derive(InitSpace)]
+        pub struct Profile {
+            pub owner: Pubkey,
+            #[max_len(32)]
+            pub data: Vec<u8>,
+            #[max_len(10, 16)]
+            pub tags: Vec<String>,
+        }
+
+        pub struct Scratch {
+            pub buffer: Vec<u8>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,74 @@
+use futures::StreamExt;
+use language_server::core::DylintDetectorManager;
+use language_server::core::dylint::constants::REQUIRED_NIGHTLY_VERSION;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tower::{Service, ServiceExt};
+use tower_lsp::jsonrpc::Request;
+use tower_lsp::lsp_types::{InitializeParams, InitializeResult};
+use tower_lsp::{Client, ClientSocket, LanguageServer, LspService};
+
+/// Server stub that only hands out its client
+struct MockServer;
+
+#[tower_lsp::async_trait]
+impl LanguageServer for MockServer {
+    async fn initialize(
+        &self,
+        _: InitializeParams,
+    ) -> tower_lsp::jsonrpc::Result<InitializeResult> {
+        Ok(InitializeResult::default())
+    }
+
+    async fn shutdown(&self) -> tower_lsp::jsonrpc::Result<()> {
+        Ok(())
+    }
+}
+
+/// Build an initialized service and return its client and the client-side socket
+async fn initialized_client() -> (LspService<MockServer>, Client, ClientSocket) {
+    let captured = Arc::new(Mutex::new(None));
+    let captured_client = Arc::clone(&captured);
+    let (mut service, socket) = LspService::new(move |client| {
+        *captured_client.lock().unwrap() = Some(client);
+        MockServer
+    });
+
+    let initialize = Request::build("initialize")
+        .params(json!({ "capabilities": {} }))
+        .id(1)
+        .finish();
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap();
+
+    let client = captured.lock().unwrap().take().unwrap();
+    (service, client, socket)
+}
+
+#[tokio::test]
+async fn test_missing_toolchain_notification_payload() {
+    let (_service, client, mut socket) = initialized_client().await;
+
+    DylintDetectorManager::report_missing_toolchain(&client, REQUIRED_NIGHTLY_VERSION).await;
+
+    let notification = socket.next().await.unwrap();
+    assert_eq!(notification.method(), "solana/detectorStatus");
+    let params = notification.params().unwrap();
+    assert_eq!(params["status"], "missing_toolchain");
+    assert_eq!(params["required_version"], REQUIRED_NIGHTLY_VERSION);
+    assert_eq!(
+        params["install_command"],
+        format!("rustup toolchain install {}", REQUIRED_NIGHTLY_VERSION)
+    );
+    assert!(
+        params["message"]
+            .as_str()
+            .unwrap()
+            .contains(REQUIRED_NIGHTLY_VERSION)
+    );
+}
@@ -0,0 +1,57 @@
+use language_server::core::detectors::{
+    detector::Detector, mut_account_argument_detector::MutAccountArgumentDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = MutAccountArgumentDetector::default();
+
+    assert_eq!(detector.id(), "MUT_ACCOUNT_ARGUMENT");
+    assert_eq!(detector.name(), "Mutable Account Argument");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_mut_account_passed_to_call() {
+    let mut detector = MutAccountArgumentDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            helper(&mut ctx.accounts.vault);
+            state::apply(amount, &mut ctx.accounts.config)?;
+            ctx.accounts.registry.track(&mut ctx.accounts.vault);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.start.character, 19);
+    assert!(diagnostics[0].message.contains("'vault'"));
+    assert!(diagnostics[1].message.contains("'state::apply'"));
+    assert!(diagnostics[2].message.contains("'track'"));
+}
+
+#[test]
+fn test_local_reborrow_not_flagged() {
+    let mut detector = MutAccountArgumentDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            let v = &mut ctx.accounts.vault;
+            v.amount -= amount;
+            helper(&ctx.accounts.vault);
+            helper(&mut ctx.accounts.vault.amount);
+            Ok(())
+        }
+
+        fn helper<T>(value: T) {
+            store(&mut accounts.vault);
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,162 @@
+use language_server::core::detectors::{
+    detector::Detector, insecure_randomness_detector::InsecureRandomnessDetector,
+    manual_lamports_zeroing::ManualLamportsZeroingDetector,
+    msg_in_loop_detector::MsgInLoopDetector,
+    unchecked_token_transfer_detector::UncheckedTokenTransferDetector,
+    unwrap_in_program_detector::UnwrapInProgramDetector,
+};
+use language_server::core::registry::{DetectorRegistry, DetectorRegistryBuilder};
+use language_server::core::utilities::{DiagnosticBuilder, LineIndex};
+use std::path::PathBuf;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn payout(ctx: Context<Payout>, amounts: Vec<u64>) -> Result<()> {
+        let seed = Clock::get().unwrap().unix_timestamp;
+        for amount in amounts.iter() {
+            msg!("Paying {}", amount);
+            token::transfer(ctx.accounts.transfer_ctx(), *amount)?;
+        }
+        msg!("Seed {}", seed % 10);
+        Ok(())
+    }
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        **ctx.accounts.vault.lamports.borrow_mut() = 0;
+        Ok(())
+    }
+}
+"#;
+
+/// Detector working on raw text, so it also runs on unparsable content
+struct TodoDetector;
+
+impl Detector for TodoDetector {
+    fn id(&self) -> &'static str {
+        "TODO"
+    }
+
+    fn name(&self) -> &'static str {
+        "Todo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects TODO markers"
+    }
+
+    fn message(&self) -> &'static str {
+        "Unresolved TODO"
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+
+    fn analyze(&mut self, content: &str, _file_path: Option<&PathBuf>) -> Vec<Diagnostic> {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.contains("TODO"))
+            .map(|(line, _)| {
+                DiagnosticBuilder::create(
+                    Range::new(Position::new(line as u32, 0), Position::new(line as u32, 1)),
+                    self.message().to_string(),
+                    self.default_severity(),
+                    self.id().to_string(),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    fn analyze_ast(
+        &mut self,
+        _syntax_tree: &syn::File,
+        content: &str,
+        file_path: Option<&PathBuf>,
+        _lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        self.analyze(content, file_path)
+    }
+}
+
+fn detectors() -> Vec<Box<dyn Detector>> {
+    vec![
+        Box::new(InsecureRandomnessDetector::default()),
+        Box::new(ManualLamportsZeroingDetector::default()),
+        Box::new(MsgInLoopDetector::default()),
+        Box::new(UncheckedTokenTransferDetector::default()),
+        Box::new(UnwrapInProgramDetector::default()),
+        Box::new(TodoDetector),
+    ]
+}
+
+fn registry() -> DetectorRegistry {
+    DetectorRegistryBuilder::new()
+        .with_detector(InsecureRandomnessDetector::default())
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .with_detector(MsgInLoopDetector::default())
+        .with_detector(UncheckedTokenTransferDetector::default())
+        .with_detector(UnwrapInProgramDetector::default())
+        .with_detector(TodoDetector)
+        .build()
+}
+
+/// Run each detector on its own, in registration order
+fn sequential(content: &str) -> Vec<Diagnostic> {
+    detectors()
+        .iter_mut()
+        .flat_map(|detector| detector.analyze(content, None))
+        .collect()
+}
+
+#[test]
+fn test_parallel_matches_sequential() {
+    let expected = sequential(PROGRAM);
+
+    // Several detectors fire, so the comparison covers ordering across chunks
+    let ids: std::collections::HashSet<_> = expected
+        .iter()
+        .map(|diagnostic| format!("{:?}", diagnostic.code))
+        .collect();
+    assert!(
+        ids.len() >= 4,
+        "expected several detectors to fire: {:?}",
+        ids
+    );
+
+    let mut registry = registry();
+    for _ in 0..5 {
+        assert_eq!(registry.analyze(PROGRAM, None), expected);
+    }
+}
+
+#[test]
+fn test_text_detectors_run_on_unparsable_content() {
+    let content = "fn broken( {\n    // TODO: finish\n";
+    let mut registry = registry();
+
+    let diagnostics = registry.analyze(content, None);
+    assert_eq!(diagnostics, sequential(content));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 1);
+}
+
+#[test]
+fn test_default_analyze_delegates_to_ast() {
+    let mut detector = UncheckedTokenTransferDetector::default();
+    let syntax_tree = syn::parse_str::<syn::File>(PROGRAM).unwrap();
+    let lines = LineIndex::new(PROGRAM);
+
+    assert_eq!(
+        detector.analyze_ast(&syntax_tree, PROGRAM, None, &lines),
+        detector.analyze(PROGRAM, None)
+    );
+}
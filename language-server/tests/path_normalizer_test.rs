@@ -0,0 +1,87 @@
+use language_server::core::utilities::PathNormalizer;
+use std::fs;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::Url;
+
+/// Create a fresh temporary directory for a test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "solana-ls-path-normalizer-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinked_root_publishes_client_uris() {
+    let base = temp_dir("symlink");
+    let real_root = base.join("real");
+    let link_root = base.join("link");
+    fs::create_dir_all(real_root.join("src")).unwrap();
+    fs::write(real_root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+    std::os::unix::fs::symlink(&real_root, &link_root).unwrap();
+
+    // The client opened the workspace through the symlink
+    let opened_uri = Url::from_file_path(link_root.join("src/lib.rs")).unwrap();
+    let normalizer = PathNormalizer::new(link_root.clone(), true);
+
+    // Scanning happens on the canonical root
+    let canonical_root = fs::canonicalize(&real_root).unwrap();
+    assert_eq!(normalizer.workspace_root(), Some(canonical_root.clone()));
+
+    // Paths from the canonical root and from the symlink both map to the opened URI
+    let scanned = canonical_root.join("src/lib.rs");
+    assert_eq!(normalizer.to_uri(&scanned), Some(opened_uri.clone()));
+    assert_eq!(
+        normalizer.to_uri(&link_root.join("src/lib.rs")),
+        Some(opened_uri)
+    );
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_canonicalization_disabled_passes_paths_through() {
+    let base = temp_dir("disabled");
+    let real_root = base.join("real");
+    let link_root = base.join("link");
+    fs::create_dir_all(real_root.join("src")).unwrap();
+    fs::write(real_root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+    std::os::unix::fs::symlink(&real_root, &link_root).unwrap();
+
+    let normalizer = PathNormalizer::new(link_root.clone(), false);
+    assert_eq!(normalizer.workspace_root(), Some(link_root.clone()));
+
+    let scanned = real_root.join("src/lib.rs");
+    assert_eq!(
+        normalizer.to_uri(&scanned),
+        Some(Url::from_file_path(&scanned).unwrap())
+    );
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn test_paths_outside_workspace_are_canonicalized() {
+    let base = temp_dir("outside");
+    let root = base.join("workspace");
+    let other = base.join("other");
+    fs::create_dir_all(&root).unwrap();
+    fs::create_dir_all(&other).unwrap();
+    fs::write(other.join("lib.rs"), "").unwrap();
+
+    let normalizer = PathNormalizer::new(root, true);
+    let unnormalized = other.join("..").join("other").join("lib.rs");
+
+    assert_eq!(
+        normalizer.to_client_path(&unnormalized),
+        fs::canonicalize(other.join("lib.rs")).unwrap()
+    );
+
+    fs::remove_dir_all(&base).unwrap();
+}
@@ -0,0 +1,65 @@
+use language_server::core::detectors::{
+    detector::Detector, payer_not_mut_detector::PayerNotMutDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = PayerNotMutDetector::default();
+
+    assert_eq!(detector.id(), "PAYER_NOT_MUT");
+    assert_eq!(detector.name(), "Payer Not Mutable");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::ERROR);
+}
+
+#[test]
+fn test_detects_immutable_payer() {
+    let mut detector = PayerNotMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(init, payer = p, space = 8 + Vault::INIT_SPACE)]
+            pub vault: Account<'info, Vault>,
+            pub p: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 5);
+    assert!(diagnostics[0].message.contains("Payer 'p'"));
+}
+
+#[test]
+fn test_mutable_payer_not_flagged() {
+    let mut detector = PayerNotMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(init, payer = p, space = 8 + Vault::INIT_SPACE)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub p: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+
+        #[derive(Accounts)]
+        pub struct InitializeIfNeeded<'info> {
+            #[account(init_if_needed, payer = payer, space = 8 + Vault::INIT_SPACE)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut @ ErrorCode::NotWritable)]
+            pub payer: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
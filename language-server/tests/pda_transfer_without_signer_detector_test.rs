@@ -0,0 +1,118 @@
+use language_server::core::detectors::{
+    detector::Detector, pda_transfer_without_signer_detector::PdaTransferWithoutSignerDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+const ACCOUNTS: &str = r#"
+    #[derive(Accounts)]
+    pub struct Withdraw<'info> {
+        #[account(mut, seeds = [b"vault", user.key().as_ref()], bump)]
+        pub vault: SystemAccount<'info>,
+        #[account(mut)]
+        pub user: Signer<'info>,
+        pub system_program: Program<'info, System>,
+    }
+"#;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = PdaTransferWithoutSignerDetector::default();
+
+    assert_eq!(detector.id(), "PDA_TRANSFER_WITHOUT_SIGNER");
+    assert_eq!(detector.name(), "PDA Transfer Without Signer Seeds");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_pda_transfer_with_invoke() {
+    let mut detector = PdaTransferWithoutSignerDetector::default();
+
+    let code = format!(
+        r#"
+        use anchor_lang::prelude::*;
+        use anchor_lang::solana_program::{{program::invoke, system_instruction}};
+
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {{
+            invoke(
+                &system_instruction::transfer(ctx.accounts.vault.key, ctx.accounts.user.key, amount),
+                &[ctx.accounts.vault.to_account_info(), ctx.accounts.user.to_account_info()],
+            )?;
+            Ok(())
+        }}
+
+        pub fn withdraw_cpi(ctx: Context<Withdraw>, amount: u64) -> Result<()> {{
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {{
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.user.to_account_info(),
+                }},
+            );
+            system_program::transfer(cpi_ctx, amount)
+        }}
+        {}
+    "#,
+        ACCOUNTS
+    );
+
+    let diagnostics = detector.analyze(&code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 5);
+    assert!(diagnostics[0].message.contains("'vault' with invoke"));
+    assert_eq!(diagnostics[1].range.start.line, 20);
+    assert!(
+        diagnostics[1]
+            .message
+            .contains("'vault' with CpiContext::new")
+    );
+}
+
+#[test]
+fn test_invoke_signed_not_flagged() {
+    let mut detector = PdaTransferWithoutSignerDetector::default();
+
+    let code = format!(
+        r#"
+        use anchor_lang::prelude::*;
+        use anchor_lang::solana_program::{{program::invoke_signed, system_instruction}};
+
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {{
+            let user_key = ctx.accounts.user.key();
+            let seeds: &[&[u8]] = &[b"vault", user_key.as_ref(), &[ctx.bumps.vault]];
+            invoke_signed(
+                &system_instruction::transfer(ctx.accounts.vault.key, ctx.accounts.user.key, amount),
+                &[ctx.accounts.vault.to_account_info(), ctx.accounts.user.to_account_info()],
+                &[seeds],
+            )?;
+            Ok(())
+        }}
+
+        pub fn withdraw_cpi(ctx: Context<Withdraw>, amount: u64) -> Result<()> {{
+            let user_key = ctx.accounts.user.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"vault", user_key.as_ref(), &[ctx.bumps.vault]]];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {{
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.user.to_account_info(),
+                }},
+                signer_seeds,
+            );
+            system_program::transfer(cpi_ctx, amount)
+        }}
+
+        pub fn deposit(ctx: Context<Withdraw>, amount: u64) -> Result<()> {{
+            invoke(
+                &system_instruction::transfer(ctx.accounts.user.key, ctx.accounts.vault.key, amount),
+                &[ctx.accounts.user.to_account_info(), ctx.accounts.vault.to_account_info()],
+            )?;
+            Ok(())
+        }}
+        {}
+    "#,
+        ACCOUNTS
+    );
+
+    let diagnostics = detector.analyze(&code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,50 @@
+use language_server::core::detectors::{
+    detector::Detector, pda_typed_as_signer_detector::PdaTypedAsSignerDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = PdaTypedAsSignerDetector::default();
+
+    assert_eq!(detector.id(), "PDA_TYPED_AS_SIGNER");
+    assert_eq!(detector.name(), "PDA Typed As Signer");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_pda_typed_as_account_not_flagged() {
+    let mut detector = PdaTypedAsSignerDetector::default();
+
+    let code = r#"
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut, seeds = [b"vault", authority.key().as_ref()], bump)]
+            pub vault: Account<'info, Vault>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_detects_pda_typed_as_signer() {
+    let mut detector = PdaTypedAsSignerDetector::default();
+
+    let code = r#"
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(seeds = [b"vault_authority"], bump)]
+            pub vault_authority: Signer<'info>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+    assert!(diagnostics[0].message.contains("'vault_authority'"));
+    assert_eq!(diagnostics[0].range.start.line, 3);
+}
@@ -0,0 +1,220 @@
+use futures::StreamExt;
+use language_server::backend::Backend;
+use language_server::core::utilities::{DiagnosticBuilder, LineIndex, PositionEncoding};
+use serde_json::{Value, json};
+use tower::{Service, ServiceExt};
+use tower_lsp::LspService;
+use tower_lsp::jsonrpc::Request;
+use tower_lsp::lsp_types::{DiagnosticSeverity, Position, PositionEncodingKind, Range};
+
+/// `🦀` is 1 char, 2 UTF-16 code units and 4 UTF-8 bytes
+const CODE: &str = "fn f() {\n    let crab = \"🦀\"; let x = y.unwrap();\n}\n";
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        /* 🦀 */ **ctx.accounts.vault.lamports.borrow_mut() = 0;
+        Ok(())
+    }
+}
+"#;
+
+#[test]
+fn test_negotiate_prefers_utf8() {
+    assert_eq!(PositionEncoding::negotiate(None), PositionEncoding::Utf16);
+    assert_eq!(
+        PositionEncoding::negotiate(Some(&[PositionEncodingKind::UTF16])),
+        PositionEncoding::Utf16
+    );
+    assert_eq!(
+        PositionEncoding::negotiate(Some(&[
+            PositionEncodingKind::UTF16,
+            PositionEncodingKind::UTF8
+        ])),
+        PositionEncoding::Utf8
+    );
+}
+
+#[test]
+fn test_line_index_positions_after_multibyte_char() {
+    let lines = LineIndex::new(CODE);
+    let offset = CODE.find("unwrap").unwrap();
+
+    assert_eq!(lines.position(offset), Position::new(1, 30));
+    assert_eq!(
+        lines.position_encoded(CODE, offset, PositionEncoding::Utf16),
+        Position::new(1, 31)
+    );
+    assert_eq!(
+        lines.position_encoded(CODE, offset, PositionEncoding::Utf8),
+        Position::new(1, 33)
+    );
+}
+
+#[test]
+fn test_encode_diagnostic_positions() {
+    let diagnostic = || {
+        DiagnosticBuilder::create(
+            Range::new(Position::new(1, 30), Position::new(1, 36)),
+            "unwrap".to_string(),
+            DiagnosticSeverity::WARNING,
+            "TEST".to_string(),
+            None,
+        )
+    };
+
+    let mut utf16 = vec![diagnostic()];
+    DiagnosticBuilder::encode_positions(&mut utf16, CODE, PositionEncoding::Utf16);
+    assert_eq!(
+        utf16[0].range,
+        Range::new(Position::new(1, 31), Position::new(1, 37))
+    );
+
+    let mut utf8 = vec![diagnostic()];
+    DiagnosticBuilder::encode_positions(&mut utf8, CODE, PositionEncoding::Utf8);
+    assert_eq!(
+        utf8[0].range,
+        Range::new(Position::new(1, 33), Position::new(1, 39))
+    );
+}
+
+#[test]
+fn test_client_positions_round_trip() {
+    let unwrap = Position::new(1, 30);
+    let client = |character| Position::new(1, character);
+
+    for (encoding, character) in [(PositionEncoding::Utf16, 31), (PositionEncoding::Utf8, 33)] {
+        assert_eq!(encoding.decode_position(CODE, client(character)), unwrap);
+        assert_eq!(
+            encoding.encode_range(CODE, Range::new(unwrap, unwrap)),
+            Range::new(client(character), client(character))
+        );
+    }
+    // Columns past the end of a line are kept
+    assert_eq!(
+        PositionEncoding::Utf8.decode_position(CODE, Position::new(1, 60)),
+        Position::new(1, 57)
+    );
+}
+
+/// Initialize a backend offering `encodings` and open `PROGRAM`
+/// Returns the service and the negotiated encoding
+async fn open_program(uri: &str, encodings: Value) -> (LspService<Backend>, Value) {
+    let (mut service, socket) = LspService::new(Backend::new);
+    // Drain server-to-client messages so publishing never blocks
+    tokio::spawn(socket.for_each(|_| async {}));
+
+    let initialize = Request::build("initialize")
+        .params(json!({
+            "capabilities": {
+                "general": { "positionEncodings": encodings },
+                "textDocument": { "diagnostic": {} },
+            }
+        }))
+        .id(1)
+        .finish();
+    let response = service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap()
+        .unwrap();
+    let encoding = response.result().unwrap()["capabilities"]["positionEncoding"].clone();
+
+    let did_open = Request::build("textDocument/didOpen")
+        .params(json!({
+            "textDocument": { "uri": uri, "languageId": "rust", "version": 1, "text": PROGRAM }
+        }))
+        .finish();
+    service.ready().await.unwrap().call(did_open).await.unwrap();
+
+    (service, encoding)
+}
+
+/// Pull the diagnostics of `uri` and return the start of the one with `code`
+async fn diagnostic_start(service: &mut LspService<Backend>, uri: &str, code: &str) -> Value {
+    let diagnostic = Request::build("textDocument/diagnostic")
+        .params(json!({ "textDocument": { "uri": uri } }))
+        .id(2)
+        .finish();
+    let response = service
+        .ready()
+        .await
+        .unwrap()
+        .call(diagnostic)
+        .await
+        .unwrap()
+        .unwrap();
+    let item = response.result().unwrap()["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["code"] == code)
+        .unwrap()
+        .clone();
+
+    item["range"]["start"].clone()
+}
+
+#[tokio::test]
+async fn test_negotiated_encoding_applies_to_diagnostics() {
+    let uri = "file:///tmp/position_encoding/lib.rs";
+
+    let (mut service, encoding) = open_program(uri, json!(["utf-16"])).await;
+    assert_eq!(encoding, "utf-16");
+    assert_eq!(
+        diagnostic_start(&mut service, uri, "MANUAL_LAMPORTS_ZEROING").await,
+        json!({ "line": 8, "character": 17 })
+    );
+
+    let (mut service, encoding) = open_program(uri, json!(["utf-8", "utf-16"])).await;
+    assert_eq!(encoding, "utf-8");
+    assert_eq!(
+        diagnostic_start(&mut service, uri, "MANUAL_LAMPORTS_ZEROING").await,
+        json!({ "line": 8, "character": 19 })
+    );
+}
+
+#[tokio::test]
+async fn test_utf8_change_after_multibyte_char() {
+    let uri = "file:///tmp/position_encoding/change.rs";
+    let (mut service, encoding) = open_program(uri, json!(["utf-8"])).await;
+    assert_eq!(encoding, "utf-8");
+
+    // Insert a statement right before `**`, at byte 19 of the line holding `🦀`
+    let did_change = Request::build("textDocument/didChange")
+        .params(json!({
+            "textDocument": { "uri": uri, "version": 2 },
+            "contentChanges": [{
+                "range": {
+                    "start": { "line": 8, "character": 19 },
+                    "end": { "line": 8, "character": 19 },
+                },
+                "text": "x.unwrap(); ",
+            }],
+        }))
+        .finish();
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(did_change)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        diagnostic_start(&mut service, uri, "UNWRAP_IN_PROGRAM").await,
+        json!({ "line": 8, "character": 21 })
+    );
+    assert_eq!(
+        diagnostic_start(&mut service, uri, "MANUAL_LAMPORTS_ZEROING").await,
+        json!({ "line": 8, "character": 31 })
+    );
+}
@@ -0,0 +1,70 @@
+use language_server::core::detectors::{
+    detector::Detector, program_id_mismatch_detector::ProgramIdMismatchDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = ProgramIdMismatchDetector::default();
+
+    assert_eq!(detector.id(), "PROGRAM_ID_MISMATCH");
+    assert_eq!(detector.name(), "Program ID Mismatch");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_mismatched_program_id() {
+    let mut detector = ProgramIdMismatchDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+        const OLD_PROGRAM: Pubkey = pubkey!("11111111111111111111111111111112");
+
+        pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+            if *program_id != Pubkey::from_str("BPFLoaderUpgradeab1e11111111111111111111111").unwrap() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            require_keys_eq!(accounts[0].owner, OLD_PROGRAM);
+            require_keys_eq!(crate::ID, OLD_PROGRAM);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 8);
+    assert_eq!(diagnostics[0].range.start.character, 30);
+    assert!(
+        diagnostics[0]
+            .message
+            .contains("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    assert_eq!(diagnostics[1].range.start.line, 12);
+}
+
+#[test]
+fn test_matching_program_id_not_flagged() {
+    let mut detector = ProgramIdMismatchDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+        pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+            require!(
+                *program_id == pubkey!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"),
+                ErrorCode::WrongProgram
+            );
+            // Other programs may be compared against other keys
+            require_keys_eq!(accounts[1].key(), pubkey!("11111111111111111111111111111112"));
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,162 @@
+use futures::{SinkExt, StreamExt};
+use language_server::core::file_scanner::FileScanner;
+use language_server::core::registry::DetectorRegistryBuilder;
+use language_server::core::{ManualLamportsZeroingDetector, WorkDoneProgressReporter};
+use serde_json::{Value, json};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use tower::{Service, ServiceExt};
+use tower_lsp::jsonrpc::{Request, Response};
+use tower_lsp::lsp_types::{InitializeParams, InitializeResult};
+use tower_lsp::{Client, ClientSocket, LanguageServer, LspService};
+
+/// Server stub that only hands out its client
+struct MockServer;
+
+#[tower_lsp::async_trait]
+impl LanguageServer for MockServer {
+    async fn initialize(
+        &self,
+        _: InitializeParams,
+    ) -> tower_lsp::jsonrpc::Result<InitializeResult> {
+        Ok(InitializeResult::default())
+    }
+
+    async fn shutdown(&self) -> tower_lsp::jsonrpc::Result<()> {
+        Ok(())
+    }
+}
+
+/// Build an initialized service and return its client and the client-side socket
+async fn initialized_client() -> (LspService<MockServer>, Client, ClientSocket) {
+    let captured = Arc::new(Mutex::new(None));
+    let captured_client = Arc::clone(&captured);
+    let (mut service, socket) = LspService::new(move |client| {
+        *captured_client.lock().unwrap() = Some(client);
+        MockServer
+    });
+
+    let initialize = Request::build("initialize")
+        .params(json!({ "capabilities": {} }))
+        .id(1)
+        .finish();
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap();
+
+    let client = captured.lock().unwrap().take().unwrap();
+    (service, client, socket)
+}
+
+/// Collect the `kind` of every `$/progress` message, acknowledging `create` requests
+async fn capture_progress(mut socket: ClientSocket) -> Vec<String> {
+    let mut kinds = Vec::new();
+
+    while let Some(request) = socket.next().await {
+        match request.method() {
+            "window/workDoneProgress/create" => {
+                kinds.push("create".to_string());
+                let id = request.id().cloned().unwrap();
+                socket
+                    .send(Response::from_ok(id, Value::Null))
+                    .await
+                    .unwrap();
+            }
+            "$/progress" => {
+                let kind = request.params().unwrap()["value"]["kind"]
+                    .as_str()
+                    .unwrap()
+                    .to_string();
+                let done = kind == "end";
+                kinds.push(kind);
+                if done {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    kinds
+}
+
+#[tokio::test]
+async fn test_reporter_emits_begin_report_end() {
+    let (_service, client, socket) = initialized_client().await;
+    let capture = tokio::spawn(capture_progress(socket));
+
+    let mut progress = WorkDoneProgressReporter::begin(&client, "Scanning", None).await;
+    progress.report(1, 4, None).await;
+    // Unchanged percentage is not re-sent
+    progress.report(1, 4, None).await;
+    progress.report(3, 4, None).await;
+    progress.end(None).await;
+
+    assert_eq!(
+        capture.await.unwrap(),
+        vec!["create", "begin", "report", "report", "end"]
+    );
+}
+
+#[tokio::test]
+async fn test_scan_reports_progress_per_file() {
+    let root = std::env::temp_dir().join(format!("solana-ls-progress-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    for name in ["a.rs", "b.rs", "c.rs", "d.rs"] {
+        fs::write(root.join("src").join(name), "pub fn f() {}\n").unwrap();
+    }
+
+    let (_service, client, socket) = initialized_client().await;
+    let capture = tokio::spawn(capture_progress(socket));
+
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.clone());
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .build();
+    let result = scanner
+        .scan_workspace_with_client(&mut registry, Some(&client))
+        .await;
+    assert_eq!(result.rust_files.len(), 4);
+
+    // One report per file after the first (0% is sent with begin)
+    assert_eq!(
+        capture.await.unwrap(),
+        vec!["create", "begin", "report", "report", "report", "end"]
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_rejected_token_sends_nothing() {
+    let (service, client, mut socket) = initialized_client().await;
+
+    let responder = tokio::spawn(async move {
+        let mut methods = Vec::new();
+        while let Some(request) = socket.next().await {
+            methods.push(request.method().to_string());
+            if let Some(id) = request.id().cloned() {
+                let error = tower_lsp::jsonrpc::Error::method_not_found();
+                socket.send(Response::from_error(id, error)).await.unwrap();
+            }
+        }
+        methods
+    });
+
+    let mut progress = WorkDoneProgressReporter::begin(&client, "Scanning", None).await;
+    progress.report(1, 2, None).await;
+    progress.end(None).await;
+    drop(client);
+    drop(service);
+
+    assert_eq!(
+        responder.await.unwrap(),
+        vec!["window/workDoneProgress/create"]
+    );
+}
@@ -0,0 +1,80 @@
+use language_server::core::utilities::PublishVersions;
+use std::collections::HashMap;
+use tower_lsp::lsp_types::Url;
+
+fn uri() -> Url {
+    Url::parse("file:///workspace/src/lib.rs").unwrap()
+}
+
+/// Diagnostics as the client last received them, per file
+#[derive(Default)]
+struct Client {
+    published: HashMap<Url, (i32, Vec<&'static str>)>,
+}
+
+impl Client {
+    fn publish(&mut self, versions: &mut PublishVersions, diagnostics: Vec<&'static str>) -> i32 {
+        let version = versions.next(&uri());
+        self.published.insert(uri(), (version, diagnostics));
+        version
+    }
+
+    fn publish_if_current(
+        &mut self,
+        versions: &mut PublishVersions,
+        diagnostics: Vec<&'static str>,
+        expected: i32,
+    ) -> bool {
+        let Some(version) = versions.next_if_current(&uri(), expected) else {
+            return false;
+        };
+        self.published.insert(uri(), (version, diagnostics));
+        true
+    }
+}
+
+#[test]
+fn test_versions_increase_per_file() {
+    let mut versions = PublishVersions::default();
+    let other = Url::parse("file:///workspace/src/other.rs").unwrap();
+
+    assert_eq!(versions.current(&uri()), 0);
+    assert_eq!(versions.next(&uri()), 1);
+    assert_eq!(versions.next(&uri()), 2);
+    assert_eq!(versions.next(&other), 1);
+    assert_eq!(versions.current(&uri()), 2);
+}
+
+#[test]
+fn test_late_dylint_result_does_not_overwrite_newer_diagnostics() {
+    let mut versions = PublishVersions::default();
+    let mut client = Client::default();
+
+    // Syn results for the first edit; dylint starts merging against them
+    let dylint_base = client.publish(&mut versions, vec!["syn: old"]);
+
+    // The user edits again before dylint finishes
+    client.publish(&mut versions, vec!["syn: new"]);
+
+    // The slow dylint run completes with results for the older content
+    let published =
+        client.publish_if_current(&mut versions, vec!["syn: old", "dylint: old"], dylint_base);
+
+    assert!(!published);
+    assert_eq!(client.published[&uri()], (2, vec!["syn: new"]));
+}
+
+#[test]
+fn test_dylint_result_published_when_current() {
+    let mut versions = PublishVersions::default();
+    let mut client = Client::default();
+
+    let dylint_base = client.publish(&mut versions, vec!["syn"]);
+    let published = client.publish_if_current(&mut versions, vec!["syn", "dylint"], dylint_base);
+
+    assert!(published);
+    assert_eq!(client.published[&uri()], (2, vec!["syn", "dylint"]));
+
+    // A second late result against the same base is now stale as well
+    assert!(!client.publish_if_current(&mut versions, vec!["dylint: again"], dylint_base));
+}
@@ -0,0 +1,144 @@
+use futures::StreamExt;
+use language_server::backend::Backend;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use tower::{Service, ServiceExt};
+use tower_lsp::LspService;
+use tower_lsp::jsonrpc::Request;
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        **ctx.accounts.vault.lamports.borrow_mut() = 0;
+        Ok(())
+    }
+}
+"#;
+
+/// Capabilities of a client that supports pull diagnostics
+fn pull_capabilities() -> Value {
+    json!({ "textDocument": { "diagnostic": {} } })
+}
+
+/// Start a backend and initialize it with `capabilities` and `options`
+/// Returns the initialize result and the URIs diagnostics were pushed for
+async fn initialized_service(
+    capabilities: Value,
+    options: Value,
+) -> (LspService<Backend>, Value, Arc<Mutex<Vec<Value>>>) {
+    let (mut service, socket) = LspService::new(Backend::new);
+    // Record pushed diagnostics, draining everything else so publishing never blocks
+    let pushed: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let recorded = Arc::clone(&pushed);
+    tokio::spawn(socket.for_each(move |request| {
+        if request.method() == "textDocument/publishDiagnostics" {
+            recorded
+                .lock()
+                .unwrap()
+                .push(request.params().unwrap()["uri"].clone());
+        }
+        async {}
+    }));
+
+    let initialize = Request::build("initialize")
+        .params(json!({ "capabilities": capabilities, "initializationOptions": options }))
+        .id(1)
+        .finish();
+    let response = service
+        .ready()
+        .await
+        .unwrap()
+        .call(initialize)
+        .await
+        .unwrap()
+        .unwrap();
+
+    (service, response.result().unwrap().clone(), pushed)
+}
+
+/// Open `PROGRAM` as `uri`
+async fn open_program(service: &mut LspService<Backend>, uri: &str) {
+    let did_open = Request::build("textDocument/didOpen")
+        .params(json!({
+            "textDocument": {
+                "uri": uri,
+                "languageId": "rust",
+                "version": 1,
+                "text": PROGRAM,
+            }
+        }))
+        .finish();
+    service.ready().await.unwrap().call(did_open).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pulled_diagnostics_report_detector_findings() {
+    let (mut service, initialize_result, pushed) =
+        initialized_service(pull_capabilities(), json!({})).await;
+    assert_eq!(
+        initialize_result["capabilities"]["diagnosticProvider"]["identifier"],
+        "solana"
+    );
+
+    let uri = "file:///tmp/pull_diagnostics/lib.rs";
+    open_program(&mut service, uri).await;
+
+    let diagnostic = Request::build("textDocument/diagnostic")
+        .params(json!({ "textDocument": { "uri": uri } }))
+        .id(2)
+        .finish();
+    let response = service
+        .ready()
+        .await
+        .unwrap()
+        .call(diagnostic)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let report = response.result().unwrap();
+    assert_eq!(report["kind"], "full");
+    let zeroing = report["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["code"] == "MANUAL_LAMPORTS_ZEROING")
+        .unwrap();
+    assert_eq!(zeroing["range"]["start"]["line"], 8);
+
+    // Pulling clients would otherwise get every diagnostic twice
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(pushed.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_diagnostics_are_pushed_without_pull_support() {
+    let (mut service, initialize_result, pushed) =
+        initialized_service(json!({}), json!({ "pullDiagnostics": true })).await;
+    assert!(initialize_result["capabilities"]["diagnosticProvider"].is_null());
+
+    let uri = "file:///tmp/pull_diagnostics/push.rs";
+    open_program(&mut service, uri).await;
+
+    // Let the recorder catch up with the notifications sent so far
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(*pushed.lock().unwrap(), vec![json!(uri)]);
+}
+
+#[tokio::test]
+async fn test_pull_diagnostics_can_be_disabled() {
+    let (mut service, initialize_result, pushed) =
+        initialized_service(pull_capabilities(), json!({ "pullDiagnostics": false })).await;
+    assert!(initialize_result["capabilities"]["diagnosticProvider"].is_null());
+
+    let uri = "file:///tmp/pull_diagnostics/disabled.rs";
+    open_program(&mut service, uri).await;
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(*pushed.lock().unwrap(), vec![json!(uri)]);
+}
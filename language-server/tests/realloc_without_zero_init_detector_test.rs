@@ -0,0 +1,56 @@
+use language_server::core::detectors::{
+    detector::Detector, realloc_without_zero_init_detector::ReallocWithoutZeroInitDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = ReallocWithoutZeroInitDetector::default();
+
+    assert_eq!(detector.id(), "REALLOC_WITHOUT_ZERO_INIT");
+    assert_eq!(detector.name(), "Realloc Without Zero Init");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_realloc_without_zero_init() {
+    let mut detector = ReallocWithoutZeroInitDetector::default();
+
+    let code = r#"
+        pub fn grow(ctx: Context<Grow>) -> Result<()> {
+            let acc = ctx.accounts.data.to_account_info();
+            acc.realloc(100, false)?;
+            AccountInfo::realloc(&acc, 200, false)?;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 3);
+    assert_eq!(diagnostics[0].range.start.character, 29);
+    assert_eq!(diagnostics[1].range.start.line, 4);
+}
+
+#[test]
+fn test_zero_init_or_memset_not_flagged() {
+    let mut detector = ReallocWithoutZeroInitDetector::default();
+
+    let code = r#"
+        pub fn grow(ctx: Context<Grow>) -> Result<()> {
+            let acc = ctx.accounts.data.to_account_info();
+            acc.realloc(100, true)?;
+            Ok(())
+        }
+
+        pub fn grow_and_clear(ctx: Context<Grow>, old_len: usize) -> Result<()> {
+            let acc = ctx.accounts.data.to_account_info();
+            acc.realloc(100, false)?;
+            sol_memset(&mut acc.try_borrow_mut_data()?[old_len..], 0, 100 - old_len);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,154 @@
+use language_server::core::file_scanner::FileScanner;
+use language_server::core::language_features::RenameProvider;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::{Position, Range};
+
+const LIB: &str = r#"use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>) -> Result<()> {
+        Ok(())
+    }
+}
+"#;
+
+const INSTRUCTIONS: &str = r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub init: Initialize<'info>,
+}
+"#;
+
+/// Create a workspace with the handlers and the accounts structs in separate files
+fn workspace(name: &str) -> PathBuf {
+    let root =
+        std::env::temp_dir().join(format!("solana-ls-rename-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("programs/vault/src")).unwrap();
+    fs::write(root.join("programs/vault/src/lib.rs"), LIB).unwrap();
+    fs::write(
+        root.join("programs/vault/src/instructions.rs"),
+        INSTRUCTIONS,
+    )
+    .unwrap();
+    root
+}
+
+fn workspace_files(root: &Path) -> Vec<PathBuf> {
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.to_path_buf());
+    scanner.rust_files()
+}
+
+fn range(line: u32, start: u32, end: u32) -> Range {
+    Range::new(Position::new(line, start), Position::new(line, end))
+}
+
+#[test]
+fn test_prepare_rename() {
+    // On `Initialize` of `Context<Initialize>`
+    assert_eq!(
+        RenameProvider::prepare_rename(LIB, Position::new(6, 38)),
+        Some((range(6, 35, 45), "Initialize".to_string()))
+    );
+
+    // On the struct definition
+    assert_eq!(
+        RenameProvider::prepare_rename(INSTRUCTIONS, Position::new(3, 13)),
+        Some((range(3, 11, 21), "Initialize".to_string()))
+    );
+
+    // On the handler name and on `Context` itself
+    assert_eq!(
+        RenameProvider::prepare_rename(LIB, Position::new(6, 12)),
+        None
+    );
+    assert_eq!(
+        RenameProvider::prepare_rename(LIB, Position::new(6, 29)),
+        None
+    );
+
+    // On a field type that is not an accounts struct
+    assert_eq!(
+        RenameProvider::prepare_rename(INSTRUCTIONS, Position::new(5, 16)),
+        None
+    );
+}
+
+#[test]
+fn test_rename_accounts_struct_across_files() {
+    let root = workspace("across");
+    let files = workspace_files(&root);
+    let lib_path = root.join("programs/vault/src/lib.rs");
+    let instructions_path = root.join("programs/vault/src/instructions.rs");
+
+    let changes = RenameProvider::new()
+        .rename(&lib_path, LIB, Position::new(6, 38), "Init", &files)
+        .expect("Context<Initialize> should be renameable");
+
+    assert_eq!(changes.len(), 2);
+
+    let lib_edits = &changes[&lib_path];
+    assert_eq!(lib_edits.len(), 1);
+    assert_eq!(lib_edits[0].range, range(6, 35, 45));
+    assert_eq!(lib_edits[0].new_text, "Init");
+
+    // Definition, impl, and the composite field of `Deposit`
+    let mut instruction_ranges: Vec<Range> = changes[&instructions_path]
+        .iter()
+        .map(|edit| edit.range)
+        .collect();
+    instruction_ranges.sort_by_key(|range| range.start);
+    assert_eq!(
+        instruction_ranges,
+        vec![range(3, 11, 21), range(8, 12, 22), range(16, 14, 24)]
+    );
+    assert!(
+        changes[&instructions_path]
+            .iter()
+            .all(|edit| edit.new_text == "Init")
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_rename_rejects_invalid_name() {
+    let root = workspace("invalid");
+    let files = workspace_files(&root);
+    let lib_path = root.join("programs/vault/src/lib.rs");
+
+    let mut provider = RenameProvider::new();
+    for new_name in ["", "Init Accounts", "fn", "9Init"] {
+        assert!(
+            provider
+                .rename(&lib_path, LIB, Position::new(6, 38), new_name, &files)
+                .is_none(),
+            "'{}' should be rejected",
+            new_name
+        );
+    }
+
+    let _ = fs::remove_dir_all(&root);
+}
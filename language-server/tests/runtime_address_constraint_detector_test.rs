@@ -0,0 +1,68 @@
+use language_server::core::detectors::{
+    detector::Detector, runtime_address_constraint_detector::RuntimeAddressConstraintDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = RuntimeAddressConstraintDetector::default();
+
+    assert_eq!(detector.id(), "RUNTIME_ADDRESS_CONSTRAINT");
+    assert_eq!(detector.name(), "Runtime Address Constraint");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_address_from_account_field() {
+    let mut detector = RuntimeAddressConstraintDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Invoke<'info> {
+            /// CHECK: any account
+            pub some_account: UncheckedAccount<'info>,
+            /// CHECK: checked by address
+            #[account(address = some_account.owner)]
+            pub program: AccountInfo<'info>,
+            /// CHECK: checked by address
+            #[account(address = some_account.key() @ ErrorCode::WrongAccount)]
+            pub other: AccountInfo<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 8);
+    assert!(diagnostics[0].message.contains("'some_account'"));
+}
+
+#[test]
+fn test_constant_addresses_not_flagged() {
+    let mut detector = RuntimeAddressConstraintDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Invoke<'info> {
+            pub config: Account<'info, Config>,
+            /// CHECK: checked by address
+            #[account(address = crate::ID)]
+            pub program: AccountInfo<'info>,
+            /// CHECK: checked by address
+            #[account(address = pubkey!("11111111111111111111111111111111"))]
+            pub system: AccountInfo<'info>,
+            /// CHECK: checked by address
+            #[account(address = TREASURY @ ErrorCode::WrongTreasury)]
+            pub treasury: AccountInfo<'info>,
+            /// CHECK: checked by address
+            #[account(address = config.admin)]
+            pub admin: AccountInfo<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
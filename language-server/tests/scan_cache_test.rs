@@ -0,0 +1,165 @@
+use language_server::core::detectors::manual_lamports_zeroing::ManualLamportsZeroingDetector;
+use language_server::core::detectors::unknown_context_detector::UnknownContextDetector;
+use language_server::core::file_scanner::{FileScanner, ScanCache};
+use language_server::core::registry::{DetectorRegistryBuilder, REGISTRY_VERSION};
+use language_server::core::utilities::WorkspaceFiles;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::{Diagnostic, Position, Range};
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+pub fn close(ctx: Context<Close>) -> Result<()> {
+    **ctx.accounts.vault.to_account_info().lamports.borrow_mut() = 0;
+    Ok(())
+}
+"#;
+
+/// Create a workspace with a single program file
+fn workspace(name: &str) -> (PathBuf, PathBuf) {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-scan-cache-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+
+    let file = root.join("src/lib.rs");
+    fs::write(&file, PROGRAM).unwrap();
+    (root, file)
+}
+
+/// A cache entry for the program file with a marker diagnostic
+fn stale_cache(root: &Path, file: PathBuf, registry_version: u32) -> ScanCache {
+    let mut cache = ScanCache {
+        registry_version,
+        ..Default::default()
+    };
+    cache.insert(
+        file,
+        PROGRAM,
+        WorkspaceFiles::load(root).content_hash(),
+        vec![Diagnostic {
+            range: Range::new(Position::new(0, 0), Position::new(0, 1)),
+            message: "stale cached diagnostic".to_string(),
+            ..Default::default()
+        }],
+        true,
+    );
+    cache
+}
+
+fn scanner_for(root: &Path) -> FileScanner {
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.to_path_buf());
+    scanner
+}
+
+#[tokio::test]
+async fn test_old_registry_version_cache_is_discarded() {
+    let (root, file) = workspace("old-version");
+    let cache_path = root.join("scan-cache.json");
+    stale_cache(&root, file, REGISTRY_VERSION - 1)
+        .save(&cache_path)
+        .unwrap();
+
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .build();
+    let result = scanner_for(&root)
+        .scan_workspace_cached(&mut registry, &cache_path)
+        .await;
+
+    // A fresh scan ran instead of returning the cached diagnostic
+    assert_eq!(result.rust_files.len(), 1);
+    let diagnostics = &result.rust_files[0].diagnostics;
+    assert!(!diagnostics.is_empty());
+    assert!(
+        diagnostics
+            .iter()
+            .all(|d| d.message != "stale cached diagnostic")
+    );
+
+    // The cache was rewritten with the current version
+    let reloaded = ScanCache::load(&cache_path).expect("cache rewritten");
+    assert_eq!(reloaded.registry_version, REGISTRY_VERSION);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn test_current_version_cache_is_reused_for_unchanged_files() {
+    let (root, file) = workspace("current-version");
+    let cache_path = root.join("scan-cache.json");
+    stale_cache(&root, file.clone(), REGISTRY_VERSION)
+        .save(&cache_path)
+        .unwrap();
+
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .build();
+    let result = scanner_for(&root)
+        .scan_workspace_cached(&mut registry, &cache_path)
+        .await;
+    assert_eq!(
+        result.rust_files[0].diagnostics[0].message,
+        "stale cached diagnostic"
+    );
+
+    // Changing the file invalidates its entry
+    fs::write(&file, format!("{}\n// edited", PROGRAM)).unwrap();
+    let result = scanner_for(&root)
+        .scan_workspace_cached(&mut registry, &cache_path)
+        .await;
+    assert!(
+        result.rust_files[0]
+            .diagnostics
+            .iter()
+            .all(|d| d.message != "stale cached diagnostic")
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn test_change_in_another_file_invalidates_cached_results() {
+    let (root, file) = workspace("other-file");
+    let cache_path = root.join("scan-cache.json");
+    fs::write(root.join("Cargo.toml"), "[package]\nname = \"vault\"\n").unwrap();
+    fs::write(
+        &file,
+        "mod instructions;\n\npub fn initialize(ctx: Context<Initialize>) {}\n",
+    )
+    .unwrap();
+    let instructions = root.join("src/instructions.rs");
+    fs::write(
+        &instructions,
+        "#[derive(Accounts)]\npub struct Initialize<'info> {\n    pub payer: Signer<'info>,\n}\n",
+    )
+    .unwrap();
+
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(UnknownContextDetector::default())
+        .build();
+    let result = scanner_for(&root)
+        .scan_workspace_cached(&mut registry, &cache_path)
+        .await;
+    assert!(result.files_with_issues().is_empty());
+
+    // Removing the accounts struct only changes `instructions.rs`, yet `lib.rs` is re-analyzed
+    fs::write(&instructions, "").unwrap();
+    let result = scanner_for(&root)
+        .scan_workspace_cached(&mut registry, &cache_path)
+        .await;
+    let lib = result
+        .rust_files
+        .iter()
+        .find(|info| info.path == file)
+        .unwrap();
+    assert_eq!(lib.diagnostics.len(), 1);
+    assert!(lib.diagnostics[0].message.contains("'Initialize'"));
+
+    fs::remove_dir_all(&root).unwrap();
+}
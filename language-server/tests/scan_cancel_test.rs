@@ -0,0 +1,138 @@
+use language_server::core::detectors::detector::Detector;
+use language_server::core::file_scanner::FileScanner;
+use language_server::core::registry::DetectorRegistry;
+use language_server::core::utilities::LineIndex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+const FILE_COUNT: usize = 6;
+
+/// Counts analyzed files and cancels the scan once `cancel_after` files were analyzed
+struct CancellingDetector {
+    analyzed: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    cancel_after: usize,
+}
+
+impl Detector for CancellingDetector {
+    fn id(&self) -> &'static str {
+        "CANCELLING"
+    }
+
+    fn name(&self) -> &'static str {
+        "Cancelling"
+    }
+
+    fn description(&self) -> &'static str {
+        "Cancels the scan midway"
+    }
+
+    fn message(&self) -> &'static str {
+        "Cancelled"
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::HINT
+    }
+
+    fn analyze_ast(
+        &mut self,
+        _syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        _lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        if self.analyzed.fetch_add(1, Ordering::SeqCst) + 1 == self.cancel_after {
+            self.cancelled.store(true, Ordering::SeqCst);
+        }
+        Vec::new()
+    }
+}
+
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-scan-cancel-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+
+    for index in 0..FILE_COUNT {
+        let path = root.join(format!("programs/vault/src/module_{}.rs", index));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "pub fn handler() {}\n").unwrap();
+    }
+    root
+}
+
+fn scanner(root: PathBuf, cancelled: &Arc<AtomicBool>) -> FileScanner {
+    let mut scanner = FileScanner::new(root);
+    scanner.set_cancel_flag(Arc::clone(cancelled));
+    scanner
+}
+
+#[tokio::test]
+async fn test_scan_stops_when_cancelled_midway() {
+    let root = workspace("midway");
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let analyzed = Arc::new(AtomicUsize::new(0));
+
+    let mut registry = DetectorRegistry::new();
+    registry.register(CancellingDetector {
+        analyzed: Arc::clone(&analyzed),
+        cancelled: Arc::clone(&cancelled),
+        cancel_after: 2,
+    });
+
+    let result = scanner(root.clone(), &cancelled)
+        .scan_workspace(&mut registry)
+        .await;
+
+    assert!(result.cancelled);
+    assert_eq!(result.rust_files.len(), 2);
+    assert_eq!(analyzed.load(Ordering::SeqCst), 2);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_scan_completes_without_cancellation() {
+    let root = workspace("complete");
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let analyzed = Arc::new(AtomicUsize::new(0));
+
+    let mut registry = DetectorRegistry::new();
+    registry.register(CancellingDetector {
+        analyzed: Arc::clone(&analyzed),
+        cancelled: Arc::new(AtomicBool::new(false)),
+        cancel_after: 2,
+    });
+
+    let result = scanner(root.clone(), &cancelled)
+        .scan_workspace(&mut registry)
+        .await;
+
+    assert!(!result.cancelled);
+    assert_eq!(result.rust_files.len(), FILE_COUNT);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_cancelled_scanner_returns_empty_result() {
+    let root = workspace("before");
+    let cancelled = Arc::new(AtomicBool::new(true));
+    let mut registry = DetectorRegistry::new();
+
+    let result = scanner(root.clone(), &cancelled)
+        .scan_workspace(&mut registry)
+        .await;
+
+    assert!(result.cancelled);
+    assert!(result.rust_files.is_empty());
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,165 @@
+use language_server::core::detectors::manual_lamports_zeroing::ManualLamportsZeroingDetector;
+use language_server::core::file_scanner::{FileScanner, RevisionContentProvider};
+use language_server::core::registry::DetectorRegistryBuilder;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const VULNERABLE: &str = r#"
+use anchor_lang::prelude::*;
+
+pub fn close(ctx: Context<Close>) -> Result<()> {
+    **ctx.accounts.vault.to_account_info().lamports.borrow_mut() = 0;
+    Ok(())
+}
+"#;
+
+const FIXED: &str = r#"
+use anchor_lang::prelude::*;
+
+pub fn close(ctx: Context<Close>) -> Result<()> {
+    Ok(())
+}
+"#;
+
+/// Serves fixed file contents per revision
+struct MockProvider {
+    revisions: HashMap<String, HashMap<PathBuf, String>>,
+}
+
+impl MockProvider {
+    fn new(revision: &str, files: &[(&str, &str)]) -> Self {
+        let files = files
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect();
+        Self {
+            revisions: HashMap::from([(revision.to_string(), files)]),
+        }
+    }
+}
+
+impl RevisionContentProvider for MockProvider {
+    fn list_files(&self, revision: &str) -> anyhow::Result<Vec<PathBuf>> {
+        let files = self
+            .revisions
+            .get(revision)
+            .ok_or_else(|| anyhow::anyhow!("unknown revision {}", revision))?;
+        let mut paths: Vec<PathBuf> = files.keys().cloned().collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn read_file(&self, revision: &str, path: &Path) -> anyhow::Result<String> {
+        self.revisions
+            .get(revision)
+            .and_then(|files| files.get(path))
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("{:?} not found at {}", path, revision))
+    }
+}
+
+/// Create a workspace whose working tree has the fixed program
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-scan-revision-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), FIXED).unwrap();
+    root
+}
+
+fn scanner(root: &Path) -> FileScanner {
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.to_path_buf());
+    scanner
+}
+
+#[test]
+fn test_scan_uses_revision_content() {
+    let root = workspace("content");
+    let provider = MockProvider::new("abc123", &[("src/lib.rs", VULNERABLE)]);
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .build();
+
+    let result = scanner(&root)
+        .scan_revision(&provider, "abc123", &mut registry)
+        .unwrap();
+
+    assert_eq!(result.rust_files.len(), 1);
+    assert_eq!(result.rust_files[0].path, root.join("src/lib.rs"));
+    assert_eq!(result.total_issues(), 1);
+
+    // The working tree is untouched
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), FIXED);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_scan_revision_applies_workspace_filters() {
+    let root = workspace("filters");
+    let provider = MockProvider::new(
+        "abc123",
+        &[
+            ("src/lib.rs", VULNERABLE),
+            ("target/debug/build/out.rs", VULNERABLE),
+            ("programs/vault/tests/close.rs", VULNERABLE),
+            ("README.md", VULNERABLE),
+        ],
+    );
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .build();
+
+    let result = scanner(&root)
+        .scan_revision(&provider, "abc123", &mut registry)
+        .unwrap();
+
+    let paths: Vec<_> = result.rust_files.iter().map(|f| f.path.clone()).collect();
+    assert_eq!(paths, vec![root.join("src/lib.rs")]);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_scan_unknown_revision_fails() {
+    let root = workspace("unknown");
+    let provider = MockProvider::new("abc123", &[("src/lib.rs", VULNERABLE)]);
+    let mut registry = DetectorRegistryBuilder::new().build();
+
+    assert!(
+        scanner(&root)
+            .scan_revision(&provider, "missing", &mut registry)
+            .is_err()
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_revision_read_without_registry_then_analyzed() {
+    let root = workspace("split");
+    let provider = MockProvider::new("abc123", &[("src/lib.rs", VULNERABLE)]);
+    let scanner = scanner(&root);
+
+    // Reading needs no registry, so it can run on another thread while the registry is in use
+    let reader = scanner.clone();
+    let files = std::thread::spawn(move || reader.read_revision(&provider, "abc123"))
+        .join()
+        .unwrap()
+        .unwrap();
+    assert_eq!(files.content(&root.join("src/lib.rs")), Some(VULNERABLE));
+
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(ManualLamportsZeroingDetector::default())
+        .build();
+    let result = scanner.scan_revision_files(files, "abc123", &mut registry);
+    assert_eq!(result.total_issues(), 1);
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,97 @@
+use language_server::core::detectors::lossy_require_comparison_detector::LossyRequireComparisonDetector;
+use language_server::core::file_scanner::FileScanner;
+use language_server::core::registry::DetectorRegistryBuilder;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEST_MODULE: &str = r#"
+pub fn handler(values: &[u64]) -> u64 {
+    values.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_handler() {
+        let total = super::handler(&[1, 2]);
+        assert_eq!(total as u8, 3);
+    }
+}
+"#;
+
+/// Create a workspace with a program source and an integration test
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-scan-tests-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+
+    for file in ["programs/vault/src/lib.rs", "programs/vault/tests/vault.rs"] {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "pub fn handler() {}\n").unwrap();
+    }
+    root
+}
+
+async fn scanned_files(scanner: &FileScanner, root: &Path) -> Vec<PathBuf> {
+    let mut registry = DetectorRegistryBuilder::new().build();
+    let mut files: Vec<PathBuf> = scanner
+        .scan_workspace(&mut registry)
+        .await
+        .rust_files
+        .into_iter()
+        .map(|file| file.path.strip_prefix(root).unwrap().to_path_buf())
+        .collect();
+    files.sort();
+    files
+}
+
+#[tokio::test]
+async fn test_test_files_skipped_by_default() {
+    let root = workspace("default");
+    let scanner = FileScanner::new(root.clone());
+
+    assert_eq!(
+        scanned_files(&scanner, &root).await,
+        vec![PathBuf::from("programs/vault/src/lib.rs")]
+    );
+    assert!(scanner.is_excluded(&root.join("programs/vault/tests/vault.rs")));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_scan_tests_includes_test_files() {
+    let root = workspace("enabled");
+    let mut scanner = FileScanner::new(root.clone());
+    scanner.set_scan_tests(true);
+
+    assert_eq!(
+        scanned_files(&scanner, &root).await,
+        vec![
+            PathBuf::from("programs/vault/src/lib.rs"),
+            PathBuf::from("programs/vault/tests/vault.rs"),
+        ]
+    );
+    assert!(!scanner.is_excluded(&root.join("programs/vault/tests/vault.rs")));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_detector_opts_into_test_code() {
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(LossyRequireComparisonDetector::default())
+        .build();
+
+    // Test modules are skipped by default
+    assert!(registry.analyze(TEST_MODULE, None).is_empty());
+
+    registry.set_include_tests("LOSSY_REQUIRE_COMPARISON", true);
+    let diagnostics = registry.analyze(TEST_MODULE, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 10);
+}
@@ -0,0 +1,104 @@
+use language_server::core::file_scanner::{FileScanner, ignore::IgnoreMatcher};
+use language_server::core::registry::DetectorRegistryBuilder;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create a workspace with program sources, generated code and a vendored example program
+fn workspace(name: &str, gitignore: Option<&str>) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-scanner-ignore-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+
+    for file in [
+        "programs/vault/src/lib.rs",
+        "programs/vault/src/generated/idl.rs",
+        "examples/tictactoe/src/lib.rs",
+    ] {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "pub fn handler() {}\n").unwrap();
+    }
+
+    if let Some(gitignore) = gitignore {
+        fs::write(root.join(".gitignore"), gitignore).unwrap();
+    }
+    root
+}
+
+async fn scanned_files(scanner: &FileScanner, root: &Path) -> Vec<PathBuf> {
+    let mut registry = DetectorRegistryBuilder::new().build();
+    let mut files: Vec<PathBuf> = scanner
+        .scan_workspace(&mut registry)
+        .await
+        .rust_files
+        .into_iter()
+        .map(|file| file.path.strip_prefix(root).unwrap().to_path_buf())
+        .collect();
+    files.sort();
+    files
+}
+
+#[tokio::test]
+async fn test_gitignored_directory_is_excluded() {
+    let root = workspace("gitignore", Some("# vendored examples\n/examples/\n"));
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.clone());
+
+    assert_eq!(
+        scanned_files(&scanner, &root).await,
+        vec![
+            PathBuf::from("programs/vault/src/generated/idl.rs"),
+            PathBuf::from("programs/vault/src/lib.rs"),
+        ]
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_ignore_globs_from_settings() {
+    let root = workspace("globs", None);
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.clone());
+    scanner.set_ignore_globs(vec!["generated/".to_string(), "examples/**".to_string()]);
+
+    assert_eq!(
+        scanned_files(&scanner, &root).await,
+        vec![PathBuf::from("programs/vault/src/lib.rs")]
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[tokio::test]
+async fn test_without_ignores_everything_is_scanned() {
+    let root = workspace("none", None);
+    let mut scanner = FileScanner::default();
+    scanner.set_workspace_root(root.clone());
+
+    assert_eq!(scanned_files(&scanner, &root).await.len(), 3);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_ignore_matcher_patterns() {
+    let matcher = IgnoreMatcher::from_lines([
+        "*.generated.rs",
+        "/build-out",
+        "docs/**/examples/",
+        "!keep.generated.rs",
+    ]);
+
+    assert!(matcher.is_ignored(Path::new("src/idl.generated.rs"), false));
+    assert!(!matcher.is_ignored(Path::new("src/keep.generated.rs"), false));
+    assert!(matcher.is_ignored(Path::new("build-out"), true));
+    assert!(!matcher.is_ignored(Path::new("nested/build-out"), true));
+    assert!(matcher.is_ignored(Path::new("docs/examples"), true));
+    assert!(matcher.is_ignored(Path::new("docs/a/b/examples"), true));
+    assert!(!matcher.is_ignored(Path::new("docs/a/b/examples"), false));
+    assert!(!matcher.is_ignored(Path::new("src/lib.rs"), false));
+}
@@ -0,0 +1,80 @@
+use language_server::core::language_features::SelectionRangeProvider;
+use tower_lsp::lsp_types::{Position, Range, SelectionRange};
+
+const LIB: &str = r#"#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.vault.amount -= amount;
+        Ok(())
+    }
+}
+"#;
+
+fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+    Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+}
+
+/// Ranges of a selection from the innermost out
+fn expansion(selection: &SelectionRange) -> Vec<Range> {
+    std::iter::successors(Some(selection), |selection| selection.parent.as_deref())
+        .map(|selection| selection.range)
+        .collect()
+}
+
+#[test]
+fn test_expands_from_account_field_access_to_module() {
+    // Inside `vault` of `ctx.accounts.vault.amount`
+    let selections =
+        SelectionRangeProvider::selection_ranges(LIB, &[Position::new(5, 23)]).unwrap();
+    assert_eq!(selections.len(), 1);
+
+    assert_eq!(
+        expansion(&selections[0]),
+        vec![
+            // `vault`
+            range((5, 21), (5, 26)),
+            // `ctx.accounts.vault`
+            range((5, 8), (5, 26)),
+            // `ctx.accounts.vault.amount`
+            range((5, 8), (5, 33)),
+            // `ctx.accounts.vault.amount -= amount`
+            range((5, 8), (5, 43)),
+            // the statement, with its semicolon
+            range((5, 8), (5, 44)),
+            // the function body
+            range((4, 71), (7, 5)),
+            // the function
+            range((4, 4), (7, 5)),
+            // the program module, with its attribute
+            range((0, 0), (8, 1)),
+        ]
+    );
+}
+
+#[test]
+fn test_one_result_per_position() {
+    let selections =
+        SelectionRangeProvider::selection_ranges(LIB, &[Position::new(4, 15), Position::new(9, 0)])
+            .unwrap();
+    assert_eq!(selections.len(), 2);
+
+    // `withdraw`, then the function and the module
+    assert_eq!(
+        expansion(&selections[0]),
+        vec![
+            range((4, 11), (4, 19)),
+            range((4, 4), (7, 5)),
+            range((0, 0), (8, 1)),
+        ]
+    );
+
+    // Past the last item there is only the position itself
+    assert_eq!(expansion(&selections[1]), vec![range((9, 0), (9, 0))]);
+}
+
+#[test]
+fn test_unparseable_document() {
+    assert!(SelectionRangeProvider::selection_ranges("fn {", &[Position::new(0, 1)]).is_none());
+}
@@ -0,0 +1,138 @@
+use language_server::core::language_features::semantic_tokens::{
+    SemanticTokenizer, TOKEN_MODIFIER_DECLARATION, TOKEN_TYPE_ACCOUNT_WRAPPER,
+    TOKEN_TYPE_CONSTRAINT, TOKEN_TYPE_HANDLER,
+};
+use tower_lsp::lsp_types::{SemanticToken, SemanticTokenType};
+
+/// Decode delta-encoded tokens into (line, start, length, token_type, modifiers)
+fn decode(tokens: &[SemanticToken]) -> Vec<(u32, u32, u32, u32, u32)> {
+    let mut line = 0;
+    let mut start = 0;
+    tokens
+        .iter()
+        .map(|token| {
+            if token.delta_line == 0 {
+                start += token.delta_start;
+            } else {
+                line += token.delta_line;
+                start = token.delta_start;
+            }
+            (
+                line,
+                start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            )
+        })
+        .collect()
+}
+
+/// Text covered by a decoded token
+fn token_text(content: &str, line: u32, start: u32, length: u32) -> String {
+    content
+        .lines()
+        .nth(line as usize)
+        .map(|l| {
+            l.chars()
+                .skip(start as usize)
+                .take(length as usize)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+const PROGRAM: &str = r#"use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = authority, space = 8 + Vault::INIT_SPACE)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#;
+
+#[test]
+fn test_legend_matches_token_indices() {
+    let legend = SemanticTokenizer::legend();
+
+    assert_eq!(
+        legend.token_types[TOKEN_TYPE_CONSTRAINT as usize],
+        SemanticTokenType::KEYWORD
+    );
+    assert_eq!(
+        legend.token_types[TOKEN_TYPE_ACCOUNT_WRAPPER as usize],
+        SemanticTokenType::TYPE
+    );
+    assert_eq!(
+        legend.token_types[TOKEN_TYPE_HANDLER as usize],
+        SemanticTokenType::FUNCTION
+    );
+}
+
+#[test]
+fn test_tokenizes_signer_field_and_init_constraint() {
+    let tokens = decode(&SemanticTokenizer::tokenize(PROGRAM));
+
+    let texts: Vec<(String, u32)> = tokens
+        .iter()
+        .map(|(line, start, length, token_type, _)| {
+            (token_text(PROGRAM, *line, *start, *length), *token_type)
+        })
+        .collect();
+
+    assert!(texts.contains(&("Signer".to_string(), TOKEN_TYPE_ACCOUNT_WRAPPER)));
+    assert!(texts.contains(&("init".to_string(), TOKEN_TYPE_CONSTRAINT)));
+    assert!(texts.contains(&("mut".to_string(), TOKEN_TYPE_CONSTRAINT)));
+    assert!(texts.contains(&("Account".to_string(), TOKEN_TYPE_ACCOUNT_WRAPPER)));
+    assert!(texts.contains(&("Program".to_string(), TOKEN_TYPE_ACCOUNT_WRAPPER)));
+}
+
+#[test]
+fn test_constraint_values_are_not_tokenized() {
+    let tokens = decode(&SemanticTokenizer::tokenize(PROGRAM));
+
+    // `payer` is a keyword but `authority` on the right-hand side is a value
+    let constraint_texts: Vec<String> = tokens
+        .iter()
+        .filter(|(_, _, _, token_type, _)| *token_type == TOKEN_TYPE_CONSTRAINT)
+        .map(|(line, start, length, _, _)| token_text(PROGRAM, *line, *start, *length))
+        .collect();
+
+    assert!(constraint_texts.contains(&"payer".to_string()));
+    assert!(constraint_texts.contains(&"space".to_string()));
+    assert!(!constraint_texts.contains(&"authority".to_string()));
+}
+
+#[test]
+fn test_tokenizes_instruction_handler_names() {
+    let tokens = decode(&SemanticTokenizer::tokenize(PROGRAM));
+
+    let handler = tokens
+        .iter()
+        .find(|(_, _, _, token_type, _)| *token_type == TOKEN_TYPE_HANDLER)
+        .expect("handler token");
+
+    assert_eq!(
+        token_text(PROGRAM, handler.0, handler.1, handler.2),
+        "initialize"
+    );
+    assert_eq!(handler.4, TOKEN_MODIFIER_DECLARATION);
+}
+
+#[test]
+fn test_invalid_syntax_produces_no_tokens() {
+    let tokens = SemanticTokenizer::tokenize("pub struct Broken {");
+    assert!(tokens.is_empty());
+}
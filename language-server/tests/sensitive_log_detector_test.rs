@@ -0,0 +1,53 @@
+use language_server::core::detectors::{
+    detector::Detector, sensitive_log_detector::SensitiveLogDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = SensitiveLogDetector::default();
+
+    assert_eq!(detector.id(), "SENSITIVE_LOG");
+    assert_eq!(detector.name(), "Sensitive Log");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_logged_seed_material() {
+    let mut detector = SensitiveLogDetector::default();
+
+    let code = r#"
+        pub fn initialize(ctx: Context<Initialize>, bump_seed: u8) -> Result<()> {
+            msg!("{}", bump_seed);
+            msg!("secret: {secret_key:?}");
+            sol_log_64(0, 0, 0, 0, ctx.accounts.vault.bump as u64);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert!(diagnostics[0].message.contains("'bump_seed'"));
+    assert!(diagnostics[1].message.contains("'secret_key'"));
+    assert!(diagnostics[2].message.contains("sol_log_64"));
+}
+
+#[test]
+fn test_plain_logs_not_flagged() {
+    let mut detector = SensitiveLogDetector::default();
+
+    let code = r#"
+        pub fn initialize(ctx: Context<Initialize>, amount: u64) -> Result<()> {
+            msg!("done");
+            msg!("bump derived, seeds ok");
+            msg!("amount {} for {}", amount, ctx.accounts.vault.key());
+            msg!("{{bump}}");
+            sol_log("initialized");
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
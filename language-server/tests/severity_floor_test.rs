@@ -0,0 +1,79 @@
+use language_server::cli::{Mode, run_scan, scan_directory, scan_exit_code};
+use language_server::core::DetectorRegistry;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+const PROGRAM: &str = r#"
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+"#;
+
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-severity-floor-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/lib.rs"), PROGRAM).unwrap();
+    root
+}
+
+#[test]
+fn test_severity_floor_from_args() {
+    let args = |args: &[&str]| Mode::from_args(args.iter().map(|arg| arg.to_string()));
+
+    assert_eq!(
+        args(&["--scan", ".", "--severity-floor", "error"]),
+        Ok(Mode::Scan {
+            root: PathBuf::from("."),
+            severity_floor: Some(DiagnosticSeverity::ERROR),
+        })
+    );
+    assert_eq!(
+        args(&["--severity-floor", "warning", "--scan", "."]),
+        Ok(Mode::Scan {
+            root: PathBuf::from("."),
+            severity_floor: Some(DiagnosticSeverity::WARNING),
+        })
+    );
+    assert!(args(&["--scan", ".", "--severity-floor", "hint"]).is_err());
+    assert!(args(&["--watch", ".", "--severity-floor", "error"]).is_err());
+}
+
+#[tokio::test]
+async fn test_warnings_raised_to_error_floor() {
+    let root = workspace("raise");
+
+    let mut result = scan_directory(&root).await;
+    let diagnostics = || result.rust_files.iter().flat_map(|file| &file.diagnostics);
+    assert!(diagnostics().count() > 0);
+    assert!(
+        diagnostics().all(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::WARNING))
+    );
+    assert_eq!(scan_exit_code(&result), 0);
+
+    for file in &mut result.rust_files {
+        DetectorRegistry::apply_severity_floor(&mut file.diagnostics, DiagnosticSeverity::ERROR);
+    }
+    assert!(
+        result
+            .rust_files
+            .iter()
+            .flat_map(|file| &file.diagnostics)
+            .all(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR))
+    );
+    assert_eq!(scan_exit_code(&result), 1);
+
+    assert_eq!(run_scan(&root, None).await, 0);
+    assert_eq!(run_scan(&root, Some(DiagnosticSeverity::ERROR)).await, 1);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
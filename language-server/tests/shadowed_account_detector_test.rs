@@ -0,0 +1,80 @@
+use language_server::core::detectors::{
+    detector::Detector, shadowed_account_detector::ShadowedAccountDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+const ACCOUNTS: &str = r#"
+    #[derive(Accounts)]
+    pub struct Transfer<'info> {
+        #[account(mut)]
+        pub vault: Account<'info, Vault>,
+        #[account(mut)]
+        pub backup_vault: Account<'info, Vault>,
+        pub authority: Signer<'info>,
+    }
+"#;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = ShadowedAccountDetector::default();
+
+    assert_eq!(detector.id(), "SHADOWED_ACCOUNT");
+    assert_eq!(detector.name(), "Shadowed Account");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_local_shadowing_account() {
+    let mut detector = ShadowedAccountDetector::default();
+
+    let code = format!(
+        r#"
+        use anchor_lang::prelude::*;
+
+        pub fn transfer(ctx: Context<Transfer>, amount: u64) -> Result<()> {{
+            let vault = &mut ctx.accounts.backup_vault;
+            vault.amount -= amount;
+            Ok(())
+        }}
+        {}
+    "#,
+        ACCOUNTS
+    );
+
+    let diagnostics = detector.analyze(&code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 4);
+    assert_eq!(diagnostics[0].range.start.character, 16);
+    assert!(diagnostics[0].message.contains("Local 'vault'"));
+}
+
+#[test]
+fn test_non_colliding_local_not_flagged() {
+    let mut detector = ShadowedAccountDetector::default();
+
+    let code = format!(
+        r#"
+        use anchor_lang::prelude::*;
+
+        pub fn transfer(ctx: Context<Transfer>, amount: u64) -> Result<()> {{
+            let backup = &mut ctx.accounts.backup_vault;
+            backup.amount += amount;
+            let vault = &mut ctx.accounts.vault;
+            vault.amount -= amount;
+            let authority: Pubkey = ctx.accounts.authority.key();
+            msg!("{{}}", authority);
+            Ok(())
+        }}
+
+        pub fn helper(amount: u64) -> u64 {{
+            let vault = amount * 2;
+            vault
+        }}
+        {}
+    "#,
+        ACCOUNTS
+    );
+
+    let diagnostics = detector.analyze(&code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,26 @@
+use language_server::core::file_scanner::ScanCache;
+use language_server::core::utilities::StableHasher;
+
+#[test]
+fn test_hash_values_are_fixed() {
+    // Persisted hashes must not change, so pin the FNV-1a output
+    assert_eq!(StableHasher::new().finish(), 0xcbf2_9ce4_8422_2325);
+
+    let mut hasher = StableHasher::new();
+    hasher.update(b"abc");
+    assert_eq!(hasher.finish(), 0xc11a_b6d2_519b_c2b2);
+    assert_eq!(ScanCache::hash_content("abc"), 0xc11a_b6d2_519b_c2b2);
+}
+
+#[test]
+fn test_chunks_are_length_prefixed() {
+    let mut split = StableHasher::new();
+    split.update(b"ab");
+    split.update(b"c");
+
+    let mut whole = StableHasher::new();
+    whole.update(b"abc");
+
+    assert_eq!(split.finish(), 0x7e60_470b_f599_cad6);
+    assert_ne!(split.finish(), whole.finish());
+}
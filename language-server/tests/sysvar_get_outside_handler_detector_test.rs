@@ -0,0 +1,96 @@
+use language_server::core::detectors::{
+    detector::Detector, detector_config::DetectorConfig,
+    sysvar_get_outside_handler_detector::SysvarGetOutsideHandlerDetector,
+};
+use language_server::core::registry::DetectorRegistryBuilder;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+const TEST_ONLY_USAGE: &str = r#"
+    pub fn helper() -> u64 {
+        42
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_expiry() {
+            let now = Clock::get().unwrap().unix_timestamp;
+            assert!(now > 0);
+        }
+    }
+"#;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = SysvarGetOutsideHandlerDetector::default();
+
+    assert_eq!(detector.id(), "SYSVAR_GET_CONTEXT");
+    assert_eq!(detector.name(), "Sysvar Get Outside Handler");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::HINT);
+}
+
+#[test]
+fn test_handler_usage_not_flagged() {
+    let mut detector = SysvarGetOutsideHandlerDetector::default();
+
+    let code = r#"
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn deposit(ctx: Context<Deposit>) -> Result<()> {
+                let clock = Clock::get()?;
+                let rent = Rent::get()?;
+                ctx.accounts.vault.last_deposit = clock.unix_timestamp;
+                ctx.accounts.vault.min_balance = rent.minimum_balance(100);
+                Ok(())
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_test_only_usage_flagged() {
+    let mut detector = SysvarGetOutsideHandlerDetector::default();
+
+    let diagnostics = detector.analyze(TEST_ONLY_USAGE, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::HINT));
+    assert!(diagnostics[0].message.contains("Clock::get()"));
+}
+
+#[test]
+fn test_build_script_usage_flagged() {
+    let mut detector = SysvarGetOutsideHandlerDetector::default();
+
+    let code = r#"
+        fn main() {
+            let rent = Rent::get().unwrap();
+            println!("{}", rent.minimum_balance(0));
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, Some(&PathBuf::from("/project/build.rs")));
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("Rent::get()"));
+}
+
+#[test]
+fn test_hint_only_when_enabled() {
+    let mut disabled = DetectorRegistryBuilder::new()
+        .with_detector(SysvarGetOutsideHandlerDetector::default())
+        .with_config("SYSVAR_GET_CONTEXT", DetectorConfig::disabled())
+        .build();
+    assert_eq!(disabled.analyze(TEST_ONLY_USAGE, None).len(), 0);
+
+    let mut enabled = DetectorRegistryBuilder::new()
+        .with_detector(SysvarGetOutsideHandlerDetector::default())
+        .build();
+    assert_eq!(enabled.analyze(TEST_ONLY_USAGE, None).len(), 1);
+}
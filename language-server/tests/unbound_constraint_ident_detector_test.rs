@@ -0,0 +1,66 @@
+use language_server::core::detectors::{
+    detector::Detector, unbound_constraint_ident_detector::UnboundConstraintIdentDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnboundConstraintIdentDetector::default();
+
+    assert_eq!(detector.id(), "UNBOUND_CONSTRAINT_IDENT");
+    assert_eq!(detector.name(), "Unbound Constraint Identifier");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_unbound_identifiers() {
+    let mut detector = UnboundConstraintIdentDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        pub struct Update<'info> {
+            #[account(mut, constraint = vault.limit > foo @ VaultError::OverLimit)]
+            pub vault: Account<'info, Vault>,
+            #[account(constraint = config.owner == program_id)]
+            pub config: Account<'info, Config>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 5);
+    assert_eq!(diagnostics[0].range.start.character, 54);
+    assert!(diagnostics[0].message.contains("'foo' is not bound"));
+    assert_eq!(diagnostics[1].range.start.line, 7);
+    assert!(diagnostics[1].message.contains("use `crate::ID`"));
+}
+
+#[test]
+fn test_bound_identifiers_not_flagged() {
+    let mut detector = UnboundConstraintIdentDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        #[instruction(limit: u64)]
+        pub struct Update<'info> {
+            #[account(
+                mut,
+                has_one = authority,
+                constraint = vault.limit >= limit && vault.limit <= MAX_LIMIT,
+                constraint = vault.members.iter().any(|member| *member == authority.key()),
+                constraint = config.owner == crate::ID @ VaultError::WrongOwner,
+                constraint = is_active(&vault),
+            )]
+            pub vault: Account<'info, Vault>,
+            pub config: Account<'info, Config>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
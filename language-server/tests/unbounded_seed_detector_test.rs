@@ -0,0 +1,129 @@
+use language_server::core::detectors::{
+    detector::Detector, unbounded_seed_detector::UnboundedSeedDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnboundedSeedDetector::default();
+
+    assert_eq!(detector.id(), "UNBOUNDED_SEED");
+    assert_eq!(detector.name(), "Unbounded Seed");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_string_seed() {
+    let mut detector = UnboundedSeedDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        #[instruction(name: String)]
+        pub struct CreateProfile<'info> {
+            #[account(init, payer = user, space = 8 + 64, seeds = [b"profile", name.as_bytes()], bump)]
+            pub profile: Account<'info, Profile>,
+            #[account(mut)]
+            pub user: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+
+        #[program]
+        pub mod profiles {
+            use super::*;
+
+            pub fn create_profile(ctx: Context<CreateProfile>, name: String) -> Result<()> {
+                Ok(())
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("'name'"));
+    assert_eq!(diagnostics[0].range.start.line, 6);
+}
+
+#[test]
+fn test_pubkey_seed_not_flagged() {
+    let mut detector = UnboundedSeedDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod profiles {
+            use super::*;
+
+            pub fn create_profile(ctx: Context<CreateProfile>, owner: Pubkey) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        #[instruction(owner: Pubkey)]
+        pub struct CreateProfile<'info> {
+            #[account(init, payer = user, space = 8 + 64, seeds = [b"profile", owner.as_ref()], bump)]
+            pub profile: Account<'info, Profile>,
+            #[account(mut)]
+            pub user: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_length_constraint_not_flagged() {
+    let mut detector = UnboundedSeedDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[derive(Accounts)]
+        #[instruction(name: String)]
+        pub struct CreateProfile<'info> {
+            #[account(
+                init,
+                payer = user,
+                space = 8 + 64,
+                seeds = [b"profile", name.as_bytes()],
+                bump,
+                constraint = name.len() <= 32 @ ErrorCode::NameTooLong
+            )]
+            pub profile: Account<'info, Profile>,
+            #[account(mut)]
+            pub user: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_detects_byte_vector_seed() {
+    let mut detector = UnboundedSeedDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        pub fn register(ctx: Context<Register>, tag: Vec<u8>) -> Result<()> {
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        #[instruction(tag: Vec<u8>)]
+        pub struct Register<'info> {
+            #[account(seeds = [&tag], bump)]
+            pub entry: Account<'info, Entry>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+}
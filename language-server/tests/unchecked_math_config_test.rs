@@ -0,0 +1,33 @@
+use language_server::core::detectors::detector_config::DetectorConfig;
+use language_server::dylint_runner::DylintRunner;
+
+fn runner() -> DylintRunner {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-unchecked-math-config-{}",
+        std::process::id()
+    ));
+    DylintRunner::new(&root.join("extension")).unwrap()
+}
+
+#[test]
+fn test_trusted_receivers_become_dylint_toml() {
+    let runner = runner();
+    runner.set_unchecked_math_config(&DetectorConfig {
+        trusted_math_receivers: vec!["spl-math".to_string(), "Wad\"s".to_string()],
+        ..Default::default()
+    });
+
+    assert_eq!(
+        runner.dylint_toml().as_deref(),
+        Some("[unchecked_math]\ntrusted_math_receivers = [\"spl-math\", \"Wad\\\"s\"]\n")
+    );
+}
+
+#[test]
+fn test_no_trusted_receivers_no_dylint_toml() {
+    let runner = runner();
+    assert!(runner.dylint_toml().is_none());
+
+    runner.set_unchecked_math_config(&DetectorConfig::default());
+    assert!(runner.dylint_toml().is_none());
+}
@@ -0,0 +1,86 @@
+use language_server::core::detectors::{
+    detector::Detector, unchecked_remaining_accounts_detector::UncheckedRemainingAccountsDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UncheckedRemainingAccountsDetector::default();
+
+    assert_eq!(detector.id(), "UNCHECKED_REMAINING_ACCOUNTS");
+    assert_eq!(detector.name(), "Unchecked Remaining Accounts");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_unchecked_loop() {
+    let mut detector = UncheckedRemainingAccountsDetector::default();
+
+    let code = r#"
+        pub fn distribute(ctx: Context<Distribute>, amount: u64) -> Result<()> {
+            for acc in ctx.remaining_accounts {
+                **acc.try_borrow_mut_lamports()? += amount;
+            }
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+    assert_eq!(diagnostics[0].range.start.line, 2);
+}
+
+#[test]
+fn test_detects_unchecked_iter_loop() {
+    let mut detector = UncheckedRemainingAccountsDetector::default();
+
+    let code = r#"
+        pub fn process(ctx: Context<Process>) -> Result<()> {
+            for (i, acc) in ctx.remaining_accounts.iter().enumerate() {
+                msg!("account {}: {}", i, acc.key);
+            }
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_checked_loop_not_flagged() {
+    let mut detector = UncheckedRemainingAccountsDetector::default();
+
+    let code = r#"
+        pub fn distribute(ctx: Context<Distribute>, amount: u64) -> Result<()> {
+            for acc in ctx.remaining_accounts.iter() {
+                require_keys_eq!(*acc.owner, crate::ID);
+                **acc.try_borrow_mut_lamports()? += amount;
+            }
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_owner_comparison_counts_as_check() {
+    let mut detector = UncheckedRemainingAccountsDetector::default();
+
+    let code = r#"
+        pub fn distribute(ctx: Context<Distribute>) -> Result<()> {
+            for acc in ctx.remaining_accounts {
+                if acc.owner != &crate::ID {
+                    return Err(ErrorCode::InvalidOwner.into());
+                }
+            }
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
@@ -0,0 +1,85 @@
+use language_server::core::detectors::{
+    detector::Detector, unchecked_set_authority_detector::UncheckedSetAuthorityDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UncheckedSetAuthorityDetector::default();
+
+    assert_eq!(detector.id(), "UNCHECKED_SET_AUTHORITY");
+    assert_eq!(detector.name(), "Unchecked Set Authority");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::ERROR);
+}
+
+#[test]
+fn test_detects_unchecked_set_authority() {
+    let mut detector = UncheckedSetAuthorityDetector::default();
+
+    let code = r#"
+        pub fn rotate(ctx: Context<Rotate>, new_authority: Pubkey) -> Result<()> {
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::set_authority(cpi_ctx, AuthorityType::MintTokens, Some(new_authority))?;
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct Rotate<'info> {
+            pub payer: Signer<'info>,
+            #[account(mut)]
+            pub mint: Account<'info, Mint>,
+            /// CHECK: PDA signing for the mint
+            #[account(seeds = [b"mint-authority"], bump)]
+            pub mint_authority: UncheckedAccount<'info>,
+            pub token_program: Program<'info, Token>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 10);
+    assert_eq!(diagnostics[0].range.start.character, 12);
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+}
+
+#[test]
+fn test_guarded_set_authority_not_flagged() {
+    let mut detector = UncheckedSetAuthorityDetector::default();
+
+    let code = r#"
+        #[derive(Accounts)]
+        pub struct Rotate<'info> {
+            #[account(has_one = current_authority @ ErrorCode::Unauthorized)]
+            pub config: Account<'info, Config>,
+            pub current_authority: Signer<'info>,
+            #[account(mut)]
+            pub mint: Account<'info, Mint>,
+            /// CHECK: PDA signing for the mint
+            #[account(seeds = [b"mint-authority"], bump)]
+            pub mint_authority: UncheckedAccount<'info>,
+        }
+
+        pub fn rotate(ctx: Context<Rotate>, new_authority: Pubkey) -> Result<()> {
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::set_authority(cpi_ctx, AuthorityType::MintTokens, Some(new_authority))?;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,126 @@
+use language_server::core::detectors::{
+    detector::Detector, unchecked_token_transfer_detector::UncheckedTokenTransferDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UncheckedTokenTransferDetector::default();
+
+    assert_eq!(detector.id(), "UNCHECKED_TOKEN_TRANSFER");
+    assert_eq!(detector.name(), "Unchecked Token Transfer");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_token_transfer() {
+    let mut detector = UncheckedTokenTransferDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+        use anchor_spl::token::{self, Transfer};
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.user.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+                token::transfer(cpi_ctx, amount)?;
+                Ok(())
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 15);
+    assert!(diagnostics[0].message.contains("transfer_checked"));
+}
+
+#[test]
+fn test_detects_fully_qualified_and_imported_transfer() {
+    let mut detector = UncheckedTokenTransferDetector::default();
+
+    let code = r#"
+        use anchor_spl::token::{transfer, Transfer};
+        use anchor_spl::token_interface::transfer as interface_transfer;
+
+        pub fn pay(cpi_ctx: CpiContext<Transfer>, amount: u64) -> Result<()> {
+            anchor_spl::token::transfer(cpi_ctx.clone(), amount)?;
+            transfer(cpi_ctx.clone(), amount)?;
+            interface_transfer(cpi_ctx, amount)
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 3);
+}
+
+#[test]
+fn test_transfer_checked_not_flagged() {
+    let mut detector = UncheckedTokenTransferDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+        use anchor_spl::token::{self, TransferChecked};
+
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_unrelated_transfer_not_flagged() {
+    let mut detector = UncheckedTokenTransferDetector::default();
+
+    let code = r#"
+        use anchor_lang::system_program;
+
+        pub fn fund(ctx: Context<Fund>, lamports: u64) -> Result<()> {
+            system_program::transfer(ctx.accounts.into_transfer_context(), lamports)?;
+            transfer(lamports);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_skips_test_modules() {
+    let mut detector = UncheckedTokenTransferDetector::default();
+
+    let code = r#"
+        #[cfg(test)]
+        mod tests {
+            use anchor_spl::token;
+
+            #[test]
+            fn test_transfer() {
+                token::transfer(cpi_ctx(), 10).unwrap();
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,67 @@
+use language_server::core::detectors::{
+    detector::Detector, unconstrained_token_account_detector::UnconstrainedTokenAccountDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnconstrainedTokenAccountDetector::default();
+
+    assert_eq!(detector.id(), "UNCONSTRAINED_TOKEN_ACCOUNT");
+    assert_eq!(detector.name(), "Unconstrained Token Account");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_unconstrained_token_account() {
+    let mut detector = UnconstrainedTokenAccountDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+        use anchor_spl::token::{Mint, Token, TokenAccount};
+
+        #[derive(Accounts)]
+        pub struct Deposit<'info> {
+            #[account(mut)]
+            pub user_tokens: Account<'info, TokenAccount>,
+            pub vault_tokens: Box<InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>>,
+            pub mint: Account<'info, Mint>,
+            pub token_program: Program<'info, Token>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 7);
+    assert!(diagnostics[0].message.contains("'user_tokens'"));
+    assert!(diagnostics[1].message.contains("'vault_tokens'"));
+}
+
+#[test]
+fn test_constrained_token_accounts_not_flagged() {
+    let mut detector = UnconstrainedTokenAccountDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+        use anchor_spl::token::{Mint, TokenAccount};
+
+        #[derive(Accounts)]
+        pub struct Deposit<'info> {
+            #[account(mut, token::mint = mint)]
+            pub user_tokens: Account<'info, TokenAccount>,
+            #[account(associated_token::mint = mint, associated_token::authority = user)]
+            pub user_ata: Account<'info, TokenAccount>,
+            #[account(constraint = fee_tokens.owner == config.treasury)]
+            pub fee_tokens: Account<'info, TokenAccount>,
+            #[account(seeds = [b"vault"], bump)]
+            pub vault_tokens: Account<'info, TokenAccount>,
+            /// CHECK: not deserialized
+            pub raw: UncheckedAccount<'info>,
+            pub mint: Account<'info, Mint>,
+            pub user: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
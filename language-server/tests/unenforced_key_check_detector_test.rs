@@ -0,0 +1,70 @@
+use language_server::core::detectors::{
+    detector::Detector, unenforced_key_check_detector::UnenforcedKeyCheckDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnenforcedKeyCheckDetector::default();
+
+    assert_eq!(detector.id(), "UNENFORCED_KEY_CHECK");
+    assert_eq!(detector.name(), "Unenforced Key Check");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_key_check_without_return() {
+    let mut detector = UnenforcedKeyCheckDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            if ctx.accounts.vault.authority != ctx.accounts.signer.key() {
+            }
+            if amount > 10 || ctx.accounts.signer.key() != ADMIN {
+                msg!("unauthorized");
+            }
+            transfer(ctx, amount)
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.start.character, 15);
+    assert_eq!(diagnostics[1].range.start.line, 4);
+}
+
+#[test]
+fn test_enforced_checks_not_flagged() {
+    let mut detector = UnenforcedKeyCheckDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.signer.key());
+            if ctx.accounts.vault.authority != ctx.accounts.signer.key() {
+                return Err(ErrorCode::Unauthorized.into());
+            }
+            if ctx.accounts.signer.key() != ADMIN {
+                check_admin(&ctx)?;
+            }
+            if ctx.accounts.signer.key() != ADMIN {
+                msg!("not admin");
+            } else {
+                msg!("admin");
+            }
+            if ctx.accounts.signer.key() == ADMIN {
+                msg!("admin");
+            }
+            Ok(())
+        }
+
+        fn helper(a: Pubkey, b: &AccountInfo) {
+            if b.key() != a {
+                msg!("mismatch");
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
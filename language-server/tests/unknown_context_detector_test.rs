@@ -0,0 +1,201 @@
+use language_server::core::detectors::{
+    detector::Detector, unknown_context_detector::UnknownContextDetector,
+};
+use language_server::core::utilities::WorkspaceFiles;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+const INSTRUCTIONS: &str = r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+"#;
+
+/// Create a crate whose accounts structs live in `instructions.rs`
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-unknown-context-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("programs/vault/src")).unwrap();
+    fs::write(root.join("Anchor.toml"), "[programs.localnet]\n").unwrap();
+    fs::write(
+        root.join("programs/vault/Cargo.toml"),
+        "[package]\nname = \"vault\"\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("programs/vault/src/instructions.rs"),
+        INSTRUCTIONS,
+    )
+    .unwrap();
+    root
+}
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnknownContextDetector::default();
+
+    assert_eq!(detector.id(), "UNKNOWN_CONTEXT_ACCOUNTS");
+    assert_eq!(detector.name(), "Unknown Context Accounts");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_undefined_context_type() {
+    let mut detector = UnknownContextDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn initialize(ctx: Context<Initialise>) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            pub payer: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 7);
+    assert_eq!(diagnostics[0].range.start.character, 43);
+    assert!(diagnostics[0].message.contains("'Initialise'"));
+}
+
+#[test]
+fn test_defined_in_same_file_not_flagged() {
+    let mut detector = UnknownContextDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn initialize(ctx: Context<'_, '_, '_, 'info, Initialize<'info>>) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            pub payer: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_defined_in_another_file_not_flagged() {
+    let root = workspace("other-file");
+    let lib_path = root.join("programs/vault/src/lib.rs");
+    let mut detector = UnknownContextDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        mod instructions;
+        use instructions::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+                Ok(())
+            }
+
+            pub fn deposit(ctx: Context<Depositt>) -> Result<()> {
+                Ok(())
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, Some(&lib_path));
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("'Depositt'"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_shared_workspace_files_used_instead_of_disk() {
+    let root = workspace("shared");
+    let lib_path = root.join("programs/vault/src/lib.rs");
+    let mut detector = UnknownContextDetector::default();
+
+    // The scan's snapshot of `instructions.rs` also defines `Deposit`, the file on disk does not
+    let files = BTreeMap::from([(
+        root.join("programs/vault/src/instructions.rs"),
+        format!(
+            "{}\n#[derive(Accounts)]\npub struct Deposit<'info> {{\n    pub payer: Signer<'info>,\n}}\n",
+            INSTRUCTIONS
+        ),
+    )]);
+    detector.set_workspace(Some(Arc::new(WorkspaceFiles::from_contents(
+        root.clone(),
+        files,
+    ))));
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+                Ok(())
+            }
+
+            pub fn deposit(ctx: Context<Deposit>) -> Result<()> {
+                Ok(())
+            }
+        }
+    "#;
+
+    assert!(detector.analyze(code, Some(&lib_path)).is_empty());
+
+    // Without the snapshot the workspace is read from disk again
+    detector.set_workspace(None);
+    let diagnostics = detector.analyze(code, Some(&lib_path));
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("'Deposit'"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_generic_context_not_flagged() {
+    let mut detector = UnknownContextDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        pub fn log_accounts<'info, T: Accounts<'info>>(ctx: Context<T>) -> Result<()> {
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,132 @@
+use language_server::core::detectors::{
+    detector::Detector, unnecessary_mut_detector::UnnecessaryMutDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnnecessaryMutDetector::default();
+
+    assert_eq!(detector.id(), "UNNECESSARY_MUT");
+    assert_eq!(detector.name(), "Unnecessary Mut");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_never_mutated_mut_field() {
+    let mut detector = UnnecessaryMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn check(ctx: Context<Check>, minimum: u64) -> Result<()> {
+                require!(ctx.accounts.vault.amount >= minimum, ErrorCode::TooLow);
+                msg!("Vault owner {}", ctx.accounts.vault.owner);
+                let key = ctx.accounts.config.key();
+                ctx.accounts.config.checks += 1;
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Check<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub config: Account<'info, Config>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 18);
+    assert!(diagnostics[0].message.contains("'vault'"));
+}
+
+#[test]
+fn test_mutated_mut_fields_not_flagged() {
+    let mut detector = UnnecessaryMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                vault.amount -= amount;
+                **ctx.accounts.treasury.try_borrow_mut_lamports()? -= amount;
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                };
+                token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub treasury: AccountInfo<'info>,
+            #[account(mut)]
+            pub source: Account<'info, TokenAccount>,
+            #[account(mut)]
+            pub destination: Account<'info, TokenAccount>,
+            pub authority: Signer<'info>,
+            pub token_program: Program<'info, Token>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_opaque_or_implicit_mut_not_flagged() {
+    let mut detector = UnnecessaryMutDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+                Ok(())
+            }
+
+            pub fn settle(ctx: Context<Settle>) -> Result<()> {
+                ctx.accounts.settle()
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(init, payer = payer, space = 8 + 32)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub payer: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+
+        #[derive(Accounts)]
+        pub struct Settle<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,86 @@
+use language_server::core::detectors::{
+    detector::Detector, unsigned_authority_detector::UnsignedAuthorityDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnsignedAuthorityDetector::default();
+
+    assert_eq!(detector.id(), "UNSIGNED_AUTHORITY");
+    assert_eq!(detector.name(), "Unsigned Authority");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_account_info_authority() {
+    let mut detector = UnsignedAuthorityDetector::default();
+
+    let code = r#"
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());
+            Ok(())
+        }
+
+        pub fn close(ctx: Context<Close>) -> Result<()> {
+            let cpi_accounts = CloseAccount {
+                account: ctx.accounts.token.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            };
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+            pub authority: AccountInfo<'info>,
+        }
+
+        #[derive(Accounts)]
+        pub struct Close<'info> {
+            pub token: Account<'info, TokenAccount>,
+            /// CHECK: closing authority
+            pub owner: UncheckedAccount<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 19);
+    assert_eq!(diagnostics[0].range.start.character, 12);
+    assert!(diagnostics[0].message.contains("'authority'"));
+    assert_eq!(diagnostics[1].range.start.line, 25);
+    assert!(diagnostics[1].message.contains("'owner'"));
+}
+
+#[test]
+fn test_signer_authority_not_flagged() {
+    let mut detector = UnsignedAuthorityDetector::default();
+
+    let code = r#"
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+            pub authority: Signer<'info>,
+            #[account(signer)]
+            pub admin: AccountInfo<'info>,
+            pub owner: AccountInfo<'info>,
+            pub mint: UncheckedAccount<'info>,
+        }
+
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());
+            require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+            require!(ctx.accounts.owner.is_signer, ErrorCode::MissingSignature);
+            require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), ErrorCode::Unauthorized);
+            require_keys_eq!(ctx.accounts.mint.key(), ctx.accounts.vault.mint);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
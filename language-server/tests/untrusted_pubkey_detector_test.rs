@@ -0,0 +1,83 @@
+use language_server::core::detectors::{
+    detector::Detector, untrusted_pubkey_detector::UntrustedPubkeyDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UntrustedPubkeyDetector::default();
+
+    assert_eq!(detector.id(), "UNTRUSTED_PUBKEY");
+    assert_eq!(detector.name(), "Untrusted Pubkey");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_unchecked_constructed_key() {
+    let mut detector = UntrustedPubkeyDetector::default();
+
+    let code = r#"
+        pub fn process(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+            let authority = Pubkey::new(&data[..32]);
+            let delegate = Pubkey::try_from(&data[32..64]).unwrap();
+            if accounts[0].key == &authority {
+                msg!("Authorized");
+            }
+            store_delegate(delegate);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 2);
+    assert_eq!(diagnostics[0].range.start.character, 28);
+    assert!(diagnostics[0].message.contains("'authority'"));
+    assert_eq!(diagnostics[1].range.start.line, 3);
+    assert!(diagnostics[1].message.contains("'delegate'"));
+}
+
+#[test]
+fn test_validated_keys_not_flagged() {
+    let mut detector = UntrustedPubkeyDetector::default();
+
+    let code = r#"
+        pub fn set_authority(ctx: Context<SetAuthority>, data: [u8; 32]) -> Result<()> {
+            let authority = Pubkey::new_from_array(data);
+            require_keys_eq!(authority, ctx.accounts.config.admin, ErrorCode::Unauthorized);
+
+            let owner = Pubkey::try_from(&data[..]).map_err(|_| ErrorCode::InvalidKey)?;
+            require!(ctx.accounts.vault.owner == &owner, ErrorCode::InvalidOwner);
+
+            let wallet = Pubkey::new(&data);
+            require!(wallet.is_on_curve(), ErrorCode::InvalidKey);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_constant_and_test_keys_not_flagged() {
+    let mut detector = UntrustedPubkeyDetector::default();
+
+    let code = r#"
+        pub fn default_key() -> Pubkey {
+            let key = Pubkey::new_from_array([0u8; 32]);
+            key
+        }
+
+        #[cfg(test)]
+        mod tests {
+            #[test]
+            fn test_key() {
+                let key = Pubkey::new(&bytes);
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,100 @@
+use language_server::core::detectors::{
+    detector::Detector, unused_signer_detector::UnusedSignerDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnusedSignerDetector::default();
+
+    assert_eq!(detector.id(), "UNUSED_SIGNER");
+    assert_eq!(detector.name(), "Signer Not Used for Authorization");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_unused_signer() {
+    let mut detector = UnusedSignerDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                ctx.accounts.vault.sub_lamports(amount)?;
+                ctx.accounts.recipient.add_lamports(amount)?;
+                Ok(())
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut)]
+            pub vault: Account<'info, Vault>,
+            #[account(mut)]
+            pub recipient: SystemAccount<'info>,
+            pub authority: Signer<'info>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 20);
+    assert!(
+        diagnostics[0]
+            .message
+            .contains("Signer 'authority' is not used for authorization")
+    );
+}
+
+#[test]
+fn test_checked_signers_not_flagged() {
+    let mut detector = UnusedSignerDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                ctx.accounts.vault.sub_lamports(amount)?;
+                Ok(())
+            }
+
+            pub fn configure(ctx: Context<Configure>) -> Result<()> {
+                require_keys_eq!(ctx.accounts.admin.key(), ctx.accounts.config.admin);
+                ctx.accounts.apply()
+            }
+        }
+
+        #[derive(Accounts)]
+        pub struct Withdraw<'info> {
+            #[account(mut, has_one = authority)]
+            pub vault: Account<'info, Vault>,
+            pub authority: Signer<'info>,
+        }
+
+        #[derive(Accounts)]
+        pub struct Configure<'info> {
+            #[account(mut)]
+            pub config: Account<'info, Config>,
+            pub admin: Signer<'info>,
+            pub operator: Signer<'info>,
+        }
+
+        impl<'info> Configure<'info> {
+            pub fn apply(&mut self) -> Result<()> {
+                self.config.operator = self.operator.key();
+                Ok(())
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,85 @@
+use language_server::core::detectors::{
+    detector::Detector, unwrap_in_program_detector::UnwrapInProgramDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = UnwrapInProgramDetector::default();
+
+    assert_eq!(detector.id(), "UNWRAP_IN_PROGRAM");
+    assert_eq!(detector.name(), "Unwrap In Program");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::WARNING);
+}
+
+#[test]
+fn test_detects_unwrap_in_handler() {
+    let mut detector = UnwrapInProgramDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let balance = ctx.accounts.vault.balance.checked_sub(amount).unwrap();
+                let bump = ctx.bumps.get("vault").expect("missing bump");
+                Ok(())
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0].message.contains("`unwrap()`"));
+    assert!(diagnostics[1].message.contains("`expect()`"));
+    assert_eq!(diagnostics[0].range.start.line, 8);
+}
+
+#[test]
+fn test_unwrap_in_test_module_not_flagged() {
+    let mut detector = UnwrapInProgramDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        #[program]
+        pub mod vault {
+            use super::*;
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                Ok(())
+            }
+
+            #[cfg(test)]
+            mod tests {
+                #[test]
+                fn parses() {
+                    let value: Option<u64> = Some(1);
+                    value.unwrap();
+                }
+            }
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
+
+#[test]
+fn test_unwrap_outside_program_module_not_flagged() {
+    let mut detector = UnwrapInProgramDetector::default();
+
+    let code = r#"
+        use anchor_lang::prelude::*;
+
+        pub fn helper(value: Option<u64>) -> u64 {
+            value.unwrap()
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 0);
+}
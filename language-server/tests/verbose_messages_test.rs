@@ -0,0 +1,144 @@
+use language_server::core::detectors::detector::Detector;
+use language_server::core::registry::DetectorRegistryBuilder;
+use language_server::core::utilities::{DiagnosticBuilder, LineIndex};
+use std::path::PathBuf;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+
+/// Minimal detector emitting a single arithmetic diagnostic with remediation text
+struct UnsafeArithmeticDetector;
+
+impl Detector for UnsafeArithmeticDetector {
+    fn id(&self) -> &'static str {
+        "UNSAFE_ARITHMETIC"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unsafe Arithmetic"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects unchecked arithmetic"
+    }
+
+    fn message(&self) -> &'static str {
+        "Unchecked subtraction may underflow. Use checked_sub() and return an error on None.\nSee the Solana security guidelines for details."
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        _syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        _lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        vec![DiagnosticBuilder::create(
+            Range::new(Position::new(0, 0), Position::new(0, 1)),
+            self.message().to_string(),
+            self.default_severity(),
+            self.id().to_string(),
+            None,
+        )]
+    }
+}
+
+/// Detector emitting a single diagnostic with a fixed message
+struct FixedMessageDetector(&'static str);
+
+impl Detector for FixedMessageDetector {
+    fn id(&self) -> &'static str {
+        "FIXED_MESSAGE"
+    }
+
+    fn name(&self) -> &'static str {
+        "Fixed Message"
+    }
+
+    fn description(&self) -> &'static str {
+        "Emits a fixed message"
+    }
+
+    fn message(&self) -> &'static str {
+        self.0
+    }
+
+    fn default_severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::WARNING
+    }
+
+    fn analyze_ast(
+        &mut self,
+        _syntax_tree: &syn::File,
+        _content: &str,
+        _file_path: Option<&PathBuf>,
+        _lines: &LineIndex,
+    ) -> Vec<Diagnostic> {
+        vec![DiagnosticBuilder::create(
+            Range::new(Position::new(0, 0), Position::new(0, 1)),
+            self.message().to_string(),
+            self.default_severity(),
+            self.id().to_string(),
+            None,
+        )]
+    }
+}
+
+fn terse_message(message: &'static str) -> String {
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(FixedMessageDetector(message))
+        .build();
+    registry.set_verbose_messages(false);
+    registry.analyze("", None).remove(0).message
+}
+
+#[test]
+fn test_verbose_messages_enabled_by_default() {
+    let registry = DetectorRegistryBuilder::new()
+        .with_detector(UnsafeArithmeticDetector)
+        .build();
+
+    assert!(registry.verbose_messages());
+}
+
+#[test]
+fn test_verbose_and_terse_messages_differ() {
+    let mut registry = DetectorRegistryBuilder::new()
+        .with_detector(UnsafeArithmeticDetector)
+        .build();
+
+    let verbose = registry.analyze("", None);
+    registry.set_verbose_messages(false);
+    let terse = registry.analyze("", None);
+
+    assert_eq!(verbose.len(), 1);
+    assert_eq!(terse.len(), 1);
+    assert_ne!(verbose[0].message, terse[0].message);
+
+    assert!(verbose[0].message.contains("checked_sub()"));
+    assert_eq!(
+        terse[0].message,
+        "Unsafe Arithmetic: Unchecked subtraction may underflow."
+    );
+    assert!(!terse[0].message.contains('\n'));
+}
+
+#[test]
+fn test_terse_message_keeps_abbreviations() {
+    assert_eq!(
+        terse_message(
+            "Seeds should be fixed, e.g. `b\"vault\"`, or bound to a signer, i.e. its key. Use a constant prefix."
+        ),
+        "Fixed Message: Seeds should be fixed, e.g. `b\"vault\"`, or bound to a signer, i.e. its key."
+    );
+    assert_eq!(
+        terse_message("Loaded with (e.g. from) another program. Check the owner."),
+        "Fixed Message: Loaded with (e.g. from) another program."
+    );
+    assert_eq!(
+        terse_message("No second sentence here"),
+        "Fixed Message: No second sentence here"
+    );
+}
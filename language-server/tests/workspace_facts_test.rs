@@ -0,0 +1,82 @@
+use language_server::core::utilities::WorkspaceFacts;
+use std::cell::Cell;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create a crate with a few source files
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "solana-ls-workspace-facts-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("Cargo.toml"), "[package]\nname = \"vault\"\n").unwrap();
+    fs::write(root.join("src/lib.rs"), "mod state;\nmod instructions;\n").unwrap();
+    fs::write(root.join("src/state.rs"), "pub struct Vault;\n").unwrap();
+    fs::write(root.join("src/instructions.rs"), "pub struct Deposit;\n").unwrap();
+    root
+}
+
+/// Number of items in each other file of the workspace, counting the files parsed
+fn item_counts(
+    facts: &mut WorkspaceFacts<usize>,
+    file_path: &Path,
+    parses: &Cell<usize>,
+) -> Vec<usize> {
+    let mut counts: Vec<usize> = facts
+        .for_file(None, file_path, |syntax_tree| {
+            parses.set(parses.get() + 1);
+            syntax_tree.items.len()
+        })
+        .copied()
+        .collect();
+    counts.sort();
+    counts
+}
+
+#[test]
+fn test_loaded_facts_reused_until_a_file_changes() {
+    let root = workspace("reuse");
+    let lib_path = root.join("src/lib.rs");
+    let mut facts = WorkspaceFacts::default();
+    let parses = Cell::new(0);
+
+    assert_eq!(item_counts(&mut facts, &lib_path, &parses), vec![1, 1]);
+    assert_eq!(parses.get(), 3);
+
+    // Nothing changed, so the workspace is neither read nor parsed again
+    assert_eq!(item_counts(&mut facts, &lib_path, &parses), vec![1, 1]);
+    assert_eq!(parses.get(), 3);
+
+    fs::write(
+        root.join("src/state.rs"),
+        "pub struct Vault;\npub struct Config;\n",
+    )
+    .unwrap();
+    assert_eq!(item_counts(&mut facts, &lib_path, &parses), vec![1, 2]);
+    assert_eq!(parses.get(), 6);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_loaded_facts_kept_per_workspace() {
+    let vault = workspace("vault");
+    let escrow = workspace("escrow");
+    let mut facts = WorkspaceFacts::default();
+    let parses = Cell::new(0);
+
+    item_counts(&mut facts, &vault.join("src/lib.rs"), &parses);
+    item_counts(&mut facts, &escrow.join("src/lib.rs"), &parses);
+    assert_eq!(parses.get(), 6);
+
+    // Switching between workspaces reuses what each one collected
+    item_counts(&mut facts, &vault.join("src/lib.rs"), &parses);
+    item_counts(&mut facts, &escrow.join("src/state.rs"), &parses);
+    assert_eq!(parses.get(), 6);
+
+    let _ = fs::remove_dir_all(&vault);
+    let _ = fs::remove_dir_all(&escrow);
+}
@@ -0,0 +1,95 @@
+use language_server::core::language_features::WorkspaceSymbolIndex;
+use tower_lsp::lsp_types::{Position, Range, SymbolKind, Url};
+
+const LIB: &str = r#"use anchor_lang::prelude::*;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        fn helper() {}
+        Ok(())
+    }
+}
+
+fn not_a_handler() {}
+"#;
+
+const STATE: &str = r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+}
+
+pub struct Plain {
+    pub value: u8,
+}
+"#;
+
+fn uri(file: &str) -> Url {
+    Url::parse(&format!("file:///workspace/programs/vault/src/{}", file)).unwrap()
+}
+
+fn index() -> WorkspaceSymbolIndex {
+    let mut index = WorkspaceSymbolIndex::default();
+    index.index_file(&uri("lib.rs"), LIB);
+    index.index_file(&uri("state.rs"), STATE);
+    index
+}
+
+#[test]
+fn test_resolves_handler_by_name() {
+    let symbols = index().query("withdraw");
+
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].name, "withdraw");
+    assert_eq!(symbols[0].kind, SymbolKind::FUNCTION);
+    assert_eq!(symbols[0].container_name.as_deref(), Some("vault"));
+    assert_eq!(symbols[0].location.uri, uri("lib.rs"));
+    assert_eq!(
+        symbols[0].location.range,
+        Range::new(Position::new(10, 11), Position::new(10, 19))
+    );
+}
+
+#[test]
+fn test_indexes_anchor_structs_across_files() {
+    let index = index();
+
+    let names: Vec<String> = index.query("").into_iter().map(|s| s.name).collect();
+    assert_eq!(names, ["Initialize", "Vault", "initialize", "withdraw"]);
+
+    // Case-insensitive: both the handler and its accounts struct match
+    let symbols = index.query("INIT");
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[0].kind, SymbolKind::STRUCT);
+    assert_eq!(symbols[0].location.uri, uri("state.rs"));
+}
+
+#[test]
+fn test_reindexing_replaces_file_symbols() {
+    let mut index = index();
+
+    index.index_file(&uri("lib.rs"), LIB.replace("withdraw", "deposit").as_str());
+    assert!(index.query("withdraw").is_empty());
+    assert_eq!(index.query("deposit").len(), 1);
+
+    // A file that does not parse keeps its previous symbols
+    index.index_file(&uri("state.rs"), "pub struct {");
+    assert_eq!(index.query("Vault").len(), 1);
+
+    index.remove_file(&uri("state.rs"));
+    assert!(index.query("Vault").is_empty());
+}
@@ -0,0 +1,63 @@
+use language_server::core::detectors::{
+    detector::Detector, zero_account_reinit_detector::ZeroAccountReinitDetector,
+};
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+#[test]
+fn test_detector_metadata() {
+    let detector = ZeroAccountReinitDetector::default();
+
+    assert_eq!(detector.id(), "ZERO_ACCOUNT_REINIT");
+    assert_eq!(detector.name(), "Zero Account Reinit");
+    assert_eq!(detector.default_severity(), DiagnosticSeverity::INFORMATION);
+}
+
+#[test]
+fn test_detects_zero_account_without_init_flag() {
+    let mut detector = ZeroAccountReinitDetector::default();
+
+    let code = r#"
+        pub fn initialize(ctx: Context<Initialize>, owner: Pubkey) -> Result<()> {
+            let mut book = ctx.accounts.book.load_mut()?;
+            book.owner = owner;
+            Ok(())
+        }
+
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(zero)]
+            pub book: AccountLoader<'info, Book>,
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 9);
+    assert_eq!(diagnostics[0].range.start.character, 12);
+    assert!(diagnostics[0].message.contains("'book'"));
+}
+
+#[test]
+fn test_init_flag_set_not_flagged() {
+    let mut detector = ZeroAccountReinitDetector::default();
+
+    let code = r#"
+        #[derive(Accounts)]
+        pub struct Initialize<'info> {
+            #[account(zero)]
+            pub book: AccountLoader<'info, Book>,
+            #[account(zero)]
+            pub orders: AccountLoader<'info, Orders>,
+        }
+
+        pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+            let mut book = ctx.accounts.book.load_init()?;
+            book.is_initialized = true;
+            ctx.accounts.orders.load_init()?.discriminator.copy_from_slice(&ORDERS);
+            Ok(())
+        }
+    "#;
+
+    let diagnostics = detector.analyze(code, None);
+    assert!(diagnostics.is_empty());
+}